@@ -45,6 +45,8 @@ export const CreditFacilityRepaymentPlan: React.FC<CreditFacilityRepaymentPlanPr
         return t("repaymentTypes.principal")
       case CreditFacilityRepaymentType.Interest:
         return t("repaymentTypes.interest")
+      case CreditFacilityRepaymentType.Penalty:
+        return t("repaymentTypes.penalty")
       default: {
         const exhaustiveCheck: never = type
         return exhaustiveCheck
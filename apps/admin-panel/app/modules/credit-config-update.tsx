@@ -46,6 +46,8 @@ const initialFormData: CreditModuleConfigureInput = {
   chartOfAccountInLiquidationParentCode: "",
   chartOfAccountInterestIncomeParentCode: "",
   chartOfAccountFeeIncomeParentCode: "",
+  chartOfAccountPenaltyIncomeParentCode: "",
+  chartOfAccountPenaltyReceivableParentCode: "",
   chartOfAccountShortTermIndividualDisbursedReceivableParentCode: "",
   chartOfAccountShortTermGovernmentEntityDisbursedReceivableParentCode: "",
   chartOfAccountShortTermPrivateCompanyDisbursedReceivableParentCode: "",
@@ -92,6 +94,8 @@ const creditModuleCodes = {
   chartOfAccountInLiquidationParentCode: "9170.00.0001",
   chartOfAccountInterestIncomeParentCode: "6110.01.0100",
   chartOfAccountFeeIncomeParentCode: "6110.01.0300",
+  chartOfAccountPenaltyIncomeParentCode: "6110.01.0200",
+  chartOfAccountPenaltyReceivableParentCode: "1141.04.9901",
   chartOfAccountShortTermIndividualInterestReceivableParentCode: "1141.04.9901",
   chartOfAccountShortTermGovernmentEntityInterestReceivableParentCode: "1141.02.9901",
   chartOfAccountShortTermPrivateCompanyInterestReceivableParentCode: "1141.03.9901",
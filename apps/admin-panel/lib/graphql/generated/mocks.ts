@@ -997,6 +997,8 @@ export const mockCreditModuleConfig = (overrides?: Partial<CreditModuleConfig>,
         chartOfAccountFacilityOmnibusParentCode: overrides && overrides.hasOwnProperty('chartOfAccountFacilityOmnibusParentCode') ? overrides.chartOfAccountFacilityOmnibusParentCode! : faker.lorem.word(),
         chartOfAccountFacilityParentCode: overrides && overrides.hasOwnProperty('chartOfAccountFacilityParentCode') ? overrides.chartOfAccountFacilityParentCode! : faker.lorem.word(),
         chartOfAccountFeeIncomeParentCode: overrides && overrides.hasOwnProperty('chartOfAccountFeeIncomeParentCode') ? overrides.chartOfAccountFeeIncomeParentCode! : faker.lorem.word(),
+        chartOfAccountPenaltyIncomeParentCode: overrides && overrides.hasOwnProperty('chartOfAccountPenaltyIncomeParentCode') ? overrides.chartOfAccountPenaltyIncomeParentCode! : faker.lorem.word(),
+        chartOfAccountPenaltyReceivableParentCode: overrides && overrides.hasOwnProperty('chartOfAccountPenaltyReceivableParentCode') ? overrides.chartOfAccountPenaltyReceivableParentCode! : faker.lorem.word(),
         chartOfAccountInLiquidationOmnibusParentCode: overrides && overrides.hasOwnProperty('chartOfAccountInLiquidationOmnibusParentCode') ? overrides.chartOfAccountInLiquidationOmnibusParentCode! : faker.lorem.word(),
         chartOfAccountInLiquidationParentCode: overrides && overrides.hasOwnProperty('chartOfAccountInLiquidationParentCode') ? overrides.chartOfAccountInLiquidationParentCode! : faker.lorem.word(),
         chartOfAccountInterestIncomeParentCode: overrides && overrides.hasOwnProperty('chartOfAccountInterestIncomeParentCode') ? overrides.chartOfAccountInterestIncomeParentCode! : faker.lorem.word(),
@@ -1048,6 +1050,8 @@ export const mockCreditModuleConfigureInput = (overrides?: Partial<CreditModuleC
         chartOfAccountFacilityOmnibusParentCode: overrides && overrides.hasOwnProperty('chartOfAccountFacilityOmnibusParentCode') ? overrides.chartOfAccountFacilityOmnibusParentCode! : faker.lorem.word(),
         chartOfAccountFacilityParentCode: overrides && overrides.hasOwnProperty('chartOfAccountFacilityParentCode') ? overrides.chartOfAccountFacilityParentCode! : faker.lorem.word(),
         chartOfAccountFeeIncomeParentCode: overrides && overrides.hasOwnProperty('chartOfAccountFeeIncomeParentCode') ? overrides.chartOfAccountFeeIncomeParentCode! : faker.lorem.word(),
+        chartOfAccountPenaltyIncomeParentCode: overrides && overrides.hasOwnProperty('chartOfAccountPenaltyIncomeParentCode') ? overrides.chartOfAccountPenaltyIncomeParentCode! : faker.lorem.word(),
+        chartOfAccountPenaltyReceivableParentCode: overrides && overrides.hasOwnProperty('chartOfAccountPenaltyReceivableParentCode') ? overrides.chartOfAccountPenaltyReceivableParentCode! : faker.lorem.word(),
         chartOfAccountInLiquidationOmnibusParentCode: overrides && overrides.hasOwnProperty('chartOfAccountInLiquidationOmnibusParentCode') ? overrides.chartOfAccountInLiquidationOmnibusParentCode! : faker.lorem.word(),
         chartOfAccountInLiquidationParentCode: overrides && overrides.hasOwnProperty('chartOfAccountInLiquidationParentCode') ? overrides.chartOfAccountInLiquidationParentCode! : faker.lorem.word(),
         chartOfAccountInterestIncomeParentCode: overrides && overrides.hasOwnProperty('chartOfAccountInterestIncomeParentCode') ? overrides.chartOfAccountInterestIncomeParentCode! : faker.lorem.word(),
@@ -65,6 +65,8 @@ const getRepaymentTypeDisplay = (type: RepaymentPlan["repaymentType"]) => {
       return "Principal"
     case "INTEREST":
       return "Interest"
+    case "PENALTY":
+      return "Penalty"
     default:
       return type
   }
@@ -180,7 +180,8 @@ export enum CreditFacilityRepaymentStatus {
 
 export enum CreditFacilityRepaymentType {
   Disbursal = 'DISBURSAL',
-  Interest = 'INTEREST'
+  Interest = 'INTEREST',
+  Penalty = 'PENALTY'
 }
 
 export enum CreditFacilityStatus {
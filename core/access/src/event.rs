@@ -13,6 +13,9 @@ pub enum CoreAccessEvent {
     UserRemoved {
         id: UserId,
     },
+    UserReactivated {
+        id: UserId,
+    },
     UserUpdatedRole {
         id: UserId,
         role_id: RoleId,
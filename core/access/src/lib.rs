@@ -290,6 +290,53 @@ where
         Ok(user)
     }
 
+    #[instrument(name = "core_access.deactivate_user", skip(self))]
+    pub async fn deactivate_user(
+        &self,
+        sub: &<Audit as AuditSvc>::Subject,
+        user_id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<User, CoreAccessError> {
+        let user_id = user_id.into();
+
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccessObject::user(user_id),
+                CoreAccessAction::USER_DEACTIVATE,
+            )
+            .await?;
+
+        let user = self
+            .users
+            .find_by_id(sub, user_id)
+            .await?
+            .ok_or(CoreAccessError::UserError(UserError::EsEntityError(
+                es_entity::EsEntityError::NotFound,
+            )))?;
+        let role = self.roles.find_by_id(user.current_role()).await?;
+
+        if role.name == ROLE_NAME_SUPERUSER {
+            return Err(CoreAccessError::AuthorizationError(
+                authz::error::AuthorizationError::NotAuthorized,
+            ));
+        }
+
+        let user = self.users.deactivate_user(sub, user_id).await?;
+
+        Ok(user)
+    }
+
+    #[instrument(name = "core_access.reactivate_user", skip(self))]
+    pub async fn reactivate_user(
+        &self,
+        sub: &<Audit as AuditSvc>::Subject,
+        user_id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<User, CoreAccessError> {
+        let user = self.users.reactivate_user(sub, user_id).await?;
+
+        Ok(user)
+    }
+
     #[instrument(name = "access.list_roles", skip(self), err)]
     pub async fn list_roles(
         &self,
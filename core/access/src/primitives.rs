@@ -126,6 +126,8 @@ impl CoreAccessAction {
     pub const USER_READ: Self = CoreAccessAction::User(UserAction::Read);
     pub const USER_LIST: Self = CoreAccessAction::User(UserAction::List);
     pub const USER_UPDATE_ROLE: Self = CoreAccessAction::User(UserAction::UpdateRole);
+    pub const USER_DEACTIVATE: Self = CoreAccessAction::User(UserAction::Deactivate);
+    pub const USER_REACTIVATE: Self = CoreAccessAction::User(UserAction::Reactivate);
 
     pub const PERMISSION_SET_LIST: Self =
         CoreAccessAction::PermissionSet(PermissionSetAction::List);
@@ -188,6 +190,8 @@ pub enum UserAction {
     List,
     Update,
     UpdateRole,
+    Deactivate,
+    Reactivate,
 }
 
 impl ActionPermission for UserAction {
@@ -195,7 +199,11 @@ impl ActionPermission for UserAction {
         match self {
             Self::Read | Self::List => PERMISSION_SET_ACCESS_VIEWER,
 
-            Self::Create | Self::Update | Self::UpdateRole => PERMISSION_SET_ACCESS_WRITER,
+            Self::Create
+            | Self::Update
+            | Self::UpdateRole
+            | Self::Deactivate
+            | Self::Reactivate => PERMISSION_SET_ACCESS_WRITER,
         }
     }
 }
@@ -54,6 +54,8 @@ where
                     id: entity.id,
                     role_id: *role_id,
                 },
+                Deactivated { .. } => CoreAccessEvent::UserRemoved { id: entity.id },
+                Reactivated { .. } => CoreAccessEvent::UserReactivated { id: entity.id },
             })
             .collect::<Vec<_>>();
 
@@ -20,6 +20,8 @@ pub enum UserEvent {
     RoleUpdated {
         role_id: RoleId,
     },
+    Deactivated {},
+    Reactivated {},
 }
 
 #[derive(EsEntity, Builder)]
@@ -50,17 +52,51 @@ impl User {
         }
     }
 
+    /// Marks the user as deactivated. Returns the role that should be revoked.
+    pub(crate) fn deactivate(&mut self) -> Idempotent<RoleId> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            UserEvent::Deactivated { .. },
+            => UserEvent::Reactivated { .. }
+        );
+        self.events.push(UserEvent::Deactivated {});
+
+        Idempotent::Executed(self.current_role())
+    }
+
+    /// Marks the user as active again. Returns the role that should be re-assigned.
+    pub(crate) fn reactivate(&mut self) -> Idempotent<RoleId> {
+        if self.is_active() {
+            return Idempotent::Ignored;
+        }
+        self.events.push(UserEvent::Reactivated {});
+
+        Idempotent::Executed(self.current_role())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|event| match event {
+                UserEvent::Deactivated { .. } => Some(false),
+                UserEvent::Reactivated { .. } => Some(true),
+                _ => None,
+            })
+            .unwrap_or(true)
+    }
+
     /// Returns the role currently assigned to this user.
     /// Always returns a role since roles are mandatory from creation.
     pub fn current_role(&self) -> RoleId {
         self.events
             .iter_all()
             .rev()
-            .map(|event| match event {
-                UserEvent::RoleUpdated { role_id, .. } => *role_id,
-                UserEvent::Initialized { role_id, .. } => *role_id,
+            .find_map(|event| match event {
+                UserEvent::RoleUpdated { role_id, .. } => Some(*role_id),
+                UserEvent::Initialized { role_id, .. } => Some(*role_id),
+                _ => None,
             })
-            .next()
             .expect("User must have a role assigned")
     }
 }
@@ -80,7 +116,9 @@ impl TryFromEvents<UserEvent> for User {
                 UserEvent::Initialized { id, email, .. } => {
                     builder = builder.id(*id).email(email.clone())
                 }
-                UserEvent::RoleUpdated { .. } => (),
+                UserEvent::RoleUpdated { .. }
+                | UserEvent::Deactivated { .. }
+                | UserEvent::Reactivated { .. } => (),
             }
         }
 
@@ -185,4 +223,21 @@ mod tests {
         assert!(matches!(second_role_change, Idempotent::Executed(id) if id == role_1.id));
         assert_eq!(user.current_role(), role_2.id);
     }
+
+    #[test]
+    fn user_deactivation_and_reactivation() {
+        let mut user = new_user();
+        let role = user.current_role();
+        assert!(user.is_active());
+        assert!(matches!(user.reactivate(), Idempotent::Ignored));
+
+        assert!(matches!(user.deactivate(), Idempotent::Executed(id) if id == role));
+        assert!(!user.is_active());
+        assert!(matches!(user.deactivate(), Idempotent::Ignored));
+        assert_eq!(user.current_role(), role);
+
+        assert!(matches!(user.reactivate(), Idempotent::Executed(id) if id == role));
+        assert!(user.is_active());
+        assert!(matches!(user.deactivate(), Idempotent::Executed(_)));
+    }
 }
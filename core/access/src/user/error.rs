@@ -14,6 +14,8 @@ pub enum UserError {
     AuditError(#[from] audit::error::AuditError),
     #[error("SubjectError - SubjectIsNotUser")]
     SubjectIsNotUser,
+    #[error("UserError - UserIsDeactivated: {0}")]
+    UserIsDeactivated(crate::primitives::UserId),
 }
 
 es_entity::from_es_entity_error!(UserError);
@@ -234,6 +234,9 @@ where
             .expect("audit info missing");
 
         let mut user = self.repo.find_by_id(id).await?;
+        if !user.is_active() {
+            return Err(UserError::UserIsDeactivated(user.id));
+        }
 
         if let Idempotent::Executed(previous) = user.update_role(role) {
             self.authz
@@ -246,6 +249,97 @@ where
         Ok(user)
     }
 
+    pub async fn subject_can_deactivate_user(
+        &self,
+        sub: &<Audit as AuditSvc>::Subject,
+        user_id: impl Into<Option<UserId>>,
+        enforce: bool,
+    ) -> Result<Option<AuditInfo>, UserError> {
+        Ok(self
+            .authz
+            .evaluate_permission(
+                sub,
+                CoreAccessObject::user(user_id),
+                CoreAccessAction::USER_DEACTIVATE,
+                enforce,
+            )
+            .await?)
+    }
+
+    pub(crate) async fn deactivate_user(
+        &self,
+        sub: &<Audit as AuditSvc>::Subject,
+        user_id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<User, UserError> {
+        let id = user_id.into();
+
+        self.subject_can_deactivate_user(sub, id, true)
+            .await?
+            .expect("audit info missing");
+
+        let mut user = self.repo.find_by_id(id).await?;
+
+        if let Idempotent::Executed(role_id) = user.deactivate() {
+            self.authz
+                .revoke_role_from_subject(user.id, role_id)
+                .await?;
+            self.repo.update(&mut user).await?;
+        }
+
+        Ok(user)
+    }
+
+    pub async fn subject_can_reactivate_user(
+        &self,
+        sub: &<Audit as AuditSvc>::Subject,
+        user_id: impl Into<Option<UserId>>,
+        enforce: bool,
+    ) -> Result<Option<AuditInfo>, UserError> {
+        Ok(self
+            .authz
+            .evaluate_permission(
+                sub,
+                CoreAccessObject::user(user_id),
+                CoreAccessAction::USER_REACTIVATE,
+                enforce,
+            )
+            .await?)
+    }
+
+    pub(crate) async fn reactivate_user(
+        &self,
+        sub: &<Audit as AuditSvc>::Subject,
+        user_id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<User, UserError> {
+        let id = user_id.into();
+
+        self.subject_can_reactivate_user(sub, id, true)
+            .await?
+            .expect("audit info missing");
+
+        let mut user = self.repo.find_by_id(id).await?;
+
+        if let Idempotent::Executed(role_id) = user.reactivate() {
+            self.authz.assign_role_to_subject(user.id, role_id).await?;
+            self.repo.update(&mut user).await?;
+        }
+
+        Ok(user)
+    }
+
+    /// Returns whether the user behind `id` may still act in the system.
+    /// Used to reject credentials of deactivated users without an audit entry.
+    pub async fn is_active_without_audit(
+        &self,
+        id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<bool, UserError> {
+        match self.repo.find_by_id(id.into()).await {
+            Ok(user) => Ok(user.is_active()),
+            Err(e) if e.was_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Creates a user with `email` and belonging to `role` (superuser).
     /// Used for bootstrapping the application.
     pub(super) async fn bootstrap_superuser_user(
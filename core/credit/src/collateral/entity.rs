@@ -9,10 +9,10 @@ use es_entity::*;
 
 use crate::primitives::{
     CalaAccountId, CollateralAction, CollateralId, CreditFacilityId, CustodyWalletId, LedgerTxId,
-    PendingCreditFacilityId, Satoshis,
+    LiquidationProcessId, PendingCreditFacilityId, Satoshis,
 };
 
-use super::CollateralUpdate;
+use super::{CollateralUpdate, error::CollateralError};
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
@@ -38,6 +38,19 @@ pub enum CollateralEvent {
         abs_diff: Satoshis,
         action: CollateralAction,
     },
+    UpdatedViaLiquidation {
+        ledger_tx_id: LedgerTxId,
+        liquidation_process_id: LiquidationProcessId,
+        collateral_amount: Satoshis,
+        abs_diff: Satoshis,
+        action: CollateralAction,
+    },
+    ReleasedOnCompletion {
+        ledger_tx_id: LedgerTxId,
+        collateral_amount: Satoshis,
+        abs_diff: Satoshis,
+        action: CollateralAction,
+    },
 }
 
 #[derive(EsEntity, Builder)]
@@ -60,11 +73,21 @@ impl Collateral {
             .expect("entity_first_persisted_at not found")
     }
 
+    pub fn is_released(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|e| matches!(e, CollateralEvent::ReleasedOnCompletion { .. }))
+    }
+
     pub fn record_collateral_update_via_custodian_sync(
         &mut self,
         new_amount: Satoshis,
         effective: chrono::NaiveDate,
     ) -> Idempotent<CollateralUpdate> {
+        if self.is_released() {
+            return Idempotent::Ignored;
+        }
+
         let current = self.amount;
 
         let (abs_diff, action) = match new_amount.cmp(&current) {
@@ -123,6 +146,74 @@ impl Collateral {
             effective,
         })
     }
+
+    /// Collateral held for a completed facility is no longer pledged.
+    /// Custodied collateral is returned to the borrower via a `CollateralReturn`.
+    pub fn release_on_completion(
+        &mut self,
+        effective: chrono::NaiveDate,
+    ) -> Idempotent<CollateralUpdate> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            CollateralEvent::ReleasedOnCompletion { .. }
+        );
+
+        let abs_diff = self.amount;
+        let tx_id = LedgerTxId::new();
+
+        self.events.push(CollateralEvent::ReleasedOnCompletion {
+            ledger_tx_id: tx_id,
+            collateral_amount: Satoshis::ZERO,
+            abs_diff,
+            action: CollateralAction::Remove,
+        });
+
+        self.amount = Satoshis::ZERO;
+
+        Idempotent::Executed(CollateralUpdate {
+            tx_id,
+            abs_diff,
+            action: CollateralAction::Remove,
+            effective,
+        })
+    }
+
+    pub fn record_collateral_sold_via_liquidation(
+        &mut self,
+        liquidation_process_id: LiquidationProcessId,
+        collateral_sold: Satoshis,
+        effective: chrono::NaiveDate,
+    ) -> Result<Idempotent<CollateralUpdate>, CollateralError> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            CollateralEvent::UpdatedViaLiquidation { liquidation_process_id: id, .. }
+                if *id == liquidation_process_id
+        );
+
+        if collateral_sold > self.amount {
+            return Err(CollateralError::InsufficientCollateralForLiquidation);
+        }
+
+        let new_amount = self.amount - collateral_sold;
+        let tx_id = LedgerTxId::new();
+
+        self.events.push(CollateralEvent::UpdatedViaLiquidation {
+            ledger_tx_id: tx_id,
+            liquidation_process_id,
+            abs_diff: collateral_sold,
+            collateral_amount: new_amount,
+            action: CollateralAction::Remove,
+        });
+
+        self.amount = new_amount;
+
+        Ok(Idempotent::Executed(CollateralUpdate {
+            tx_id,
+            abs_diff: collateral_sold,
+            action: CollateralAction::Remove,
+            effective,
+        }))
+    }
 }
 
 #[derive(Debug, Builder)]
@@ -173,6 +264,14 @@ impl TryFromEvents<CollateralEvent> for Collateral {
                 | CollateralEvent::UpdatedViaCustodianSync {
                     collateral_amount: new_value,
                     ..
+                }
+                | CollateralEvent::UpdatedViaLiquidation {
+                    collateral_amount: new_value,
+                    ..
+                }
+                | CollateralEvent::ReleasedOnCompletion {
+                    collateral_amount: new_value,
+                    ..
                 } => {
                     builder = builder.amount(*new_value);
                 }
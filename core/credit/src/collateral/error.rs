@@ -10,6 +10,10 @@ pub enum CollateralError {
     CreditLedgerError(#[from] crate::ledger::error::CreditLedgerError),
     #[error("CollateralError - ManualUpdateError: Cannot update collateral with a custodian")]
     ManualUpdateError,
+    #[error(
+        "CollateralError - InsufficientCollateralForLiquidation: Cannot sell more collateral than is held"
+    )]
+    InsufficientCollateralForLiquidation,
 }
 
 es_entity::from_es_entity_error!(CollateralError);
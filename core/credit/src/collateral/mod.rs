@@ -62,6 +62,13 @@ where
         }
     }
 
+    pub async fn find_by_id_without_audit(
+        &self,
+        id: impl Into<CollateralId> + std::fmt::Debug,
+    ) -> Result<Collateral, CollateralError> {
+        self.repo.find_by_id(id.into()).await
+    }
+
     pub async fn find_all<T: From<Collateral>>(
         &self,
         ids: &[CollateralId],
@@ -119,6 +126,61 @@ where
         Ok(res)
     }
 
+    #[instrument(
+        name = "collateral.record_collateral_released_on_completion_in_op",
+        skip(db, self),
+        err
+    )]
+    pub(super) async fn record_collateral_released_on_completion_in_op(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        collateral_id: CollateralId,
+        effective: chrono::NaiveDate,
+    ) -> Result<Option<CollateralUpdate>, CollateralError> {
+        let mut collateral = self.repo.find_by_id(collateral_id).await?;
+
+        let res = if let es_entity::Idempotent::Executed(data) =
+            collateral.release_on_completion(effective)
+        {
+            self.repo.update_in_op(db, &mut collateral).await?;
+            Some(data)
+        } else {
+            None
+        };
+
+        Ok(res)
+    }
+
+    #[instrument(
+        name = "collateral.record_collateral_sold_via_liquidation_in_op",
+        skip(db, self),
+        err
+    )]
+    pub(super) async fn record_collateral_sold_via_liquidation_in_op(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        collateral_id: CollateralId,
+        liquidation_process_id: LiquidationProcessId,
+        collateral_sold: core_money::Satoshis,
+        effective: chrono::NaiveDate,
+    ) -> Result<Option<CollateralUpdate>, CollateralError> {
+        let mut collateral = self.repo.find_by_id(collateral_id).await?;
+
+        let res = if let es_entity::Idempotent::Executed(data) = collateral
+            .record_collateral_sold_via_liquidation(
+                liquidation_process_id,
+                collateral_sold,
+                effective,
+            )? {
+            self.repo.update_in_op(db, &mut collateral).await?;
+            Some(data)
+        } else {
+            None
+        };
+
+        Ok(res)
+    }
+
     #[instrument(
         name = "collateral.record_collateral_update_via_custodian_sync",
         fields(updated_collateral = %updated_collateral, effective = %effective),
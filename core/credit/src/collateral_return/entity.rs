@@ -0,0 +1,334 @@
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use crate::primitives::*;
+
+use super::error::CollateralReturnError;
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "CollateralReturnId")]
+pub enum CollateralReturnEvent {
+    Initialized {
+        id: CollateralReturnId,
+        credit_facility_id: CreditFacilityId,
+        collateral_id: CollateralId,
+        custody_wallet_id: CustodyWalletId,
+        destination_address: String,
+        amount: Satoshis,
+        approval_process_id: ApprovalProcessId,
+    },
+    ApprovalProcessConcluded {
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+    },
+    TransferRequested {
+        external_transfer_id: String,
+    },
+    TransferConfirmed {
+        confirmed_at: DateTime<Utc>,
+    },
+    TransferFailed {
+        failed_at: DateTime<Utc>,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct CollateralReturn {
+    pub id: CollateralReturnId,
+    pub credit_facility_id: CreditFacilityId,
+    pub collateral_id: CollateralId,
+    pub custody_wallet_id: CustodyWalletId,
+    pub destination_address: String,
+    pub amount: Satoshis,
+    pub approval_process_id: ApprovalProcessId,
+    #[builder(setter(strip_option), default)]
+    pub external_transfer_id: Option<String>,
+    events: EntityEvents<CollateralReturnEvent>,
+}
+
+impl CollateralReturn {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("entity_first_persisted_at not found")
+    }
+
+    pub fn status(&self) -> CollateralReturnStatus {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|e| match e {
+                CollateralReturnEvent::ApprovalProcessConcluded { approved: true, .. } => {
+                    Some(CollateralReturnStatus::PendingTransfer)
+                }
+                CollateralReturnEvent::ApprovalProcessConcluded {
+                    approved: false, ..
+                } => Some(CollateralReturnStatus::Denied),
+                CollateralReturnEvent::TransferRequested { .. } => {
+                    Some(CollateralReturnStatus::AwaitingConfirmation)
+                }
+                CollateralReturnEvent::TransferConfirmed { .. } => {
+                    Some(CollateralReturnStatus::Confirmed)
+                }
+                CollateralReturnEvent::TransferFailed { .. } => {
+                    Some(CollateralReturnStatus::Failed)
+                }
+                _ => None,
+            })
+            .unwrap_or(CollateralReturnStatus::PendingApproval)
+    }
+
+    /// Denied and failed returns leave the collateral in custody, so the
+    /// facility may initiate another return.
+    pub fn allows_new_return(&self) -> bool {
+        matches!(
+            self.status(),
+            CollateralReturnStatus::Denied | CollateralReturnStatus::Failed
+        )
+    }
+
+    pub(crate) fn approval_process_concluded(&mut self, approved: bool) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            CollateralReturnEvent::ApprovalProcessConcluded { .. }
+        );
+
+        self.events
+            .push(CollateralReturnEvent::ApprovalProcessConcluded {
+                approval_process_id: self.approval_process_id,
+                approved,
+            });
+
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn record_transfer_requested(
+        &mut self,
+        external_transfer_id: String,
+    ) -> Result<Idempotent<()>, CollateralReturnError> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            CollateralReturnEvent::TransferRequested { .. }
+        );
+
+        if self.status() != CollateralReturnStatus::PendingTransfer {
+            return Err(CollateralReturnError::NotApproved(self.id));
+        }
+
+        self.external_transfer_id = Some(external_transfer_id.clone());
+        self.events.push(CollateralReturnEvent::TransferRequested {
+            external_transfer_id,
+        });
+
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub(crate) fn confirm_transfer(&mut self, confirmed_at: DateTime<Utc>) -> Idempotent<()> {
+        if self.status() != CollateralReturnStatus::AwaitingConfirmation {
+            return Idempotent::Ignored;
+        }
+
+        self.events
+            .push(CollateralReturnEvent::TransferConfirmed { confirmed_at });
+
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn fail_transfer(&mut self, failed_at: DateTime<Utc>) -> Idempotent<()> {
+        if self.status() != CollateralReturnStatus::AwaitingConfirmation {
+            return Idempotent::Ignored;
+        }
+
+        self.events
+            .push(CollateralReturnEvent::TransferFailed { failed_at });
+
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<CollateralReturnEvent> for CollateralReturn {
+    fn try_from_events(events: EntityEvents<CollateralReturnEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = CollateralReturnBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                CollateralReturnEvent::Initialized {
+                    id,
+                    credit_facility_id,
+                    collateral_id,
+                    custody_wallet_id,
+                    destination_address,
+                    amount,
+                    approval_process_id,
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .credit_facility_id(*credit_facility_id)
+                        .collateral_id(*collateral_id)
+                        .custody_wallet_id(*custody_wallet_id)
+                        .destination_address(destination_address.clone())
+                        .amount(*amount)
+                        .approval_process_id(*approval_process_id)
+                }
+                CollateralReturnEvent::TransferRequested {
+                    external_transfer_id,
+                } => builder = builder.external_transfer_id(external_transfer_id.clone()),
+                CollateralReturnEvent::ApprovalProcessConcluded { .. } => (),
+                CollateralReturnEvent::TransferConfirmed { .. } => (),
+                CollateralReturnEvent::TransferFailed { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewCollateralReturn {
+    #[builder(setter(into))]
+    pub(crate) id: CollateralReturnId,
+    #[builder(setter(into))]
+    pub(crate) credit_facility_id: CreditFacilityId,
+    #[builder(setter(into))]
+    pub(crate) collateral_id: CollateralId,
+    #[builder(setter(into))]
+    pub(crate) custody_wallet_id: CustodyWalletId,
+    #[builder(setter(into))]
+    pub(crate) destination_address: String,
+    pub(crate) amount: Satoshis,
+    #[builder(setter(into))]
+    pub(crate) approval_process_id: ApprovalProcessId,
+}
+
+impl NewCollateralReturn {
+    pub fn builder() -> NewCollateralReturnBuilder {
+        NewCollateralReturnBuilder::default()
+    }
+}
+
+impl IntoEvents<CollateralReturnEvent> for NewCollateralReturn {
+    fn into_events(self) -> EntityEvents<CollateralReturnEvent> {
+        EntityEvents::init(
+            self.id,
+            [CollateralReturnEvent::Initialized {
+                id: self.id,
+                credit_facility_id: self.credit_facility_id,
+                collateral_id: self.collateral_id,
+                custody_wallet_id: self.custody_wallet_id,
+                destination_address: self.destination_address,
+                amount: self.amount,
+                approval_process_id: self.approval_process_id,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collateral_return_from(events: Vec<CollateralReturnEvent>) -> CollateralReturn {
+        CollateralReturn::try_from_events(EntityEvents::init(CollateralReturnId::new(), events))
+            .unwrap()
+    }
+
+    fn initial_events() -> Vec<CollateralReturnEvent> {
+        let id = CollateralReturnId::new();
+        vec![CollateralReturnEvent::Initialized {
+            id,
+            credit_facility_id: CreditFacilityId::new(),
+            collateral_id: CollateralId::new(),
+            custody_wallet_id: CustodyWalletId::new(),
+            destination_address: "bc1qdestination".to_string(),
+            amount: Satoshis::from(100_000),
+            approval_process_id: id.into(),
+        }]
+    }
+
+    #[test]
+    fn approved_return_moves_through_transfer_lifecycle() {
+        let mut collateral_return = collateral_return_from(initial_events());
+        assert_eq!(
+            collateral_return.status(),
+            CollateralReturnStatus::PendingApproval
+        );
+
+        assert!(
+            collateral_return
+                .approval_process_concluded(true)
+                .did_execute()
+        );
+        assert_eq!(
+            collateral_return.status(),
+            CollateralReturnStatus::PendingTransfer
+        );
+
+        assert!(
+            collateral_return
+                .record_transfer_requested("transfer-1".to_string())
+                .unwrap()
+                .did_execute()
+        );
+        assert_eq!(
+            collateral_return.external_transfer_id.as_deref(),
+            Some("transfer-1")
+        );
+        assert!(
+            collateral_return
+                .record_transfer_requested("transfer-1".to_string())
+                .unwrap()
+                .was_ignored()
+        );
+
+        assert!(collateral_return.confirm_transfer(Utc::now()).did_execute());
+        assert_eq!(
+            collateral_return.status(),
+            CollateralReturnStatus::Confirmed
+        );
+        assert!(collateral_return.fail_transfer(Utc::now()).was_ignored());
+        assert!(!collateral_return.allows_new_return());
+    }
+
+    #[test]
+    fn denied_return_cannot_request_transfer() {
+        let mut collateral_return = collateral_return_from(initial_events());
+
+        assert!(
+            collateral_return
+                .approval_process_concluded(false)
+                .did_execute()
+        );
+        assert!(
+            collateral_return
+                .approval_process_concluded(true)
+                .was_ignored()
+        );
+        assert_eq!(collateral_return.status(), CollateralReturnStatus::Denied);
+        assert!(collateral_return.allows_new_return());
+
+        assert!(matches!(
+            collateral_return.record_transfer_requested("transfer-1".to_string()),
+            Err(CollateralReturnError::NotApproved(_))
+        ));
+    }
+
+    #[test]
+    fn failed_transfer_frees_up_facility_for_new_return() {
+        let mut collateral_return = collateral_return_from(initial_events());
+        let _ = collateral_return.approval_process_concluded(true);
+        let _ = collateral_return
+            .record_transfer_requested("transfer-1".to_string())
+            .unwrap();
+
+        assert!(collateral_return.fail_transfer(Utc::now()).did_execute());
+        assert_eq!(collateral_return.status(), CollateralReturnStatus::Failed);
+        assert!(collateral_return.allows_new_return());
+        assert!(collateral_return.confirm_transfer(Utc::now()).was_ignored());
+    }
+}
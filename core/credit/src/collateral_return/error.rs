@@ -0,0 +1,35 @@
+use thiserror::Error;
+
+use crate::primitives::{CollateralReturnId, CreditFacilityId};
+
+#[derive(Error, Debug)]
+pub enum CollateralReturnError {
+    #[error("CollateralReturnError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("CollateralReturnError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("CollateralReturnError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("CollateralReturnError - AuthorizationError: {0}")]
+    AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("CollateralReturnError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("CollateralReturnError - GovernanceError: {0}")]
+    GovernanceError(#[from] governance::error::GovernanceError),
+    #[error("CollateralReturnError - CoreCustodyError: {0}")]
+    CoreCustodyError(#[from] core_custody::error::CoreCustodyError),
+    #[error("CollateralReturnError - NotApproved: {0}")]
+    NotApproved(CollateralReturnId),
+    #[error("CollateralReturnError - ReturnAlreadyInProgress: {0}")]
+    ReturnAlreadyInProgress(CreditFacilityId),
+    #[error("CollateralReturnError - CreditFacilityNotCompleted: {0}")]
+    CreditFacilityNotCompleted(CreditFacilityId),
+    #[error("CollateralReturnError - CollateralNotInCustody: {0}")]
+    CollateralNotInCustody(CreditFacilityId),
+    #[error("CollateralReturnError - NoCollateralToReturn: {0}")]
+    NoCollateralToReturn(CreditFacilityId),
+    #[error("CollateralReturnError - InvalidDestinationAddress: {0}")]
+    InvalidDestinationAddress(String),
+}
+
+es_entity::from_es_entity_error!(CollateralReturnError);
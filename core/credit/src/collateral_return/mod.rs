@@ -0,0 +1,355 @@
+mod entity;
+pub mod error;
+mod repo;
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use core_custody::{CoreCustody, CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject, Wallet};
+use governance::{
+    ApprovalProcessContext, Governance, GovernanceAction, GovernanceEvent, GovernanceObject,
+};
+use outbox::OutboxEventMarker;
+
+use crate::{
+    collateral::Collateral, credit_facility::CreditFacility, event::CoreCreditEvent, primitives::*,
+};
+
+pub use entity::CollateralReturn;
+pub(super) use entity::*;
+
+#[cfg(feature = "json-schema")]
+pub use entity::CollateralReturnEvent;
+use error::CollateralReturnError;
+use repo::CollateralReturnRepo;
+
+pub struct CollateralReturns<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    authz: Arc<Perms>,
+    repo: Arc<CollateralReturnRepo>,
+    governance: Arc<Governance<Perms, E>>,
+    custody: Arc<CoreCustody<Perms, E>>,
+}
+
+impl<Perms, E> Clone for CollateralReturns<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            authz: self.authz.clone(),
+            repo: self.repo.clone(),
+            governance: self.governance.clone(),
+            custody: self.custody.clone(),
+        }
+    }
+}
+
+impl<Perms, E> CollateralReturns<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub async fn init(
+        pool: &sqlx::PgPool,
+        authz: Arc<Perms>,
+        governance: Arc<Governance<Perms, E>>,
+        custody: Arc<CoreCustody<Perms, E>>,
+    ) -> Result<Self, CollateralReturnError> {
+        match governance
+            .init_policy(crate::APPROVE_COLLATERAL_RETURN_PROCESS)
+            .await
+        {
+            Err(governance::error::GovernanceError::PolicyError(
+                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+            )) => (),
+            Err(e) => return Err(e.into()),
+            _ => (),
+        }
+
+        Ok(Self {
+            authz,
+            repo: Arc::new(CollateralReturnRepo::new(pool)),
+            governance,
+            custody,
+        })
+    }
+
+    #[instrument(
+        name = "credit.collateral_return.list_for_credit_facility",
+        skip(self),
+        err
+    )]
+    pub async fn list_for_credit_facility(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        credit_facility_id: impl Into<CreditFacilityId> + std::fmt::Debug,
+    ) -> Result<Vec<CollateralReturn>, CollateralReturnError> {
+        let credit_facility_id = credit_facility_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCreditObject::credit_facility(credit_facility_id),
+                CoreCreditAction::CREDIT_FACILITY_READ,
+            )
+            .await?;
+
+        self.list_for_credit_facility_without_audit(credit_facility_id)
+            .await
+    }
+
+    async fn list_for_credit_facility_without_audit(
+        &self,
+        credit_facility_id: CreditFacilityId,
+    ) -> Result<Vec<CollateralReturn>, CollateralReturnError> {
+        Ok(self
+            .repo
+            .list_for_credit_facility_id_by_created_at(
+                credit_facility_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities)
+    }
+
+    #[instrument(
+        name = "credit.collateral_return.initiate",
+        skip(self, credit_facility, collateral),
+        fields(credit_facility_id = %credit_facility.id),
+        err
+    )]
+    pub(crate) async fn initiate(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        credit_facility: &CreditFacility,
+        collateral: &Collateral,
+        destination_address: String,
+    ) -> Result<CollateralReturn, CollateralReturnError> {
+        let credit_facility_id = credit_facility.id;
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCreditObject::credit_facility(credit_facility_id),
+                CoreCreditAction::CREDIT_FACILITY_RETURN_COLLATERAL,
+            )
+            .await?;
+
+        if !credit_facility.is_completed() {
+            return Err(CollateralReturnError::CreditFacilityNotCompleted(
+                credit_facility_id,
+            ));
+        }
+
+        let Some(custody_wallet_id) = collateral.custody_wallet_id else {
+            return Err(CollateralReturnError::CollateralNotInCustody(
+                credit_facility_id,
+            ));
+        };
+
+        let Some(wallet) = self
+            .custody
+            .find_all_wallets::<Wallet>(&[custody_wallet_id])
+            .await?
+            .remove(&custody_wallet_id)
+        else {
+            return Err(CollateralReturnError::NoCollateralToReturn(
+                credit_facility_id,
+            ));
+        };
+
+        if !wallet.network.is_valid_address(&destination_address) {
+            return Err(CollateralReturnError::InvalidDestinationAddress(
+                destination_address,
+            ));
+        }
+
+        let amount = wallet.balance();
+        if amount == Satoshis::ZERO {
+            return Err(CollateralReturnError::NoCollateralToReturn(
+                credit_facility_id,
+            ));
+        }
+
+        let id = CollateralReturnId::new();
+        let new_collateral_return = NewCollateralReturn::builder()
+            .id(id)
+            .credit_facility_id(credit_facility_id)
+            .collateral_id(collateral.id)
+            .custody_wallet_id(custody_wallet_id)
+            .destination_address(destination_address)
+            .amount(amount)
+            .approval_process_id(id)
+            .build()
+            .expect("all fields for new collateral return provided");
+
+        let mut db = self.repo.begin_op().await?;
+        self.governance
+            .start_process(
+                &mut db,
+                new_collateral_return.approval_process_id,
+                credit_facility_id.to_string(),
+                crate::APPROVE_COLLATERAL_RETURN_PROCESS,
+                ApprovalProcessContext::default(),
+            )
+            .await?;
+        // Only one return per facility may be outstanding at a time, which
+        // the partial unique index on `allows_new_return` enforces.
+        let collateral_return = match self.repo.create_in_op(&mut db, new_collateral_return).await {
+            Err(CollateralReturnError::Sqlx(e)) if is_in_progress_violation(&e) => {
+                return Err(CollateralReturnError::ReturnAlreadyInProgress(
+                    credit_facility_id,
+                ));
+            }
+            res => res?,
+        };
+        db.commit().await?;
+
+        Ok(collateral_return)
+    }
+
+    /// Records the approval outcome and, once approved, requests the transfer
+    /// from the custodian. The collateral return id doubles as the custodian
+    /// reference so that a retried request is deduplicated on their side.
+    #[instrument(
+        name = "credit.collateral_return.conclude_approval_process",
+        skip(self),
+        err
+    )]
+    pub(crate) async fn conclude_approval_process(
+        &self,
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+    ) -> Result<CollateralReturn, CollateralReturnError> {
+        let mut collateral_return = self
+            .repo
+            .find_by_approval_process_id(approval_process_id)
+            .await?;
+
+        if collateral_return
+            .approval_process_concluded(approved)
+            .did_execute()
+        {
+            let mut db = self.repo.begin_op().await?;
+            self.authz
+                .audit()
+                .record_system_entry_in_tx(
+                    &mut db,
+                    CoreCreditObject::credit_facility(collateral_return.credit_facility_id),
+                    CoreCreditAction::CREDIT_FACILITY_CONCLUDE_APPROVAL_PROCESS,
+                )
+                .await?;
+            self.repo
+                .update_in_op(&mut db, &mut collateral_return)
+                .await?;
+            db.commit().await?;
+        }
+
+        if collateral_return.status() != CollateralReturnStatus::PendingTransfer {
+            return Ok(collateral_return);
+        }
+
+        let transfer = self
+            .custody
+            .send_from_wallet(
+                collateral_return.custody_wallet_id,
+                &collateral_return.destination_address,
+                collateral_return.amount,
+                &collateral_return.id.to_string(),
+            )
+            .await?;
+
+        if collateral_return
+            .record_transfer_requested(transfer.external_id)?
+            .did_execute()
+        {
+            self.repo.update(&mut collateral_return).await?;
+        }
+
+        Ok(collateral_return)
+    }
+
+    #[instrument(name = "credit.collateral_return.confirm_transfer", skip(self), err)]
+    pub(crate) async fn confirm_transfer(
+        &self,
+        external_transfer_id: &str,
+        confirmed_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), CollateralReturnError> {
+        let Some(mut collateral_return) = self
+            .find_by_external_transfer_id(external_transfer_id)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if collateral_return
+            .confirm_transfer(confirmed_at)
+            .did_execute()
+        {
+            self.repo.update(&mut collateral_return).await?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(name = "credit.collateral_return.fail_transfer", skip(self), err)]
+    pub(crate) async fn fail_transfer(
+        &self,
+        external_transfer_id: &str,
+        failed_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), CollateralReturnError> {
+        let Some(mut collateral_return) = self
+            .find_by_external_transfer_id(external_transfer_id)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if collateral_return.fail_transfer(failed_at).did_execute() {
+            self.repo.update(&mut collateral_return).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Transfers not initiated by a collateral return (e.g. manual custodian
+    /// operations) are not tracked here.
+    async fn find_by_external_transfer_id(
+        &self,
+        external_transfer_id: &str,
+    ) -> Result<Option<CollateralReturn>, CollateralReturnError> {
+        match self
+            .repo
+            .find_by_external_transfer_id(Some(external_transfer_id.to_owned()))
+            .await
+        {
+            Ok(collateral_return) => Ok(Some(collateral_return)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn is_in_progress_violation(error: &sqlx::Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|e| e.constraint())
+        .is_some_and(|constraint| constraint == "idx_core_collateral_returns_in_progress")
+}
@@ -0,0 +1,33 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::*;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "CollateralReturn",
+    err = "CollateralReturnError",
+    columns(
+        credit_facility_id(ty = "CreditFacilityId", list_for, update(persist = false)),
+        approval_process_id(ty = "ApprovalProcessId", update(persist = false)),
+        external_transfer_id(ty = "Option<String>", create(persist = false)),
+        allows_new_return(
+            ty = "bool",
+            create(persist = false),
+            update(accessor = "allows_new_return()")
+        ),
+    ),
+    tbl_prefix = "core"
+)]
+pub struct CollateralReturnRepo {
+    pool: PgPool,
+}
+
+impl CollateralReturnRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
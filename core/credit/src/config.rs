@@ -9,24 +9,27 @@ use serde::{Deserialize, Serialize};
 pub struct CreditConfig {
     #[serde(default = "default_customer_active_check_enabled")]
     pub customer_active_check_enabled: bool,
-    #[serde(default = "default_collateralization_from_price_job_interval_secs")]
-    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
-    #[cfg_attr(feature = "json-schema", schemars(with = "u64"))]
-    pub collateralization_from_price_job_interval: std::time::Duration,
-    #[serde(default = "default_pending_collateralization_from_price_job_interval_secs")]
-    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
-    #[cfg_attr(feature = "json-schema", schemars(with = "u64"))]
-    pub pending_collateralization_from_price_job_interval: std::time::Duration,
+    /// Deprecated: the cure period is part of the facility terms. Ignored.
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
+    pub margin_call_cure_period: Option<u64>,
+    /// Deprecated: collateralization is recomputed on price updates. Ignored.
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
+    pub collateralization_from_price_job_interval: Option<u64>,
+    /// Deprecated: collateralization is recomputed on price updates. Ignored.
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "json-schema", schemars(skip))]
+    pub pending_collateralization_from_price_job_interval: Option<u64>,
 }
 
 impl Default for CreditConfig {
     fn default() -> Self {
         CreditConfig {
             customer_active_check_enabled: default_customer_active_check_enabled(),
-            collateralization_from_price_job_interval:
-                default_collateralization_from_price_job_interval_secs(),
-            pending_collateralization_from_price_job_interval:
-                default_pending_collateralization_from_price_job_interval_secs(),
+            margin_call_cure_period: None,
+            collateralization_from_price_job_interval: None,
+            pending_collateralization_from_price_job_interval: None,
         }
     }
 }
@@ -34,11 +37,3 @@ impl Default for CreditConfig {
 fn default_customer_active_check_enabled() -> bool {
     true
 }
-
-fn default_collateralization_from_price_job_interval_secs() -> std::time::Duration {
-    std::time::Duration::from_secs(30)
-}
-
-fn default_pending_collateralization_from_price_job_interval_secs() -> std::time::Duration {
-    std::time::Duration::from_secs(30)
-}
@@ -8,9 +8,9 @@ use es_entity::*;
 
 use crate::{
     ledger::*,
-    obligation::{NewObligation, ObligationsAmounts},
+    obligation::{NewObligation, ObligationsAmounts, PrincipalInstallment},
     primitives::*,
-    terms::{InterestPeriod, TermValues},
+    terms::{DisbursalPolicy, InterestPeriod, ScheduledDisbursal, TermValues},
 };
 
 use super::{error::CreditFacilityError, interest_accrual_cycle::*};
@@ -34,6 +34,8 @@ pub enum CreditFacilityEvent {
         public_id: PublicId,
         activated_at: DateTime<Utc>,
         maturity_date: EffectiveDate,
+        #[serde(default)]
+        disbursal_policy: DisbursalPolicy,
     },
     InterestAccrualCycleStarted {
         interest_accrual_id: InterestAccrualCycleId,
@@ -50,9 +52,35 @@ pub enum CreditFacilityEvent {
         collateral: Satoshis,
         outstanding: CreditFacilityReceivable,
         price: PriceOfOneBTC,
+        #[serde(default)]
+        price_observation_id: Option<PriceObservationId>,
     },
     CollateralizationRatioChanged {
         collateralization_ratio: CollateralizationRatio,
+        #[serde(default)]
+        price_observation_id: Option<PriceObservationId>,
+    },
+    TermsAmendmentInitiated {
+        approval_process_id: ApprovalProcessId,
+        terms: TermValues,
+    },
+    TermsAmendmentDenied {
+        approval_process_id: ApprovalProcessId,
+    },
+    TermsAmended {
+        approval_process_id: ApprovalProcessId,
+        old_terms: TermValues,
+        new_terms: TermValues,
+        old_maturity_date: EffectiveDate,
+        new_maturity_date: EffectiveDate,
+    },
+    ScheduledDisbursalExecuted {
+        drawdown_idx: usize,
+        disbursal_id: DisbursalId,
+        amount: UsdCents,
+    },
+    LedgerAccountsBackfilled {
+        account_ids: CreditFacilityBackfilledAccountIds,
     },
     Matured {},
     Completed {},
@@ -93,11 +121,37 @@ impl CreditFacilityReceivable {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PayoffQuote {
+    pub credit_facility_id: CreditFacilityId,
+    pub as_of: DateTime<Utc>,
+    pub outstanding: UsdCents,
+    pub accrued_interest: UsdCents,
+    pub prepayment_fee: UsdCents,
+}
+
+impl PayoffQuote {
+    pub fn total(&self) -> UsdCents {
+        self.outstanding + self.accrued_interest + self.prepayment_fee
+    }
+
+    pub(crate) fn obligations_total(&self) -> UsdCents {
+        self.outstanding + self.accrued_interest
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct NewAccrualPeriods {
     pub(crate) accrual: InterestPeriod,
 }
 
+#[derive(Debug)]
+pub(crate) struct RecordedInterestAccrualCycle {
+    pub(crate) accrual_cycle_data: InterestAccrualCycleData,
+    pub(crate) new_obligation: Option<NewObligation>,
+    pub(crate) principal_installment: Option<PrincipalInstallment>,
+}
+
 struct InterestAccrualCycleInCreditFacility {
     idx: InterestAccrualCycleIdx,
     period: InterestPeriod,
@@ -161,6 +215,7 @@ pub struct CreditFacility {
     pub public_id: PublicId,
     pub activated_at: DateTime<Utc>,
     pub maturity_date: EffectiveDate,
+    pub disbursal_policy: DisbursalPolicy,
 
     #[es_entity(nested)]
     #[builder(default)]
@@ -216,7 +271,12 @@ impl CreditFacility {
         self.events
             .iter_all()
             .rev()
-            .any(|event| matches!(event, CreditFacilityEvent::Matured { .. }))
+            .find_map(|event| match event {
+                CreditFacilityEvent::Matured { .. } => Some(true),
+                CreditFacilityEvent::TermsAmended { .. } => Some(false),
+                _ => None,
+            })
+            .unwrap_or(false)
     }
 
     pub fn status(&self) -> CreditFacilityStatus {
@@ -230,20 +290,152 @@ impl CreditFacility {
     }
 
     pub(crate) fn mature(&mut self) -> Idempotent<()> {
-        idempotency_guard!(self.events.iter_all(), CreditFacilityEvent::Matured { .. });
+        if self.is_matured() {
+            return Idempotent::Ignored;
+        }
 
         if self.status() == CreditFacilityStatus::Closed {
             return Idempotent::Ignored;
         }
 
+        // maturity may have been extended by a terms amendment since the job was scheduled
+        if crate::time::now() < self.matures_at() {
+            return Idempotent::Ignored;
+        }
+
         self.events.push(CreditFacilityEvent::Matured {});
         Idempotent::Executed(())
     }
 
+    pub fn pending_terms_amendment(&self) -> Option<(ApprovalProcessId, TermValues)> {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|event| match event {
+                CreditFacilityEvent::TermsAmendmentInitiated {
+                    approval_process_id,
+                    terms,
+                } => Some(Some((*approval_process_id, *terms))),
+                CreditFacilityEvent::TermsAmendmentDenied { .. }
+                | CreditFacilityEvent::TermsAmended { .. } => Some(None),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    pub(crate) fn initiate_terms_amendment(
+        &mut self,
+        approval_process_id: ApprovalProcessId,
+        terms: TermValues,
+    ) -> Result<(), CreditFacilityError> {
+        if self.is_completed() {
+            return Err(CreditFacilityError::AlreadyCompleted);
+        }
+        if self.pending_terms_amendment().is_some() {
+            return Err(CreditFacilityError::TermsAmendmentInProgress);
+        }
+        if terms.maturity_date(self.activated_at).start_of_day() <= crate::time::now() {
+            return Err(CreditFacilityError::AmendedMaturityDateInPast);
+        }
+
+        self.events
+            .push(CreditFacilityEvent::TermsAmendmentInitiated {
+                approval_process_id,
+                terms,
+            });
+
+        Ok(())
+    }
+
+    pub(crate) fn conclude_terms_amendment(
+        &mut self,
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+    ) -> Idempotent<()> {
+        let new_terms = match self.pending_terms_amendment() {
+            Some((id, terms)) if id == approval_process_id => terms,
+            _ => return Idempotent::Ignored,
+        };
+
+        if !approved || self.is_completed() {
+            self.events.push(CreditFacilityEvent::TermsAmendmentDenied {
+                approval_process_id,
+            });
+            return Idempotent::Executed(());
+        }
+
+        let new_maturity_date = new_terms.maturity_date(self.activated_at);
+        self.events.push(CreditFacilityEvent::TermsAmended {
+            approval_process_id,
+            old_terms: self.terms,
+            new_terms,
+            old_maturity_date: self.maturity_date,
+            new_maturity_date,
+        });
+        self.terms = new_terms;
+        self.maturity_date = new_maturity_date;
+
+        Idempotent::Executed(())
+    }
+
     pub(crate) fn check_disbursal_date(&self, initiated_at: DateTime<Utc>) -> bool {
         initiated_at < self.matures_at()
     }
 
+    pub(crate) fn next_scheduled_disbursal(&self) -> Option<(usize, ScheduledDisbursal)> {
+        let executed = self
+            .events
+            .iter_all()
+            .filter(|event| {
+                matches!(
+                    event,
+                    CreditFacilityEvent::ScheduledDisbursalExecuted { .. }
+                )
+            })
+            .count();
+
+        self.disbursal_policy
+            .drawdowns(self.activated_at, self.amount - self.structuring_fee())
+            .into_iter()
+            .enumerate()
+            .nth(executed)
+    }
+
+    pub(crate) fn next_scheduled_disbursal_at(&self) -> Option<DateTime<Utc>> {
+        if self.is_completed() {
+            return None;
+        }
+
+        let (_, drawdown) = self.next_scheduled_disbursal()?;
+        let drawdown_at = EffectiveDate::from(drawdown.date)
+            .start_of_day()
+            .max(self.activated_at);
+        self.check_disbursal_date(drawdown_at)
+            .then_some(drawdown_at)
+    }
+
+    pub(crate) fn record_scheduled_disbursal(
+        &mut self,
+        drawdown_idx: usize,
+        disbursal_id: DisbursalId,
+        amount: UsdCents,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            CreditFacilityEvent::ScheduledDisbursalExecuted { drawdown_idx: idx, .. }
+                if *idx == drawdown_idx
+        );
+
+        self.events
+            .push(CreditFacilityEvent::ScheduledDisbursalExecuted {
+                drawdown_idx,
+                disbursal_id,
+                amount,
+            });
+
+        Idempotent::Executed(())
+    }
+
     fn last_started_accrual_cycle(&self) -> Option<InterestAccrualCycleInCreditFacility> {
         self.events.iter_all().rev().find_map(|event| match event {
             CreditFacilityEvent::InterestAccrualCycleStarted {
@@ -348,13 +540,17 @@ impl CreditFacility {
 
     pub(crate) fn record_interest_accrual_cycle(
         &mut self,
-    ) -> Result<Idempotent<(InterestAccrualCycleData, Option<NewObligation>)>, CreditFacilityError>
-    {
-        let accrual_cycle_data = self
-            .interest_accrual_cycle_in_progress()
-            .expect("accrual not found")
+        principal_to_amortize: UsdCents,
+    ) -> Result<Idempotent<RecordedInterestAccrualCycle>, CreditFacilityError> {
+        // the cycle may already have been posted early by a payoff
+        let Some(accrual_cycle) = self.interest_accrual_cycle_in_progress() else {
+            return Ok(Idempotent::Ignored);
+        };
+        let accrual_cycle_data = accrual_cycle
             .accrual_cycle_data()
             .ok_or(CreditFacilityError::InterestAccrualNotCompletedYet)?;
+        let principal_installment =
+            accrual_cycle.principal_installment(&accrual_cycle_data, principal_to_amortize);
 
         let (idx, new_obligation) = {
             let accrual = self
@@ -379,7 +575,11 @@ impl CreditFacility {
                 ledger_tx_id: accrual_cycle_data.tx_id,
             });
 
-        Ok(Idempotent::Executed((accrual_cycle_data, new_obligation)))
+        Ok(Idempotent::Executed(RecordedInterestAccrualCycle {
+            accrual_cycle_data,
+            new_obligation,
+            principal_installment,
+        }))
     }
 
     pub fn interest_accrual_cycle_in_progress(&self) -> Option<&InterestAccrualCycle> {
@@ -430,20 +630,43 @@ impl CreditFacility {
             .unwrap_or_default()
     }
 
+    /// Whether moving to `price` would cross one of the thresholds of the facility, judging by
+    /// the last recorded collateralization ratio.
+    pub(crate) fn collateralization_could_change(
+        &self,
+        price: PriceOfOneBTC,
+        upgrade_buffer_cvl_pct: CVLPct,
+    ) -> bool {
+        match self.last_collateralization_state() {
+            CollateralizationState::NoExposure => false,
+            state => self
+                .terms
+                .collateralization_update(
+                    self.last_collateralization_ratio().cvl(price),
+                    state,
+                    Some(upgrade_buffer_cvl_pct),
+                    false,
+                )
+                .is_some(),
+        }
+    }
+
     pub(crate) fn update_collateralization(
         &mut self,
-        price: PriceOfOneBTC,
+        price: &PriceObservation,
         upgrade_buffer_cvl_pct: CVLPct,
         balances: CreditFacilityBalanceSummary,
     ) -> Idempotent<Option<CollateralizationState>> {
-        let ratio_changed = self.update_collateralization_ratio(&balances).did_execute();
+        let ratio_changed = self
+            .update_collateralization_ratio(&balances, price.id)
+            .did_execute();
 
         let last_collateralization_state = self.last_collateralization_state();
 
         let collateralization_update = match self.status() {
             CreditFacilityStatus::Active | CreditFacilityStatus::Matured => {
                 self.terms.collateralization_update(
-                    balances.current_cvl(price),
+                    balances.current_cvl(price.price),
                     last_collateralization_state,
                     Some(upgrade_buffer_cvl_pct),
                     false,
@@ -458,7 +681,8 @@ impl CreditFacility {
                     collateralization_state: calculated_collateralization,
                     collateral: balances.collateral(),
                     outstanding: balances.into(),
-                    price,
+                    price: price.price,
+                    price_observation_id: Some(price.id),
                 });
 
             Idempotent::Executed(Some(calculated_collateralization))
@@ -476,6 +700,23 @@ impl CreditFacility {
             .any(|event| matches!(event, CreditFacilityEvent::Completed { .. }))
     }
 
+    /// Allocates the accounts introduced after this facility was activated.
+    pub(crate) fn backfill_ledger_accounts(
+        &mut self,
+    ) -> Idempotent<CreditFacilityBackfilledAccountIds> {
+        let existing = self.account_ids.backfilled_account_ids();
+        if existing.is_complete() {
+            return Idempotent::Ignored;
+        }
+
+        let account_ids = existing.completed();
+        self.account_ids = self.account_ids.with_backfilled_account_ids(account_ids);
+        self.events
+            .push(CreditFacilityEvent::LedgerAccountsBackfilled { account_ids });
+
+        Idempotent::Executed(account_ids)
+    }
+
     pub(crate) fn complete(
         &mut self,
         _price: PriceOfOneBTC,
@@ -501,9 +742,103 @@ impl CreditFacility {
         Ok(Idempotent::Executed(res))
     }
 
+    pub(crate) fn payoff_quote(
+        &self,
+        as_of: DateTime<Utc>,
+        balances: CreditFacilityBalanceSummary,
+        outstanding: UsdCents,
+    ) -> Result<PayoffQuote, CreditFacilityError> {
+        if self.is_completed() {
+            return Err(CreditFacilityError::AlreadyCompleted);
+        }
+
+        let principal = balances.disbursed_outstanding();
+        let accrued_interest = self
+            .interest_accrual_cycle_in_progress()
+            .map(|cycle| cycle.accrued_interest_until(principal, as_of))
+            .unwrap_or(UsdCents::ZERO);
+        let prepayment_fee = if as_of < self.matures_at() {
+            self.terms.prepayment_fee(principal)
+        } else {
+            UsdCents::ZERO
+        };
+
+        Ok(PayoffQuote {
+            credit_facility_id: self.id,
+            as_of,
+            outstanding,
+            accrued_interest,
+            prepayment_fee,
+        })
+    }
+
+    pub(crate) fn pay_off(
+        &mut self,
+        quote: &PayoffQuote,
+        balances: CreditFacilityBalanceSummary,
+    ) -> Result<(CreditFacilityPayoff, Option<NewObligation>), CreditFacilityError> {
+        if self.is_completed() {
+            return Err(CreditFacilityError::AlreadyCompleted);
+        }
+
+        let account_ids = self.account_ids;
+        let (accruals, accrual_cycle, new_obligation) = match self
+            .interest_accrual_cycle_in_progress_mut()
+        {
+            Some(cycle) => {
+                let accruals = cycle
+                    .record_accruals_until(balances.disbursed_outstanding(), quote.as_of)
+                    .into_iter()
+                    .map(|accrual| (accrual, account_ids).into())
+                    .collect::<Vec<_>>();
+                let accrual_cycle_data = cycle.early_accrual_cycle_data();
+                let new_obligation = match cycle.record_accrual_cycle(accrual_cycle_data.clone()) {
+                    Idempotent::Executed(new_obligation) => new_obligation,
+                    Idempotent::Ignored => None,
+                };
+                let idx = cycle.idx;
+
+                self.events
+                    .push(CreditFacilityEvent::InterestAccrualCycleConcluded {
+                        interest_accrual_cycle_idx: idx,
+                        obligation_id: new_obligation.as_ref().map(|o| o.id),
+                        ledger_tx_id: accrual_cycle_data.tx_id,
+                    });
+
+                (
+                    accruals,
+                    Some((accrual_cycle_data, account_ids).into()),
+                    new_obligation,
+                )
+            }
+            None => (vec![], None, None),
+        };
+
+        let completion = CreditFacilityCompletion {
+            tx_id: LedgerTxId::new(),
+            collateral: balances.collateral(),
+            credit_facility_account_ids: account_ids,
+        };
+        self.events.push(CreditFacilityEvent::Completed {});
+
+        Ok((
+            CreditFacilityPayoff {
+                accruals,
+                accrual_cycle,
+                prepayment_fee_tx_id: LedgerTxId::new(),
+                prepayment_fee: quote.prepayment_fee,
+                debit_account_id: self.disbursal_credit_account_id,
+                effective: quote.as_of.date_naive(),
+                completion,
+            },
+            new_obligation,
+        ))
+    }
+
     fn update_collateralization_ratio(
         &mut self,
         balance: &CreditFacilityBalanceSummary,
+        price_observation_id: PriceObservationId,
     ) -> Idempotent<()> {
         let ratio = balance.current_collateralization_ratio();
 
@@ -511,6 +846,7 @@ impl CreditFacility {
             self.events
                 .push(CreditFacilityEvent::CollateralizationRatioChanged {
                     collateralization_ratio: ratio,
+                    price_observation_id: Some(price_observation_id),
                 });
         } else {
             return Idempotent::Ignored;
@@ -523,6 +859,7 @@ impl CreditFacility {
 impl TryFromEvents<CreditFacilityEvent> for CreditFacility {
     fn try_from_events(events: EntityEvents<CreditFacilityEvent>) -> Result<Self, EsEntityError> {
         let mut builder = CreditFacilityBuilder::default();
+        let mut facility_account_ids = None;
         for event in events.iter_all() {
             match event {
                 CreditFacilityEvent::Initialized {
@@ -537,6 +874,7 @@ impl TryFromEvents<CreditFacilityEvent> for CreditFacility {
                     public_id,
                     maturity_date,
                     activated_at,
+                    disbursal_policy,
                     ..
                 } => {
                     builder = builder
@@ -550,12 +888,31 @@ impl TryFromEvents<CreditFacilityEvent> for CreditFacility {
                         .disbursal_credit_account_id(*disbursal_credit_account_id)
                         .public_id(public_id.clone())
                         .activated_at(*activated_at)
-                        .maturity_date(*maturity_date);
+                        .maturity_date(*maturity_date)
+                        .disbursal_policy(disbursal_policy.clone());
+                    facility_account_ids = Some(*account_ids);
                 }
                 CreditFacilityEvent::InterestAccrualCycleStarted { .. } => (),
                 CreditFacilityEvent::InterestAccrualCycleConcluded { .. } => (),
                 CreditFacilityEvent::CollateralizationStateChanged { .. } => (),
                 CreditFacilityEvent::CollateralizationRatioChanged { .. } => (),
+                CreditFacilityEvent::TermsAmendmentInitiated { .. } => (),
+                CreditFacilityEvent::TermsAmendmentDenied { .. } => (),
+                CreditFacilityEvent::TermsAmended {
+                    new_terms,
+                    new_maturity_date,
+                    ..
+                } => {
+                    builder = builder.terms(*new_terms).maturity_date(*new_maturity_date);
+                }
+                CreditFacilityEvent::ScheduledDisbursalExecuted { .. } => (),
+                CreditFacilityEvent::LedgerAccountsBackfilled { account_ids } => {
+                    if let Some(ids) = facility_account_ids {
+                        let ids = ids.with_backfilled_account_ids(*account_ids);
+                        builder = builder.account_ids(ids);
+                        facility_account_ids = Some(ids);
+                    }
+                }
                 CreditFacilityEvent::Matured { .. } => (),
                 CreditFacilityEvent::Completed { .. } => (),
             }
@@ -577,10 +934,12 @@ pub struct NewCreditFacility {
     pub(super) customer_type: CustomerType,
     #[builder(setter(into))]
     pub(super) collateral_id: CollateralId,
-    terms: TermValues,
+    pub(super) terms: TermValues,
     amount: UsdCents,
     activated_at: DateTime<Utc>,
     maturity_date: EffectiveDate,
+    #[builder(default)]
+    disbursal_policy: DisbursalPolicy,
     #[builder(setter(skip), default)]
     pub(super) status: CreditFacilityStatus,
     #[builder(setter(skip), default)]
@@ -615,6 +974,7 @@ impl IntoEvents<CreditFacilityEvent> for NewCreditFacility {
                 public_id: self.public_id,
                 activated_at: self.activated_at,
                 maturity_date: self.maturity_date,
+                disbursal_policy: self.disbursal_policy,
             }],
         )
     }
@@ -625,7 +985,7 @@ mod test {
     use rust_decimal_macros::dec;
 
     use crate::{
-        terms::{FacilityDuration, InterestInterval, OneTimeFeeRatePct},
+        terms::{AnnualRatePct, FacilityDuration, InterestInterval, OneTimeFeeRatePct},
         *,
     };
 
@@ -688,6 +1048,9 @@ mod test {
             interest_defaulted_account_id: CalaAccountId::new(),
             interest_income_account_id: CalaAccountId::new(),
             fee_income_account_id: CalaAccountId::new(),
+            unapplied_payments_account_id: CalaAccountId::new(),
+            penalty_receivable_account_id: CalaAccountId::new(),
+            penalty_income_account_id: CalaAccountId::new(),
         }
     }
 
@@ -707,6 +1070,7 @@ mod test {
             public_id: PublicId::new(format!("test-public-id-{}", uuid::Uuid::new_v4())),
             activated_at: activated_at(),
             maturity_date: EffectiveDate::from(activated_at() + chrono::Duration::days(90)),
+            disbursal_policy: DisbursalPolicy::default(),
         }]
     }
 
@@ -879,6 +1243,109 @@ mod test {
         }
     }
 
+    mod terms_amendment {
+        use super::*;
+
+        fn extended_terms() -> TermValues {
+            let mut terms = default_terms();
+            terms.duration = FacilityDuration::Months(1200);
+            terms.annual_rate = dec!(8).into();
+            terms
+        }
+
+        #[test]
+        fn approved_amendment_updates_terms_and_maturity() {
+            let mut credit_facility = facility_from(initial_events());
+            let approval_process_id = ApprovalProcessId::new();
+            let old_maturity_date = credit_facility.maturity_date;
+
+            credit_facility
+                .initiate_terms_amendment(approval_process_id, extended_terms())
+                .unwrap();
+            assert!(credit_facility.pending_terms_amendment().is_some());
+
+            assert!(
+                credit_facility
+                    .conclude_terms_amendment(approval_process_id, true)
+                    .did_execute()
+            );
+            assert_eq!(
+                credit_facility.terms.annual_rate,
+                AnnualRatePct::from(dec!(8))
+            );
+            assert!(matches!(
+                credit_facility.terms.duration,
+                FacilityDuration::Months(1200)
+            ));
+            assert!(credit_facility.maturity_date > old_maturity_date);
+            assert!(credit_facility.pending_terms_amendment().is_none());
+        }
+
+        #[test]
+        fn denied_amendment_keeps_terms() {
+            let mut credit_facility = facility_from(initial_events());
+            let approval_process_id = ApprovalProcessId::new();
+
+            credit_facility
+                .initiate_terms_amendment(approval_process_id, extended_terms())
+                .unwrap();
+            assert!(
+                credit_facility
+                    .conclude_terms_amendment(approval_process_id, false)
+                    .did_execute()
+            );
+            assert_eq!(
+                credit_facility.terms.annual_rate,
+                AnnualRatePct::from(dec!(12))
+            );
+            assert!(matches!(
+                credit_facility.terms.duration,
+                FacilityDuration::Months(3)
+            ));
+            assert!(credit_facility.pending_terms_amendment().is_none());
+        }
+
+        #[test]
+        fn errors_if_amendment_in_progress() {
+            let mut credit_facility = facility_from(initial_events());
+
+            credit_facility
+                .initiate_terms_amendment(ApprovalProcessId::new(), extended_terms())
+                .unwrap();
+            assert!(matches!(
+                credit_facility
+                    .initiate_terms_amendment(ApprovalProcessId::new(), extended_terms()),
+                Err(CreditFacilityError::TermsAmendmentInProgress)
+            ));
+        }
+
+        #[test]
+        fn errors_if_amended_maturity_in_past() {
+            let mut credit_facility = facility_from(initial_events());
+
+            assert!(matches!(
+                credit_facility.initiate_terms_amendment(ApprovalProcessId::new(), default_terms()),
+                Err(CreditFacilityError::AmendedMaturityDateInPast)
+            ));
+        }
+
+        #[test]
+        fn conclude_is_idempotent() {
+            let mut credit_facility = facility_from(initial_events());
+            let approval_process_id = ApprovalProcessId::new();
+
+            credit_facility
+                .initiate_terms_amendment(approval_process_id, extended_terms())
+                .unwrap();
+            let _ = credit_facility.conclude_terms_amendment(approval_process_id, true);
+            assert!(
+                credit_facility
+                    .conclude_terms_amendment(approval_process_id, true)
+                    .was_ignored()
+            );
+        }
+    }
+
     #[test]
     fn structuring_fee() {
         let credit_facility = facility_from(initial_events());
@@ -886,6 +1353,203 @@ mod test {
         assert_eq!(credit_facility.structuring_fee(), expected_fee);
     }
 
+    mod scheduled_disbursals {
+        use super::*;
+
+        fn facility_with_policy(policy: DisbursalPolicy) -> CreditFacility {
+            let mut events = initial_events();
+            if let CreditFacilityEvent::Initialized {
+                disbursal_policy, ..
+            } = &mut events[0]
+            {
+                *disbursal_policy = policy;
+            }
+            facility_from(events)
+        }
+
+        #[test]
+        fn none_for_on_demand() {
+            let credit_facility = facility_with_policy(DisbursalPolicy::OnDemand);
+            assert!(credit_facility.next_scheduled_disbursal().is_none());
+            assert!(credit_facility.next_scheduled_disbursal_at().is_none());
+        }
+
+        #[test]
+        fn full_at_activation_disburses_remainder_after_structuring_fee() {
+            let mut credit_facility = facility_with_policy(DisbursalPolicy::FullAtActivation);
+
+            let (drawdown_idx, drawdown) = credit_facility.next_scheduled_disbursal().unwrap();
+            assert_eq!(drawdown_idx, 0);
+            assert_eq!(
+                drawdown.amount,
+                default_facility() - credit_facility.structuring_fee()
+            );
+            assert_eq!(
+                credit_facility.next_scheduled_disbursal_at(),
+                Some(activated_at())
+            );
+
+            assert!(
+                credit_facility
+                    .record_scheduled_disbursal(drawdown_idx, DisbursalId::new(), drawdown.amount)
+                    .did_execute()
+            );
+            assert!(credit_facility.next_scheduled_disbursal_at().is_none());
+        }
+
+        #[test]
+        fn scheduled_drawdowns_are_executed_in_order_once() {
+            let first = ScheduledDisbursal {
+                date: (activated_at() + chrono::Duration::days(10)).date_naive(),
+                amount: UsdCents::from(3_00),
+            };
+            let second = ScheduledDisbursal {
+                date: (activated_at() + chrono::Duration::days(40)).date_naive(),
+                amount: UsdCents::from(5_00),
+            };
+            let after_maturity = ScheduledDisbursal {
+                date: (activated_at() + chrono::Duration::days(120)).date_naive(),
+                amount: UsdCents::from(1_00),
+            };
+            let mut credit_facility = facility_with_policy(DisbursalPolicy::Scheduled(vec![
+                after_maturity,
+                second,
+                first,
+            ]));
+
+            assert_eq!(credit_facility.next_scheduled_disbursal(), Some((0, first)));
+            assert_eq!(
+                credit_facility.next_scheduled_disbursal_at(),
+                Some(EffectiveDate::from(first.date).start_of_day())
+            );
+
+            let _ = credit_facility.record_scheduled_disbursal(0, DisbursalId::new(), first.amount);
+            assert!(
+                credit_facility
+                    .record_scheduled_disbursal(0, DisbursalId::new(), first.amount)
+                    .was_ignored()
+            );
+            assert_eq!(
+                credit_facility.next_scheduled_disbursal(),
+                Some((1, second))
+            );
+
+            let _ =
+                credit_facility.record_scheduled_disbursal(1, DisbursalId::new(), second.amount);
+            assert_eq!(
+                credit_facility.next_scheduled_disbursal(),
+                Some((2, after_maturity))
+            );
+            assert!(credit_facility.next_scheduled_disbursal_at().is_none());
+        }
+    }
+
+    mod payoff {
+        #![allow(clippy::inconsistent_digit_grouping)]
+
+        use super::*;
+
+        fn facility_with_prepayment_fee() -> CreditFacility {
+            let mut events = initial_events();
+            if let CreditFacilityEvent::Initialized { terms, .. } = &mut events[0] {
+                terms.prepayment_fee_rate = Some(OneTimeFeeRatePct::new(2));
+            }
+            facility_from(events)
+        }
+
+        fn balances() -> CreditFacilityBalanceSummary {
+            CreditFacilityBalanceSummary {
+                not_yet_due_disbursed_outstanding: UsdCents::from(1_000_00),
+                collateral: Satoshis::from(1_000_000),
+
+                due_disbursed_outstanding: UsdCents::ZERO,
+                overdue_disbursed_outstanding: UsdCents::ZERO,
+                disbursed_defaulted: UsdCents::ZERO,
+                not_yet_due_interest_outstanding: UsdCents::ZERO,
+                due_interest_outstanding: UsdCents::ZERO,
+                overdue_interest_outstanding: UsdCents::ZERO,
+                interest_defaulted: UsdCents::ZERO,
+                penalty_outstanding: UsdCents::ZERO,
+                facility: UsdCents::from(2_000_00),
+                facility_remaining: UsdCents::from(1_000_00),
+                disbursed: UsdCents::from(1_000_00),
+                interest_posted: UsdCents::ZERO,
+            }
+        }
+
+        #[test]
+        fn prepayment_fee_only_charged_before_maturity() {
+            let credit_facility = facility_with_prepayment_fee();
+
+            let quote = credit_facility
+                .payoff_quote(
+                    activated_at() + chrono::Duration::days(10),
+                    balances(),
+                    UsdCents::from(1_000_00),
+                )
+                .unwrap();
+            assert_eq!(quote.prepayment_fee, UsdCents::from(20_00));
+            assert_eq!(quote.accrued_interest, UsdCents::ZERO);
+            assert_eq!(quote.total(), UsdCents::from(1_020_00));
+
+            let quote = credit_facility
+                .payoff_quote(
+                    credit_facility.matures_at() + chrono::Duration::days(1),
+                    balances(),
+                    UsdCents::from(1_000_00),
+                )
+                .unwrap();
+            assert_eq!(quote.prepayment_fee, UsdCents::ZERO);
+        }
+
+        #[test]
+        fn quote_includes_accrued_interest() {
+            let mut credit_facility = facility_with_prepayment_fee();
+            start_interest_accrual_cycle(&mut credit_facility);
+
+            let as_of = activated_at() + chrono::Duration::days(4);
+            let quote = credit_facility
+                .payoff_quote(as_of, balances(), UsdCents::from(1_000_00))
+                .unwrap();
+
+            let expected_interest = default_terms()
+                .annual_rate
+                .interest_for_time_period(balances().disbursed_outstanding(), 5);
+            assert_eq!(quote.accrued_interest, expected_interest);
+        }
+
+        #[test]
+        fn pay_off_posts_accrual_cycle_and_completes() {
+            let mut credit_facility = facility_with_prepayment_fee();
+            start_interest_accrual_cycle(&mut credit_facility);
+
+            let as_of = activated_at() + chrono::Duration::days(4);
+            let quote = credit_facility
+                .payoff_quote(as_of, balances(), UsdCents::from(1_000_00))
+                .unwrap();
+            let (payoff, new_obligation) = credit_facility.pay_off(&quote, balances()).unwrap();
+
+            assert_eq!(payoff.accruals.len(), 5);
+            assert_eq!(
+                payoff.accrual_cycle.map(|cycle| cycle.interest),
+                Some(quote.accrued_interest)
+            );
+            assert!(new_obligation.is_some());
+            assert_eq!(payoff.prepayment_fee, quote.prepayment_fee);
+            assert!(
+                credit_facility
+                    .interest_accrual_cycle_in_progress()
+                    .is_none()
+            );
+            assert!(credit_facility.is_completed());
+
+            assert!(matches!(
+                credit_facility.pay_off(&quote, balances()),
+                Err(CreditFacilityError::AlreadyCompleted)
+            ));
+        }
+    }
+
     mod completion {
         use super::*;
 
@@ -916,6 +1580,7 @@ mod test {
                         due_interest_outstanding: UsdCents::ZERO,
                         overdue_interest_outstanding: UsdCents::ZERO,
                         interest_defaulted: UsdCents::ZERO,
+                        penalty_outstanding: UsdCents::ZERO,
 
                         facility: UsdCents::from(2),
                         facility_remaining: UsdCents::from(1),
@@ -946,6 +1611,7 @@ mod test {
                     due_interest_outstanding: UsdCents::ZERO,
                     overdue_interest_outstanding: UsdCents::ZERO,
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     facility: UsdCents::from(2),
                     facility_remaining: UsdCents::from(1),
@@ -972,6 +1638,7 @@ mod test {
                     due_interest_outstanding: UsdCents::ZERO,
                     overdue_interest_outstanding: UsdCents::ZERO,
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     facility: UsdCents::from(2),
                     facility_remaining: UsdCents::from(1),
@@ -1003,6 +1670,7 @@ mod test {
                     not_yet_due_interest_outstanding: UsdCents::ZERO,
                     overdue_interest_outstanding: UsdCents::ZERO,
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     facility: UsdCents::from(2),
                     facility_remaining: UsdCents::from(1),
@@ -1029,6 +1697,7 @@ mod test {
                     not_yet_due_interest_outstanding: UsdCents::ZERO,
                     overdue_interest_outstanding: UsdCents::ZERO,
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     facility: UsdCents::from(2),
                     facility_remaining: UsdCents::from(1),
@@ -1060,6 +1729,7 @@ mod test {
                     not_yet_due_interest_outstanding: UsdCents::ZERO,
                     due_interest_outstanding: UsdCents::ZERO,
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     facility: UsdCents::from(2),
                     facility_remaining: UsdCents::from(1),
@@ -1086,6 +1756,7 @@ mod test {
                     not_yet_due_interest_outstanding: UsdCents::ZERO,
                     due_interest_outstanding: UsdCents::ZERO,
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     facility: UsdCents::from(2),
                     facility_remaining: UsdCents::from(1),
@@ -1109,6 +1780,7 @@ mod test {
                 CreditFacilityBalanceSummary {
                     disbursed_defaulted: UsdCents::from(1),
                     interest_defaulted: UsdCents::ZERO,
+                    penalty_outstanding: UsdCents::ZERO,
 
                     collateral: Satoshis::ZERO,
                     not_yet_due_disbursed_outstanding: UsdCents::ZERO,
@@ -1135,6 +1807,7 @@ mod test {
                 CreditFacilityBalanceSummary {
                     disbursed_defaulted: UsdCents::ZERO,
                     interest_defaulted: UsdCents::from(1),
+                    penalty_outstanding: UsdCents::ZERO,
 
                     collateral: Satoshis::ZERO,
                     not_yet_due_disbursed_outstanding: UsdCents::ZERO,
@@ -1156,4 +1829,115 @@ mod test {
             ));
         }
     }
+
+    mod backfill_ledger_accounts {
+        use super::*;
+
+        fn legacy_account_ids() -> CreditFacilityLedgerAccountIds {
+            let mut json = serde_json::to_value(account_ids()).unwrap();
+            let fields = json.as_object_mut().unwrap();
+            fields.remove("unapplied_payments_account_id");
+            fields.remove("penalty_receivable_account_id");
+            fields.remove("penalty_income_account_id");
+            serde_json::from_value(json).unwrap()
+        }
+
+        #[test]
+        fn allocates_missing_accounts_once() {
+            let mut events = initial_events();
+            let legacy = legacy_account_ids();
+            if let CreditFacilityEvent::Initialized { account_ids, .. } = &mut events[0] {
+                *account_ids = legacy;
+            }
+            let mut credit_facility = facility_from(events);
+            assert!(
+                !credit_facility
+                    .account_ids
+                    .backfilled_account_ids()
+                    .is_complete()
+            );
+
+            let backfilled = credit_facility.backfill_ledger_accounts().unwrap();
+            assert!(backfilled.is_complete());
+            assert_eq!(
+                credit_facility.account_ids.backfilled_account_ids(),
+                backfilled
+            );
+            assert_eq!(
+                credit_facility.account_ids.facility_account_id,
+                legacy.facility_account_id
+            );
+
+            assert!(credit_facility.backfill_ledger_accounts().was_ignored());
+        }
+
+        #[test]
+        fn ignores_facilities_with_all_accounts() {
+            let mut credit_facility = facility_from(initial_events());
+            assert!(credit_facility.backfill_ledger_accounts().was_ignored());
+        }
+    }
+
+    mod collateralization_could_change {
+        use super::*;
+
+        fn facility_in(state: CollateralizationState) -> CreditFacility {
+            let mut events = initial_events();
+            events.extend([
+                CreditFacilityEvent::CollateralizationRatioChanged {
+                    collateralization_ratio: CollateralizationRatio::Finite(dec!(30)),
+                    price_observation_id: None,
+                },
+                CreditFacilityEvent::CollateralizationStateChanged {
+                    collateralization_state: state,
+                    collateral: Satoshis::from(1_000_000),
+                    outstanding: CreditFacilityReceivable {
+                        disbursed: UsdCents::from(100_000),
+                        interest: UsdCents::ZERO,
+                    },
+                    price: default_price(),
+                    price_observation_id: None,
+                },
+            ]);
+            facility_from(events)
+        }
+
+        fn price(usd: u64) -> PriceOfOneBTC {
+            PriceOfOneBTC::new(UsdCents::from(usd * 100))
+        }
+
+        #[test]
+        fn only_when_margin_call_threshold_is_crossed() {
+            let facility = facility_in(CollateralizationState::FullyCollateralized);
+            assert!(
+                !facility.collateralization_could_change(
+                    price(44_000),
+                    default_upgrade_buffer_cvl_pct()
+                )
+            );
+            assert!(
+                facility.collateralization_could_change(
+                    price(40_000),
+                    default_upgrade_buffer_cvl_pct()
+                )
+            );
+        }
+
+        #[test]
+        fn only_when_upgrade_buffer_is_cleared() {
+            let facility = facility_in(CollateralizationState::UnderMarginCallThreshold);
+            assert!(
+                !facility.collateralization_could_change(
+                    price(43_000),
+                    default_upgrade_buffer_cvl_pct()
+                )
+            );
+            assert!(
+                facility.collateralization_could_change(
+                    price(50_000),
+                    default_upgrade_buffer_cvl_pct()
+                )
+            );
+        }
+    }
 }
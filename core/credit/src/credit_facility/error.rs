@@ -42,6 +42,16 @@ pub enum CreditFacilityError {
     FacilityLedgerBalanceMismatch,
     #[error("CreditFacilityError - OutstandingAmount")]
     OutstandingAmount,
+    #[error("CreditFacilityError - AlreadyCompleted")]
+    AlreadyCompleted,
+    #[error("CreditFacilityError - TermsAmendmentInProgress")]
+    TermsAmendmentInProgress,
+    #[error("CreditFacilityError - AmendedMaturityDateInPast")]
+    AmendedMaturityDateInPast,
+    #[error("CreditFacilityError - PayoffAmountMismatch: expected {0}, got {1}")]
+    PayoffAmountMismatch(UsdCents, UsdCents),
+    #[error("CreditFacilityError - PayoffQuoteDateInPast")]
+    PayoffQuoteDateInPast,
     #[error("CreditFacilityError - InterestAccrualCycleWithInvalidFutureStartDate")]
     InterestAccrualCycleWithInvalidFutureStartDate,
     #[error("CreditFacilityError - InProgressInterestAccrualCycleNotCompletedYet")]
@@ -7,8 +7,8 @@ use serde::{Deserialize, Serialize};
 use es_entity::*;
 
 use crate::{
-    ledger::CreditFacilityLedgerAccountIds,
-    obligation::{NewObligation, ObligationAccounts},
+    ledger::{CreditFacilityLedgerAccountIds, missing_account_id},
+    obligation::{NewObligation, ObligationAccounts, ObligationPenaltyTerms, PrincipalInstallment},
     primitives::*,
     terms::{InterestPeriod, TermValues},
 };
@@ -22,6 +22,10 @@ pub struct InterestAccrualCycleLedgerAccountIds {
     pub interest_defaulted_account_id: CalaAccountId,
     pub interest_income_account_id: CalaAccountId,
     pub in_liquidation_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub penalty_receivable_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub penalty_income_account_id: CalaAccountId,
 }
 
 impl From<CreditFacilityLedgerAccountIds> for InterestAccrualCycleLedgerAccountIds {
@@ -37,6 +41,9 @@ impl From<CreditFacilityLedgerAccountIds> for InterestAccrualCycleLedgerAccountI
                 .interest_defaulted_account_id,
             interest_income_account_id: credit_facility_account_ids.interest_income_account_id,
             in_liquidation_account_id: credit_facility_account_ids.in_liquidation_account_id,
+            penalty_receivable_account_id: credit_facility_account_ids
+                .penalty_receivable_account_id,
+            penalty_income_account_id: credit_facility_account_ids.penalty_income_account_id,
         }
     }
 }
@@ -150,6 +157,71 @@ impl InterestAccrualCycle {
             .into()
     }
 
+    fn remaining_installments(&self) -> u32 {
+        let maturity = self.facility_maturity_date.start_of_day();
+
+        let mut count = 0;
+        let mut next_period = Some(self.period);
+        while let Some(period) = next_period {
+            count += 1;
+            next_period = period.next().truncate(maturity);
+        }
+        count
+    }
+
+    fn obligation_dates(&self) -> (EffectiveDate, Option<EffectiveDate>, Option<EffectiveDate>) {
+        let due_date = self.accrual_cycle_ends_at();
+        let overdue_date = self
+            .terms
+            .obligation_overdue_duration_from_due
+            .map(|d| d.end_date(due_date));
+        let liquidation_date = self
+            .terms
+            .obligation_liquidation_duration_from_due
+            .map(|d| d.end_date(due_date));
+        (due_date, overdue_date, liquidation_date)
+    }
+
+    pub(crate) fn amortization_due_date(&self) -> Option<EffectiveDate> {
+        self.terms
+            .amortization
+            .map(|_| self.accrual_cycle_ends_at())
+    }
+
+    pub(crate) fn principal_installment(
+        &self,
+        InterestAccrualCycleData {
+            interest,
+            effective,
+            ..
+        }: &InterestAccrualCycleData,
+        principal_outstanding: UsdCents,
+    ) -> Option<PrincipalInstallment> {
+        let schedule = self.terms.amortization?;
+        let amount = schedule.principal_installment_for_period(
+            principal_outstanding,
+            *interest,
+            self.remaining_installments(),
+            &self.period,
+        );
+        if amount.is_zero() {
+            return None;
+        }
+
+        let (due_date, overdue_date, liquidation_date) = self.obligation_dates();
+        Some(PrincipalInstallment {
+            reference: format!(
+                "{}-principal-installment-{}",
+                self.credit_facility_id, self.idx
+            ),
+            amount,
+            due_date,
+            overdue_date,
+            liquidation_date,
+            effective: *effective,
+        })
+    }
+
     fn total_accrued(&self) -> UsdCents {
         self.events
             .iter_all()
@@ -211,16 +283,54 @@ impl InterestAccrualCycle {
         untruncated_period.truncate(self.accrual_cycle_ends_at().end_of_day())
     }
 
+    fn accrual_periods_until(&self, as_of: DateTime<Utc>) -> Vec<InterestPeriod> {
+        let cycle_ends_at = self.accrual_cycle_ends_at().end_of_day();
+
+        let mut periods = vec![];
+        let mut next_period = self.next_accrual_period();
+        while let Some(period) = next_period.and_then(|p| p.truncate(as_of)) {
+            next_period = period.next().truncate(cycle_ends_at);
+            periods.push(period);
+        }
+        periods
+    }
+
+    pub(crate) fn accrued_interest_until(
+        &self,
+        amount: UsdCents,
+        as_of: DateTime<Utc>,
+    ) -> UsdCents {
+        self.accrual_periods_until(as_of)
+            .into_iter()
+            .map(|period| self.terms.interest_for_period(amount, &period))
+            .fold(self.total_accrued(), |acc, interest| acc + interest)
+    }
+
     pub(crate) fn record_accrual(&mut self, amount: UsdCents) -> InterestAccrualData {
         let accrual_period = self
             .next_accrual_period()
             .expect("Accrual period should exist inside this function");
 
-        let days_in_interest_period = accrual_period.days();
-        let interest_for_period = self
-            .terms
-            .annual_rate
-            .interest_for_time_period(amount, days_in_interest_period);
+        self.record_accrual_for_period(amount, accrual_period)
+    }
+
+    pub(crate) fn record_accruals_until(
+        &mut self,
+        amount: UsdCents,
+        as_of: DateTime<Utc>,
+    ) -> Vec<InterestAccrualData> {
+        self.accrual_periods_until(as_of)
+            .into_iter()
+            .map(|period| self.record_accrual_for_period(amount, period))
+            .collect()
+    }
+
+    fn record_accrual_for_period(
+        &mut self,
+        amount: UsdCents,
+        accrual_period: InterestPeriod,
+    ) -> InterestAccrualData {
+        let interest_for_period = self.terms.interest_for_period(amount, &accrual_period);
 
         let accrual_tx_ref = format!("{}-interest-accrual-{}", self.id, self.count_accrued() + 1);
         let interest_accrual = InterestAccrualData {
@@ -266,6 +376,24 @@ impl InterestAccrualCycle {
         }
     }
 
+    pub(crate) fn early_accrual_cycle_data(&self) -> InterestAccrualCycleData {
+        let effective = self
+            .last_accrual_period()
+            .map(|period| period.end)
+            .unwrap_or(self.period.start)
+            .date_naive();
+
+        InterestAccrualCycleData {
+            interest: self.total_accrued(),
+            tx_ref: format!(
+                "{}-interest-accrual-cycle-{}",
+                self.credit_facility_id, self.idx
+            ),
+            tx_id: LedgerTxId::new(),
+            effective,
+        }
+    }
+
     pub(crate) fn record_accrual_cycle(
         &mut self,
         InterestAccrualCycleData {
@@ -294,15 +422,16 @@ impl InterestAccrualCycle {
             return Idempotent::Executed(None);
         }
 
-        let due_date = self.accrual_cycle_ends_at();
-        let overdue_date = self
+        let (due_date, overdue_date, liquidation_date) = self.obligation_dates();
+        let penalty = self
             .terms
-            .obligation_overdue_duration_from_due
-            .map(|d| d.end_date(due_date));
-        let liquidation_date = self
-            .terms
-            .obligation_liquidation_duration_from_due
-            .map(|d| d.end_date(due_date));
+            .penalty_annual_rate
+            .map(|annual_rate| ObligationPenaltyTerms {
+                annual_rate,
+                day_count_convention: self.terms.day_count_convention,
+                receivable_account_id: self.account_ids.penalty_receivable_account_id,
+                income_account_id: self.account_ids.penalty_income_account_id,
+            });
         let new_obligation = NewObligation::builder()
             .id(ObligationId::new())
             .credit_facility_id(self.credit_facility_id)
@@ -327,6 +456,7 @@ impl InterestAccrualCycle {
             .due_date(due_date)
             .overdue_date(overdue_date)
             .liquidation_date(liquidation_date)
+            .penalty(penalty)
             .effective(effective)
             .build()
             .expect("could not build new interest accrual cycle obligation");
@@ -391,7 +521,10 @@ mod test {
     use chrono::{Datelike, TimeZone, Utc};
     use rust_decimal_macros::dec;
 
-    use crate::terms::{FacilityDuration, InterestInterval, ObligationDuration, OneTimeFeeRatePct};
+    use crate::terms::{
+        AmortizationSchedule, DayCountConvention, FacilityDuration, InterestInterval,
+        ObligationDuration, OneTimeFeeRatePct,
+    };
 
     use super::*;
 
@@ -732,4 +865,97 @@ mod test {
         let InterestAccrualCycleData { interest, .. } = accrual.accrual_cycle_data().unwrap();
         assert_eq!(interest, expected_accrual_sum);
     }
+
+    #[test]
+    fn accrual_uses_day_count_convention() {
+        let mut events = initial_events();
+        if let InterestAccrualCycleEvent::Initialized { terms, .. } = &mut events[0] {
+            terms.day_count_convention = DayCountConvention::Actual360;
+        }
+        let mut accrual = accrual_from(events);
+
+        let InterestAccrualData { interest, .. } =
+            accrual.record_accrual(UsdCents::from(1_000_000_00));
+        assert_eq!(interest, UsdCents::from(333_34));
+    }
+
+    #[test]
+    fn accrued_interest_until_matches_recorded_accruals() {
+        let disbursed_outstanding_amount = UsdCents::from(1_000_000_00);
+        let expected_daily_interest = default_terms()
+            .annual_rate
+            .interest_for_time_period(disbursed_outstanding_amount, 1);
+
+        let mut accrual = accrual_from(initial_events());
+        accrual.record_accrual(disbursed_outstanding_amount);
+
+        let as_of = default_started_at() + chrono::Duration::days(3);
+        let quoted = accrual.accrued_interest_until(disbursed_outstanding_amount, as_of);
+        assert_eq!(quoted, expected_daily_interest * 4);
+
+        let accruals = accrual.record_accruals_until(disbursed_outstanding_amount, as_of);
+        assert_eq!(accruals.len(), 3);
+        assert_eq!(accruals.last().unwrap().period.end, as_of);
+        assert!(
+            accrual
+                .record_accruals_until(disbursed_outstanding_amount, as_of)
+                .is_empty()
+        );
+
+        let InterestAccrualCycleData { interest, .. } = accrual.early_accrual_cycle_data();
+        assert_eq!(interest, quoted);
+    }
+
+    #[test]
+    fn principal_installment_for_amortizing_terms() {
+        let mut events = initial_events();
+        if let InterestAccrualCycleEvent::Initialized { terms, period, .. } = &mut events[0] {
+            terms.amortization = Some(AmortizationSchedule::StraightLine);
+            *period = InterestInterval::EndOfMonth.period_from(default_started_at());
+        }
+        let accrual = accrual_from(events);
+
+        // the first cycle covers 17 of January's 31 days
+        let installment = accrual
+            .principal_installment(&accrual.early_accrual_cycle_data(), UsdCents::from(300_00))
+            .unwrap();
+        assert_eq!(installment.amount, UsdCents::from(41_13));
+        assert_eq!(
+            installment.due_date,
+            EffectiveDate::from(end_of_month(default_started_at()))
+        );
+    }
+
+    #[test]
+    fn principal_installment_for_full_cycle_is_not_prorated() {
+        let started_at = "2024-02-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut events = initial_events();
+        if let InterestAccrualCycleEvent::Initialized {
+            terms,
+            period,
+            facility_maturity_date,
+            ..
+        } = &mut events[0]
+        {
+            terms.amortization = Some(AmortizationSchedule::StraightLine);
+            *period = InterestInterval::EndOfMonth.period_from(started_at);
+            *facility_maturity_date = terms.maturity_date(started_at);
+        }
+        let accrual = accrual_from(events);
+
+        let installment = accrual
+            .principal_installment(&accrual.early_accrual_cycle_data(), UsdCents::from(300_00))
+            .unwrap();
+        assert_eq!(installment.amount, UsdCents::from(75_00));
+    }
+
+    #[test]
+    fn no_principal_installment_without_amortization() {
+        let accrual = accrual_from(initial_events());
+        assert!(
+            accrual
+                .principal_installment(&accrual.early_accrual_cycle_data(), UsdCents::from(300_00))
+                .is_none()
+        );
+    }
 }
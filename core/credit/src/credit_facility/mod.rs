@@ -3,32 +3,38 @@ pub mod error;
 pub mod interest_accrual_cycle;
 mod repo;
 
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tracing::instrument;
 
 use audit::AuditSvc;
 use authz::PermissionCheck;
 use core_price::Price;
-use governance::{Governance, GovernanceAction, GovernanceEvent, GovernanceObject};
+use governance::{
+    ApprovalProcessContext, Governance, GovernanceAction, GovernanceEvent, GovernanceObject,
+};
 use job::{JobId, Jobs};
 use outbox::OutboxEventMarker;
 
 use crate::{
     PublicIds,
-    disbursal::Disbursals,
+    disbursal::{Disbursals, NewDisbursal},
     event::CoreCreditEvent,
-    jobs::{credit_facility_maturity, interest_accruals},
-    ledger::{CreditFacilityInterestAccrual, CreditFacilityInterestAccrualCycle, CreditLedger},
-    obligation::Obligations,
+    jobs::{credit_facility_maturity, credit_facility_scheduled_disbursals, interest_accruals},
+    ledger::{
+        CreditFacilityInterestAccrual, CreditFacilityInterestAccrualCycle, CreditFacilityPayoff,
+        CreditLedger,
+    },
+    obligation::{Obligation, Obligations},
     pending_credit_facility::{PendingCreditFacilities, PendingCreditFacilityCompletionOutcome},
     primitives::*,
-    terms::InterestPeriod,
+    terms::{InterestPeriod, TermValues},
 };
 
 use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
 
-pub use entity::CreditFacility;
 pub(crate) use entity::*;
+pub use entity::{CreditFacility, PayoffQuote};
 use interest_accrual_cycle::NewInterestAccrualCycleData;
 
 #[cfg(feature = "json-schema")]
@@ -94,6 +100,13 @@ pub(super) struct ConfirmedAccrual {
     pub(super) accrued_count: usize,
 }
 
+pub(super) struct PayoffOutcome {
+    pub(super) credit_facility: CreditFacility,
+    pub(super) quote: PayoffQuote,
+    pub(super) payoff: CreditFacilityPayoff,
+    pub(super) new_obligation: Option<Obligation>,
+}
+
 impl<Perms, E> CreditFacilities<Perms, E>
 where
     Perms: PermissionCheck,
@@ -105,7 +118,8 @@ where
         + OutboxEventMarker<GovernanceEvent>
         + OutboxEventMarker<CoreCustodyEvent>,
 {
-    pub fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub async fn init(
         pool: &sqlx::PgPool,
         authz: Arc<Perms>,
         obligations: Arc<Obligations<Perms, E>>,
@@ -117,10 +131,21 @@ where
         publisher: &crate::CreditFacilityPublisher<E>,
         governance: Arc<Governance<Perms, E>>,
         public_ids: Arc<PublicIds>,
-    ) -> Self {
+    ) -> Result<Self, CreditFacilityError> {
+        match governance
+            .init_policy(crate::APPROVE_CREDIT_FACILITY_TERMS_AMENDMENT_PROCESS)
+            .await
+        {
+            Err(governance::error::GovernanceError::PolicyError(
+                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+            )) => (),
+            Err(e) => return Err(e.into()),
+            _ => (),
+        }
+
         let repo = CreditFacilityRepo::new(pool, publisher);
 
-        Self {
+        let credit_facilities = Self {
             repo: Arc::new(repo),
             obligations,
             pending_credit_facilities,
@@ -131,7 +156,51 @@ where
             jobs,
             governance,
             public_ids,
+        };
+        credit_facilities.backfill_ledger_accounts().await?;
+
+        Ok(credit_facilities)
+    }
+
+    /// Creates the ledger accounts introduced after some facilities were activated
+    /// and records them on those facilities.
+    #[instrument(
+        name = "credit.credit_facility.backfill_ledger_accounts",
+        skip(self),
+        err
+    )]
+    async fn backfill_ledger_accounts(&self) -> Result<(), CreditFacilityError> {
+        let mut next = Some(es_entity::PaginatedQueryArgs::default());
+        while let Some(query) = next.take() {
+            let mut ret = self.repo.list_by_id(query, Default::default()).await?;
+
+            for credit_facility in ret.entities.iter_mut() {
+                let existing = credit_facility.account_ids.backfilled_account_ids();
+                let es_entity::Idempotent::Executed(backfilled) =
+                    credit_facility.backfill_ledger_accounts()
+                else {
+                    continue;
+                };
+
+                let mut db = self.repo.begin_op().await?;
+                self.authz
+                    .audit()
+                    .record_system_entry_in_tx(
+                        &mut db,
+                        CoreCreditObject::credit_facility(credit_facility.id),
+                        CoreCreditAction::CREDIT_FACILITY_ACTIVATE,
+                    )
+                    .await?;
+                self.repo.update_in_op(&mut db, credit_facility).await?;
+                self.ledger
+                    .backfill_credit_facility_accounts(db, credit_facility.id, existing, backfilled)
+                    .await?;
+            }
+
+            next = ret.into_next_query();
         }
+
+        Ok(())
     }
 
     pub(super) async fn begin_op(&self) -> Result<es_entity::DbOp<'_>, CreditFacilityError> {
@@ -215,6 +284,23 @@ where
             )
             .await?;
 
+        if let Some(scheduled_disbursal_at) = credit_facility.next_scheduled_disbursal_at() {
+            self.jobs
+                .create_and_spawn_at_in_op(
+                    &mut db,
+                    JobId::new(),
+                    credit_facility_scheduled_disbursals::CreditFacilityScheduledDisbursalsJobConfig::<
+                        Perms,
+                        E,
+                    > {
+                        credit_facility_id: credit_facility.id,
+                        _phantom: std::marker::PhantomData,
+                    },
+                    scheduled_disbursal_at,
+                )
+                .await?;
+        }
+
         if let Some(mut new_disbursal_builder) = initial_disbursal {
             let public_id = self
                 .public_ids
@@ -229,7 +315,7 @@ where
                 .build()
                 .expect("could not build new disbursal");
 
-            let disbursal_id = self
+            let (disbursal, _) = self
                 .disbursals
                 .create_pre_approved_disbursal_in_op(&mut db, new_disbursal)
                 .await?;
@@ -237,7 +323,7 @@ where
                 .handle_activation_with_structuring_fee(
                     db,
                     credit_facility.activation_data(),
-                    disbursal_id,
+                    disbursal.id,
                 )
                 .await?;
             return Ok(());
@@ -254,7 +340,7 @@ where
         &self,
         op: &mut impl es_entity::AtomicOperation,
         id: CreditFacilityId,
-    ) -> Result<ConfirmedAccrual, CreditFacilityError> {
+    ) -> Result<Option<ConfirmedAccrual>, CreditFacilityError> {
         self.authz
             .audit()
             .record_system_entry_in_tx(
@@ -270,9 +356,9 @@ where
             let account_ids = credit_facility.account_ids;
             let balances = self.ledger.get_credit_facility_balance(account_ids).await?;
 
-            let accrual = credit_facility
-                .interest_accrual_cycle_in_progress_mut()
-                .expect("Accrual in progress should exist for scheduled job");
+            let Some(accrual) = credit_facility.interest_accrual_cycle_in_progress_mut() else {
+                return Ok(None);
+            };
 
             let interest_accrual = accrual.record_accrual(balances.disbursed_outstanding());
 
@@ -286,7 +372,7 @@ where
 
         self.repo.update_in_op(op, &mut credit_facility).await?;
 
-        Ok(confirmed_accrual)
+        Ok(Some(confirmed_accrual))
     }
 
     pub(super) async fn complete_in_op(
@@ -317,6 +403,51 @@ where
         Ok(CompletionOutcome::Completed((credit_facility, completion)))
     }
 
+    #[instrument(name = "credit.credit_facility.pay_off_in_op", skip(self, db), err)]
+    pub(super) async fn pay_off_in_op(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        id: CreditFacilityId,
+        amount: UsdCents,
+    ) -> Result<PayoffOutcome, CreditFacilityError> {
+        let mut credit_facility = self.repo.find_by_id(id).await?;
+
+        let balances = self
+            .ledger
+            .get_credit_facility_balance(credit_facility.account_ids)
+            .await?;
+        let outstanding = self
+            .obligations
+            .outstanding_for_facility(credit_facility.id)
+            .await?;
+        let quote = credit_facility.payoff_quote(crate::time::now(), balances, outstanding)?;
+        if quote.total() != amount {
+            return Err(CreditFacilityError::PayoffAmountMismatch(
+                quote.total(),
+                amount,
+            ));
+        }
+
+        let (payoff, new_obligation) = credit_facility.pay_off(&quote, balances)?;
+        let new_obligation = match new_obligation {
+            Some(new_obligation) => Some(
+                self.obligations
+                    .create_with_jobs_in_op(db, new_obligation)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        self.repo.update_in_op(db, &mut credit_facility).await?;
+
+        Ok(PayoffOutcome {
+            credit_facility,
+            quote,
+            payoff,
+            new_obligation,
+        })
+    }
+
     #[instrument(
         name = "credit.facility.complete_interest_cycle_and_maybe_start_new_cycle",
         skip(self, db)
@@ -325,18 +456,24 @@ where
         &self,
         db: &mut es_entity::DbOp<'_>,
         id: CreditFacilityId,
-    ) -> Result<CompletedAccrualCycle, CreditFacilityError> {
+    ) -> Result<Option<CompletedAccrualCycle>, CreditFacilityError> {
         let mut credit_facility = self.repo.find_by_id(id).await?;
 
-        let (accrual_cycle_data, new_obligation) = if let es_entity::Idempotent::Executed(res) =
-            credit_facility.record_interest_accrual_cycle()?
+        let principal_to_amortize = match credit_facility
+            .interest_accrual_cycle_in_progress()
+            .and_then(|cycle| cycle.amortization_due_date())
         {
-            res
-        } else {
-            unreachable!(
-                "record_interest_accrual_cycle returned Idempotent::Ignored, \
-                 but this should only execute when there is an accrual cycle to record"
-            );
+            Some(due_date) => self.obligations.principal_to_amortize(id, due_date).await?,
+            None => UsdCents::ZERO,
+        };
+
+        let RecordedInterestAccrualCycle {
+            accrual_cycle_data,
+            new_obligation,
+            principal_installment,
+        } = match credit_facility.record_interest_accrual_cycle(principal_to_amortize)? {
+            es_entity::Idempotent::Executed(res) => res,
+            es_entity::Idempotent::Ignored => return Ok(None),
         };
 
         if let Some(new_obligation) = new_obligation {
@@ -345,6 +482,12 @@ where
                 .await?;
         };
 
+        if let Some(principal_installment) = principal_installment {
+            self.obligations
+                .schedule_principal_installment_in_op(db, id, principal_installment)
+                .await?;
+        }
+
         let res = credit_facility.start_interest_accrual_cycle()?;
         self.repo.update_in_op(db, &mut credit_facility).await?;
 
@@ -360,10 +503,10 @@ where
             }
         });
 
-        Ok(CompletedAccrualCycle {
+        Ok(Some(CompletedAccrualCycle {
             facility_accrual_cycle_data: (accrual_cycle_data, credit_facility.account_ids).into(),
             new_cycle_data,
-        })
+        }))
     }
 
     pub async fn find_by_id_without_audit(
@@ -373,6 +516,13 @@ where
         self.repo.find_by_id(id.into()).await
     }
 
+    pub(crate) async fn not_closed_exists_for_customer(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<bool, CreditFacilityError> {
+        self.repo.not_closed_exists_for_customer(customer_id).await
+    }
+
     #[instrument(name = "credit.credit_facility.find_by_id", skip(self), err)]
     pub async fn find_by_id(
         &self,
@@ -395,6 +545,141 @@ where
         }
     }
 
+    #[instrument(
+        name = "credit.credit_facility.initiate_terms_amendment",
+        skip(self),
+        err
+    )]
+    pub async fn initiate_terms_amendment(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<CreditFacilityId> + std::fmt::Debug,
+        terms: TermValues,
+    ) -> Result<CreditFacility, CreditFacilityError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCreditObject::credit_facility(id),
+                CoreCreditAction::CREDIT_FACILITY_AMEND_TERMS,
+            )
+            .await?;
+
+        let mut credit_facility = self.repo.find_by_id(id).await?;
+        let approval_process_id = ApprovalProcessId::new();
+        credit_facility.initiate_terms_amendment(approval_process_id, terms)?;
+
+        let mut db = self.repo.begin_op().await?;
+        self.governance
+            .start_process(
+                &mut db,
+                approval_process_id,
+                id.to_string(),
+                crate::APPROVE_CREDIT_FACILITY_TERMS_AMENDMENT_PROCESS,
+                ApprovalProcessContext::default(),
+            )
+            .await?;
+        self.repo
+            .update_in_op(&mut db, &mut credit_facility)
+            .await?;
+        db.commit().await?;
+
+        Ok(credit_facility)
+    }
+
+    #[instrument(
+        name = "credit.credit_facility.conclude_terms_amendment",
+        skip(self),
+        err
+    )]
+    pub(super) async fn conclude_terms_amendment(
+        &self,
+        id: CreditFacilityId,
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+    ) -> Result<CreditFacility, CreditFacilityError> {
+        let mut db = self.repo.begin_op().await?;
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                &mut db,
+                CoreCreditObject::credit_facility(id),
+                CoreCreditAction::CREDIT_FACILITY_CONCLUDE_APPROVAL_PROCESS,
+            )
+            .await?;
+
+        let mut credit_facility = self.repo.find_by_id(id).await?;
+        let previous_maturity_date = credit_facility.maturity_date;
+        if credit_facility
+            .conclude_terms_amendment(approval_process_id, approved)
+            .was_ignored()
+        {
+            return Ok(credit_facility);
+        }
+
+        if approved {
+            self.obligations
+                .reschedule_for_amended_terms_in_op(
+                    &mut db,
+                    id,
+                    &credit_facility.terms,
+                    previous_maturity_date,
+                    credit_facility.maturity_date,
+                )
+                .await?;
+        }
+
+        if credit_facility.maturity_date != previous_maturity_date {
+            self.jobs
+                .create_and_spawn_at_in_op(
+                    &mut db,
+                    JobId::new(),
+                    credit_facility_maturity::CreditFacilityMaturityJobConfig::<Perms, E> {
+                        credit_facility_id: credit_facility.id,
+                        _phantom: std::marker::PhantomData,
+                    },
+                    credit_facility.matures_at(),
+                )
+                .await?;
+        }
+
+        // accrual cycles stop at the old maturity date, restart them if it was extended
+        let restarted_accrual = if credit_facility
+            .interest_accrual_cycle_in_progress()
+            .is_none()
+        {
+            credit_facility.start_interest_accrual_cycle()?
+        } else {
+            None
+        };
+
+        self.repo
+            .update_in_op(&mut db, &mut credit_facility)
+            .await?;
+
+        if let Some(periods) = restarted_accrual {
+            let accrual_id = credit_facility
+                .interest_accrual_cycle_in_progress()
+                .expect("Accrual cycle not found")
+                .id;
+            self.jobs
+                .create_and_spawn_at_in_op(
+                    &mut db,
+                    accrual_id,
+                    interest_accruals::InterestAccrualJobConfig::<Perms, E> {
+                        credit_facility_id: id,
+                        _phantom: std::marker::PhantomData,
+                    },
+                    periods.accrual.end,
+                )
+                .await?;
+        }
+
+        db.commit().await?;
+
+        Ok(credit_facility)
+    }
+
     pub(super) async fn mark_facility_as_matured(
         &self,
         id: CreditFacilityId,
@@ -408,6 +693,113 @@ where
         Ok(())
     }
 
+    #[instrument(
+        name = "credit.credit_facility.execute_scheduled_disbursal",
+        skip(self),
+        err
+    )]
+    pub(super) async fn execute_scheduled_disbursal(
+        &self,
+        id: CreditFacilityId,
+    ) -> Result<Option<DateTime<Utc>>, CreditFacilityError> {
+        let mut credit_facility = self.repo.find_by_id(id).await?;
+
+        let now = crate::time::now();
+        let Some(scheduled_disbursal_at) = credit_facility.next_scheduled_disbursal_at() else {
+            return Ok(None);
+        };
+        if scheduled_disbursal_at > now {
+            return Ok(Some(scheduled_disbursal_at));
+        }
+        let (drawdown_idx, drawdown) = credit_facility
+            .next_scheduled_disbursal()
+            .expect("scheduled disbursal should exist");
+
+        let balance = self
+            .ledger
+            .get_credit_facility_balance(credit_facility.account_ids)
+            .await?;
+        let price = self.price.usd_cents_per_btc().await?;
+        if !credit_facility
+            .terms
+            .is_disbursal_allowed(balance, drawdown.amount, price)
+        {
+            tracing::warn!(
+                credit_facility_id = %id,
+                drawdown_idx,
+                "scheduled disbursal below margin limit, retrying tomorrow"
+            );
+            return Ok(Some(now + chrono::Duration::days(1)));
+        }
+
+        let mut db = self.repo.begin_op().await?.with_db_time().await?;
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                &mut db,
+                CoreCreditObject::all_disbursals(),
+                CoreCreditAction::DISBURSAL_INITIATE,
+            )
+            .await?;
+
+        let disbursal_id = DisbursalId::new();
+        if credit_facility
+            .record_scheduled_disbursal(drawdown_idx, disbursal_id, drawdown.amount)
+            .was_ignored()
+        {
+            return Ok(credit_facility.next_scheduled_disbursal_at());
+        }
+        self.repo
+            .update_in_op(&mut db, &mut credit_facility)
+            .await?;
+
+        let public_id = self
+            .public_ids
+            .create_in_op(&mut db, DISBURSAL_REF_TARGET, disbursal_id)
+            .await?;
+        let due_date = credit_facility.maturity_date;
+        let new_disbursal = NewDisbursal::builder()
+            .id(disbursal_id)
+            .approval_process_id(disbursal_id)
+            .credit_facility_id(id)
+            .amount(drawdown.amount)
+            .account_ids(credit_facility.account_ids)
+            .disbursal_credit_account_id(credit_facility.disbursal_credit_account_id)
+            .due_date(due_date)
+            .overdue_date(
+                credit_facility
+                    .terms
+                    .get_overdue_date_from_due_date(due_date),
+            )
+            .liquidation_date(
+                credit_facility
+                    .terms
+                    .get_liquidation_date_from_due_date(due_date),
+            )
+            .penalty_annual_rate(credit_facility.terms.penalty_annual_rate)
+            .day_count_convention(credit_facility.terms.day_count_convention)
+            .public_id(public_id.id)
+            .build()
+            .expect("could not build new disbursal");
+
+        let (disbursal, obligation) = self
+            .disbursals
+            .create_pre_approved_disbursal_in_op(&mut db, new_disbursal)
+            .await?;
+
+        self.ledger
+            .initiate_and_settle_disbursal(
+                disbursal.id,
+                db,
+                disbursal.initiated_tx_id,
+                obligation,
+                credit_facility.account_ids.facility_account_id,
+            )
+            .await?;
+
+        Ok(credit_facility.next_scheduled_disbursal_at())
+    }
+
     #[instrument(name = "credit.credit_facility.find_by_public_id", skip(self), err)]
     pub async fn find_by_public_id(
         &self,
@@ -429,11 +821,25 @@ where
         }
     }
 
+    /// Walks the open facilities in ascending order of collateralization ratio and stops at the
+    /// first one whose CVL stays above every threshold that any facility could still cross,
+    /// both at the previous and the new price.
     pub(super) async fn update_collateralization_from_price(
         &self,
+        price: &PriceObservation,
+        previous_price: Option<PriceOfOneBTC>,
         upgrade_buffer_cvl_pct: CVLPct,
     ) -> Result<(), CreditFacilityError> {
-        let price = self.price.usd_cents_per_btc().await?;
+        let Some(highest_margin_call_cvl) = self
+            .repo
+            .highest_margin_call_cvl_of_open_facilities()
+            .await?
+        else {
+            return Ok(());
+        };
+        let highest_threshold = highest_margin_call_cvl + upgrade_buffer_cvl_pct;
+        let lowest_price = previous_price.map_or(price.price, |previous| previous.min(price.price));
+
         let mut has_next_page = true;
         let mut after: Option<CreditFacilitiesByCollateralizationRatioCursor> = None;
         while has_next_page {
@@ -466,7 +872,13 @@ where
             let mut at_least_one = false;
 
             for facility in credit_facilities.entities.iter_mut() {
-                if facility.status() == CreditFacilityStatus::Closed {
+                if facility.last_collateralization_ratio().cvl(lowest_price) > highest_threshold {
+                    has_next_page = false;
+                    break;
+                }
+                if facility.status() == CreditFacilityStatus::Closed
+                    || !facility.collateralization_could_change(price.price, upgrade_buffer_cvl_pct)
+                {
                     continue;
                 }
                 let balances = self
@@ -484,8 +896,6 @@ where
 
             if at_least_one {
                 op.commit().await?;
-            } else {
-                break;
             }
         }
         Ok(())
@@ -521,10 +931,10 @@ where
             .ledger
             .get_credit_facility_balance(credit_facility.account_ids)
             .await?;
-        let price = self.price.usd_cents_per_btc().await?;
+        let price = self.price.current_observation().await?;
 
         if credit_facility
-            .update_collateralization(price, upgrade_buffer_cvl_pct, balances)
+            .update_collateralization(&price, upgrade_buffer_cvl_pct, balances)
             .did_execute()
         {
             self.repo
@@ -671,6 +1081,70 @@ where
         Ok(balances)
     }
 
+    #[instrument(
+        name = "credit.credit_facility.unapplied_payments_balance",
+        skip(self),
+        err
+    )]
+    pub async fn unapplied_payments_balance(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<CreditFacilityId> + std::fmt::Debug,
+    ) -> Result<UsdCents, CreditFacilityError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCreditObject::credit_facility(id),
+                CoreCreditAction::CREDIT_FACILITY_READ,
+            )
+            .await?;
+
+        let credit_facility = self.repo.find_by_id(id).await?;
+
+        Ok(self
+            .ledger
+            .get_unapplied_payments_balance(
+                credit_facility.account_ids.unapplied_payments_account_id,
+            )
+            .await?)
+    }
+
+    #[instrument(name = "credit.credit_facility.payoff_quote", skip(self), err)]
+    pub async fn payoff_quote(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<CreditFacilityId> + std::fmt::Debug,
+        as_of: chrono::NaiveDate,
+    ) -> Result<PayoffQuote, CreditFacilityError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCreditObject::credit_facility(id),
+                CoreCreditAction::CREDIT_FACILITY_READ,
+            )
+            .await?;
+
+        let as_of = EffectiveDate::from(as_of).end_of_day();
+        if as_of < crate::time::now() {
+            return Err(CreditFacilityError::PayoffQuoteDateInPast);
+        }
+
+        let credit_facility = self.repo.find_by_id(id).await?;
+
+        let balances = self
+            .ledger
+            .get_credit_facility_balance(credit_facility.account_ids)
+            .await?;
+        let outstanding = self
+            .obligations
+            .outstanding_for_facility(credit_facility.id)
+            .await?;
+
+        credit_facility.payoff_quote(as_of, balances, outstanding)
+    }
+
     pub async fn has_outstanding_obligations(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
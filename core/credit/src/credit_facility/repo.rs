@@ -32,6 +32,11 @@ use super::{
             update(accessor = "last_collateralization_state()")
         ),
         status(ty = "CreditFacilityStatus", list_for, update(accessor = "status()")),
+        margin_call_cvl(
+            ty = "CVLPct",
+            create(accessor = "terms.margin_call_cvl"),
+            update(accessor = "terms.margin_call_cvl")
+        ),
         public_id(ty = "PublicId", list_by)
     ),
     tbl_prefix = "core",
@@ -74,6 +79,37 @@ where
         }
     }
 
+    pub async fn not_closed_exists_for_customer(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<bool, CreditFacilityError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM core_credit_facilities
+                WHERE customer_id = $1 AND status != $2
+            ) AS "exists!""#,
+            customer_id as CustomerId,
+            CreditFacilityStatus::Closed as CreditFacilityStatus,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(exists)
+    }
+
+    pub async fn highest_margin_call_cvl_of_open_facilities(
+        &self,
+    ) -> Result<Option<CVLPct>, CreditFacilityError> {
+        let cvl = sqlx::query_scalar!(
+            r#"SELECT MAX(margin_call_cvl) AS "cvl: CVLPct"
+            FROM core_credit_facilities
+            WHERE status != $1"#,
+            CreditFacilityStatus::Closed as CreditFacilityStatus,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(cvl)
+    }
+
     async fn publish(
         &self,
         op: &mut impl es_entity::AtomicOperation,
@@ -278,6 +314,52 @@ mod facility_collateralization_ratio_sqlx {
     }
 }
 
+mod facility_cvl_pct_sqlx {
+    use rust_decimal::Decimal;
+    use sqlx::{Type, postgres::*};
+
+    use crate::primitives::CVLPct;
+
+    impl Type<Postgres> for CVLPct {
+        fn type_info() -> PgTypeInfo {
+            <Option<Decimal> as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <Option<Decimal> as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl sqlx::Encode<'_, Postgres> for CVLPct {
+        fn encode_by_ref(
+            &self,
+            buf: &mut PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            let opt: Option<Decimal> = match *self {
+                CVLPct::Finite(d) => Some(d),
+                CVLPct::Infinite => None,
+            };
+            <Option<Decimal> as sqlx::Encode<'_, Postgres>>::encode(opt, buf)
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for CVLPct {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let opt: Option<Decimal> = <Option<Decimal> as sqlx::Decode<Postgres>>::decode(value)?;
+            Ok(match opt {
+                Some(d) => CVLPct::Finite(d),
+                None => CVLPct::Infinite,
+            })
+        }
+    }
+
+    impl PgHasArrayType for CVLPct {
+        fn array_type_info() -> PgTypeInfo {
+            <Option<Decimal> as sqlx::postgres::PgHasArrayType>::array_type_info()
+        }
+    }
+}
+
 impl From<(CreditFacilitiesSortBy, &CreditFacility)>
     for credit_facility_cursor::CreditFacilitiesCursor
 {
@@ -10,7 +10,7 @@ use es_entity::*;
 use crate::{
     pending_credit_facility::{NewPendingCreditFacility, NewPendingCreditFacilityBuilder},
     primitives::*,
-    terms::TermValues,
+    terms::{DisbursalPolicy, TermValues},
 };
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +28,8 @@ pub enum CreditFacilityProposalEvent {
         terms: TermValues,
         amount: UsdCents,
         status: CreditFacilityProposalStatus,
+        #[serde(default)]
+        disbursal_policy: DisbursalPolicy,
     },
     ApprovalProcessConcluded {
         approval_process_id: ApprovalProcessId,
@@ -46,6 +48,7 @@ pub struct CreditFacilityProposal {
     pub disbursal_credit_account_id: CalaAccountId,
     pub amount: UsdCents,
     pub terms: TermValues,
+    pub disbursal_policy: DisbursalPolicy,
 
     events: EntityEvents<CreditFacilityProposalEvent>,
 }
@@ -100,6 +103,7 @@ impl CreditFacilityProposal {
                 .collateral_id(CollateralId::new())
                 .terms(self.terms)
                 .amount(self.amount)
+                .disbursal_policy(self.disbursal_policy.clone())
                 .build()
                 .expect("Could not build new pending credit facility");
 
@@ -138,6 +142,7 @@ impl TryFromEvents<CreditFacilityProposalEvent> for CreditFacilityProposal {
                     disbursal_credit_account_id,
                     terms,
                     amount,
+                    disbursal_policy,
                     ..
                 } => {
                     builder = builder
@@ -148,7 +153,8 @@ impl TryFromEvents<CreditFacilityProposalEvent> for CreditFacilityProposal {
                         .approval_process_id(*approval_process_id)
                         .disbursal_credit_account_id(*disbursal_credit_account_id)
                         .terms(*terms)
-                        .amount(*amount);
+                        .amount(*amount)
+                        .disbursal_policy(disbursal_policy.clone());
                 }
                 CreditFacilityProposalEvent::ApprovalProcessConcluded { .. } => {}
             }
@@ -171,13 +177,19 @@ pub struct NewCreditFacilityProposal {
     #[builder(setter(into))]
     pub(super) disbursal_credit_account_id: CalaAccountId,
     terms: TermValues,
-    amount: UsdCents,
+    pub(super) amount: UsdCents,
+    #[builder(default)]
+    disbursal_policy: DisbursalPolicy,
 }
 
 impl NewCreditFacilityProposal {
     pub fn builder() -> NewCreditFacilityProposalBuilder {
         NewCreditFacilityProposalBuilder::default()
     }
+
+    pub(super) fn status(&self) -> CreditFacilityProposalStatus {
+        CreditFacilityProposalStatus::PendingApproval
+    }
 }
 
 impl IntoEvents<CreditFacilityProposalEvent> for NewCreditFacilityProposal {
@@ -194,6 +206,7 @@ impl IntoEvents<CreditFacilityProposalEvent> for NewCreditFacilityProposal {
                 terms: self.terms,
                 amount: self.amount,
                 status: CreditFacilityProposalStatus::PendingApproval,
+                disbursal_policy: self.disbursal_policy,
             }],
         )
     }
@@ -14,6 +14,8 @@ pub enum CreditFacilityProposalError {
     LedgerError(#[from] crate::ledger::error::CreditLedgerError),
     #[error("CreditFacilityProposalError - PriceError: {0}")]
     PriceError(#[from] core_price::error::PriceError),
+    #[error("CreditFacilityProposalError - TermsError: {0}")]
+    TermsError(#[from] crate::terms::error::TermsError),
     #[error("CreditFacilityProposalError - ApprovalInProgress")]
     ApprovalInProgress,
     #[error("CreditFacilityProposalError - BelowMarginLimit")]
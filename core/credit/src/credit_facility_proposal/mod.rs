@@ -7,7 +7,9 @@ use std::sync::Arc;
 use audit::AuditSvc;
 use authz::PermissionCheck;
 use core_custody::CustodianId;
-use governance::{Governance, GovernanceAction, GovernanceEvent, GovernanceObject};
+use governance::{
+    ApprovalProcessContext, Governance, GovernanceAction, GovernanceEvent, GovernanceObject,
+};
 use job::Jobs;
 use outbox::OutboxEventMarker;
 use tracing::instrument;
@@ -107,6 +109,8 @@ where
                 new_proposal.id,
                 new_proposal.id.to_string(),
                 crate::APPROVE_CREDIT_FACILITY_PROPOSAL_PROCESS,
+                ApprovalProcessContext::for_amount(new_proposal.amount)
+                    .with_customer_type(new_proposal.customer_type),
             )
             .await?;
         self.repo.create_in_op(db, new_proposal).await
@@ -220,4 +224,13 @@ where
             .await?
             .entities)
     }
+
+    pub(crate) async fn pending_approval_exists_for_customer(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<bool, CreditFacilityProposalError> {
+        self.repo
+            .pending_approval_exists_for_customer(customer_id)
+            .await
+    }
 }
@@ -14,6 +14,11 @@ use super::{entity::*, error::CreditFacilityProposalError};
     columns(
         customer_id(ty = "CustomerId", list_for, update(persist = false)),
         approval_process_id(ty = "ApprovalProcessId", list_by, update(persist = "false")),
+        status(
+            ty = "CreditFacilityProposalStatus",
+            create(accessor = "status()"),
+            update(accessor = "status()")
+        ),
     ),
     tbl_prefix = "core",
     post_persist_hook = "publish"
@@ -49,6 +54,23 @@ where
         }
     }
 
+    pub async fn pending_approval_exists_for_customer(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<bool, CreditFacilityProposalError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM core_credit_facility_proposals
+                WHERE customer_id = $1 AND status = $2
+            ) AS "exists!""#,
+            customer_id as CustomerId,
+            CreditFacilityProposalStatus::PendingApproval as CreditFacilityProposalStatus,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(exists)
+    }
+
     async fn publish(
         &self,
         op: &mut impl es_entity::AtomicOperation,
@@ -60,3 +82,41 @@ where
             .await
     }
 }
+
+mod proposal_status_sqlx {
+    use sqlx::{Type, postgres::*};
+
+    use crate::primitives::CreditFacilityProposalStatus;
+
+    impl Type<Postgres> for CreditFacilityProposalStatus {
+        fn type_info() -> PgTypeInfo {
+            <String as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <String as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl sqlx::Encode<'_, Postgres> for CreditFacilityProposalStatus {
+        fn encode_by_ref(
+            &self,
+            buf: &mut PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            <String as sqlx::Encode<'_, Postgres>>::encode(self.to_string(), buf)
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for CreditFacilityProposalStatus {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let s = <String as sqlx::Decode<Postgres>>::decode(value)?;
+            Ok(s.parse().map_err(|e: strum::ParseError| Box::new(e))?)
+        }
+    }
+
+    impl PgHasArrayType for CreditFacilityProposalStatus {
+        fn array_type_info() -> PgTypeInfo {
+            <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+        }
+    }
+}
@@ -8,8 +8,9 @@ use es_entity::*;
 
 use crate::{
     ledger::CreditFacilityLedgerAccountIds,
-    obligation::{NewObligation, ObligationAccounts},
+    obligation::{NewObligation, ObligationAccounts, ObligationPenaltyTerms},
     primitives::*,
+    terms::{AnnualRatePct, DayCountConvention},
 };
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +30,10 @@ pub enum DisbursalEvent {
         overdue_date: Option<EffectiveDate>,
         liquidation_date: Option<EffectiveDate>,
         public_id: PublicId,
+        #[serde(default)]
+        penalty_annual_rate: Option<AnnualRatePct>,
+        #[serde(default)]
+        day_count_convention: DayCountConvention,
     },
     ApprovalProcessConcluded {
         approval_process_id: ApprovalProcessId,
@@ -57,6 +62,8 @@ pub struct Disbursal {
     pub due_date: EffectiveDate,
     pub overdue_date: Option<EffectiveDate>,
     pub liquidation_date: Option<EffectiveDate>,
+    pub penalty_annual_rate: Option<AnnualRatePct>,
+    pub day_count_convention: DayCountConvention,
     pub initiated_tx_id: LedgerTxId,
     #[builder(setter(strip_option), default)]
     pub concluded_tx_id: Option<LedgerTxId>,
@@ -81,6 +88,8 @@ impl TryFromEvents<DisbursalEvent> for Disbursal {
                     overdue_date,
                     liquidation_date,
                     public_id,
+                    penalty_annual_rate,
+                    day_count_convention,
                     ..
                 } => {
                     builder = builder
@@ -94,6 +103,8 @@ impl TryFromEvents<DisbursalEvent> for Disbursal {
                         .due_date(*due_date)
                         .overdue_date(*overdue_date)
                         .liquidation_date(*liquidation_date)
+                        .penalty_annual_rate(*penalty_annual_rate)
+                        .day_count_convention(*day_count_convention)
                         .public_id(public_id.clone())
                 }
                 DisbursalEvent::Settled { ledger_tx_id, .. } => {
@@ -191,6 +202,14 @@ impl Disbursal {
     ) -> Idempotent<NewObligation> {
         idempotency_guard!(self.events.iter_all(), DisbursalEvent::Settled { .. });
         let obligation_id = ObligationId::new();
+        let penalty = self
+            .penalty_annual_rate
+            .map(|annual_rate| ObligationPenaltyTerms {
+                annual_rate,
+                day_count_convention: self.day_count_convention,
+                receivable_account_id: self.account_ids.penalty_receivable_account_id,
+                income_account_id: self.account_ids.penalty_income_account_id,
+            });
         self.events.push(DisbursalEvent::Settled {
             ledger_tx_id: tx_id,
             obligation_id,
@@ -225,6 +244,7 @@ impl Disbursal {
                 .due_date(self.due_date)
                 .overdue_date(self.overdue_date)
                 .liquidation_date(self.liquidation_date)
+                .penalty(penalty)
                 .effective(effective)
                 .build()
                 .expect("could not build new disbursal obligation"),
@@ -269,6 +289,10 @@ pub struct NewDisbursal {
     pub(super) due_date: EffectiveDate,
     pub(super) overdue_date: Option<EffectiveDate>,
     pub(super) liquidation_date: Option<EffectiveDate>,
+    #[builder(default)]
+    pub(super) penalty_annual_rate: Option<AnnualRatePct>,
+    #[builder(default)]
+    pub(super) day_count_convention: DayCountConvention,
     #[builder(setter(into))]
     pub(super) public_id: PublicId,
 }
@@ -310,6 +334,8 @@ impl IntoEvents<DisbursalEvent> for NewDisbursal {
                 overdue_date: self.overdue_date,
                 liquidation_date: self.liquidation_date,
                 public_id: self.public_id,
+                penalty_annual_rate: self.penalty_annual_rate,
+                day_count_convention: self.day_count_convention,
             }],
         )
     }
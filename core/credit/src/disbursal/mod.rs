@@ -8,7 +8,9 @@ use tracing::instrument;
 
 use audit::AuditSvc;
 use authz::PermissionCheck;
-use governance::{Governance, GovernanceAction, GovernanceEvent, GovernanceObject};
+use governance::{
+    ApprovalProcessContext, Governance, GovernanceAction, GovernanceEvent, GovernanceObject,
+};
 use outbox::OutboxEventMarker;
 
 use crate::{Obligation, Obligations, event::CoreCreditEvent, primitives::*};
@@ -98,6 +100,7 @@ where
         &self,
         db: &mut es_entity::DbOp<'_>,
         new_disbursal: NewDisbursal,
+        customer_type: CustomerType,
     ) -> Result<Disbursal, DisbursalError> {
         self.governance
             .start_process(
@@ -105,6 +108,8 @@ where
                 new_disbursal.approval_process_id,
                 new_disbursal.approval_process_id.to_string(),
                 crate::APPROVE_DISBURSAL_PROCESS,
+                ApprovalProcessContext::for_amount(new_disbursal.amount)
+                    .with_customer_type(customer_type),
             )
             .await?;
         let disbursal = self.repo.create_in_op(db, new_disbursal).await?;
@@ -120,7 +125,7 @@ where
         &self,
         db: &mut es_entity::DbOpWithTime<'_>,
         new_disbursal: NewDisbursal,
-    ) -> Result<DisbursalId, DisbursalError> {
+    ) -> Result<(Disbursal, Obligation), DisbursalError> {
         let mut disbursal = self.repo.create_in_op(db, new_disbursal).await?;
 
         let new_obligation = disbursal
@@ -128,13 +133,14 @@ where
             .expect("First instance of idempotent action ignored")
             .expect("First disbursal obligation was already created");
 
-        self.obligations
+        let obligation = self
+            .obligations
             .create_with_jobs_in_op(db, new_obligation)
             .await?;
 
         self.repo.update_in_op(db, &mut disbursal).await?;
 
-        Ok(disbursal.id)
+        Ok((disbursal, obligation))
     }
 
     #[instrument(name = "core_credit.disbursals.find_by_id", skip(self), err)]
@@ -32,6 +32,8 @@ pub enum CoreCreditError {
     RepaymentPlanError(#[from] super::repayment_plan::error::CreditFacilityRepaymentPlanError),
     #[error("CoreCreditError - CollateralError: {0}")]
     CollateralError(#[from] super::collateral::error::CollateralError),
+    #[error("CoreCreditError - CollateralReturnError: {0}")]
+    CollateralReturnError(Box<super::collateral_return::error::CollateralReturnError>),
     #[error("CoreCreditError - CoreCustodyError: {0}")]
     CustodyError(#[from] core_custody::error::CoreCustodyError),
     #[error("CoreCreditError - PaymentError: {0}")]
@@ -63,3 +65,9 @@ pub enum CoreCreditError {
     #[error("CoreCreditError - PublicIdError: {0}")]
     PublicIdError(#[from] public_id::PublicIdError),
 }
+
+impl From<super::collateral_return::error::CollateralReturnError> for CoreCreditError {
+    fn from(e: super::collateral_return::error::CollateralReturnError) -> Self {
+        Self::CollateralReturnError(Box::new(e))
+    }
+}
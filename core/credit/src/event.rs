@@ -33,6 +33,13 @@ pub enum CoreCreditEvent {
         id: CreditFacilityId,
         completed_at: DateTime<Utc>,
     },
+    FacilityTermsAmended {
+        id: CreditFacilityId,
+        old_terms: TermValues,
+        new_terms: TermValues,
+        maturity_date: EffectiveDate,
+        amended_at: DateTime<Utc>,
+    },
     FacilityRepaymentRecorded {
         credit_facility_id: CreditFacilityId,
         obligation_id: ObligationId,
@@ -60,6 +67,8 @@ pub enum CoreCreditEvent {
         collateral: Satoshis,
         outstanding: CreditFacilityReceivable,
         price: PriceOfOneBTC,
+        #[serde(default)]
+        price_observation_id: Option<PriceObservationId>,
     },
     DisbursalSettled {
         credit_facility_id: CreditFacilityId,
@@ -109,6 +118,19 @@ pub enum CoreCreditEvent {
         id: ObligationId,
         credit_facility_id: CreditFacilityId,
     },
+    ObligationPrincipalInstallmentScheduled {
+        id: ObligationId,
+        installment_obligation_id: ObligationId,
+        credit_facility_id: CreditFacilityId,
+        amount: UsdCents,
+    },
+    ObligationRescheduled {
+        id: ObligationId,
+        credit_facility_id: CreditFacilityId,
+        due_at: EffectiveDate,
+        overdue_at: Option<EffectiveDate>,
+        defaulted_at: Option<EffectiveDate>,
+    },
     LiquidationProcessStarted {
         id: LiquidationProcessId,
         obligation_id: ObligationId,
@@ -118,9 +140,45 @@ pub enum CoreCreditEvent {
         recorded_at: DateTime<Utc>,
         effective: chrono::NaiveDate,
     },
+    LiquidationCollateralSold {
+        id: LiquidationProcessId,
+        obligation_id: ObligationId,
+        credit_facility_id: CreditFacilityId,
+        ledger_tx_id: LedgerTxId,
+        collateral_sold: Satoshis,
+        proceeds: UsdCents,
+        execution_price: PriceOfOneBTC,
+        recorded_at: DateTime<Utc>,
+        effective: chrono::NaiveDate,
+    },
     LiquidationProcessConcluded {
         id: LiquidationProcessId,
         obligation_id: ObligationId,
         credit_facility_id: CreditFacilityId,
+        amount_applied: UsdCents,
+        shortfall: UsdCents,
+        surplus: UsdCents,
+    },
+    MarginCallOpened {
+        id: MarginCallId,
+        credit_facility_id: CreditFacilityId,
+        collateral_required: Satoshis,
+        repayment_required: UsdCents,
+        cure_deadline: DateTime<Utc>,
+        recorded_at: DateTime<Utc>,
+        effective: chrono::NaiveDate,
+    },
+    MarginCallCured {
+        id: MarginCallId,
+        credit_facility_id: CreditFacilityId,
+        recorded_at: DateTime<Utc>,
+        effective: chrono::NaiveDate,
+    },
+    MarginCallEscalated {
+        id: MarginCallId,
+        credit_facility_id: CreditFacilityId,
+        obligation_ids: Vec<ObligationId>,
+        recorded_at: DateTime<Utc>,
+        effective: chrono::NaiveDate,
     },
 }
@@ -138,12 +138,19 @@ impl CreditFacilityHistory {
                     ));
             }
             FacilityCompleted { .. } => {}
+            FacilityTermsAmended { .. } => {}
             ObligationCreated { .. } => {}
             ObligationDue { .. } => {}
             ObligationOverdue { .. } => {}
             ObligationDefaulted { .. } => {}
+            LiquidationCollateralSold { .. } => {}
             LiquidationProcessConcluded { .. } => {}
             ObligationCompleted { .. } => {}
+            ObligationPrincipalInstallmentScheduled { .. } => {}
+            ObligationRescheduled { .. } => {}
+            MarginCallOpened { .. } => {}
+            MarginCallCured { .. } => {}
+            MarginCallEscalated { .. } => {}
         }
     }
 }
@@ -0,0 +1,216 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use tracing::{Span, instrument};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use job::*;
+use outbox::{Outbox, OutboxEventMarker, PersistentOutboxEvent};
+
+use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+
+use crate::{CollateralReturns, CoreCreditAction, CoreCreditEvent, CoreCreditObject};
+
+#[derive(serde::Serialize)]
+pub(crate) struct CollateralReturnTransferSyncJobConfig<Perms, E> {
+    _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> CollateralReturnTransferSyncJobConfig<Perms, E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Perms, E> Default for CollateralReturnTransferSyncJobConfig<Perms, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Perms, E> JobConfig for CollateralReturnTransferSyncJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    type Initializer = CollateralReturnTransferSyncInit<Perms, E>;
+}
+
+#[derive(Default, Clone, Copy, serde::Deserialize, serde::Serialize)]
+struct CollateralReturnTransferSyncJobData {
+    sequence: outbox::EventSequence,
+}
+
+pub struct CollateralReturnTransferSyncJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    collateral_returns: CollateralReturns<Perms, E>,
+    outbox: Outbox<E>,
+}
+
+impl<Perms, E> CollateralReturnTransferSyncJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    #[instrument(name = "core_credit.collateral_return_transfer_sync_job.process_message", parent = None, skip(self, message), fields(seq = %message.sequence, handled = false, event_type = tracing::field::Empty))]
+    async fn process_message(
+        &self,
+        message: &PersistentOutboxEvent<E>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match message.as_event() {
+            Some(
+                event @ CoreCustodyEvent::TransferConfirmed {
+                    external_transfer_id,
+                    confirmed_at,
+                },
+            ) => {
+                message.inject_trace_parent();
+                Span::current().record("handled", true);
+                Span::current().record("event_type", event.as_ref());
+
+                self.collateral_returns
+                    .confirm_transfer(external_transfer_id, *confirmed_at)
+                    .await?;
+            }
+            Some(
+                event @ CoreCustodyEvent::TransferFailed {
+                    external_transfer_id,
+                    failed_at,
+                },
+            ) => {
+                message.inject_trace_parent();
+                Span::current().record("handled", true);
+                Span::current().record("event_type", event.as_ref());
+
+                self.collateral_returns
+                    .fail_transfer(external_transfer_id, *failed_at)
+                    .await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for CollateralReturnTransferSyncJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut state = current_job
+            .execution_state::<CollateralReturnTransferSyncJobData>()?
+            .unwrap_or_default();
+        let mut stream = self.outbox.listen_persisted(Some(state.sequence)).await?;
+
+        while let Some(message) = stream.next().await {
+            self.process_message(message.as_ref()).await?;
+            state.sequence = message.sequence;
+            current_job.update_execution_state(&state).await?;
+        }
+
+        Ok(JobCompletion::RescheduleNow)
+    }
+}
+
+pub struct CollateralReturnTransferSyncInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    outbox: Outbox<E>,
+    collateral_returns: CollateralReturns<Perms, E>,
+}
+
+impl<Perms, E> CollateralReturnTransferSyncInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    pub fn new(outbox: &Outbox<E>, collateral_returns: &CollateralReturns<Perms, E>) -> Self {
+        Self {
+            outbox: outbox.clone(),
+            collateral_returns: collateral_returns.clone(),
+        }
+    }
+}
+
+const COLLATERAL_RETURN_TRANSFER_SYNC_JOB: JobType =
+    JobType::new("outbox.collateral-return-transfer-sync");
+impl<Perms, E> JobInitializer for CollateralReturnTransferSyncInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        COLLATERAL_RETURN_TRANSFER_SYNC_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(CollateralReturnTransferSyncJobRunner {
+            outbox: self.outbox.clone(),
+            collateral_returns: self.collateral_returns.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
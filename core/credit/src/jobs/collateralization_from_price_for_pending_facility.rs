@@ -1,26 +1,24 @@
 use async_trait::async_trait;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-
-use std::time::Duration;
+use tracing::{Span, instrument};
 
 use audit::AuditSvc;
 use authz::PermissionCheck;
 use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
 use job::*;
-use outbox::OutboxEventMarker;
+use outbox::{EventSequence, Outbox, OutboxEventMarker, PersistentOutboxEvent};
 
 use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+use core_price::CorePriceEvent;
 
 use crate::{
     CoreCreditAction, CoreCreditEvent, CoreCreditObject,
-    pending_credit_facility::PendingCreditFacilities,
+    pending_credit_facility::PendingCreditFacilities, primitives::*,
 };
 
-#[serde_with::serde_as]
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Serialize, Deserialize)]
 pub(crate) struct PendingCreditFacilityCollateralizationFromPriceJobConfig<Perms, E> {
-    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
-    pub job_interval: Duration,
     pub _phantom: std::marker::PhantomData<(Perms, E)>,
 }
 impl<Perms, E> JobConfig for PendingCreditFacilityCollateralizationFromPriceJobConfig<Perms, E>
@@ -32,7 +30,8 @@ where
         From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
-        + OutboxEventMarker<CoreCustodyEvent>,
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
     type Initializer = PendingCreditFacilityCollateralizationFromPriceInit<Perms, E>;
 }
@@ -41,8 +40,10 @@ where
     Perms: PermissionCheck,
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
-        + OutboxEventMarker<CoreCustodyEvent>,
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
+    outbox: Outbox<E>,
     pending_credit_facilities: PendingCreditFacilities<Perms, E>,
 }
 
@@ -55,17 +56,22 @@ where
         From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
-        + OutboxEventMarker<CoreCustodyEvent>,
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
-    pub fn new(pending_credit_facilities: PendingCreditFacilities<Perms, E>) -> Self {
+    pub fn new(
+        outbox: &Outbox<E>,
+        pending_credit_facilities: &PendingCreditFacilities<Perms, E>,
+    ) -> Self {
         Self {
-            pending_credit_facilities,
+            outbox: outbox.clone(),
+            pending_credit_facilities: pending_credit_facilities.clone(),
         }
     }
 }
 
 const PENDING_CREDIT_FACILITY_COLLATERALZIATION_FROM_PRICE_JOB: JobType =
-    JobType::new("cron.pending-credit-facility-collateralization-from-price");
+    JobType::new("outbox.pending-credit-facility-collateralization-from-price");
 impl<Perms, E> JobInitializer for PendingCreditFacilityCollateralizationFromPriceInit<Perms, E>
 where
     Perms: PermissionCheck,
@@ -75,7 +81,8 @@ where
         From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
-        + OutboxEventMarker<CoreCustodyEvent>,
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
     fn job_type() -> JobType
     where
@@ -84,10 +91,10 @@ where
         PENDING_CREDIT_FACILITY_COLLATERALZIATION_FROM_PRICE_JOB
     }
 
-    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+    fn init(&self, _job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
         Ok(Box::new(
             PendingCreditFacilityCollateralizationFromPriceJobRunner::<Perms, E> {
-                config: job.config()?,
+                outbox: self.outbox.clone(),
                 pending_credit_facilities: self.pending_credit_facilities.clone(),
             },
         ))
@@ -101,17 +108,65 @@ where
     }
 }
 
+#[derive(Default, Clone, Copy, serde::Deserialize, serde::Serialize)]
+struct PendingCreditFacilityCollateralizationFromPriceData {
+    sequence: EventSequence,
+}
+
 pub struct PendingCreditFacilityCollateralizationFromPriceJobRunner<Perms, E>
 where
     Perms: PermissionCheck,
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
-        + OutboxEventMarker<CoreCustodyEvent>,
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
-    config: PendingCreditFacilityCollateralizationFromPriceJobConfig<Perms, E>,
+    outbox: Outbox<E>,
     pending_credit_facilities: PendingCreditFacilities<Perms, E>,
 }
 
+impl<Perms, E> PendingCreditFacilityCollateralizationFromPriceJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
+{
+    #[instrument(name = "core_credit.pending_collateralization_from_price_job.process_message", parent = None, skip(self, message), fields(seq = %message.sequence, handled = false, event_type = tracing::field::Empty))]
+    async fn process_message(
+        &self,
+        message: &PersistentOutboxEvent<E>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(
+            event @ CorePriceEvent::PriceUpdated {
+                observation_id,
+                price,
+                observed_at,
+                ..
+            },
+        ) = message.as_event()
+        {
+            message.inject_trace_parent();
+            Span::current().record("handled", true);
+            Span::current().record("event_type", event.as_ref());
+
+            self.pending_credit_facilities
+                .update_collateralization_from_price(&PriceObservation::aggregated(
+                    *observation_id,
+                    *price,
+                    *observed_at,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl<Perms, E> JobRunner for PendingCreditFacilityCollateralizationFromPriceJobRunner<Perms, E>
 where
@@ -122,16 +177,24 @@ where
         From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
-        + OutboxEventMarker<CoreCustodyEvent>,
+        + OutboxEventMarker<CoreCustodyEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
     async fn run(
         &self,
-        _current_job: CurrentJob,
+        mut current_job: CurrentJob,
     ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
-        self.pending_credit_facilities
-            .update_collateralization_from_price()
-            .await?;
+        let mut state = current_job
+            .execution_state::<PendingCreditFacilityCollateralizationFromPriceData>()?
+            .unwrap_or_default();
+        let mut stream = self.outbox.listen_persisted(Some(state.sequence)).await?;
+
+        while let Some(message) = stream.next().await {
+            self.process_message(&message).await?;
+            state.sequence = message.sequence;
+            current_job.update_execution_state(state).await?;
+        }
 
-        Ok(JobCompletion::RescheduleIn(self.config.job_interval))
+        Ok(JobCompletion::RescheduleNow)
     }
 }
@@ -46,6 +46,7 @@ where
             }
             Some(event @ FacilityActivated { id, .. })
             | Some(event @ FacilityCompleted { id, .. })
+            | Some(event @ FacilityTermsAmended { id, .. })
             | Some(
                 event @ FacilityRepaymentRecorded {
                     credit_facility_id: id,
@@ -101,6 +102,18 @@ where
                     ..
                 },
             )
+            | Some(
+                event @ ObligationPrincipalInstallmentScheduled {
+                    credit_facility_id: id,
+                    ..
+                },
+            )
+            | Some(
+                event @ ObligationRescheduled {
+                    credit_facility_id: id,
+                    ..
+                },
+            )
             | Some(
                 event @ LiquidationProcessStarted {
                     credit_facility_id: id,
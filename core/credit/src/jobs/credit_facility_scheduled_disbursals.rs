@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use job::*;
+use outbox::OutboxEventMarker;
+
+use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+
+use crate::{credit_facility::CreditFacilities, event::CoreCreditEvent, primitives::*};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CreditFacilityScheduledDisbursalsJobConfig<Perms, E> {
+    pub credit_facility_id: CreditFacilityId,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> JobConfig for CreditFacilityScheduledDisbursalsJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    type Initializer = CreditFacilityScheduledDisbursalsInit<Perms, E>;
+}
+
+pub struct CreditFacilityScheduledDisbursalsInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    credit_facilities: CreditFacilities<Perms, E>,
+}
+
+impl<Perms, E> CreditFacilityScheduledDisbursalsInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub fn new(credit_facilities: &CreditFacilities<Perms, E>) -> Self {
+        Self {
+            credit_facilities: credit_facilities.clone(),
+        }
+    }
+}
+
+const CREDIT_FACILITY_SCHEDULED_DISBURSALS_JOB: JobType =
+    JobType::new("task.credit-facility-scheduled-disbursals");
+impl<Perms, E> JobInitializer for CreditFacilityScheduledDisbursalsInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        CREDIT_FACILITY_SCHEDULED_DISBURSALS_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(CreditFacilityScheduledDisbursalsJobRunner::<
+            Perms,
+            E,
+        > {
+            config: job.config()?,
+            credit_facilities: self.credit_facilities.clone(),
+        }))
+    }
+}
+
+pub struct CreditFacilityScheduledDisbursalsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    config: CreditFacilityScheduledDisbursalsJobConfig<Perms, E>,
+    credit_facilities: CreditFacilities<Perms, E>,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for CreditFacilityScheduledDisbursalsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    #[instrument(
+        name = "credit.job.credit-facility-scheduled-disbursals",
+        skip(self, _current_job),
+        fields(attempt)
+    )]
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        match self
+            .credit_facilities
+            .execute_scheduled_disbursal(self.config.credit_facility_id)
+            .await?
+        {
+            Some(next_disbursal_at) => Ok(JobCompletion::RescheduleAt(next_disbursal_at)),
+            None => Ok(JobCompletion::Complete),
+        }
+    }
+}
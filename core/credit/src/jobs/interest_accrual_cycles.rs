@@ -162,16 +162,23 @@ where
             )
             .await?;
 
-        let CompletedAccrualCycle {
+        let Some(CompletedAccrualCycle {
             facility_accrual_cycle_data,
             new_cycle_data,
-        } = self
+        }) = self
             .credit_facilities
             .complete_interest_cycle_and_maybe_start_new_cycle(
                 &mut op,
                 self.config.credit_facility_id,
             )
-            .await?;
+            .await?
+        else {
+            tracing::info!(
+                credit_facility_id = %self.config.credit_facility_id,
+                "Accrual cycle already posted, facility was paid off"
+            );
+            return Ok(JobCompletion::Complete);
+        };
 
         if let Some(new_cycle_data) = new_cycle_data {
             let NewInterestAccrualCycleData {
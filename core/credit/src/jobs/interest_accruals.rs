@@ -133,15 +133,22 @@ where
     ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
         let mut db = self.credit_facilities.begin_op().await?;
 
-        let crate::ConfirmedAccrual {
+        let Some(crate::ConfirmedAccrual {
             accrual: interest_accrual,
             next_period: next_accrual_period,
             accrual_idx,
             accrued_count,
-        } = self
+        }) = self
             .credit_facilities
             .confirm_interest_accrual_in_op(&mut db, self.config.credit_facility_id)
-            .await?;
+            .await?
+        else {
+            tracing::info!(
+                credit_facility_id = %self.config.credit_facility_id,
+                "No accrual cycle in progress, facility was paid off"
+            );
+            return Ok(JobCompletion::Complete);
+        };
 
         if let Some(period) = next_accrual_period {
             self.ledger
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{event::CoreCreditEvent, margin_call::MarginCalls, primitives::*};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct MarginCallDeadlineJobConfig<Perms, E> {
+    pub margin_call_id: MarginCallId,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> JobConfig for MarginCallDeadlineJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    type Initializer = MarginCallDeadlineInit<Perms, E>;
+}
+
+pub struct MarginCallDeadlineInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    margin_calls: MarginCalls<Perms, E>,
+}
+
+impl<Perms, E> MarginCallDeadlineInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    pub fn new(margin_calls: &MarginCalls<Perms, E>) -> Self {
+        Self {
+            margin_calls: margin_calls.clone(),
+        }
+    }
+}
+
+const MARGIN_CALL_DEADLINE_JOB: JobType = JobType::new("task.margin-call-deadline");
+impl<Perms, E> JobInitializer for MarginCallDeadlineInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        MARGIN_CALL_DEADLINE_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(MarginCallDeadlineJobRunner::<Perms, E> {
+            config: job.config()?,
+            margin_calls: self.margin_calls.clone(),
+        }))
+    }
+}
+
+pub struct MarginCallDeadlineJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    config: MarginCallDeadlineJobConfig<Perms, E>,
+    margin_calls: MarginCalls<Perms, E>,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for MarginCallDeadlineJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    #[instrument(
+        name = "credit.job.margin-call-deadline",
+        skip(self, _current_job),
+        fields(attempt)
+    )]
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        self.margin_calls
+            .escalate(self.config.margin_call_id)
+            .await?;
+        Ok(JobCompletion::Complete)
+    }
+}
@@ -0,0 +1,223 @@
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::{Span, instrument};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use job::*;
+use outbox::{EventSequence, Outbox, OutboxEventMarker, PersistentOutboxEvent};
+
+use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+
+use crate::{
+    credit_facility::CreditFacilities, event::CoreCreditEvent, margin_call::MarginCalls,
+    primitives::*,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct MarginCallFromEventsJobConfig<Perms, E> {
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> JobConfig for MarginCallFromEventsJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    type Initializer = MarginCallFromEventsInit<Perms, E>;
+}
+
+pub struct MarginCallFromEventsInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    outbox: Outbox<E>,
+    credit_facilities: CreditFacilities<Perms, E>,
+    margin_calls: MarginCalls<Perms, E>,
+}
+
+impl<Perms, E> MarginCallFromEventsInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub fn new(
+        outbox: &Outbox<E>,
+        credit_facilities: &CreditFacilities<Perms, E>,
+        margin_calls: &MarginCalls<Perms, E>,
+    ) -> Self {
+        Self {
+            outbox: outbox.clone(),
+            credit_facilities: credit_facilities.clone(),
+            margin_calls: margin_calls.clone(),
+        }
+    }
+}
+
+const MARGIN_CALL_FROM_EVENTS_JOB: JobType = JobType::new("outbox.margin-call");
+
+impl<Perms, E> JobInitializer for MarginCallFromEventsInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        MARGIN_CALL_FROM_EVENTS_JOB
+    }
+
+    fn init(&self, _job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(MarginCallFromEventsRunner::<Perms, E> {
+            outbox: self.outbox.clone(),
+            credit_facilities: self.credit_facilities.clone(),
+            margin_calls: self.margin_calls.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+#[derive(Default, Clone, Copy, serde::Deserialize, serde::Serialize)]
+struct MarginCallFromEventsData {
+    sequence: EventSequence,
+}
+
+pub struct MarginCallFromEventsRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    outbox: Outbox<E>,
+    credit_facilities: CreditFacilities<Perms, E>,
+    margin_calls: MarginCalls<Perms, E>,
+}
+
+impl<Perms, E> MarginCallFromEventsRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    #[instrument(name = "core_credit.margin_call_job.process_message", parent = None, skip(self, message), fields(seq = %message.sequence, handled = false, event_type = tracing::field::Empty, credit_facility_id = tracing::field::Empty))]
+    async fn process_message(
+        &self,
+        message: &PersistentOutboxEvent<E>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match message.as_event() {
+            Some(
+                event @ CoreCreditEvent::FacilityCollateralizationChanged {
+                    id,
+                    state,
+                    price,
+                    effective,
+                    ..
+                },
+            ) => {
+                message.inject_trace_parent();
+                Span::current().record("handled", true);
+                Span::current().record("event_type", event.as_ref());
+                Span::current().record("credit_facility_id", tracing::field::display(id));
+
+                match state {
+                    CollateralizationState::UnderMarginCallThreshold
+                    | CollateralizationState::UnderLiquidationThreshold => {
+                        let credit_facility =
+                            self.credit_facilities.find_by_id_without_audit(*id).await?;
+                        self.margin_calls
+                            .open_for_credit_facility(&credit_facility, *state, *price, *effective)
+                            .await?;
+                    }
+                    CollateralizationState::FullyCollateralized
+                    | CollateralizationState::NoExposure => {
+                        self.margin_calls
+                            .cure_for_credit_facility(*id, *effective)
+                            .await?;
+                    }
+                    CollateralizationState::NoCollateral => {}
+                }
+            }
+            Some(event @ CoreCreditEvent::FacilityCompleted { id, completed_at }) => {
+                message.inject_trace_parent();
+                Span::current().record("handled", true);
+                Span::current().record("event_type", event.as_ref());
+                Span::current().record("credit_facility_id", tracing::field::display(id));
+
+                self.margin_calls
+                    .cure_for_credit_facility(*id, completed_at.date_naive())
+                    .await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Perms, E> JobRunner for MarginCallFromEventsRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut state = current_job
+            .execution_state::<MarginCallFromEventsData>()?
+            .unwrap_or_default();
+        let mut stream = self.outbox.listen_persisted(Some(state.sequence)).await?;
+
+        while let Some(message) = stream.next().await {
+            self.process_message(&message).await?;
+            state.sequence = message.sequence;
+            current_job.update_execution_state(state).await?;
+        }
+
+        Ok(JobCompletion::RescheduleNow)
+    }
+}
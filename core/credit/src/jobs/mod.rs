@@ -1,3 +1,4 @@
+pub mod collateral_return_transfer_sync;
 pub mod collateralization_from_events;
 pub mod collateralization_from_events_for_pending_facility;
 pub mod collateralization_from_price;
@@ -5,10 +6,14 @@ pub mod collateralization_from_price_for_pending_facility;
 pub mod credit_facility_history;
 pub mod credit_facility_maturity;
 pub mod credit_facility_repayment_plan;
+pub mod credit_facility_scheduled_disbursals;
 pub mod interest_accrual_cycles;
 pub mod interest_accruals;
+pub mod margin_call_deadline;
+pub mod margin_call_from_events;
 pub mod obligation_defaulted;
 pub mod obligation_due;
 pub mod obligation_liquidation;
 pub mod obligation_overdue;
+pub mod obligation_penalty_accrual;
 pub mod wallet_collateral_sync;
@@ -8,7 +8,7 @@ use outbox::OutboxEventMarker;
 
 use crate::{event::CoreCreditEvent, ledger::CreditLedger, obligation::Obligations, primitives::*};
 
-use super::{obligation_defaulted, obligation_liquidation};
+use super::{obligation_defaulted, obligation_liquidation, obligation_penalty_accrual};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub(crate) struct ObligationOverdueJobConfig<Perms, E> {
@@ -139,6 +139,19 @@ where
                 .await?;
         }
 
+        if obligation.is_accruing_penalty() {
+            self.jobs
+                .create_and_spawn_in_op(
+                    &mut db,
+                    JobId::new(),
+                    obligation_penalty_accrual::ObligationPenaltyAccrualJobConfig::<Perms, E> {
+                        obligation_id: obligation.id,
+                        _phantom: std::marker::PhantomData,
+                    },
+                )
+                .await?;
+        }
+
         self.ledger.record_obligation_overdue(db, overdue).await?;
 
         Ok(JobCompletion::Complete)
@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{event::CoreCreditEvent, ledger::CreditLedger, obligation::Obligations, primitives::*};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct ObligationPenaltyAccrualJobConfig<Perms, E> {
+    pub obligation_id: ObligationId,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> JobConfig for ObligationPenaltyAccrualJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    type Initializer = ObligationPenaltyAccrualInit<Perms, E>;
+}
+pub struct ObligationPenaltyAccrualInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    obligations: Obligations<Perms, E>,
+    ledger: CreditLedger,
+}
+
+impl<Perms, E> ObligationPenaltyAccrualInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    pub fn new(ledger: &CreditLedger, obligations: &Obligations<Perms, E>) -> Self {
+        Self {
+            ledger: ledger.clone(),
+            obligations: obligations.clone(),
+        }
+    }
+}
+
+const OBLIGATION_PENALTY_ACCRUAL_JOB: JobType = JobType::new("task.obligation-penalty-accrual");
+impl<Perms, E> JobInitializer for ObligationPenaltyAccrualInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        OBLIGATION_PENALTY_ACCRUAL_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(ObligationPenaltyAccrualJobRunner::<Perms, E> {
+            config: job.config()?,
+            obligations: self.obligations.clone(),
+            ledger: self.ledger.clone(),
+        }))
+    }
+}
+
+pub struct ObligationPenaltyAccrualJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    config: ObligationPenaltyAccrualJobConfig<Perms, E>,
+    obligations: Obligations<Perms, E>,
+    ledger: CreditLedger,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for ObligationPenaltyAccrualJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let effective = crate::time::now().date_naive();
+
+        let mut db = self.obligations.begin_op().await?;
+        let (obligation, penalty_obligation) = self
+            .obligations
+            .accrue_penalty_in_op(&mut db, self.config.obligation_id, effective)
+            .await?;
+
+        if !obligation.is_accruing_penalty() {
+            return Ok(JobCompletion::Complete);
+        }
+
+        if let Some(penalty_obligation) = penalty_obligation {
+            self.ledger
+                .record_penalty_accrual(db, penalty_obligation)
+                .await?;
+        } else {
+            db.commit().await?;
+        }
+
+        let next_accrual_at = EffectiveDate::from(effective + chrono::Days::new(1)).start_of_day();
+        Ok(JobCompletion::RescheduleAt(next_accrual_at))
+    }
+}
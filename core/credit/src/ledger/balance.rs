@@ -1,5 +1,5 @@
 use core_price::PriceOfOneBTC;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -25,6 +25,7 @@ pub struct CreditFacilityBalanceSummary {
     pub(super) due_interest_outstanding: UsdCents,
     pub(super) overdue_interest_outstanding: UsdCents,
     pub(super) interest_defaulted: UsdCents,
+    pub(super) penalty_outstanding: UsdCents,
 }
 
 // For testing we want to be able to construct the struct
@@ -45,6 +46,7 @@ pub struct CreditFacilityBalanceSummary {
     pub due_interest_outstanding: UsdCents,
     pub overdue_interest_outstanding: UsdCents,
     pub interest_defaulted: UsdCents,
+    pub penalty_outstanding: UsdCents,
 }
 
 impl CreditFacilityBalanceSummary {
@@ -84,8 +86,12 @@ impl CreditFacilityBalanceSummary {
         self.not_yet_due_interest_outstanding + self.interest_outstanding_payable()
     }
 
+    pub fn penalty_outstanding(&self) -> UsdCents {
+        self.penalty_outstanding
+    }
+
     pub fn total_outstanding(&self) -> UsdCents {
-        self.disbursed_outstanding() + self.interest_outstanding()
+        self.disbursed_outstanding() + self.interest_outstanding() + self.penalty_outstanding
     }
 
     pub fn interest_posted(&self) -> UsdCents {
@@ -96,7 +102,9 @@ impl CreditFacilityBalanceSummary {
         self.collateral
     }
     pub fn total_outstanding_payable(&self) -> UsdCents {
-        self.disbursed_outstanding_payable() + self.interest_outstanding_payable()
+        self.disbursed_outstanding_payable()
+            + self.interest_outstanding_payable()
+            + self.penalty_outstanding
     }
 
     fn total_outstanding_not_yet_payable(&self) -> UsdCents {
@@ -168,6 +176,54 @@ impl CreditFacilityBalanceSummary {
 
         CollateralizationRatio::Finite(collateral / amount)
     }
+
+    pub fn shortfall_to_cvl(
+        &self,
+        price: PriceOfOneBTC,
+        target_cvl: CVLPct,
+    ) -> CollateralizationShortfall {
+        let amount = if self.disbursed.is_zero() {
+            self.facility
+        } else {
+            self.total_outstanding()
+        };
+        let pct = match target_cvl {
+            CVLPct::Finite(pct) if !amount.is_zero() && pct > Decimal::ZERO => pct,
+            _ => return CollateralizationShortfall::default(),
+        };
+
+        let collateral_value = price.sats_to_cents_round_down(self.collateral);
+        let required_value = target_cvl.scale(amount);
+        let collateral = if required_value > collateral_value {
+            price.cents_to_sats_round_up(required_value - collateral_value)
+        } else {
+            Satoshis::ZERO
+        };
+
+        let supported_amount = UsdCents::try_from_usd(
+            (collateral_value.to_usd() * Decimal::ONE_HUNDRED / pct)
+                .round_dp_with_strategy(2, RoundingStrategy::ToZero),
+        )
+        .expect("supported amount should be a valid non-negative amount");
+        let repayment = if amount > supported_amount {
+            amount - supported_amount
+        } else {
+            UsdCents::ZERO
+        };
+
+        CollateralizationShortfall {
+            collateral,
+            repayment,
+        }
+    }
+}
+
+/// Either of the amounts on its own restores the facility to the target CVL.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct CollateralizationShortfall {
+    pub collateral: Satoshis,
+    pub repayment: UsdCents,
 }
 
 #[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
@@ -247,6 +303,7 @@ mod test {
             due_interest_outstanding: UsdCents::ZERO,
             overdue_interest_outstanding: UsdCents::ZERO,
             interest_defaulted: UsdCents::ZERO,
+            penalty_outstanding: UsdCents::ZERO,
 
             facility_remaining: UsdCents::from(1),
             interest_posted: UsdCents::from(1),
@@ -278,6 +335,7 @@ mod test {
             due_interest_outstanding: UsdCents::ZERO,
             overdue_interest_outstanding: UsdCents::ZERO,
             interest_defaulted: UsdCents::ZERO,
+            penalty_outstanding: UsdCents::ZERO,
 
             facility_remaining: UsdCents::from(1),
             interest_posted: UsdCents::from(1),
@@ -309,6 +367,7 @@ mod test {
             due_interest_outstanding: UsdCents::ZERO,
             overdue_interest_outstanding: UsdCents::ZERO,
             interest_defaulted: UsdCents::ZERO,
+            penalty_outstanding: UsdCents::ZERO,
 
             facility_remaining: UsdCents::from(1),
             interest_posted: UsdCents::from(1),
@@ -333,6 +392,7 @@ mod test {
             due_interest_outstanding: UsdCents::ZERO,
             overdue_interest_outstanding: UsdCents::ZERO,
             interest_defaulted: UsdCents::ZERO,
+            penalty_outstanding: UsdCents::ZERO,
 
             facility_remaining: UsdCents::from(1),
             interest_posted: UsdCents::from(1),
@@ -361,6 +421,7 @@ mod test {
             due_interest_outstanding: UsdCents::ZERO,
             overdue_interest_outstanding: UsdCents::ZERO,
             interest_defaulted: UsdCents::ZERO,
+            penalty_outstanding: UsdCents::ZERO,
 
             facility_remaining: UsdCents::from(1),
             interest_posted: UsdCents::from(1),
@@ -374,4 +435,23 @@ mod test {
             CollateralizationRatio::Finite(expected)
         );
     }
+
+    #[test]
+    fn shortfall_to_cvl() {
+        let balances = CreditFacilityBalanceSummary {
+            collateral: Satoshis::from(100_000_000),
+            facility: UsdCents::from(50_000_00),
+            disbursed: UsdCents::from(40_000_00),
+            not_yet_due_disbursed_outstanding: UsdCents::from(40_000_00),
+            ..Default::default()
+        };
+        let price = PriceOfOneBTC::new(UsdCents::from(50_000_00));
+
+        let shortfall = balances.shortfall_to_cvl(price, CVLPct::new(150));
+        assert_eq!(shortfall.collateral, Satoshis::from(20_000_000));
+        assert_eq!(shortfall.repayment, UsdCents::from(6_666_67));
+
+        let shortfall = balances.shortfall_to_cvl(price, CVLPct::new(125));
+        assert_eq!(shortfall, CollateralizationShortfall::default());
+    }
 }
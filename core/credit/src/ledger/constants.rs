@@ -188,6 +188,18 @@ pub const CREDIT_INTEREST_INCOME_ACCOUNT_SET_REF: &str = "credit-interest-income
 pub const CREDIT_FEE_INCOME_ACCOUNT_SET_NAME: &str = "Credit Fee Income Account Set";
 pub const CREDIT_FEE_INCOME_ACCOUNT_SET_REF: &str = "credit-fee-income-account-set";
 
+pub const CREDIT_FACILITY_UNAPPLIED_PAYMENTS_ACCOUNT_SET_NAME: &str =
+    "Credit Facility Unapplied Payments Account Set";
+pub const CREDIT_FACILITY_UNAPPLIED_PAYMENTS_ACCOUNT_SET_REF: &str =
+    "credit-facility-unapplied-payments-account-set";
+
+pub const CREDIT_PENALTY_RECEIVABLE_ACCOUNT_SET_NAME: &str =
+    "Credit Penalty Receivable Account Set";
+pub const CREDIT_PENALTY_RECEIVABLE_ACCOUNT_SET_REF: &str = "credit-penalty-receivable-account-set";
+
+pub const CREDIT_PENALTY_INCOME_ACCOUNT_SET_NAME: &str = "Credit Penalty Income Account Set";
+pub const CREDIT_PENALTY_INCOME_ACCOUNT_SET_REF: &str = "credit-penalty-income-account-set";
+
 // Velocity Controls
 pub(super) const CREDIT_FACILITY_VELOCITY_CONTROL_ID: uuid::Uuid =
     uuid::uuid!("00000000-0000-0000-0000-000000000002");
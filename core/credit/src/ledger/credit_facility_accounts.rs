@@ -25,6 +25,12 @@ pub struct CreditFacilityLedgerAccountIds {
     pub interest_defaulted_account_id: CalaAccountId,
     pub interest_income_account_id: CalaAccountId,
     pub fee_income_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub unapplied_payments_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub penalty_receivable_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub penalty_income_account_id: CalaAccountId,
 }
 
 impl CreditFacilityLedgerAccountIds {
@@ -45,6 +51,72 @@ impl CreditFacilityLedgerAccountIds {
             interest_defaulted_account_id: CalaAccountId::new(),
             interest_income_account_id: CalaAccountId::new(),
             fee_income_account_id: CalaAccountId::new(),
+            unapplied_payments_account_id: CalaAccountId::new(),
+            penalty_receivable_account_id: CalaAccountId::new(),
+            penalty_income_account_id: CalaAccountId::new(),
+        }
+    }
+}
+
+impl CreditFacilityLedgerAccountIds {
+    pub(crate) fn backfilled_account_ids(&self) -> CreditFacilityBackfilledAccountIds {
+        CreditFacilityBackfilledAccountIds {
+            unapplied_payments_account_id: self.unapplied_payments_account_id,
+            penalty_receivable_account_id: self.penalty_receivable_account_id,
+            penalty_income_account_id: self.penalty_income_account_id,
+        }
+    }
+
+    pub(crate) fn with_backfilled_account_ids(
+        self,
+        backfilled: CreditFacilityBackfilledAccountIds,
+    ) -> Self {
+        Self {
+            unapplied_payments_account_id: backfilled.unapplied_payments_account_id,
+            penalty_receivable_account_id: backfilled.penalty_receivable_account_id,
+            penalty_income_account_id: backfilled.penalty_income_account_id,
+            ..self
+        }
+    }
+}
+
+/// Placeholder for accounts that did not exist yet when a facility was activated.
+pub(crate) fn missing_account_id() -> CalaAccountId {
+    CalaAccountId::from(uuid::Uuid::nil())
+}
+
+/// Accounts added to the facility account layout after launch. Facilities activated
+/// before an account was introduced get it created and recorded by a backfill.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct CreditFacilityBackfilledAccountIds {
+    pub unapplied_payments_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub penalty_receivable_account_id: CalaAccountId,
+    #[serde(default = "missing_account_id")]
+    pub penalty_income_account_id: CalaAccountId,
+}
+
+impl CreditFacilityBackfilledAccountIds {
+    pub(crate) fn is_complete(&self) -> bool {
+        self.unapplied_payments_account_id != missing_account_id()
+            && self.penalty_receivable_account_id != missing_account_id()
+            && self.penalty_income_account_id != missing_account_id()
+    }
+
+    /// Keeps the accounts that already exist and allocates ids for the missing ones.
+    pub(crate) fn completed(self) -> Self {
+        let or_new = |id: CalaAccountId| {
+            if id == missing_account_id() {
+                CalaAccountId::new()
+            } else {
+                id
+            }
+        };
+        Self {
+            unapplied_payments_account_id: or_new(self.unapplied_payments_account_id),
+            penalty_receivable_account_id: or_new(self.penalty_receivable_account_id),
+            penalty_income_account_id: or_new(self.penalty_income_account_id),
         }
     }
 }
@@ -65,6 +137,9 @@ impl From<PendingCreditFacilityAccountIds> for CreditFacilityLedgerAccountIds {
             interest_defaulted_account_id: CalaAccountId::new(),
             interest_income_account_id: CalaAccountId::new(),
             fee_income_account_id: CalaAccountId::new(),
+            unapplied_payments_account_id: CalaAccountId::new(),
+            penalty_receivable_account_id: CalaAccountId::new(),
+            penalty_income_account_id: CalaAccountId::new(),
         }
     }
 }
@@ -93,6 +168,17 @@ pub struct CreditFacilityCompletion {
     pub credit_facility_account_ids: CreditFacilityLedgerAccountIds,
 }
 
+#[derive(Debug, Clone)]
+pub struct CreditFacilityPayoff {
+    pub accruals: Vec<CreditFacilityInterestAccrual>,
+    pub accrual_cycle: Option<CreditFacilityInterestAccrualCycle>,
+    pub prepayment_fee_tx_id: LedgerTxId,
+    pub prepayment_fee: UsdCents,
+    pub debit_account_id: CalaAccountId,
+    pub effective: chrono::NaiveDate,
+    pub completion: CreditFacilityCompletion,
+}
+
 #[derive(Debug, Clone)]
 pub struct PendingCreditFacilityCreation {
     pub tx_id: LedgerTxId,
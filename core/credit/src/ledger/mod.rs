@@ -20,10 +20,12 @@ use cala_ledger::{
 };
 
 use crate::{
-    COLLATERAL_ENTITY_TYPE, ChartOfAccountsIntegrationConfig, CollateralId, FacilityDurationType,
-    Obligation, ObligationDefaultedReallocationData, ObligationDueReallocationData,
+    AppliedLiquidationProceeds, COLLATERAL_ENTITY_TYPE, ChartOfAccountsIntegrationConfig,
+    CollateralId, FacilityDurationType, LiquidationShortfallData, LiquidationSurplusData, Obligation,
+    ObligationDefaultedReallocationData, ObligationDueReallocationData,
     ObligationOverdueReallocationData,
-    liquidation_process::LiquidationProcess,
+    liquidation_process::{LiquidationProceedsData, LiquidationProcess},
+    payment::UnappliedPaymentData,
     payment_allocation::PaymentAllocation,
     primitives::{
         CREDIT_FACILITY_ENTITY_TYPE, CREDIT_FACILITY_PROPOSAL_ENTITY_TYPE, CalaAccountId,
@@ -119,6 +121,9 @@ pub struct CreditFacilityInternalAccountSets {
     pub interest_defaulted: InternalAccountSetDetails,
     pub interest_income: InternalAccountSetDetails,
     pub fee_income: InternalAccountSetDetails,
+    pub unapplied_payments: InternalAccountSetDetails,
+    pub penalty_receivable: InternalAccountSetDetails,
+    pub penalty_income: InternalAccountSetDetails,
 }
 
 impl CreditFacilityInternalAccountSets {
@@ -129,6 +134,9 @@ impl CreditFacilityInternalAccountSets {
             in_liquidation,
             interest_income,
             fee_income,
+            unapplied_payments,
+            penalty_receivable,
+            penalty_income,
 
             disbursed_receivable:
                 DisbursedReceivable {
@@ -153,6 +161,9 @@ impl CreditFacilityInternalAccountSets {
             fee_income.id,
             disbursed_defaulted.id,
             interest_defaulted.id,
+            unapplied_payments.id,
+            penalty_receivable.id,
+            penalty_income.id,
         ];
         ids.extend(
             disbursed_short_term
@@ -197,6 +208,13 @@ impl CreditLedger {
         templates::CancelDisbursal::init(cala).await?;
         templates::ConfirmDisbursal::init(cala).await?;
         templates::ReserveForLiquidation::init(cala).await?;
+        templates::RecordLiquidationProceeds::init(cala).await?;
+        templates::RecordLiquidationShortfall::init(cala).await?;
+        templates::RecordLiquidationSurplus::init(cala).await?;
+        templates::RecordUnappliedPayment::init(cala).await?;
+        templates::RefundUnappliedPayment::init(cala).await?;
+        templates::RecordPrepaymentFee::init(cala).await?;
+        templates::CreditFacilityAccruePenalty::init(cala).await?;
         templates::CreateCreditFacilityProposal::init(cala).await?;
 
         let collateral_omnibus_normal_balance_type = DebitOrCredit::Debit;
@@ -594,6 +612,36 @@ impl CreditLedger {
         )
         .await?;
 
+        let unapplied_payments_normal_balance_type = DebitOrCredit::Credit;
+        let unapplied_payments_account_set_id = Self::find_or_create_account_set(
+            cala,
+            journal_id,
+            format!("{journal_id}:{CREDIT_FACILITY_UNAPPLIED_PAYMENTS_ACCOUNT_SET_REF}"),
+            CREDIT_FACILITY_UNAPPLIED_PAYMENTS_ACCOUNT_SET_NAME.to_string(),
+            unapplied_payments_normal_balance_type,
+        )
+        .await?;
+
+        let penalty_receivable_normal_balance_type = DebitOrCredit::Debit;
+        let penalty_receivable_account_set_id = Self::find_or_create_account_set(
+            cala,
+            journal_id,
+            format!("{journal_id}:{CREDIT_PENALTY_RECEIVABLE_ACCOUNT_SET_REF}"),
+            CREDIT_PENALTY_RECEIVABLE_ACCOUNT_SET_NAME.to_string(),
+            penalty_receivable_normal_balance_type,
+        )
+        .await?;
+
+        let penalty_income_normal_balance_type = DebitOrCredit::Credit;
+        let penalty_income_account_set_id = Self::find_or_create_account_set(
+            cala,
+            journal_id,
+            format!("{journal_id}:{CREDIT_PENALTY_INCOME_ACCOUNT_SET_REF}"),
+            CREDIT_PENALTY_INCOME_ACCOUNT_SET_NAME.to_string(),
+            penalty_income_normal_balance_type,
+        )
+        .await?;
+
         let interest_income_normal_balance_type = DebitOrCredit::Credit;
         let interest_income_account_set_id = Self::find_or_create_account_set(
             cala,
@@ -801,6 +849,18 @@ impl CreditLedger {
                 id: fee_income_account_set_id,
                 normal_balance_type: fee_income_normal_balance_type,
             },
+            unapplied_payments: InternalAccountSetDetails {
+                id: unapplied_payments_account_set_id,
+                normal_balance_type: unapplied_payments_normal_balance_type,
+            },
+            penalty_receivable: InternalAccountSetDetails {
+                id: penalty_receivable_account_set_id,
+                normal_balance_type: penalty_receivable_normal_balance_type,
+            },
+            penalty_income: InternalAccountSetDetails {
+                id: penalty_income_account_set_id,
+                normal_balance_type: penalty_income_normal_balance_type,
+            },
         };
 
         let disbursal_limit_id = velocity::DisbursalLimit::init(cala).await?;
@@ -1009,10 +1069,13 @@ impl CreditLedger {
             interest_receivable_due_account_id,
             interest_receivable_overdue_account_id,
             interest_defaulted_account_id,
+            penalty_receivable_account_id,
 
             in_liquidation_account_id: _,
             fee_income_account_id: _,
             interest_income_account_id: _,
+            unapplied_payments_account_id: _,
+            penalty_income_account_id: _,
         }: CreditFacilityLedgerAccountIds,
     ) -> Result<CreditFacilityBalanceSummary, CreditLedgerError> {
         let facility_id = (self.journal_id, facility_account_id, self.usd);
@@ -1049,6 +1112,7 @@ impl CreditLedger {
             self.usd,
         );
         let interest_defaulted_id = (self.journal_id, interest_defaulted_account_id, self.usd);
+        let penalty_receivable_id = (self.journal_id, penalty_receivable_account_id, self.usd);
         let balances = self
             .cala
             .balances()
@@ -1063,6 +1127,7 @@ impl CreditLedger {
                 interest_receivable_due_id,
                 interest_receivable_overdue_id,
                 interest_defaulted_id,
+                penalty_receivable_id,
             ])
             .await?;
         let facility = if let Some(b) = balances.get(&facility_id) {
@@ -1132,6 +1197,12 @@ impl CreditLedger {
             UsdCents::ZERO
         };
 
+        let penalty_outstanding = if let Some(b) = balances.get(&penalty_receivable_id) {
+            UsdCents::try_from_usd(b.settled())?
+        } else {
+            UsdCents::ZERO
+        };
+
         let collateral = if let Some(b) = balances.get(&collateral_id) {
             Satoshis::try_from_btc(b.settled())?
         } else {
@@ -1154,6 +1225,8 @@ impl CreditLedger {
             due_interest_outstanding,
             overdue_interest_outstanding,
             interest_defaulted,
+
+            penalty_outstanding,
         })
     }
 
@@ -1326,6 +1399,104 @@ impl CreditLedger {
         Ok(())
     }
 
+    pub async fn record_payment(
+        &self,
+        op: es_entity::DbOp<'_>,
+        allocations: Vec<PaymentAllocation>,
+        unapplied: Option<UnappliedPaymentData>,
+        deposit_account_id: CalaAccountId,
+        unapplied_payments_account_id: CalaAccountId,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        for allocation in allocations {
+            self.record_obligation_repayment_in_op(&mut op, allocation)
+                .await?;
+        }
+
+        if let Some(UnappliedPaymentData {
+            tx_id,
+            amount,
+            effective,
+        }) = unapplied
+        {
+            self.cala
+                .post_transaction_in_op(
+                    &mut op,
+                    tx_id,
+                    templates::RECORD_UNAPPLIED_PAYMENT_CODE,
+                    templates::RecordUnappliedPaymentParams {
+                        journal_id: self.journal_id,
+                        currency: self.usd,
+                        amount: amount.to_usd(),
+                        deposit_account_id,
+                        unapplied_payments_account_id,
+                        effective,
+                    },
+                )
+                .await?;
+        }
+
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn refund_unapplied_payments(
+        &self,
+        op: es_entity::DbOp<'_>,
+        refunds: Vec<UnappliedPaymentData>,
+        deposit_account_id: CalaAccountId,
+        unapplied_payments_account_id: CalaAccountId,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        for UnappliedPaymentData {
+            tx_id,
+            amount,
+            effective,
+        } in refunds
+        {
+            self.cala
+                .post_transaction_in_op(
+                    &mut op,
+                    tx_id,
+                    templates::REFUND_UNAPPLIED_PAYMENT_CODE,
+                    templates::RefundUnappliedPaymentParams {
+                        journal_id: self.journal_id,
+                        currency: self.usd,
+                        amount: amount.to_usd(),
+                        deposit_account_id,
+                        unapplied_payments_account_id,
+                        effective,
+                    },
+                )
+                .await?;
+        }
+
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn get_unapplied_payments_balance(
+        &self,
+        unapplied_payments_account_id: CalaAccountId,
+    ) -> Result<UsdCents, CreditLedgerError> {
+        let unapplied_id = (self.journal_id, unapplied_payments_account_id, self.usd);
+        let balances = self.cala.balances().find_all(&[unapplied_id]).await?;
+
+        let unapplied = if let Some(b) = balances.get(&unapplied_id) {
+            UsdCents::try_from_usd(b.settled())?
+        } else {
+            UsdCents::ZERO
+        };
+
+        Ok(unapplied)
+    }
+
     pub async fn record_obligation_due(
         &self,
         op: es_entity::DbOp<'_>,
@@ -1392,6 +1563,44 @@ impl CreditLedger {
         Ok(())
     }
 
+    pub async fn record_penalty_accrual(
+        &self,
+        op: es_entity::DbOp<'_>,
+        obligation: Obligation,
+    ) -> Result<(), CreditLedgerError> {
+        let penalty_receivable_account_id = obligation.not_yet_due_accounts().receivable_account_id;
+        let penalty_income_account_id = obligation.not_yet_due_accounts().account_to_be_credited_id;
+        let Obligation {
+            tx_id,
+            reference: external_id,
+            initial_amount: amount,
+            effective,
+            ..
+        } = obligation;
+
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                tx_id,
+                templates::CREDIT_FACILITY_ACCRUE_PENALTY_CODE,
+                templates::CreditFacilityAccruePenaltyParams {
+                    journal_id: self.journal_id,
+                    currency: self.usd,
+                    amount: amount.to_usd(),
+                    penalty_receivable_account_id,
+                    penalty_income_account_id,
+                    external_id,
+                    effective,
+                },
+            )
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
     pub async fn record_obligation_defaulted(
         &self,
         op: es_entity::DbOp<'_>,
@@ -1459,21 +1668,158 @@ impl CreditLedger {
         Ok(())
     }
 
+    pub async fn record_liquidation_execution(
+        &self,
+        op: es_entity::DbOp<'_>,
+        collateral_update: Option<CollateralUpdate>,
+        collateral_account_id: CalaAccountId,
+        LiquidationProceedsData {
+            tx_id,
+            proceeds,
+            reserved_amount,
+            in_liquidation_account_id,
+            effective,
+        }: LiquidationProceedsData,
+        proceeds_receiving_account_id: CalaAccountId,
+        AppliedLiquidationProceeds {
+            allocations,
+            shortfalls,
+            surplus,
+            ..
+        }: AppliedLiquidationProceeds,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        if let Some(CollateralUpdate {
+            tx_id,
+            abs_diff,
+            effective,
+            ..
+        }) = collateral_update
+        {
+            self.cala
+                .post_transaction_in_op(
+                    &mut op,
+                    tx_id,
+                    templates::REMOVE_COLLATERAL_CODE,
+                    templates::RemoveCollateralParams {
+                        journal_id: self.journal_id,
+                        currency: self.btc,
+                        amount: abs_diff.to_btc(),
+                        collateral_account_id,
+                        bank_collateral_account_id: self.collateral_omnibus_account_ids.account_id,
+                        effective,
+                    },
+                )
+                .await?;
+        }
+
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                tx_id,
+                templates::RECORD_LIQUIDATION_PROCEEDS_CODE,
+                templates::RecordLiquidationProceedsParams {
+                    journal_id: self.journal_id,
+                    reserved_amount: reserved_amount.to_usd(),
+                    proceeds_amount: (proceeds
+                        - surplus.as_ref().map(|s| s.amount).unwrap_or(UsdCents::ZERO))
+                    .to_usd(),
+                    liquidation_omnibus_account_id: self
+                        .in_liquidation_omnibus_account_ids
+                        .account_id,
+                    facility_liquidation_account_id: in_liquidation_account_id,
+                    proceeds_receiving_account_id,
+                    effective,
+                },
+            )
+            .await?;
+
+        for allocation in allocations {
+            self.record_obligation_repayment_in_op(&mut op, allocation)
+                .await?;
+        }
+
+        for LiquidationShortfallData {
+            tx_id,
+            amount,
+            receivable_account_id,
+            defaulted_account_id,
+            effective,
+        } in shortfalls
+        {
+            self.cala
+                .post_transaction_in_op(
+                    &mut op,
+                    tx_id,
+                    templates::RECORD_LIQUIDATION_SHORTFALL_CODE,
+                    templates::RecordLiquidationShortfallParams {
+                        journal_id: self.journal_id,
+                        amount: amount.to_usd(),
+                        receivable_account_id,
+                        defaulted_account_id,
+                        effective,
+                    },
+                )
+                .await?;
+        }
+
+        if let Some(LiquidationSurplusData {
+            tx_id,
+            amount,
+            effective,
+        }) = surplus
+        {
+            self.cala
+                .post_transaction_in_op(
+                    &mut op,
+                    tx_id,
+                    templates::RECORD_LIQUIDATION_SURPLUS_CODE,
+                    templates::RecordLiquidationSurplusParams {
+                        journal_id: self.journal_id,
+                        amount: amount.to_usd(),
+                        liquidation_omnibus_account_id: self
+                            .in_liquidation_omnibus_account_ids
+                            .account_id,
+                        surplus_receiving_account_id: proceeds_receiving_account_id,
+                        effective,
+                    },
+                )
+                .await?;
+        }
+
+        op.commit().await?;
+        Ok(())
+    }
+
     pub async fn complete_credit_facility(
         &self,
         op: es_entity::DbOp<'_>,
+        completion: CreditFacilityCompletion,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+        self.complete_credit_facility_in_op(&mut op, completion)
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    async fn complete_credit_facility_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
         CreditFacilityCompletion {
             tx_id,
             collateral,
             credit_facility_account_ids,
         }: CreditFacilityCompletion,
     ) -> Result<(), CreditLedgerError> {
-        let mut op = self
-            .cala
-            .ledger_operation_from_db_op(op.with_db_time().await?);
         self.cala
             .post_transaction_in_op(
-                &mut op,
+                op,
                 tx_id,
                 templates::REMOVE_COLLATERAL_CODE,
                 templates::RemoveCollateralParams {
@@ -1486,6 +1832,60 @@ impl CreditLedger {
                 },
             )
             .await?;
+        Ok(())
+    }
+
+    pub async fn record_facility_payoff(
+        &self,
+        op: es_entity::DbOp<'_>,
+        CreditFacilityPayoff {
+            accruals,
+            accrual_cycle,
+            prepayment_fee_tx_id,
+            prepayment_fee,
+            debit_account_id,
+            effective,
+            completion,
+        }: CreditFacilityPayoff,
+        allocations: Vec<PaymentAllocation>,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        for accrual in accruals {
+            self.record_interest_accrual_in_op(&mut op, accrual).await?;
+        }
+        if let Some(accrual_cycle) = accrual_cycle {
+            self.record_interest_accrual_cycle_in_op(&mut op, accrual_cycle)
+                .await?;
+        }
+        for allocation in allocations {
+            self.record_obligation_repayment_in_op(&mut op, allocation)
+                .await?;
+        }
+        if !prepayment_fee.is_zero() {
+            self.cala
+                .post_transaction_in_op(
+                    &mut op,
+                    prepayment_fee_tx_id,
+                    templates::RECORD_PREPAYMENT_FEE_CODE,
+                    templates::RecordPrepaymentFeeParams {
+                        journal_id: self.journal_id,
+                        currency: self.usd,
+                        amount: prepayment_fee.to_usd(),
+                        deposit_account_id: debit_account_id,
+                        fee_income_account_id: completion
+                            .credit_facility_account_ids
+                            .fee_income_account_id,
+                        effective,
+                    },
+                )
+                .await?;
+        }
+        self.complete_credit_facility_in_op(&mut op, completion)
+            .await?;
+
         op.commit().await?;
         Ok(())
     }
@@ -1655,6 +2055,19 @@ impl CreditLedger {
     pub async fn record_interest_accrual(
         &self,
         op: es_entity::DbOp<'_>,
+        accrual: CreditFacilityInterestAccrual,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+        self.record_interest_accrual_in_op(&mut op, accrual).await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    async fn record_interest_accrual_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
         CreditFacilityInterestAccrual {
             tx_id,
             tx_ref,
@@ -1663,12 +2076,9 @@ impl CreditLedger {
             credit_facility_account_ids,
         }: CreditFacilityInterestAccrual,
     ) -> Result<(), CreditLedgerError> {
-        let mut op = self
-            .cala
-            .ledger_operation_from_db_op(op.with_db_time().await?);
         self.cala
             .post_transaction_in_op(
-                &mut op,
+                op,
                 tx_id,
                 templates::CREDIT_FACILITY_ACCRUE_INTEREST_CODE,
                 templates::CreditFacilityAccrueInterestParams {
@@ -1684,13 +2094,26 @@ impl CreditLedger {
                 },
             )
             .await?;
-        op.commit().await?;
         Ok(())
     }
 
     pub async fn record_interest_accrual_cycle(
         &self,
         op: es_entity::DbOp<'_>,
+        accrual_cycle: CreditFacilityInterestAccrualCycle,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+        self.record_interest_accrual_cycle_in_op(&mut op, accrual_cycle)
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    async fn record_interest_accrual_cycle_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
         CreditFacilityInterestAccrualCycle {
             tx_id,
             tx_ref,
@@ -1699,12 +2122,9 @@ impl CreditLedger {
             credit_facility_account_ids,
         }: CreditFacilityInterestAccrualCycle,
     ) -> Result<(), CreditLedgerError> {
-        let mut op = self
-            .cala
-            .ledger_operation_from_db_op(op.with_db_time().await?);
         self.cala
             .post_transaction_in_op(
-                &mut op,
+                op,
                 tx_id,
                 templates::CREDIT_FACILITY_POST_ACCRUED_INTEREST_CODE,
                 templates::CreditFacilityPostAccruedInterestParams {
@@ -1720,7 +2140,6 @@ impl CreditLedger {
                 },
             )
             .await?;
-        op.commit().await?;
         Ok(())
     }
 
@@ -1753,6 +2172,60 @@ impl CreditLedger {
         Ok(())
     }
 
+    pub async fn initiate_and_settle_disbursal(
+        &self,
+        entity_id: DisbursalId,
+        op: es_entity::DbOpWithTime<'_>,
+        initiated_tx_id: LedgerTxId,
+        obligation: Obligation,
+        facility_account_id: CalaAccountId,
+    ) -> Result<(), CreditLedgerError> {
+        let facility_disbursed_receivable_account =
+            obligation.not_yet_due_accounts().receivable_account_id;
+        let account_to_be_credited_id = obligation.not_yet_due_accounts().account_to_be_credited_id;
+        let Obligation {
+            tx_id,
+            reference: external_id,
+            initial_amount: amount,
+            ..
+        } = obligation;
+
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                initiated_tx_id,
+                templates::INITIATE_DISBURSAL_CODE,
+                templates::InitiateDisbursalParams {
+                    entity_id: entity_id.into(),
+                    journal_id: self.journal_id,
+                    credit_omnibus_account: self.facility_omnibus_account_ids.account_id,
+                    credit_facility_account: facility_account_id,
+                    disbursed_amount: amount.to_usd(),
+                },
+            )
+            .await?;
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                tx_id,
+                templates::CONFIRM_DISBURSAL_CODE,
+                templates::ConfirmDisbursalParams {
+                    entity_id: entity_id.into(),
+                    journal_id: self.journal_id,
+                    credit_omnibus_account: self.facility_omnibus_account_ids.account_id,
+                    credit_facility_account: facility_account_id,
+                    facility_disbursed_receivable_account,
+                    account_to_be_credited_id,
+                    disbursed_amount: amount.to_usd(),
+                    external_id,
+                },
+            )
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
     pub async fn cancel_disbursal(
         &self,
         entity_id: DisbursalId,
@@ -2044,6 +2517,9 @@ impl CreditLedger {
             interest_defaulted_account_id,
             interest_income_account_id,
             fee_income_account_id,
+            unapplied_payments_account_id,
+            penalty_receivable_account_id,
+            penalty_income_account_id,
 
             // these accounts are created during proposal creation
             collateral_account_id: _collateral_account_id,
@@ -2219,6 +2695,30 @@ impl CreditLedger {
             fee_income_reference,
             fee_income_name,
             fee_income_name,
+            entity_ref.clone(),
+        )
+        .await?;
+
+        self.create_unapplied_payments_account_in_op(
+            op,
+            credit_facility_id,
+            unapplied_payments_account_id,
+            entity_ref.clone(),
+        )
+        .await?;
+
+        self.create_penalty_receivable_account_in_op(
+            op,
+            credit_facility_id,
+            penalty_receivable_account_id,
+            entity_ref.clone(),
+        )
+        .await?;
+
+        self.create_penalty_income_account_in_op(
+            op,
+            credit_facility_id,
+            penalty_income_account_id,
             entity_ref,
         )
         .await?;
@@ -2226,6 +2726,115 @@ impl CreditLedger {
         Ok(())
     }
 
+    /// Creates accounts for a facility activated before they were part of the
+    /// facility account layout.
+    pub async fn backfill_credit_facility_accounts(
+        &self,
+        op: es_entity::DbOp<'_>,
+        credit_facility_id: CreditFacilityId,
+        existing: CreditFacilityBackfilledAccountIds,
+        backfilled: CreditFacilityBackfilledAccountIds,
+    ) -> Result<(), CreditLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+        let entity_ref = EntityRef::new(CREDIT_FACILITY_ENTITY_TYPE, credit_facility_id);
+
+        if backfilled.unapplied_payments_account_id != existing.unapplied_payments_account_id {
+            self.create_unapplied_payments_account_in_op(
+                &mut op,
+                credit_facility_id,
+                backfilled.unapplied_payments_account_id,
+                entity_ref.clone(),
+            )
+            .await?;
+        }
+        if backfilled.penalty_receivable_account_id != existing.penalty_receivable_account_id {
+            self.create_penalty_receivable_account_in_op(
+                &mut op,
+                credit_facility_id,
+                backfilled.penalty_receivable_account_id,
+                entity_ref.clone(),
+            )
+            .await?;
+        }
+        if backfilled.penalty_income_account_id != existing.penalty_income_account_id {
+            self.create_penalty_income_account_in_op(
+                &mut op,
+                credit_facility_id,
+                backfilled.penalty_income_account_id,
+                entity_ref,
+            )
+            .await?;
+        }
+
+        op.commit().await?;
+        Ok(())
+    }
+
+    async fn create_unapplied_payments_account_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        credit_facility_id: CreditFacilityId,
+        account_id: CalaAccountId,
+        entity_ref: EntityRef,
+    ) -> Result<(), CreditLedgerError> {
+        let reference = &format!("credit-facility-unapplied-payments:{credit_facility_id}");
+        let name = &format!("Unapplied Payments Account for Credit Facility {credit_facility_id}");
+        self.create_account_in_op(
+            op,
+            account_id,
+            self.internal_account_sets.unapplied_payments,
+            reference,
+            name,
+            name,
+            entity_ref,
+        )
+        .await
+    }
+
+    async fn create_penalty_receivable_account_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        credit_facility_id: CreditFacilityId,
+        account_id: CalaAccountId,
+        entity_ref: EntityRef,
+    ) -> Result<(), CreditLedgerError> {
+        let reference = &format!("credit-facility-penalty-receivable:{credit_facility_id}");
+        let name = &format!("Penalty Receivable Account for Credit Facility {credit_facility_id}");
+        self.create_account_in_op(
+            op,
+            account_id,
+            self.internal_account_sets.penalty_receivable,
+            reference,
+            name,
+            name,
+            entity_ref,
+        )
+        .await
+    }
+
+    async fn create_penalty_income_account_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        credit_facility_id: CreditFacilityId,
+        account_id: CalaAccountId,
+        entity_ref: EntityRef,
+    ) -> Result<(), CreditLedgerError> {
+        let reference = &format!("credit-facility-penalty-income:{credit_facility_id}");
+        let name = &format!("Penalty Income Account for Credit Facility {credit_facility_id}");
+        self.create_account_in_op(
+            op,
+            account_id,
+            self.internal_account_sets.penalty_income,
+            reference,
+            name,
+            name,
+            entity_ref,
+        )
+        .await
+    }
+
     pub async fn get_chart_of_accounts_integration_config(
         &self,
     ) -> Result<Option<ChartOfAccountsIntegrationConfig>, CreditLedgerError> {
@@ -2332,6 +2941,8 @@ impl CreditLedger {
             in_liquidation_parent_account_set_id,
             interest_income_parent_account_set_id,
             fee_income_parent_account_set_id,
+            penalty_income_parent_account_set_id,
+            penalty_receivable_parent_account_set_id,
             short_term_disbursed_integration_meta,
             long_term_disbursed_integration_meta,
             short_term_interest_integration_meta,
@@ -2414,6 +3025,24 @@ impl CreditLedger {
             |meta| meta.fee_income_parent_account_set_id,
         )
         .await?;
+        self.attach_charts_account_set(
+            &mut op,
+            &mut account_sets,
+            self.internal_account_sets.penalty_income.id,
+            *penalty_income_parent_account_set_id,
+            &charts_integration_meta,
+            |meta| meta.penalty_income_parent_account_set_id,
+        )
+        .await?;
+        self.attach_charts_account_set(
+            &mut op,
+            &mut account_sets,
+            self.internal_account_sets.penalty_receivable.id,
+            *penalty_receivable_parent_account_set_id,
+            &charts_integration_meta,
+            |meta| meta.penalty_receivable_parent_account_set_id,
+        )
+        .await?;
 
         self.attach_short_term_disbursed_receivable_account_sets(
             &mut op,
@@ -3097,6 +3726,8 @@ pub struct ChartOfAccountsIntegrationMeta {
     pub in_liquidation_parent_account_set_id: CalaAccountSetId,
     pub interest_income_parent_account_set_id: CalaAccountSetId,
     pub fee_income_parent_account_set_id: CalaAccountSetId,
+    pub penalty_income_parent_account_set_id: CalaAccountSetId,
+    pub penalty_receivable_parent_account_set_id: CalaAccountSetId,
 
     pub short_term_disbursed_integration_meta: ShortTermDisbursedIntegrationMeta,
     pub long_term_disbursed_integration_meta: LongTermDisbursedIntegrationMeta,
@@ -1,4 +1,5 @@
 mod accrue_interest;
+mod accrue_penalty;
 mod activate_credit_facility;
 mod add_collateral;
 mod add_structuring_fee;
@@ -11,10 +12,17 @@ mod obligation_due_balance;
 mod obligation_overdue_balance;
 mod payment_allocation;
 mod post_accrued_interest;
+mod record_liquidation_proceeds;
+mod record_liquidation_shortfall;
+mod record_liquidation_surplus;
+mod record_prepayment_fee;
+mod record_unapplied_payment;
+mod refund_unapplied_payment;
 mod remove_collateral;
 mod reserve_for_liquidation;
 
 pub use accrue_interest::*;
+pub use accrue_penalty::*;
 pub use activate_credit_facility::*;
 pub use add_collateral::*;
 pub use add_structuring_fee::*;
@@ -27,5 +35,11 @@ pub use obligation_due_balance::*;
 pub use obligation_overdue_balance::*;
 pub use payment_allocation::*;
 pub use post_accrued_interest::*;
+pub use record_liquidation_proceeds::*;
+pub use record_liquidation_shortfall::*;
+pub use record_liquidation_surplus::*;
+pub use record_prepayment_fee::*;
+pub use record_unapplied_payment::*;
+pub use refund_unapplied_payment::*;
 pub use remove_collateral::*;
 pub use reserve_for_liquidation::*;
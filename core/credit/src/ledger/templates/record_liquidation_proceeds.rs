@@ -0,0 +1,166 @@
+use rust_decimal::Decimal;
+use tracing::instrument;
+
+use cala_ledger::{
+    tx_template::{Params, error::TxTemplateError, *},
+    *,
+};
+
+use crate::{ledger::error::*, primitives::CalaAccountId};
+
+pub const RECORD_LIQUIDATION_PROCEEDS_CODE: &str = "RECORD_LIQUIDATION_PROCEEDS";
+
+#[derive(Debug)]
+pub struct RecordLiquidationProceedsParams {
+    pub journal_id: JournalId,
+    pub reserved_amount: Decimal,
+    pub proceeds_amount: Decimal,
+    pub liquidation_omnibus_account_id: CalaAccountId,
+    pub facility_liquidation_account_id: CalaAccountId,
+    pub proceeds_receiving_account_id: CalaAccountId,
+    pub effective: chrono::NaiveDate,
+}
+
+impl RecordLiquidationProceedsParams {
+    pub fn defs() -> Vec<NewParamDefinition> {
+        vec![
+            NewParamDefinition::builder()
+                .name("journal_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("reserved_amount")
+                .r#type(ParamDataType::Decimal)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("proceeds_amount")
+                .r#type(ParamDataType::Decimal)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("liquidation_omnibus_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("facility_liquidation_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("proceeds_receiving_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("effective")
+                .r#type(ParamDataType::Date)
+                .build()
+                .unwrap(),
+        ]
+    }
+}
+impl From<RecordLiquidationProceedsParams> for Params {
+    fn from(
+        RecordLiquidationProceedsParams {
+            journal_id,
+            reserved_amount,
+            proceeds_amount,
+            liquidation_omnibus_account_id,
+            facility_liquidation_account_id,
+            proceeds_receiving_account_id,
+            effective,
+        }: RecordLiquidationProceedsParams,
+    ) -> Self {
+        let mut params = Self::default();
+        params.insert("journal_id", journal_id);
+        params.insert("reserved_amount", reserved_amount);
+        params.insert("proceeds_amount", proceeds_amount);
+        params.insert(
+            "liquidation_omnibus_account_id",
+            liquidation_omnibus_account_id,
+        );
+        params.insert(
+            "facility_liquidation_account_id",
+            facility_liquidation_account_id,
+        );
+        params.insert(
+            "proceeds_receiving_account_id",
+            proceeds_receiving_account_id,
+        );
+        params.insert("effective", effective);
+
+        params
+    }
+}
+
+pub struct RecordLiquidationProceeds;
+
+impl RecordLiquidationProceeds {
+    #[instrument(name = "ledger.record_liquidation_proceeds.init", skip_all)]
+    pub async fn init(ledger: &CalaLedger) -> Result<(), CreditLedgerError> {
+        let tx_input = NewTxTemplateTransaction::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .description(
+                "'Release the amount reserved for liquidation and record the proceeds of the collateral sale'",
+            )
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            NewTxTemplateEntry::builder()
+                .entry_type("'RELEASE_LIQUIDATION_RESERVATION_DR'")
+                .currency("'USD'")
+                .account_id("params.facility_liquidation_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.reserved_amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'RELEASE_LIQUIDATION_RESERVATION_CR'")
+                .currency("'USD'")
+                .account_id("params.liquidation_omnibus_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.reserved_amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'RECORD_LIQUIDATION_PROCEEDS_DR'")
+                .currency("'USD'")
+                .account_id("params.liquidation_omnibus_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.proceeds_amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'RECORD_LIQUIDATION_PROCEEDS_CR'")
+                .currency("'USD'")
+                .account_id("params.proceeds_receiving_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.proceeds_amount")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = RecordLiquidationProceedsParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(TxTemplateId::new())
+            .code(RECORD_LIQUIDATION_PROCEEDS_CODE)
+            .transaction(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build template");
+        match ledger.tx_templates().create(template).await {
+            Err(TxTemplateError::DuplicateCode) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
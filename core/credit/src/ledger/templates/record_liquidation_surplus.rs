@@ -0,0 +1,121 @@
+use rust_decimal::Decimal;
+use tracing::instrument;
+
+use cala_ledger::{
+    tx_template::{Params, error::TxTemplateError, *},
+    *,
+};
+
+use crate::{ledger::error::*, primitives::CalaAccountId};
+
+pub const RECORD_LIQUIDATION_SURPLUS_CODE: &str = "RECORD_LIQUIDATION_SURPLUS";
+
+#[derive(Debug)]
+pub struct RecordLiquidationSurplusParams {
+    pub journal_id: JournalId,
+    pub amount: Decimal,
+    pub liquidation_omnibus_account_id: CalaAccountId,
+    pub surplus_receiving_account_id: CalaAccountId,
+    pub effective: chrono::NaiveDate,
+}
+
+impl RecordLiquidationSurplusParams {
+    pub fn defs() -> Vec<NewParamDefinition> {
+        vec![
+            NewParamDefinition::builder()
+                .name("journal_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("amount")
+                .r#type(ParamDataType::Decimal)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("liquidation_omnibus_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("surplus_receiving_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("effective")
+                .r#type(ParamDataType::Date)
+                .build()
+                .unwrap(),
+        ]
+    }
+}
+impl From<RecordLiquidationSurplusParams> for Params {
+    fn from(
+        RecordLiquidationSurplusParams {
+            journal_id,
+            amount,
+            liquidation_omnibus_account_id,
+            surplus_receiving_account_id,
+            effective,
+        }: RecordLiquidationSurplusParams,
+    ) -> Self {
+        let mut params = Self::default();
+        params.insert("journal_id", journal_id);
+        params.insert("amount", amount);
+        params.insert("liquidation_omnibus_account_id", liquidation_omnibus_account_id);
+        params.insert("surplus_receiving_account_id", surplus_receiving_account_id);
+        params.insert("effective", effective);
+
+        params
+    }
+}
+
+pub struct RecordLiquidationSurplus;
+
+impl RecordLiquidationSurplus {
+    #[instrument(name = "ledger.record_liquidation_surplus.init", skip_all)]
+    pub async fn init(ledger: &CalaLedger) -> Result<(), CreditLedgerError> {
+        let tx_input = NewTxTemplateTransaction::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .description("'Return the liquidation proceeds left after repaying the facility'")
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            NewTxTemplateEntry::builder()
+                .entry_type("'RECORD_LIQUIDATION_SURPLUS_DR'")
+                .currency("'USD'")
+                .account_id("params.liquidation_omnibus_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'RECORD_LIQUIDATION_SURPLUS_CR'")
+                .currency("'USD'")
+                .account_id("params.surplus_receiving_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = RecordLiquidationSurplusParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(TxTemplateId::new())
+            .code(RECORD_LIQUIDATION_SURPLUS_CODE)
+            .transaction(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build template");
+        match ledger.tx_templates().create(template).await {
+            Err(TxTemplateError::DuplicateCode) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 mod chart_of_accounts_integration;
 mod collateral;
+mod collateral_return;
 mod config;
 mod credit_facility;
 mod credit_facility_proposal;
@@ -14,6 +15,7 @@ mod history;
 mod jobs;
 pub mod ledger;
 mod liquidation_process;
+mod margin_call;
 mod obligation;
 mod payment;
 mod payment_allocation;
@@ -35,7 +37,7 @@ use core_custody::{
     CoreCustody, CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject, CustodianId,
 };
 use core_customer::{CoreCustomerAction, CoreCustomerEvent, CustomerObject, Customers};
-use core_price::Price;
+use core_price::{CorePriceEvent, Price};
 use governance::{Governance, GovernanceAction, GovernanceEvent, GovernanceObject};
 use job::Jobs;
 use outbox::{Outbox, OutboxEventMarker};
@@ -47,6 +49,7 @@ pub use chart_of_accounts_integration::{
     error::ChartOfAccountsIntegrationError,
 };
 pub use collateral::*;
+pub use collateral_return::{error::CollateralReturnError, *};
 pub use config::*;
 pub use credit_facility::error::CreditFacilityError;
 pub use credit_facility::*;
@@ -58,13 +61,18 @@ use for_subject::CreditFacilitiesForSubject;
 pub use history::*;
 use jobs::*;
 pub use ledger::*;
+use liquidation_process::LiquidationProceedsData;
+pub use margin_call::{error::MarginCallError, *};
 pub use obligation::{error::*, obligation_cursor::*, *};
 pub use payment::*;
 pub use payment_allocation::*;
 pub use pending_credit_facility::*;
 pub use primitives::*;
-use processes::activate_credit_facility::*;
-pub use processes::{approve_credit_facility_proposal::*, approve_disbursal::*};
+use processes::{activate_credit_facility::*, apply_unapplied_payments::*};
+pub use processes::{
+    approve_collateral_return::*, approve_credit_facility_proposal::*, approve_disbursal::*,
+    approve_terms_amendment::*,
+};
 use publisher::CreditFacilityPublisher;
 pub use repayment_plan::*;
 pub use terms::*;
@@ -73,11 +81,13 @@ pub use terms_template::{error as terms_template_error, *};
 #[cfg(feature = "json-schema")]
 pub mod event_schema {
     pub use crate::{
-        TermsTemplateEvent, collateral::CollateralEvent, credit_facility::CreditFacilityEvent,
+        TermsTemplateEvent, collateral::CollateralEvent, collateral_return::CollateralReturnEvent,
+        credit_facility::CreditFacilityEvent,
         credit_facility_proposal::CreditFacilityProposalEvent, disbursal::DisbursalEvent,
         interest_accrual_cycle::InterestAccrualCycleEvent,
-        liquidation_process::LiquidationProcessEvent, obligation::ObligationEvent,
-        payment::PaymentEvent, payment_allocation::PaymentAllocationEvent,
+        liquidation_process::LiquidationProcessEvent, margin_call::MarginCallEvent,
+        obligation::ObligationEvent, payment::PaymentEvent,
+        payment_allocation::PaymentAllocationEvent,
         pending_credit_facility::PendingCreditFacilityEvent,
     };
 }
@@ -88,7 +98,8 @@ where
     E: OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<GovernanceEvent>
         + OutboxEventMarker<CoreCustodyEvent>
-        + OutboxEventMarker<CoreCustomerEvent>,
+        + OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
     authz: Arc<Perms>,
     credit_facility_proposals: Arc<CreditFacilityProposals<Perms, E>>,
@@ -108,7 +119,9 @@ where
     cala: Arc<CalaLedger>,
     activate_credit_facility: Arc<ActivateCreditFacility<Perms, E>>,
     obligations: Arc<Obligations<Perms, E>>,
+    margin_calls: Arc<MarginCalls<Perms, E>>,
     collaterals: Arc<Collaterals<Perms, E>>,
+    collateral_returns: Arc<CollateralReturns<Perms, E>>,
     custody: Arc<CoreCustody<Perms, E>>,
     chart_of_accounts_integrations: Arc<ChartOfAccountsIntegrations<Perms>>,
     terms_templates: Arc<TermsTemplates<Perms>>,
@@ -121,7 +134,8 @@ where
     E: OutboxEventMarker<GovernanceEvent>
         + OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<CoreCustodyEvent>
-        + OutboxEventMarker<CoreCustomerEvent>,
+        + OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
     fn clone(&self) -> Self {
         Self {
@@ -130,7 +144,9 @@ where
             pending_credit_facilities: self.pending_credit_facilities.clone(),
             facilities: self.facilities.clone(),
             obligations: self.obligations.clone(),
+            margin_calls: self.margin_calls.clone(),
             collaterals: self.collaterals.clone(),
+            collateral_returns: self.collateral_returns.clone(),
             custody: self.custody.clone(),
             disbursals: self.disbursals.clone(),
             payments: self.payments.clone(),
@@ -166,7 +182,8 @@ where
     E: OutboxEventMarker<GovernanceEvent>
         + OutboxEventMarker<CoreCreditEvent>
         + OutboxEventMarker<CoreCustodyEvent>
-        + OutboxEventMarker<CoreCustomerEvent>,
+        + OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CorePriceEvent>,
 {
     pub async fn init(
         pool: &sqlx::PgPool,
@@ -197,13 +214,7 @@ where
         let ledger = CreditLedger::init(cala, journal_id).await?;
         let ledger_arc = Arc::new(ledger);
 
-        let obligations = Obligations::new(
-            pool,
-            authz_arc.clone(),
-            ledger_arc.clone(),
-            jobs_arc.clone(),
-            &publisher,
-        );
+        let obligations = Obligations::new(pool, authz_arc.clone(), jobs_arc.clone(), &publisher);
         let obligations_arc = Arc::new(obligations);
 
         let credit_facility_proposals = CreditFacilityProposals::init(
@@ -244,7 +255,7 @@ where
         .await?;
         let disbursals_arc = Arc::new(disbursals);
 
-        let credit_facilities = CreditFacilities::new(
+        let credit_facilities = CreditFacilities::init(
             pool,
             authz_arc.clone(),
             obligations_arc.clone(),
@@ -256,9 +267,30 @@ where
             &publisher,
             governance_arc.clone(),
             public_ids_arc.clone(),
-        );
+        )
+        .await?;
         let facilities_arc = Arc::new(credit_facilities);
 
+        let margin_calls = MarginCalls::new(
+            pool,
+            authz_arc.clone(),
+            obligations_arc.clone(),
+            ledger_arc.clone(),
+            price_arc.clone(),
+            jobs_arc.clone(),
+            &publisher,
+        );
+        let margin_calls_arc = Arc::new(margin_calls);
+
+        let collateral_returns = CollateralReturns::init(
+            pool,
+            authz_arc.clone(),
+            governance_arc.clone(),
+            custody_arc.clone(),
+        )
+        .await?;
+        let collateral_returns_arc = Arc::new(collateral_returns);
+
         let payments = Payments::new(pool, authz_arc.clone());
         let payments_arc = Arc::new(payments);
 
@@ -279,6 +311,11 @@ where
         );
         let approve_disbursal_arc = Arc::new(approve_disbursal);
 
+        let approve_terms_amendment = ApproveTermsAmendment::new(facilities_arc.clone());
+
+        let approve_collateral_return =
+            ApproveCollateralReturn::new(collateral_returns_arc.clone());
+
         let approve_proposal = ApproveCreditFacilityProposal::new(
             proposals_arc.clone(),
             pending_credit_facilities_arc.clone(),
@@ -298,6 +335,14 @@ where
         );
         let activate_credit_facility_arc = Arc::new(activate_credit_facility);
 
+        let apply_unapplied_payments = ApplyUnappliedPayments::new(
+            facilities_arc.clone(),
+            obligations_arc.clone(),
+            payments_arc.clone(),
+            ledger_arc.clone(),
+            audit_arc.clone(),
+        );
+
         let chart_of_accounts_integrations =
             ChartOfAccountsIntegrations::new(authz_arc.clone(), ledger_arc.clone());
         let chart_of_accounts_integrations_arc = Arc::new(chart_of_accounts_integrations);
@@ -309,9 +354,8 @@ where
             collateralization_from_price_for_pending_facility::PendingCreditFacilityCollateralizationFromPriceInit::<
                 Perms,
                 E,
-            >::new(pending_credit_facilities_arc.as_ref().clone()),
+            >::new(outbox, pending_credit_facilities_arc.as_ref()),
             collateralization_from_price_for_pending_facility::PendingCreditFacilityCollateralizationFromPriceJobConfig {
-                job_interval: config_arc.pending_collateralization_from_price_job_interval,
                 _phantom: std::marker::PhantomData,
             },
         ).await?;
@@ -321,9 +365,8 @@ where
                 collateralization_from_price::CreditFacilityCollateralizationFromPriceInit::<
                     Perms,
                     E,
-                >::new(facilities_arc.as_ref().clone()),
+                >::new(outbox, facilities_arc.as_ref()),
                 collateralization_from_price::CreditFacilityCollateralizationFromPriceJobConfig {
-                    job_interval: config_arc.collateralization_from_price_job_interval,
                     _phantom: std::marker::PhantomData,
                 },
             )
@@ -350,6 +393,22 @@ where
                 },
             )
             .await?;
+        jobs.add_initializer_and_spawn_unique(
+            margin_call_from_events::MarginCallFromEventsInit::<Perms, E>::new(
+                outbox,
+                facilities_arc.as_ref(),
+                margin_calls_arc.as_ref(),
+            ),
+            margin_call_from_events::MarginCallFromEventsJobConfig {
+                _phantom: std::marker::PhantomData,
+            },
+        )
+        .await?;
+        jobs.add_initializer(
+            margin_call_deadline::MarginCallDeadlineInit::<Perms, E>::new(
+                margin_calls_arc.as_ref(),
+            ),
+        );
         jobs.add_initializer_and_spawn_unique(
             credit_facility_history::HistoryProjectionInit::<E>::new(
                 outbox,
@@ -407,20 +466,45 @@ where
                 obligations_arc.as_ref(),
             ),
         );
+        jobs.add_initializer(obligation_penalty_accrual::ObligationPenaltyAccrualInit::<
+            Perms,
+            E,
+        >::new(ledger_arc.as_ref(), obligations_arc.as_ref()));
         jobs.add_initializer(credit_facility_maturity::CreditFacilityMaturityInit::<
             Perms,
             E,
         >::new(facilities_arc.as_ref()));
+        jobs.add_initializer(
+            credit_facility_scheduled_disbursals::CreditFacilityScheduledDisbursalsInit::<
+                Perms,
+                E,
+            >::new(facilities_arc.as_ref()),
+        );
         jobs.add_initializer_and_spawn_unique(
             DisbursalApprovalInit::new(outbox, approve_disbursal_arc.as_ref()),
             DisbursalApprovalJobConfig::<Perms, E>::new(),
         )
         .await?;
+        jobs.add_initializer_and_spawn_unique(
+            TermsAmendmentApprovalInit::new(outbox, &approve_terms_amendment),
+            TermsAmendmentApprovalJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+        jobs.add_initializer_and_spawn_unique(
+            CollateralReturnApprovalInit::new(outbox, &approve_collateral_return),
+            CollateralReturnApprovalJobConfig::<Perms, E>::new(),
+        )
+        .await?;
         jobs.add_initializer_and_spawn_unique(
             CreditFacilityActivationInit::new(outbox, activate_credit_facility_arc.as_ref()),
             CreditFacilityActivationJobConfig::<Perms, E>::new(),
         )
         .await?;
+        jobs.add_initializer_and_spawn_unique(
+            ApplyUnappliedPaymentsInit::new(outbox, &apply_unapplied_payments),
+            ApplyUnappliedPaymentsJobConfig::<Perms, E>::new(),
+        )
+        .await?;
         jobs.add_initializer_and_spawn_unique(
             CreditFacilityProposalApprovalInit::new(outbox, approve_proposal_arc.as_ref()),
             CreditFacilityProposalApprovalJobConfig::<Perms, E>::new(),
@@ -433,6 +517,16 @@ where
         )
         .await?;
 
+        jobs.add_initializer_and_spawn_unique(
+            collateral_return_transfer_sync::CollateralReturnTransferSyncInit::new(
+                outbox,
+                collateral_returns_arc.as_ref(),
+            ),
+            collateral_return_transfer_sync::CollateralReturnTransferSyncJobConfig::<Perms, E>::new(
+            ),
+        )
+        .await?;
+
         Ok(Self {
             authz: authz_arc,
             customer: customer_arc,
@@ -440,7 +534,9 @@ where
             pending_credit_facilities: pending_credit_facilities_arc,
             facilities: facilities_arc,
             obligations: obligations_arc,
+            margin_calls: margin_calls_arc,
             collaterals: collaterals_arc,
+            collateral_returns: collateral_returns_arc,
             custody: custody_arc,
             disbursals: disbursals_arc,
             payments: payments_arc,
@@ -464,10 +560,18 @@ where
         self.obligations.as_ref()
     }
 
+    pub fn margin_calls(&self) -> &MarginCalls<Perms, E> {
+        self.margin_calls.as_ref()
+    }
+
     pub fn collaterals(&self) -> &Collaterals<Perms, E> {
         self.collaterals.as_ref()
     }
 
+    pub fn collateral_returns(&self) -> &CollateralReturns<Perms, E> {
+        self.collateral_returns.as_ref()
+    }
+
     pub fn disbursals(&self) -> &Disbursals<Perms, E> {
         self.disbursals.as_ref()
     }
@@ -484,6 +588,28 @@ where
         self.facilities.as_ref()
     }
 
+    /// Whether the customer has a proposal awaiting approval, a facility awaiting
+    /// collateralization or a facility that has not been closed yet.
+    #[instrument(name = "credit.customer_has_open_credit_facilities", skip(self), err)]
+    pub async fn customer_has_open_credit_facilities(
+        &self,
+        customer_id: impl Into<CustomerId> + std::fmt::Debug,
+    ) -> Result<bool, CoreCreditError> {
+        let customer_id = customer_id.into();
+        Ok(self
+            .credit_facility_proposals
+            .pending_approval_exists_for_customer(customer_id)
+            .await?
+            || self
+                .pending_credit_facilities
+                .pending_collateralization_exists_for_customer(customer_id)
+                .await?
+            || self
+                .facilities
+                .not_closed_exists_for_customer(customer_id)
+                .await?)
+    }
+
     pub fn payments(&self) -> &Payments<Perms> {
         self.payments.as_ref()
     }
@@ -534,7 +660,6 @@ where
         ))
     }
 
-    #[instrument(name = "credit.create_proposal", skip(self), err)]
     pub async fn create_facility_proposal(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
@@ -543,11 +668,39 @@ where
         amount: UsdCents,
         terms: TermValues,
         custodian_id: Option<impl Into<CustodianId> + std::fmt::Debug + Copy>,
+    ) -> Result<CreditFacilityProposal, CoreCreditError> {
+        self.create_facility_proposal_with_disbursal_policy(
+            sub,
+            customer_id,
+            disbursal_credit_account_id,
+            amount,
+            terms,
+            DisbursalPolicy::OnDemand,
+            custodian_id,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(name = "credit.create_proposal", skip(self), err)]
+    pub async fn create_facility_proposal_with_disbursal_policy(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        customer_id: impl Into<CustomerId> + std::fmt::Debug + Copy,
+        disbursal_credit_account_id: impl Into<CalaAccountId> + std::fmt::Debug,
+        amount: UsdCents,
+        terms: TermValues,
+        disbursal_policy: DisbursalPolicy,
+        custodian_id: Option<impl Into<CustodianId> + std::fmt::Debug + Copy>,
     ) -> Result<CreditFacilityProposal, CoreCreditError> {
         self.subject_can_create(sub, true)
             .await?
             .expect("audit info missing");
 
+        disbursal_policy
+            .validate(amount)
+            .map_err(credit_facility_proposal::error::CreditFacilityProposalError::from)?;
+
         let customer = self.customer.find_by_id_without_audit(customer_id).await?;
         if self.config.customer_active_check_enabled && !customer.kyc_verification.is_verified() {
             return Err(CoreCreditError::CustomerNotVerified);
@@ -566,6 +719,7 @@ where
             .disbursal_credit_account_id(disbursal_credit_account_id.into())
             .terms(terms)
             .amount(amount)
+            .disbursal_policy(disbursal_policy)
             .build()
             .expect("could not build new credit facility proposal");
 
@@ -694,10 +848,15 @@ where
             .due_date(due_date)
             .overdue_date(overdue_date)
             .liquidation_date(liquidation_date)
+            .penalty_annual_rate(facility.terms.penalty_annual_rate)
+            .day_count_convention(facility.terms.day_count_convention)
             .public_id(public_id.id)
             .build()?;
 
-        let disbursal = self.disbursals.create_in_op(&mut db, new_disbursal).await?;
+        let disbursal = self
+            .disbursals
+            .create_in_op(&mut db, new_disbursal, customer.customer_type)
+            .await?;
 
         self.ledger
             .initiate_disbursal(
@@ -856,22 +1015,14 @@ where
             .find_by_id_without_audit(credit_facility_id)
             .await?;
 
-        let mut db = self.facilities.begin_op().await?;
-
-        let payment = self
-            .payments
-            .record_in_op(&mut db, credit_facility_id, amount)
-            .await?;
-
-        self.obligations
-            .allocate_payment_in_op(
-                db,
-                credit_facility_id,
-                payment.id,
-                amount,
-                crate::time::now().date_naive(),
-            )
-            .await?;
+        let db = self.facilities.begin_op().await?;
+        self.record_payment_in_op(
+            db,
+            &credit_facility,
+            amount,
+            crate::time::now().date_naive(),
+        )
+        .await?;
 
         Ok(credit_facility)
     }
@@ -912,15 +1063,216 @@ where
             .find_by_id_without_audit(credit_facility_id)
             .await?;
 
+        let db = self.facilities.begin_op().await?;
+        self.record_payment_in_op(db, &credit_facility, amount, effective.into())
+            .await?;
+
+        Ok(credit_facility)
+    }
+
+    async fn record_payment_in_op(
+        &self,
+        mut db: es_entity::DbOp<'_>,
+        credit_facility: &CreditFacility,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Result<(), CoreCreditError> {
+        let mut payment = self
+            .payments
+            .record_in_op(&mut db, credit_facility.id, amount)
+            .await?;
+
+        let allocations = self
+            .obligations
+            .allocate_payment_in_op(
+                &mut db,
+                credit_facility.id,
+                payment.id,
+                amount,
+                None,
+                credit_facility.terms.payment_allocation_strategy,
+                effective,
+            )
+            .await?;
+
+        let amount_allocated = allocations.iter().fold(UsdCents::ZERO, |c, a| c + a.amount);
+        let unapplied = self
+            .payments
+            .record_unapplied_in_op(&mut db, &mut payment, amount - amount_allocated, effective)
+            .await?;
+
+        self.ledger
+            .record_payment(
+                db,
+                allocations,
+                unapplied,
+                credit_facility.disbursal_credit_account_id,
+                credit_facility.account_ids.unapplied_payments_account_id,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn subject_can_refund_unapplied_payments(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        enforce: bool,
+    ) -> Result<Option<AuditInfo>, CoreCreditError> {
+        Ok(self
+            .authz
+            .evaluate_permission(
+                sub,
+                CoreCreditObject::all_credit_facilities(),
+                CoreCreditAction::CREDIT_FACILITY_REFUND_UNAPPLIED_PAYMENTS,
+                enforce,
+            )
+            .await?)
+    }
+
+    #[instrument(name = "credit.refund_unapplied_payments", skip(self), err)]
+    #[es_entity::retry_on_concurrent_modification(any_error = true)]
+    pub async fn refund_unapplied_payments(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        credit_facility_id: impl Into<CreditFacilityId> + std::fmt::Debug + Copy,
+    ) -> Result<CreditFacility, CoreCreditError> {
+        self.subject_can_refund_unapplied_payments(sub, true)
+            .await?
+            .expect("audit info missing");
+
+        let credit_facility = self
+            .facilities
+            .find_by_id_without_audit(credit_facility_id.into())
+            .await?;
+
+        let mut payments = self
+            .payments
+            .list_with_unapplied_remaining_for_facility(credit_facility.id)
+            .await?;
+
         let mut db = self.facilities.begin_op().await?;
+        let effective = crate::time::now().date_naive();
+        let mut refunds = Vec::new();
+        for payment in payments.iter_mut() {
+            if let Some(refund) = self
+                .payments
+                .refund_unapplied_in_op(&mut db, payment, effective)
+                .await?
+            {
+                refunds.push(refund);
+            }
+        }
+
+        self.ledger
+            .refund_unapplied_payments(
+                db,
+                refunds,
+                credit_facility.disbursal_credit_account_id,
+                credit_facility.account_ids.unapplied_payments_account_id,
+            )
+            .await?;
+
+        Ok(credit_facility)
+    }
+
+    pub async fn subject_can_record_liquidation_execution(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        enforce: bool,
+    ) -> Result<Option<AuditInfo>, CoreCreditError> {
+        Ok(self
+            .authz
+            .evaluate_permission(
+                sub,
+                CoreCreditObject::all_obligations(),
+                CoreCreditAction::OBLIGATION_RECORD_LIQUIDATION_EXECUTION,
+                enforce,
+            )
+            .await?)
+    }
+
+    #[instrument(name = "credit.record_liquidation_execution", skip(self), err)]
+    #[es_entity::retry_on_concurrent_modification(any_error = true)]
+    pub async fn record_liquidation_execution(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        liquidation_process_id: impl Into<LiquidationProcessId> + std::fmt::Debug + Copy,
+        collateral_sold: Satoshis,
+        proceeds: UsdCents,
+        effective: impl Into<chrono::NaiveDate> + std::fmt::Debug + Copy,
+    ) -> Result<CreditFacility, CoreCreditError> {
+        let effective = effective.into();
+
+        self.subject_can_record_liquidation_execution(sub, true)
+            .await?
+            .expect("audit info missing");
+
+        let mut db = self.facilities.begin_op().await?;
+
+        let (mut liquidation_process, proceeds_data) = self
+            .obligations
+            .record_liquidation_collateral_sold_in_op(
+                &mut db,
+                liquidation_process_id.into(),
+                collateral_sold,
+                proceeds,
+                effective,
+            )
+            .await?;
+        let proceeds_data = if let Some(proceeds_data) = proceeds_data {
+            proceeds_data
+        } else {
+            return Ok(self
+                .facilities
+                .find_by_id_without_audit(liquidation_process.credit_facility_id)
+                .await?);
+        };
+
+        let credit_facility = self
+            .facilities
+            .find_by_id_without_audit(liquidation_process.credit_facility_id)
+            .await?;
+
+        let collateral_update = self
+            .collaterals
+            .record_collateral_sold_via_liquidation_in_op(
+                &mut db,
+                credit_facility.collateral_id,
+                liquidation_process.id,
+                collateral_sold,
+                effective,
+            )
+            .await?;
 
         let payment = self
             .payments
-            .record_in_op(&mut db, credit_facility_id, amount)
+            .record_in_op(&mut db, credit_facility.id, proceeds)
             .await?;
 
-        self.obligations
-            .allocate_payment_in_op(db, credit_facility_id, payment.id, amount, effective.into())
+        let applied = self
+            .obligations
+            .apply_liquidation_proceeds_in_op(
+                &mut db,
+                &mut liquidation_process,
+                payment.id,
+                credit_facility.terms.payment_allocation_strategy,
+                effective,
+            )
+            .await?;
+
+        self.ledger
+            .record_liquidation_execution(
+                db,
+                collateral_update,
+                credit_facility.account_ids.collateral_account_id,
+                LiquidationProceedsData {
+                    reserved_amount: applied.reserved_amount,
+                    ..proceeds_data
+                },
+                credit_facility.disbursal_credit_account_id,
+                applied,
+            )
             .await?;
 
         Ok(credit_facility)
@@ -966,10 +1318,9 @@ where
 
             CompletionOutcome::Completed((facility, completion)) => {
                 self.collaterals
-                    .record_collateral_update_via_manual_input_in_op(
+                    .record_collateral_released_on_completion_in_op(
                         &mut db,
                         facility.collateral_id,
-                        Satoshis::ZERO,
                         crate::time::now().date_naive(),
                     )
                     .await?;
@@ -982,6 +1333,118 @@ where
         Ok(credit_facility)
     }
 
+    pub async fn subject_can_pay_off_facility(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        enforce: bool,
+    ) -> Result<Option<AuditInfo>, CoreCreditError> {
+        Ok(self
+            .authz
+            .evaluate_permission(
+                sub,
+                CoreCreditObject::all_credit_facilities(),
+                CoreCreditAction::CREDIT_FACILITY_PAY_OFF,
+                enforce,
+            )
+            .await?)
+    }
+
+    #[instrument(name = "credit.pay_off_facility", skip(self), err)]
+    #[es_entity::retry_on_concurrent_modification(any_error = true)]
+    pub async fn pay_off_facility(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        credit_facility_id: impl Into<CreditFacilityId> + std::fmt::Debug + Copy,
+        amount: UsdCents,
+    ) -> Result<CreditFacility, CoreCreditError> {
+        self.subject_can_pay_off_facility(sub, true)
+            .await?
+            .expect("audit info missing");
+
+        let mut db = self.facilities.begin_op().await?;
+
+        let PayoffOutcome {
+            credit_facility,
+            quote,
+            payoff,
+            new_obligation,
+        } = self
+            .facilities
+            .pay_off_in_op(&mut db, credit_facility_id.into(), amount)
+            .await?;
+
+        let payment = self
+            .payments
+            .record_in_op(&mut db, credit_facility.id, quote.obligations_total())
+            .await?;
+
+        let allocations = self
+            .obligations
+            .allocate_payoff_in_op(
+                &mut db,
+                credit_facility.id,
+                new_obligation.into_iter().collect(),
+                payment.id,
+                quote.obligations_total(),
+                credit_facility.terms.payment_allocation_strategy,
+                payoff.effective,
+            )
+            .await?;
+
+        self.collaterals
+            .record_collateral_released_on_completion_in_op(
+                &mut db,
+                credit_facility.collateral_id,
+                payoff.effective,
+            )
+            .await?;
+
+        self.ledger
+            .record_facility_payoff(db, payoff, allocations)
+            .await?;
+
+        Ok(credit_facility)
+    }
+
+    pub async fn subject_can_return_collateral(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        enforce: bool,
+    ) -> Result<Option<AuditInfo>, CoreCreditError> {
+        Ok(self
+            .authz
+            .evaluate_permission(
+                sub,
+                CoreCreditObject::all_credit_facilities(),
+                CoreCreditAction::CREDIT_FACILITY_RETURN_COLLATERAL,
+                enforce,
+            )
+            .await?)
+    }
+
+    #[instrument(name = "credit.initiate_collateral_return", skip(self), err)]
+    pub async fn initiate_collateral_return(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        credit_facility_id: impl Into<CreditFacilityId> + std::fmt::Debug + Copy,
+        destination_address: String,
+    ) -> Result<CreditFacility, CoreCreditError> {
+        let credit_facility = self
+            .facilities
+            .find_by_id_without_audit(credit_facility_id.into())
+            .await?;
+        let collateral = self
+            .collaterals
+            .find_by_id_without_audit(credit_facility.collateral_id)
+            .await?;
+
+        self.collateral_returns
+            .initiate(sub, &credit_facility, &collateral, destination_address)
+            .await?;
+
+        Ok(credit_facility)
+    }
+
     pub async fn can_be_completed(&self, entity: &CreditFacility) -> Result<bool, CoreCreditError> {
         Ok(self.outstanding(entity).await?.is_zero())
     }
@@ -7,6 +7,8 @@ use es_entity::*;
 
 use crate::primitives::*;
 
+use super::error::LiquidationProcessError;
+
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -21,7 +23,20 @@ pub enum LiquidationProcessEvent {
         initial_amount: UsdCents,
         effective: chrono::NaiveDate,
     },
-    Completed {},
+    CollateralSold {
+        ledger_tx_id: LedgerTxId,
+        collateral_sold: Satoshis,
+        proceeds: UsdCents,
+        execution_price: PriceOfOneBTC,
+        effective: chrono::NaiveDate,
+    },
+    Completed {
+        payment_id: PaymentId,
+        amount_applied: UsdCents,
+        shortfall: UsdCents,
+        surplus: UsdCents,
+        effective: chrono::NaiveDate,
+    },
 }
 
 #[derive(EsEntity, Builder)]
@@ -37,6 +52,134 @@ pub struct LiquidationProcess {
     events: EntityEvents<LiquidationProcessEvent>,
 }
 
+impl LiquidationProcess {
+    pub fn is_completed(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|e| matches!(e, LiquidationProcessEvent::Completed { .. }))
+    }
+
+    pub fn collateral_sold(&self) -> Option<Satoshis> {
+        self.events.iter_all().find_map(|e| match e {
+            LiquidationProcessEvent::CollateralSold {
+                collateral_sold, ..
+            } => Some(*collateral_sold),
+            _ => None,
+        })
+    }
+
+    pub fn proceeds(&self) -> Option<UsdCents> {
+        self.events.iter_all().find_map(|e| match e {
+            LiquidationProcessEvent::CollateralSold { proceeds, .. } => Some(*proceeds),
+            _ => None,
+        })
+    }
+
+    pub fn execution_price(&self) -> Option<PriceOfOneBTC> {
+        self.events.iter_all().find_map(|e| match e {
+            LiquidationProcessEvent::CollateralSold {
+                execution_price, ..
+            } => Some(*execution_price),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn record_collateral_sold(
+        &mut self,
+        collateral_sold: Satoshis,
+        proceeds: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Result<Idempotent<LiquidationProceedsData>, LiquidationProcessError> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            LiquidationProcessEvent::CollateralSold { .. }
+        );
+
+        if collateral_sold == Satoshis::ZERO {
+            return Err(LiquidationProcessError::NoCollateralSold);
+        }
+
+        let execution_price = PriceOfOneBTC::new(
+            UsdCents::try_from_usd(
+                (proceeds.to_usd() / collateral_sold.to_btc())
+                    .round_dp_with_strategy(2, rust_decimal::RoundingStrategy::ToZero),
+            )
+            .expect("Decimal should have no fractional component here"),
+        );
+
+        let data = LiquidationProceedsData {
+            tx_id: LedgerTxId::new(),
+            proceeds,
+            reserved_amount: self.initial_amount,
+            in_liquidation_account_id: self.in_liquidation_account_id,
+            effective,
+        };
+
+        self.events.push(LiquidationProcessEvent::CollateralSold {
+            ledger_tx_id: data.tx_id,
+            collateral_sold,
+            proceeds,
+            execution_price,
+            effective,
+        });
+
+        Ok(Idempotent::Executed(data))
+    }
+
+    pub(crate) fn complete(
+        &mut self,
+        payment_id: PaymentId,
+        outstanding_before: UsdCents,
+        amount_applied: UsdCents,
+        proceeds_applied: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Result<Idempotent<()>, LiquidationProcessError> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            LiquidationProcessEvent::Completed { .. }
+        );
+
+        let proceeds = self
+            .proceeds()
+            .ok_or(LiquidationProcessError::CollateralSaleNotRecorded)?;
+
+        self.events.push(LiquidationProcessEvent::Completed {
+            payment_id,
+            amount_applied,
+            shortfall: outstanding_before - amount_applied,
+            surplus: proceeds - proceeds_applied,
+            effective,
+        });
+
+        Ok(Idempotent::Executed(()))
+    }
+
+    /// Completes a process whose obligation was settled by the proceeds of another
+    /// liquidation on the same facility.
+    pub(crate) fn complete_alongside(
+        &mut self,
+        payment_id: PaymentId,
+        outstanding_before: UsdCents,
+        amount_applied: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            LiquidationProcessEvent::Completed { .. }
+        );
+
+        self.events.push(LiquidationProcessEvent::Completed {
+            payment_id,
+            amount_applied,
+            shortfall: outstanding_before - amount_applied,
+            surplus: UsdCents::ZERO,
+            effective,
+        });
+
+        Idempotent::Executed(())
+    }
+}
+
 impl TryFromEvents<LiquidationProcessEvent> for LiquidationProcess {
     fn try_from_events(
         events: EntityEvents<LiquidationProcessEvent>,
@@ -63,6 +206,7 @@ impl TryFromEvents<LiquidationProcessEvent> for LiquidationProcess {
                         .initial_amount(*initial_amount)
                         .effective(*effective)
                 }
+                LiquidationProcessEvent::CollateralSold { .. } => (),
                 LiquidationProcessEvent::Completed { .. } => (),
             }
         }
@@ -86,6 +230,14 @@ pub struct NewLiquidationProcess {
     pub(super) effective: chrono::NaiveDate,
 }
 
+pub struct LiquidationProceedsData {
+    pub tx_id: LedgerTxId,
+    pub proceeds: UsdCents,
+    pub reserved_amount: UsdCents,
+    pub in_liquidation_account_id: CalaAccountId,
+    pub effective: chrono::NaiveDate,
+}
+
 impl NewLiquidationProcess {
     pub fn builder() -> NewLiquidationProcessBuilder {
         NewLiquidationProcessBuilder::default()
@@ -108,3 +260,193 @@ impl IntoEvents<LiquidationProcessEvent> for NewLiquidationProcess {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn liquidation_process_from(events: Vec<LiquidationProcessEvent>) -> LiquidationProcess {
+        LiquidationProcess::try_from_events(EntityEvents::init(LiquidationProcessId::new(), events))
+            .unwrap()
+    }
+
+    fn initial_events() -> Vec<LiquidationProcessEvent> {
+        vec![LiquidationProcessEvent::Initialized {
+            id: LiquidationProcessId::new(),
+            ledger_tx_id: LedgerTxId::new(),
+            obligation_id: ObligationId::new(),
+            credit_facility_id: CreditFacilityId::new(),
+            in_liquidation_account_id: CalaAccountId::new(),
+            initial_amount: UsdCents::from(1_000_000),
+            effective: chrono::Utc::now().date_naive(),
+        }]
+    }
+
+    #[test]
+    fn records_collateral_sold_with_execution_price() {
+        let mut process = liquidation_process_from(initial_events());
+        let data = process
+            .record_collateral_sold(
+                Satoshis::from(10_000_000),
+                UsdCents::from(500_000),
+                chrono::Utc::now().date_naive(),
+            )
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(data.proceeds, UsdCents::from(500_000));
+        assert_eq!(data.reserved_amount, UsdCents::from(1_000_000));
+        assert_eq!(
+            process.execution_price(),
+            Some(PriceOfOneBTC::new(UsdCents::from(5_000_000)))
+        );
+    }
+
+    #[test]
+    fn collateral_sold_is_idempotent() {
+        let mut process = liquidation_process_from(initial_events());
+        let _ = process.record_collateral_sold(
+            Satoshis::from(10_000_000),
+            UsdCents::from(500_000),
+            chrono::Utc::now().date_naive(),
+        );
+        let res = process
+            .record_collateral_sold(
+                Satoshis::from(10_000_000),
+                UsdCents::from(500_000),
+                chrono::Utc::now().date_naive(),
+            )
+            .unwrap();
+        assert!(res.was_ignored());
+    }
+
+    #[test]
+    fn errors_if_no_collateral_sold() {
+        let mut process = liquidation_process_from(initial_events());
+        let res = process.record_collateral_sold(
+            Satoshis::ZERO,
+            UsdCents::from(500_000),
+            chrono::Utc::now().date_naive(),
+        );
+        assert!(matches!(
+            res,
+            Err(LiquidationProcessError::NoCollateralSold)
+        ));
+    }
+
+    #[test]
+    fn cannot_complete_before_collateral_sold() {
+        let mut process = liquidation_process_from(initial_events());
+        let res = process.complete(
+            PaymentId::new(),
+            UsdCents::from(1_000_000),
+            UsdCents::from(500_000),
+            UsdCents::from(500_000),
+            chrono::Utc::now().date_naive(),
+        );
+        assert!(matches!(
+            res,
+            Err(LiquidationProcessError::CollateralSaleNotRecorded)
+        ));
+    }
+
+    #[test]
+    fn completes_with_shortfall() {
+        let mut process = liquidation_process_from(initial_events());
+        let _ = process.record_collateral_sold(
+            Satoshis::from(10_000_000),
+            UsdCents::from(500_000),
+            chrono::Utc::now().date_naive(),
+        );
+        assert!(
+            process
+                .complete(
+                    PaymentId::new(),
+                    UsdCents::from(1_000_000),
+                    UsdCents::from(500_000),
+                    UsdCents::from(500_000),
+                    chrono::Utc::now().date_naive(),
+                )
+                .unwrap()
+                .did_execute()
+        );
+
+        assert!(process.is_completed());
+        let (shortfall, surplus) = process
+            .events
+            .iter_all()
+            .find_map(|e| match e {
+                LiquidationProcessEvent::Completed {
+                    shortfall, surplus, ..
+                } => Some((*shortfall, *surplus)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(shortfall, UsdCents::from(500_000));
+        assert_eq!(surplus, UsdCents::ZERO);
+    }
+
+    #[test]
+    fn completes_with_surplus() {
+        let mut process = liquidation_process_from(initial_events());
+        let _ = process.record_collateral_sold(
+            Satoshis::from(10_000_000),
+            UsdCents::from(1_200_000),
+            chrono::Utc::now().date_naive(),
+        );
+        assert!(
+            process
+                .complete(
+                    PaymentId::new(),
+                    UsdCents::from(1_000_000),
+                    UsdCents::from(1_000_000),
+                    UsdCents::from(1_000_000),
+                    chrono::Utc::now().date_naive(),
+                )
+                .unwrap()
+                .did_execute()
+        );
+
+        let (shortfall, surplus) = process
+            .events
+            .iter_all()
+            .find_map(|e| match e {
+                LiquidationProcessEvent::Completed {
+                    shortfall, surplus, ..
+                } => Some((*shortfall, *surplus)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(shortfall, UsdCents::ZERO);
+        assert_eq!(surplus, UsdCents::from(200_000));
+    }
+
+    #[test]
+    fn completes_alongside_without_collateral_sold() {
+        let mut process = liquidation_process_from(initial_events());
+        assert!(
+            process
+                .complete_alongside(
+                    PaymentId::new(),
+                    UsdCents::from(1_000_000),
+                    UsdCents::from(400_000),
+                    chrono::Utc::now().date_naive(),
+                )
+                .did_execute()
+        );
+
+        assert!(process.is_completed());
+        let (shortfall, surplus) = process
+            .events
+            .iter_all()
+            .find_map(|e| match e {
+                LiquidationProcessEvent::Completed {
+                    shortfall, surplus, ..
+                } => Some((*shortfall, *surplus)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(shortfall, UsdCents::from(600_000));
+        assert_eq!(surplus, UsdCents::ZERO);
+    }
+}
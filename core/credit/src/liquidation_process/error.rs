@@ -8,6 +8,10 @@ pub enum LiquidationProcessError {
     EsEntityError(es_entity::EsEntityError),
     #[error("LiquidationProcessError - CursorDestructureError: {0}")]
     CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("LiquidationProcessError - NoCollateralSold")]
+    NoCollateralSold,
+    #[error("LiquidationProcessError - CollateralSaleNotRecorded")]
+    CollateralSaleNotRecorded,
 }
 
 es_entity::from_es_entity_error!(LiquidationProcessError);
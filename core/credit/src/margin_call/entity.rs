@@ -0,0 +1,268 @@
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use crate::{ledger::CollateralizationShortfall, primitives::*};
+
+use super::error::MarginCallError;
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "MarginCallId")]
+pub enum MarginCallEvent {
+    Initialized {
+        id: MarginCallId,
+        credit_facility_id: CreditFacilityId,
+        collateralization_state: CollateralizationState,
+        target_cvl: CVLPct,
+        price: PriceOfOneBTC,
+        collateral_required: Satoshis,
+        repayment_required: UsdCents,
+        cure_deadline: DateTime<Utc>,
+        effective: chrono::NaiveDate,
+    },
+    Cured {
+        effective: chrono::NaiveDate,
+    },
+    Escalated {
+        obligation_ids: Vec<ObligationId>,
+        effective: chrono::NaiveDate,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct MarginCall {
+    pub id: MarginCallId,
+    pub credit_facility_id: CreditFacilityId,
+    pub target_cvl: CVLPct,
+    pub price: PriceOfOneBTC,
+    pub collateral_required: Satoshis,
+    pub repayment_required: UsdCents,
+    pub cure_deadline: DateTime<Utc>,
+    pub effective: chrono::NaiveDate,
+    events: EntityEvents<MarginCallEvent>,
+}
+
+impl MarginCall {
+    pub fn status(&self) -> MarginCallStatus {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|e| match e {
+                MarginCallEvent::Cured { .. } => Some(MarginCallStatus::Cured),
+                MarginCallEvent::Escalated { .. } => Some(MarginCallStatus::Escalated),
+                _ => None,
+            })
+            .unwrap_or(MarginCallStatus::Open)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status() == MarginCallStatus::Open
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("entity_first_persisted_at not found")
+    }
+
+    pub(crate) fn cure(&mut self, effective: chrono::NaiveDate) -> Idempotent<()> {
+        idempotency_guard!(self.events.iter_all().rev(), MarginCallEvent::Cured { .. });
+
+        if !self.is_open() {
+            return Idempotent::Ignored;
+        }
+
+        self.events.push(MarginCallEvent::Cured { effective });
+
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn escalate(
+        &mut self,
+        obligation_ids: Vec<ObligationId>,
+        effective: chrono::NaiveDate,
+    ) -> Result<Idempotent<()>, MarginCallError> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            MarginCallEvent::Escalated { .. }
+        );
+
+        if !self.is_open() {
+            return Ok(Idempotent::Ignored);
+        }
+
+        if obligation_ids.is_empty() {
+            return Err(MarginCallError::NoObligationsToLiquidate(self.id));
+        }
+
+        self.events.push(MarginCallEvent::Escalated {
+            obligation_ids,
+            effective,
+        });
+
+        Ok(Idempotent::Executed(()))
+    }
+}
+
+impl TryFromEvents<MarginCallEvent> for MarginCall {
+    fn try_from_events(events: EntityEvents<MarginCallEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = MarginCallBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                MarginCallEvent::Initialized {
+                    id,
+                    credit_facility_id,
+                    target_cvl,
+                    price,
+                    collateral_required,
+                    repayment_required,
+                    cure_deadline,
+                    effective,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .credit_facility_id(*credit_facility_id)
+                        .target_cvl(*target_cvl)
+                        .price(*price)
+                        .collateral_required(*collateral_required)
+                        .repayment_required(*repayment_required)
+                        .cure_deadline(*cure_deadline)
+                        .effective(*effective)
+                }
+                MarginCallEvent::Cured { .. } => (),
+                MarginCallEvent::Escalated { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewMarginCall {
+    #[builder(setter(into))]
+    pub(crate) id: MarginCallId,
+    #[builder(setter(into))]
+    pub(crate) credit_facility_id: CreditFacilityId,
+    pub(super) collateralization_state: CollateralizationState,
+    pub(super) target_cvl: CVLPct,
+    pub(super) price: PriceOfOneBTC,
+    pub(super) shortfall: CollateralizationShortfall,
+    pub(super) cure_deadline: DateTime<Utc>,
+    pub(super) effective: chrono::NaiveDate,
+}
+
+impl NewMarginCall {
+    pub fn builder() -> NewMarginCallBuilder {
+        NewMarginCallBuilder::default()
+    }
+}
+
+impl IntoEvents<MarginCallEvent> for NewMarginCall {
+    fn into_events(self) -> EntityEvents<MarginCallEvent> {
+        EntityEvents::init(
+            self.id,
+            [MarginCallEvent::Initialized {
+                id: self.id,
+                credit_facility_id: self.credit_facility_id,
+                collateralization_state: self.collateralization_state,
+                target_cvl: self.target_cvl,
+                price: self.price,
+                collateral_required: self.shortfall.collateral,
+                repayment_required: self.shortfall.repayment,
+                cure_deadline: self.cure_deadline,
+                effective: self.effective,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn margin_call_from(events: Vec<MarginCallEvent>) -> MarginCall {
+        MarginCall::try_from_events(EntityEvents::init(MarginCallId::new(), events)).unwrap()
+    }
+
+    fn initial_events() -> Vec<MarginCallEvent> {
+        vec![MarginCallEvent::Initialized {
+            id: MarginCallId::new(),
+            credit_facility_id: CreditFacilityId::new(),
+            collateralization_state: CollateralizationState::UnderMarginCallThreshold,
+            target_cvl: CVLPct::new(140),
+            price: PriceOfOneBTC::new(UsdCents::from(5_000_000)),
+            collateral_required: Satoshis::from(10_000_000),
+            repayment_required: UsdCents::from(300_000),
+            cure_deadline: chrono::Utc::now(),
+            effective: chrono::Utc::now().date_naive(),
+        }]
+    }
+
+    #[test]
+    fn cures_open_margin_call() {
+        let mut margin_call = margin_call_from(initial_events());
+        assert_eq!(margin_call.status(), MarginCallStatus::Open);
+
+        assert!(
+            margin_call
+                .cure(chrono::Utc::now().date_naive())
+                .did_execute()
+        );
+        assert_eq!(margin_call.status(), MarginCallStatus::Cured);
+        assert!(
+            margin_call
+                .cure(chrono::Utc::now().date_naive())
+                .was_ignored()
+        );
+    }
+
+    #[test]
+    fn cannot_escalate_cured_margin_call() {
+        let mut margin_call = margin_call_from(initial_events());
+        let _ = margin_call.cure(chrono::Utc::now().date_naive());
+
+        assert!(
+            margin_call
+                .escalate(vec![], chrono::Utc::now().date_naive())
+                .unwrap()
+                .was_ignored()
+        );
+        assert_eq!(margin_call.status(), MarginCallStatus::Cured);
+    }
+
+    #[test]
+    fn escalated_margin_call_is_not_cured() {
+        let mut margin_call = margin_call_from(initial_events());
+        assert!(
+            margin_call
+                .escalate(vec![ObligationId::new()], chrono::Utc::now().date_naive())
+                .unwrap()
+                .did_execute()
+        );
+
+        assert!(
+            margin_call
+                .cure(chrono::Utc::now().date_naive())
+                .was_ignored()
+        );
+        assert_eq!(margin_call.status(), MarginCallStatus::Escalated);
+    }
+
+    #[test]
+    fn escalation_without_obligations_keeps_call_open() {
+        let mut margin_call = margin_call_from(initial_events());
+        assert!(matches!(
+            margin_call.escalate(vec![], chrono::Utc::now().date_naive()),
+            Err(MarginCallError::NoObligationsToLiquidate(_))
+        ));
+        assert!(margin_call.is_open());
+    }
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use crate::primitives::MarginCallId;
+
+#[derive(Error, Debug)]
+pub enum MarginCallError {
+    #[error("MarginCallError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("MarginCallError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("MarginCallError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("MarginCallError - AuthorizationError: {0}")]
+    AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("MarginCallError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("MarginCallError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
+    #[error("MarginCallError - CreditLedgerError: {0}")]
+    CreditLedgerError(#[from] crate::ledger::error::CreditLedgerError),
+    #[error("MarginCallError - CreditFacilityError: {0}")]
+    CreditFacilityError(#[from] crate::credit_facility::error::CreditFacilityError),
+    #[error("MarginCallError - PriceError: {0}")]
+    PriceError(#[from] core_price::error::PriceError),
+    #[error("MarginCallError - ObligationError: {0}")]
+    ObligationError(#[from] crate::obligation::error::ObligationError),
+    #[error("MarginCallError - NoObligationsToLiquidate: {0}")]
+    NoObligationsToLiquidate(MarginCallId),
+}
+
+es_entity::from_es_entity_error!(MarginCallError);
@@ -0,0 +1,319 @@
+mod entity;
+pub mod error;
+mod repo;
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use core_price::Price;
+use job::{JobId, Jobs};
+use outbox::OutboxEventMarker;
+
+use crate::{
+    CreditFacilityPublisher, CreditLedger,
+    credit_facility::{CreditFacility, CreditFacilityRepo},
+    event::CoreCreditEvent,
+    jobs::{margin_call_deadline, obligation_liquidation},
+    obligation::Obligations,
+    primitives::*,
+};
+
+pub use entity::MarginCall;
+pub(super) use entity::*;
+
+#[cfg(feature = "json-schema")]
+pub use entity::MarginCallEvent;
+use error::MarginCallError;
+use repo::MarginCallRepo;
+
+pub struct MarginCalls<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    authz: Arc<Perms>,
+    repo: Arc<MarginCallRepo<E>>,
+    credit_facility_repo: Arc<CreditFacilityRepo<E>>,
+    obligations: Arc<Obligations<Perms, E>>,
+    ledger: Arc<CreditLedger>,
+    price: Arc<Price>,
+    jobs: Arc<Jobs>,
+}
+
+impl<Perms, E> Clone for MarginCalls<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            authz: self.authz.clone(),
+            repo: self.repo.clone(),
+            credit_facility_repo: self.credit_facility_repo.clone(),
+            obligations: self.obligations.clone(),
+            ledger: self.ledger.clone(),
+            price: self.price.clone(),
+            jobs: self.jobs.clone(),
+        }
+    }
+}
+
+impl<Perms, E> MarginCalls<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCreditAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCreditObject>,
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    pub(crate) fn new(
+        pool: &sqlx::PgPool,
+        authz: Arc<Perms>,
+        obligations: Arc<Obligations<Perms, E>>,
+        ledger: Arc<CreditLedger>,
+        price: Arc<Price>,
+        jobs: Arc<Jobs>,
+        publisher: &CreditFacilityPublisher<E>,
+    ) -> Self {
+        Self {
+            authz,
+            repo: Arc::new(MarginCallRepo::new(pool, publisher)),
+            credit_facility_repo: Arc::new(CreditFacilityRepo::new(pool, publisher)),
+            obligations,
+            ledger,
+            price,
+            jobs,
+        }
+    }
+
+    pub async fn find_by_id_without_audit(
+        &self,
+        id: impl Into<MarginCallId> + std::fmt::Debug,
+    ) -> Result<MarginCall, MarginCallError> {
+        self.repo.find_by_id(id.into()).await
+    }
+
+    #[instrument(name = "credit.margin_call.list_for_credit_facility", skip(self), err)]
+    pub async fn list_for_credit_facility(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        credit_facility_id: impl Into<CreditFacilityId> + std::fmt::Debug,
+    ) -> Result<Vec<MarginCall>, MarginCallError> {
+        let credit_facility_id = credit_facility_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCreditObject::credit_facility(credit_facility_id),
+                CoreCreditAction::CREDIT_FACILITY_READ,
+            )
+            .await?;
+
+        Ok(self
+            .repo
+            .list_for_credit_facility_id_by_created_at(
+                credit_facility_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities)
+    }
+
+    async fn find_open_for_credit_facility(
+        &self,
+        credit_facility_id: CreditFacilityId,
+    ) -> Result<Option<MarginCall>, MarginCallError> {
+        let latest = self
+            .repo
+            .list_for_credit_facility_id_by_created_at(
+                credit_facility_id,
+                es_entity::PaginatedQueryArgs {
+                    first: 1,
+                    after: None,
+                },
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities
+            .pop();
+
+        Ok(latest.filter(|margin_call| margin_call.is_open()))
+    }
+
+    #[instrument(
+        name = "credit.margin_call.open_for_credit_facility",
+        skip(self, credit_facility),
+        fields(credit_facility_id = %credit_facility.id),
+        err
+    )]
+    pub(crate) async fn open_for_credit_facility(
+        &self,
+        credit_facility: &CreditFacility,
+        collateralization_state: CollateralizationState,
+        price: PriceOfOneBTC,
+        effective: chrono::NaiveDate,
+    ) -> Result<Option<MarginCall>, MarginCallError> {
+        if self
+            .find_open_for_credit_facility(credit_facility.id)
+            .await?
+            .is_some()
+        {
+            return Ok(None);
+        }
+
+        let balances = self
+            .ledger
+            .get_credit_facility_balance(credit_facility.account_ids)
+            .await?;
+        let target_cvl = credit_facility.terms.initial_cvl;
+        let cure_deadline = credit_facility
+            .terms
+            .margin_call_cure_deadline(crate::time::now());
+
+        let new_margin_call = NewMarginCall::builder()
+            .id(MarginCallId::new())
+            .credit_facility_id(credit_facility.id)
+            .collateralization_state(collateralization_state)
+            .target_cvl(target_cvl)
+            .price(price)
+            .shortfall(balances.shortfall_to_cvl(price, target_cvl))
+            .cure_deadline(cure_deadline)
+            .effective(effective)
+            .build()
+            .expect("all fields for new margin call provided");
+
+        let mut op = self.repo.begin_op().await?;
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                &mut op,
+                CoreCreditObject::credit_facility(credit_facility.id),
+                CoreCreditAction::CREDIT_FACILITY_UPDATE_COLLATERALIZATION_STATE,
+            )
+            .await?;
+
+        let margin_call = self.repo.create_in_op(&mut op, new_margin_call).await?;
+        self.jobs
+            .create_and_spawn_at_in_op(
+                &mut op,
+                margin_call.id,
+                margin_call_deadline::MarginCallDeadlineJobConfig::<Perms, E> {
+                    margin_call_id: margin_call.id,
+                    _phantom: std::marker::PhantomData,
+                },
+                margin_call.cure_deadline,
+            )
+            .await?;
+        op.commit().await?;
+
+        Ok(Some(margin_call))
+    }
+
+    #[instrument(name = "credit.margin_call.cure_for_credit_facility", skip(self), err)]
+    pub(crate) async fn cure_for_credit_facility(
+        &self,
+        credit_facility_id: CreditFacilityId,
+        effective: chrono::NaiveDate,
+    ) -> Result<(), MarginCallError> {
+        let mut margin_call = match self
+            .find_open_for_credit_facility(credit_facility_id)
+            .await?
+        {
+            Some(margin_call) => margin_call,
+            None => return Ok(()),
+        };
+
+        if margin_call.cure(effective).was_ignored() {
+            return Ok(());
+        }
+
+        let mut op = self.repo.begin_op().await?;
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                &mut op,
+                CoreCreditObject::credit_facility(credit_facility_id),
+                CoreCreditAction::CREDIT_FACILITY_UPDATE_COLLATERALIZATION_STATE,
+            )
+            .await?;
+        self.repo.update_in_op(&mut op, &mut margin_call).await?;
+        op.commit().await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "credit.margin_call.escalate", skip(self), err)]
+    pub(crate) async fn escalate(&self, id: MarginCallId) -> Result<(), MarginCallError> {
+        let mut margin_call = self.repo.find_by_id(id).await?;
+        if !margin_call.is_open() {
+            return Ok(());
+        }
+
+        let credit_facility = self
+            .credit_facility_repo
+            .find_by_id(margin_call.credit_facility_id)
+            .await?;
+
+        let price = self.price.usd_cents_per_btc().await?;
+        let balances = self
+            .ledger
+            .get_credit_facility_balance(credit_facility.account_ids)
+            .await?;
+        let effective = crate::time::now().date_naive();
+
+        let cured = matches!(
+            credit_facility
+                .terms
+                .collateralization(balances.current_cvl(price)),
+            CollateralizationState::FullyCollateralized | CollateralizationState::NoExposure
+        );
+        let obligation_ids = if cured {
+            if margin_call.cure(effective).was_ignored() {
+                return Ok(());
+            }
+            Vec::new()
+        } else {
+            let obligation_ids = self
+                .obligations
+                .liquidatable_ids_for_facility(credit_facility.id)
+                .await?;
+            if margin_call
+                .escalate(obligation_ids.clone(), effective)?
+                .was_ignored()
+            {
+                return Ok(());
+            }
+            obligation_ids
+        };
+
+        let mut op = self.repo.begin_op().await?;
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                &mut op,
+                CoreCreditObject::credit_facility(credit_facility.id),
+                CoreCreditAction::CREDIT_FACILITY_UPDATE_COLLATERALIZATION_STATE,
+            )
+            .await?;
+        self.repo.update_in_op(&mut op, &mut margin_call).await?;
+
+        for obligation_id in obligation_ids {
+            self.jobs
+                .create_and_spawn_in_op(
+                    &mut op,
+                    JobId::new(),
+                    obligation_liquidation::ObligationLiquidationJobConfig::<Perms, E> {
+                        obligation_id,
+                        effective,
+                        _phantom: std::marker::PhantomData,
+                    },
+                )
+                .await?;
+        }
+        op.commit().await?;
+
+        Ok(())
+    }
+}
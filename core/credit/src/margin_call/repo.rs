@@ -0,0 +1,59 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+use outbox::OutboxEventMarker;
+
+use crate::{event::CoreCreditEvent, primitives::*, publisher::CreditFacilityPublisher};
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo)]
+#[es_repo(
+    entity = "MarginCall",
+    err = "MarginCallError",
+    columns(credit_facility_id(ty = "CreditFacilityId", list_for, update(persist = false)),),
+    tbl_prefix = "core",
+    post_persist_hook = "publish"
+)]
+pub struct MarginCallRepo<E>
+where
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    pool: PgPool,
+    publisher: CreditFacilityPublisher<E>,
+}
+
+impl<E> Clone for MarginCallRepo<E>
+where
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            publisher: self.publisher.clone(),
+        }
+    }
+}
+
+impl<E> MarginCallRepo<E>
+where
+    E: OutboxEventMarker<CoreCreditEvent>,
+{
+    pub fn new(pool: &PgPool, publisher: &CreditFacilityPublisher<E>) -> Self {
+        Self {
+            pool: pool.clone(),
+            publisher: publisher.clone(),
+        }
+    }
+
+    async fn publish(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        entity: &MarginCall,
+        new_events: es_entity::LastPersisted<'_, MarginCallEvent>,
+    ) -> Result<(), MarginCallError> {
+        self.publisher
+            .publish_margin_call(op, entity, new_events)
+            .await
+    }
+}
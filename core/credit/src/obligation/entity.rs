@@ -9,7 +9,7 @@ use es_entity::*;
 
 use crate::{
     CreditFacilityId, liquidation_process::NewLiquidationProcess,
-    payment_allocation::NewPaymentAllocation, primitives::*,
+    payment_allocation::NewPaymentAllocation, primitives::*, terms::PaymentAllocationStrategy,
 };
 
 use super::{error::ObligationError, primitives::*};
@@ -36,6 +36,8 @@ pub enum ObligationEvent {
         defaulted_date: Option<EffectiveDate>,
         liquidation_date: Option<EffectiveDate>,
         effective: chrono::NaiveDate,
+        #[serde(default)]
+        penalty: Option<ObligationPenaltyTerms>,
     },
     DueRecorded {
         ledger_tx_id: LedgerTxId,
@@ -64,6 +66,26 @@ pub enum ObligationEvent {
     LiquidationProcessConcluded {
         liquidation_process_id: LiquidationProcessId,
     },
+    PenaltyAccrued {
+        penalty_obligation_id: ObligationId,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    },
+    PrincipalInstallmentScheduled {
+        installment_obligation_id: ObligationId,
+        reference: String,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    },
+    Rescheduled {
+        due_date: EffectiveDate,
+        overdue_date: Option<EffectiveDate>,
+        liquidation_date: Option<EffectiveDate>,
+        defaulted_date: Option<EffectiveDate>,
+    },
+    SplitIntoInstallments {
+        effective: chrono::NaiveDate,
+    },
     Completed {
         effective: chrono::NaiveDate,
     },
@@ -92,6 +114,7 @@ impl Obligation {
     pub fn lifecycle_dates(&self) -> ObligationLifecycleDates {
         self.events
             .iter_all()
+            .rev()
             .find_map(|e| match e {
                 ObligationEvent::Initialized {
                     due_date,
@@ -99,6 +122,12 @@ impl Obligation {
                     liquidation_date,
                     defaulted_date,
                     ..
+                }
+                | ObligationEvent::Rescheduled {
+                    due_date,
+                    overdue_date,
+                    liquidation_date,
+                    defaulted_date,
                 } => Some(ObligationLifecycleDates {
                     due: *due_date,
                     overdue: *overdue_date,
@@ -191,6 +220,16 @@ impl Obligation {
             .expect("Entity was not Initialized")
     }
 
+    pub fn penalty_terms(&self) -> Option<ObligationPenaltyTerms> {
+        self.events
+            .iter_all()
+            .find_map(|e| match e {
+                ObligationEvent::Initialized { penalty, .. } => Some(*penalty),
+                _ => None,
+            })
+            .expect("Entity was not Initialized")
+    }
+
     pub fn receivable_account_id(&self) -> Option<CalaAccountId> {
         let (not_yet_due_accounts, due_accounts, overdue_accounts) = self
             .events
@@ -269,7 +308,8 @@ impl Obligation {
                 ObligationEvent::DueRecorded { .. } => Some(ObligationStatus::Due),
                 ObligationEvent::OverdueRecorded { .. } => Some(ObligationStatus::Overdue),
                 ObligationEvent::DefaultedRecorded { .. } => Some(ObligationStatus::Defaulted),
-                ObligationEvent::Completed { .. } => Some(ObligationStatus::Paid),
+                ObligationEvent::Completed { .. }
+                | ObligationEvent::SplitIntoInstallments { .. } => Some(ObligationStatus::Paid),
                 _ => None,
             })
             .unwrap_or(ObligationStatus::NotYetDue)
@@ -290,7 +330,8 @@ impl Obligation {
                     ObligationEvent::PaymentAllocated {
                         payment_allocation_amount: amount,
                         ..
-                    } => {
+                    }
+                    | ObligationEvent::PrincipalInstallmentScheduled { amount, .. } => {
                         total_sum -= *amount;
                     }
                     _ => (),
@@ -303,6 +344,23 @@ impl Obligation {
         !self.outstanding().is_zero()
     }
 
+    pub fn is_accruing_penalty(&self) -> bool {
+        self.penalty_terms().is_some()
+            && matches!(
+                self.status(),
+                ObligationStatus::Overdue | ObligationStatus::Defaulted
+            )
+    }
+
+    /// Whether the obligation still has a balance that a liquidation could recover,
+    /// whether or not it has fallen due yet.
+    pub fn is_liquidatable(&self) -> bool {
+        matches!(
+            self.status(),
+            ObligationStatus::NotYetDue | ObligationStatus::Due | ObligationStatus::Overdue
+        ) && self.has_outstanding_balance()
+    }
+
     pub fn is_in_liquidation(&self) -> bool {
         self.events
             .iter_all()
@@ -315,6 +373,21 @@ impl Obligation {
             .unwrap_or_default()
     }
 
+    pub fn liquidation_process_id(&self) -> Option<LiquidationProcessId> {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|e| match e {
+                ObligationEvent::LiquidationProcessStarted {
+                    liquidation_process_id,
+                    ..
+                } => Some(Some(*liquidation_process_id)),
+                ObligationEvent::LiquidationProcessConcluded { .. } => Some(None),
+                _ => None,
+            })
+            .flatten()
+    }
+
     pub(crate) fn record_due(
         &mut self,
         effective: chrono::NaiveDate,
@@ -411,14 +484,14 @@ impl Obligation {
         Ok(Idempotent::Executed(res))
     }
 
-    pub(crate) fn start_liquidation(
+    /// Defaults whatever the proceeds of a concluded liquidation did not cover.
+    pub(crate) fn record_liquidation_shortfall(
         &mut self,
         effective: chrono::NaiveDate,
-    ) -> Idempotent<NewLiquidationProcess> {
+    ) -> Idempotent<LiquidationShortfallData> {
         idempotency_guard!(
             self.events.iter_all().rev(),
-            ObligationEvent::LiquidationProcessStarted { .. },
-            => ObligationEvent::LiquidationProcessConcluded {..}
+            ObligationEvent::DefaultedRecorded { .. }
         );
 
         match self.status() {
@@ -426,7 +499,37 @@ impl Obligation {
             _ => return Idempotent::Ignored,
         }
 
-        if !self.has_outstanding_balance() {
+        if self.is_in_liquidation() || !self.has_outstanding_balance() {
+            return Idempotent::Ignored;
+        }
+
+        let res = LiquidationShortfallData {
+            tx_id: LedgerTxId::new(),
+            amount: self.outstanding(),
+            receivable_account_id: self.receivable_account_id().expect("Obligation is Paid"),
+            defaulted_account_id: self.defaulted_account(),
+            effective,
+        };
+
+        self.events.push(ObligationEvent::DefaultedRecorded {
+            ledger_tx_id: res.tx_id,
+            defaulted_amount: res.amount,
+        });
+
+        Idempotent::Executed(res)
+    }
+
+    pub(crate) fn start_liquidation(
+        &mut self,
+        effective: chrono::NaiveDate,
+    ) -> Idempotent<NewLiquidationProcess> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            ObligationEvent::LiquidationProcessStarted { .. },
+            => ObligationEvent::LiquidationProcessConcluded {..}
+        );
+
+        if !self.is_liquidatable() {
             return Idempotent::Ignored;
         }
 
@@ -455,10 +558,183 @@ impl Obligation {
         Idempotent::Executed(new_liquidation_process)
     }
 
+    pub(crate) fn conclude_liquidation(
+        &mut self,
+        liquidation_process_id: LiquidationProcessId,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            ObligationEvent::LiquidationProcessConcluded { liquidation_process_id: id }
+                if *id == liquidation_process_id
+        );
+
+        if !self.is_in_liquidation() {
+            return Idempotent::Ignored;
+        }
+
+        self.events
+            .push(ObligationEvent::LiquidationProcessConcluded {
+                liquidation_process_id,
+            });
+
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn accrue_penalty(
+        &mut self,
+        effective: chrono::NaiveDate,
+    ) -> Idempotent<NewObligation> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            ObligationEvent::PenaltyAccrued { effective: date, .. } if *date == effective
+        );
+
+        let Some(penalty) = self.penalty_terms() else {
+            return Idempotent::Ignored;
+        };
+
+        match self.status() {
+            ObligationStatus::Overdue | ObligationStatus::Defaulted => (),
+            _ => return Idempotent::Ignored,
+        }
+
+        if self.is_in_liquidation() {
+            return Idempotent::Ignored;
+        }
+
+        let amount = penalty.annual_rate.interest_for_days(
+            self.outstanding(),
+            effective,
+            effective,
+            penalty.day_count_convention,
+        );
+        if amount.is_zero() {
+            return Idempotent::Ignored;
+        }
+
+        let penalty_accounts = ObligationAccounts {
+            receivable_account_id: penalty.receivable_account_id,
+            account_to_be_credited_id: penalty.income_account_id,
+        };
+        let new_obligation = NewObligation::builder()
+            .id(ObligationId::new())
+            .tx_id(LedgerTxId::new())
+            .credit_facility_id(self.credit_facility_id)
+            .obligation_type(ObligationType::Penalty)
+            .reference(format!("{}-penalty-{}", self.reference, effective))
+            .amount(amount)
+            .not_yet_due_accounts(penalty_accounts)
+            .due_accounts(penalty_accounts)
+            .overdue_accounts(penalty_accounts)
+            .in_liquidation_account_id(self.in_liquidation_account())
+            .defaulted_account_id(penalty.receivable_account_id)
+            .due_date(effective.into())
+            .overdue_date(None)
+            .liquidation_date(None)
+            .effective(effective)
+            .build()
+            .expect("could not build new penalty obligation");
+
+        self.events.push(ObligationEvent::PenaltyAccrued {
+            penalty_obligation_id: new_obligation.id,
+            amount,
+            effective,
+        });
+
+        Idempotent::Executed(new_obligation)
+    }
+
+    /// Moves the lifecycle dates of an obligation that has not become due yet,
+    /// e.g. after the terms of its facility were amended.
+    pub(crate) fn reschedule(&mut self, dates: ObligationLifecycleDates) -> Idempotent<()> {
+        if self.lifecycle_dates() == dates {
+            return Idempotent::Ignored;
+        }
+
+        if self.status() != ObligationStatus::NotYetDue || self.is_in_liquidation() {
+            return Idempotent::Ignored;
+        }
+
+        self.events.push(ObligationEvent::Rescheduled {
+            due_date: dates.due,
+            overdue_date: dates.overdue,
+            liquidation_date: dates.liquidation,
+            defaulted_date: dates.defaulted,
+        });
+
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn is_amortizable_by(&self, installment_due_date: EffectiveDate) -> bool {
+        self.obligation_type == ObligationType::Disbursal
+            && self.status() == ObligationStatus::NotYetDue
+            && !self.is_in_liquidation()
+            && self.due_at() > installment_due_date.start_of_day()
+    }
+
+    pub(crate) fn schedule_principal_installment(
+        &mut self,
+        installment: &PrincipalInstallment,
+    ) -> Idempotent<NewObligation> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            ObligationEvent::PrincipalInstallmentScheduled { reference, .. }
+                if *reference == installment.reference
+        );
+
+        if !self.is_amortizable_by(installment.due_date) {
+            return Idempotent::Ignored;
+        }
+
+        let amount = std::cmp::min(self.outstanding(), installment.amount);
+        if amount.is_zero() {
+            return Idempotent::Ignored;
+        }
+
+        let new_obligation = NewObligation::builder()
+            .id(ObligationId::new())
+            .tx_id(LedgerTxId::new())
+            .credit_facility_id(self.credit_facility_id)
+            .obligation_type(ObligationType::Disbursal)
+            .reference(format!("{}-{}", installment.reference, self.reference))
+            .amount(amount)
+            .not_yet_due_accounts(self.not_yet_due_accounts())
+            .due_accounts(self.due_accounts())
+            .overdue_accounts(self.overdue_accounts())
+            .in_liquidation_account_id(self.in_liquidation_account())
+            .defaulted_account_id(self.defaulted_account())
+            .due_date(installment.due_date)
+            .overdue_date(installment.overdue_date)
+            .liquidation_date(installment.liquidation_date)
+            .penalty(self.penalty_terms())
+            .effective(installment.effective)
+            .build()
+            .expect("could not build new principal installment obligation");
+
+        self.events
+            .push(ObligationEvent::PrincipalInstallmentScheduled {
+                installment_obligation_id: new_obligation.id,
+                reference: installment.reference.clone(),
+                amount,
+                effective: installment.effective,
+            });
+
+        // Nothing is owed on this obligation anymore but it was not repaid either,
+        // so it must not publish `ObligationCompleted`.
+        if self.outstanding().is_zero() {
+            self.events.push(ObligationEvent::SplitIntoInstallments {
+                effective: installment.effective,
+            });
+        }
+
+        Idempotent::Executed(new_obligation)
+    }
+
     pub(crate) fn allocate_payment(
         &mut self,
         amount: UsdCents,
         payment_id: PaymentId,
+        strategy: PaymentAllocationStrategy,
         effective: chrono::NaiveDate,
     ) -> Idempotent<NewPaymentAllocation> {
         idempotency_guard!(
@@ -494,6 +770,7 @@ impl Obligation {
             .obligation_id(self.id)
             .payment_allocation_idx(payment_allocation_idx)
             .obligation_type(self.obligation_type)
+            .allocation_strategy(strategy)
             .receivable_account_id(
                 self.receivable_account_id()
                     .expect("Obligation was already paid"),
@@ -545,6 +822,10 @@ impl TryFromEvents<ObligationEvent> for Obligation {
                 ObligationEvent::PaymentAllocated { .. } => (),
                 ObligationEvent::LiquidationProcessStarted { .. } => (),
                 ObligationEvent::LiquidationProcessConcluded { .. } => (),
+                ObligationEvent::PenaltyAccrued { .. } => (),
+                ObligationEvent::PrincipalInstallmentScheduled { .. } => (),
+                ObligationEvent::Rescheduled { .. } => (),
+                ObligationEvent::SplitIntoInstallments { .. } => (),
                 ObligationEvent::Completed { .. } => (),
             }
         }
@@ -579,6 +860,8 @@ pub struct NewObligation {
     #[builder(setter(strip_option), default)]
     defaulted_date: Option<EffectiveDate>,
     effective: chrono::NaiveDate,
+    #[builder(default)]
+    penalty: Option<ObligationPenaltyTerms>,
 }
 
 impl NewObligationBuilder {
@@ -625,16 +908,68 @@ impl IntoEvents<ObligationEvent> for NewObligation {
                 defaulted_date: self.defaulted_date,
                 liquidation_date: self.liquidation_date,
                 effective: self.effective,
+                penalty: self.penalty,
             }],
         )
     }
 }
 
+impl Obligation {
+    pub(crate) fn cmp_for_allocation(
+        &self,
+        other: &Self,
+        strategy: PaymentAllocationStrategy,
+    ) -> Ordering {
+        match strategy {
+            PaymentAllocationStrategy::InterestFirst => self.cmp(other),
+            PaymentAllocationStrategy::OverdueFirst => self
+                .delinquency_rank()
+                .cmp(&other.delinquency_rank())
+                .then_with(|| self.effective.cmp(&other.effective))
+                .then_with(|| self.cmp(other)),
+            PaymentAllocationStrategy::PrincipalFirst => {
+                match (&self.obligation_type, &other.obligation_type) {
+                    (
+                        ObligationType::Disbursal,
+                        ObligationType::Interest | ObligationType::Penalty,
+                    ) => Ordering::Less,
+                    (
+                        ObligationType::Interest | ObligationType::Penalty,
+                        ObligationType::Disbursal,
+                    ) => Ordering::Greater,
+                    _ => self.cmp(other),
+                }
+            }
+            PaymentAllocationStrategy::DefaultedLast => self
+                .is_defaulted()
+                .cmp(&other.is_defaulted())
+                .then_with(|| self.cmp(other)),
+        }
+    }
+
+    fn is_defaulted(&self) -> bool {
+        self.status() == ObligationStatus::Defaulted
+    }
+
+    fn delinquency_rank(&self) -> u8 {
+        match self.status() {
+            ObligationStatus::Defaulted | ObligationStatus::Overdue => 0,
+            ObligationStatus::Due => 1,
+            ObligationStatus::NotYetDue => 2,
+            ObligationStatus::Paid => 3,
+        }
+    }
+}
+
 impl Ord for Obligation {
     fn cmp(&self, other: &Self) -> Ordering {
         match (&self.obligation_type, &other.obligation_type) {
-            (ObligationType::Interest, ObligationType::Disbursal) => Ordering::Less,
-            (ObligationType::Disbursal, ObligationType::Interest) => Ordering::Greater,
+            (ObligationType::Interest | ObligationType::Penalty, ObligationType::Disbursal) => {
+                Ordering::Less
+            }
+            (ObligationType::Disbursal, ObligationType::Interest | ObligationType::Penalty) => {
+                Ordering::Greater
+            }
             _ => self
                 .effective
                 .cmp(&other.effective)
@@ -654,6 +989,7 @@ impl PartialEq for Obligation {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ObligationLifecycleDates {
     pub due: EffectiveDate,
     pub overdue: Option<EffectiveDate>,
@@ -715,6 +1051,7 @@ mod test {
             defaulted_date: None,
             liquidation_date: None,
             effective: Utc::now().date_naive(),
+            penalty: None,
         }]
     }
 
@@ -852,7 +1189,12 @@ mod test {
     fn completes_on_final_payment_allocation() {
         let mut obligation = obligation_from(initial_events());
         obligation
-            .allocate_payment(UsdCents::ONE, PaymentId::new(), Utc::now().date_naive())
+            .allocate_payment(
+                UsdCents::ONE,
+                PaymentId::new(),
+                PaymentAllocationStrategy::default(),
+                Utc::now().date_naive(),
+            )
             .unwrap();
         assert_eq!(obligation.status(), ObligationStatus::NotYetDue);
 
@@ -860,23 +1202,413 @@ mod test {
             .allocate_payment(
                 obligation.outstanding(),
                 PaymentId::new(),
+                PaymentAllocationStrategy::default(),
                 Utc::now().date_naive(),
             )
             .unwrap();
         assert_eq!(obligation.status(), ObligationStatus::Paid);
     }
 
+    #[test]
+    fn not_yet_due_obligation_is_liquidatable() {
+        let mut obligation = obligation_from(initial_events());
+        assert_eq!(obligation.status(), ObligationStatus::NotYetDue);
+        assert!(obligation.is_liquidatable());
+        assert!(
+            obligation
+                .start_liquidation(Utc::now().date_naive())
+                .did_execute()
+        );
+    }
+
+    #[test]
+    fn payment_allocation_allowed_after_liquidation_concluded() {
+        let mut obligation = obligation_from(initial_events());
+        let liquidation_process = obligation
+            .start_liquidation(Utc::now().date_naive())
+            .unwrap();
+        assert!(
+            obligation
+                .conclude_liquidation(liquidation_process.id)
+                .did_execute()
+        );
+        assert!(!obligation.is_in_liquidation());
+        assert!(
+            obligation
+                .allocate_payment(
+                    UsdCents::ONE,
+                    PaymentId::new(),
+                    PaymentAllocationStrategy::default(),
+                    Utc::now().date_naive(),
+                )
+                .did_execute()
+        );
+    }
+
+    #[test]
+    fn records_liquidation_shortfall_once_liquidation_concluded() {
+        let mut obligation = obligation_from(initial_events());
+        let _ = obligation.record_due(Utc::now().date_naive());
+        let liquidation_process = obligation
+            .start_liquidation(Utc::now().date_naive())
+            .unwrap();
+        assert_eq!(
+            obligation.liquidation_process_id(),
+            Some(liquidation_process.id)
+        );
+        assert!(
+            obligation
+                .record_liquidation_shortfall(Utc::now().date_naive())
+                .was_ignored()
+        );
+
+        let _ = obligation.conclude_liquidation(liquidation_process.id);
+        assert_eq!(obligation.liquidation_process_id(), None);
+        let _ = obligation.allocate_payment(
+            UsdCents::ONE,
+            PaymentId::new(),
+            PaymentAllocationStrategy::default(),
+            Utc::now().date_naive(),
+        );
+        let res = obligation
+            .record_liquidation_shortfall(Utc::now().date_naive())
+            .unwrap();
+        assert_eq!(res.amount, obligation.outstanding());
+        assert_eq!(obligation.status(), ObligationStatus::Defaulted);
+    }
+
     #[test]
     fn payment_allocation_ignored_in_liquidation() {
         let mut obligation = obligation_from(initial_events());
         let _ = obligation.start_liquidation(Utc::now().date_naive());
         assert!(
             obligation
-                .allocate_payment(UsdCents::ONE, PaymentId::new(), Utc::now().date_naive(),)
+                .allocate_payment(
+                    UsdCents::ONE,
+                    PaymentId::new(),
+                    PaymentAllocationStrategy::default(),
+                    Utc::now().date_naive(),
+                )
                 .was_ignored()
         );
     }
 
+    mod accrue_penalty {
+        use rust_decimal_macros::dec;
+
+        use crate::terms::DayCountConvention;
+
+        use super::*;
+
+        fn overdue_obligation_with_penalty() -> Obligation {
+            let mut events = initial_events();
+            if let ObligationEvent::Initialized { penalty, .. } = &mut events[0] {
+                *penalty = Some(ObligationPenaltyTerms {
+                    annual_rate: dec!(365).into(),
+                    day_count_convention: DayCountConvention::default(),
+                    receivable_account_id: CalaAccountId::new(),
+                    income_account_id: CalaAccountId::new(),
+                });
+            }
+            let mut obligation = obligation_from(events);
+            let _ = obligation.record_due(Utc::now().date_naive());
+            let _ = obligation.record_overdue(Utc::now().date_naive()).unwrap();
+            obligation
+        }
+
+        #[test]
+        fn accrues_penalty_once_per_day() {
+            let mut obligation = overdue_obligation_with_penalty();
+            let today = Utc::now().date_naive();
+
+            let new_obligation = obligation.accrue_penalty(today).unwrap();
+            assert_eq!(new_obligation.obligation_type, ObligationType::Penalty);
+            assert_eq!(new_obligation.amount, UsdCents::ONE);
+
+            assert!(obligation.accrue_penalty(today).was_ignored());
+            assert!(
+                obligation
+                    .accrue_penalty(today + chrono::Days::new(1))
+                    .did_execute()
+            );
+        }
+
+        #[test]
+        fn ignored_without_penalty_terms() {
+            let mut obligation = obligation_from(initial_events());
+            let _ = obligation.record_due(Utc::now().date_naive());
+            let _ = obligation.record_overdue(Utc::now().date_naive()).unwrap();
+
+            assert!(
+                obligation
+                    .accrue_penalty(Utc::now().date_naive())
+                    .was_ignored()
+            );
+        }
+
+        #[test]
+        fn ignored_if_not_overdue() {
+            let mut events = initial_events();
+            if let ObligationEvent::Initialized { penalty, .. } = &mut events[0] {
+                *penalty = Some(ObligationPenaltyTerms {
+                    annual_rate: dec!(365).into(),
+                    day_count_convention: DayCountConvention::default(),
+                    receivable_account_id: CalaAccountId::new(),
+                    income_account_id: CalaAccountId::new(),
+                });
+            }
+            let mut obligation = obligation_from(events);
+            let _ = obligation.record_due(Utc::now().date_naive());
+
+            assert!(
+                obligation
+                    .accrue_penalty(Utc::now().date_naive())
+                    .was_ignored()
+            );
+        }
+
+        #[test]
+        fn ignored_in_liquidation() {
+            let mut obligation = overdue_obligation_with_penalty();
+            let _ = obligation.start_liquidation(Utc::now().date_naive());
+
+            assert!(
+                obligation
+                    .accrue_penalty(Utc::now().date_naive())
+                    .was_ignored()
+            );
+        }
+
+        #[test]
+        fn ignored_once_paid() {
+            let mut obligation = overdue_obligation_with_penalty();
+            let _ = obligation.allocate_payment(
+                obligation.outstanding(),
+                PaymentId::new(),
+                PaymentAllocationStrategy::default(),
+                Utc::now().date_naive(),
+            );
+
+            assert!(
+                obligation
+                    .accrue_penalty(Utc::now().date_naive())
+                    .was_ignored()
+            );
+        }
+    }
+
+    mod reschedule {
+        use super::*;
+
+        fn later_dates(obligation: &Obligation) -> ObligationLifecycleDates {
+            let due = obligation
+                .lifecycle_dates()
+                .due
+                .checked_add_days(chrono::Days::new(30))
+                .unwrap();
+            ObligationLifecycleDates {
+                due,
+                overdue: due.checked_add_days(chrono::Days::new(7)),
+                liquidation: due.checked_add_days(chrono::Days::new(60)),
+                defaulted: None,
+            }
+        }
+
+        #[test]
+        fn moves_lifecycle_dates_of_not_yet_due_obligation() {
+            let mut obligation = obligation_from(initial_events());
+            let dates = later_dates(&obligation);
+
+            assert!(obligation.reschedule(dates).did_execute());
+            assert_eq!(obligation.lifecycle_dates(), dates);
+            assert_eq!(obligation.due_at(), dates.due.start_of_day());
+        }
+
+        #[test]
+        fn ignored_if_dates_unchanged() {
+            let mut obligation = obligation_from(initial_events());
+            let dates = obligation.lifecycle_dates();
+
+            assert!(obligation.reschedule(dates).was_ignored());
+        }
+
+        #[test]
+        fn ignored_once_due() {
+            let mut obligation = obligation_from(initial_events());
+            assert!(obligation.record_due(Utc::now().date_naive()).did_execute());
+            let dates = later_dates(&obligation);
+
+            assert!(obligation.reschedule(dates).was_ignored());
+            assert_ne!(obligation.lifecycle_dates(), dates);
+        }
+    }
+
+    mod schedule_principal_installment {
+        use super::*;
+
+        fn obligation_maturing_in_days(days: u64) -> Obligation {
+            let mut events = initial_events();
+            if let ObligationEvent::Initialized { due_date, .. } = &mut events[0] {
+                *due_date = (Utc::now() + chrono::Days::new(days)).into();
+            }
+            obligation_from(events)
+        }
+
+        fn installment(amount: UsdCents) -> PrincipalInstallment {
+            PrincipalInstallment {
+                reference: "installment-1".to_string(),
+                amount,
+                due_date: Utc::now().into(),
+                overdue_date: None,
+                liquidation_date: None,
+                effective: Utc::now().date_naive(),
+            }
+        }
+
+        #[test]
+        fn carves_installment_out_of_outstanding() {
+            let mut obligation = obligation_maturing_in_days(30);
+
+            let new_obligation = obligation
+                .schedule_principal_installment(&installment(UsdCents::from(4)))
+                .unwrap();
+            assert_eq!(new_obligation.obligation_type, ObligationType::Disbursal);
+            assert_eq!(new_obligation.amount, UsdCents::from(4));
+            assert_eq!(obligation.outstanding(), UsdCents::from(6));
+
+            assert!(
+                obligation
+                    .schedule_principal_installment(&installment(UsdCents::from(4)))
+                    .was_ignored()
+            );
+        }
+
+        #[test]
+        fn split_into_installments_when_fully_scheduled() {
+            let mut obligation = obligation_maturing_in_days(30);
+
+            let new_obligation = obligation
+                .schedule_principal_installment(&installment(UsdCents::from(100)))
+                .unwrap();
+            assert_eq!(new_obligation.amount, UsdCents::from(10));
+            assert_eq!(obligation.status(), ObligationStatus::Paid);
+            assert!(matches!(
+                obligation.events.iter_all().last(),
+                Some(ObligationEvent::SplitIntoInstallments { .. })
+            ));
+        }
+
+        #[test]
+        fn ignored_if_not_due_after_installment() {
+            let mut obligation = obligation_maturing_in_days(0);
+
+            assert!(
+                obligation
+                    .schedule_principal_installment(&installment(UsdCents::from(4)))
+                    .was_ignored()
+            );
+        }
+    }
+
+    mod cmp_for_allocation {
+        use super::*;
+
+        fn obligation(obligation_type: ObligationType, days_ago: u64) -> Obligation {
+            let mut events = initial_events();
+            if let ObligationEvent::Initialized {
+                obligation_type: t,
+                effective,
+                ..
+            } = &mut events[0]
+            {
+                *t = obligation_type;
+                *effective = Utc::now().date_naive() - chrono::Days::new(days_ago);
+            }
+            obligation_from(events)
+        }
+
+        fn sorted(
+            mut obligations: Vec<Obligation>,
+            strategy: PaymentAllocationStrategy,
+        ) -> Vec<ObligationId> {
+            obligations.sort_by(|a, b| a.cmp_for_allocation(b, strategy));
+            obligations.into_iter().map(|o| o.id).collect()
+        }
+
+        #[test]
+        fn interest_first_is_default_order() {
+            let disbursal = obligation(ObligationType::Disbursal, 10);
+            let interest = obligation(ObligationType::Interest, 1);
+            let expected = vec![interest.id, disbursal.id];
+
+            let res = sorted(
+                vec![disbursal, interest],
+                PaymentAllocationStrategy::default(),
+            );
+            assert_eq!(res, expected);
+        }
+
+        #[test]
+        fn principal_first() {
+            let disbursal = obligation(ObligationType::Disbursal, 1);
+            let interest = obligation(ObligationType::Interest, 10);
+            let expected = vec![disbursal.id, interest.id];
+
+            let res = sorted(
+                vec![interest, disbursal],
+                PaymentAllocationStrategy::PrincipalFirst,
+            );
+            assert_eq!(res, expected);
+        }
+
+        #[test]
+        fn overdue_first_across_types() {
+            let mut disbursal = obligation(ObligationType::Disbursal, 10);
+            let _ = disbursal.record_due(Utc::now().date_naive());
+            assert!(
+                disbursal
+                    .record_overdue(Utc::now().date_naive())
+                    .unwrap()
+                    .did_execute()
+            );
+            let mut interest = obligation(ObligationType::Interest, 1);
+            let _ = interest.record_due(Utc::now().date_naive());
+            let expected = vec![disbursal.id, interest.id];
+
+            let res = sorted(
+                vec![interest, disbursal],
+                PaymentAllocationStrategy::OverdueFirst,
+            );
+            assert_eq!(res, expected);
+        }
+
+        #[test]
+        fn defaulted_last() {
+            let disbursal = obligation(ObligationType::Disbursal, 1);
+            let mut interest = obligation(ObligationType::Interest, 10);
+            let _ = interest.record_due(Utc::now().date_naive());
+            assert!(
+                interest
+                    .record_overdue(Utc::now().date_naive())
+                    .unwrap()
+                    .did_execute()
+            );
+            assert!(
+                interest
+                    .record_defaulted(Utc::now().date_naive())
+                    .unwrap()
+                    .did_execute()
+            );
+            let expected = vec![disbursal.id, interest.id];
+
+            let res = sorted(
+                vec![interest, disbursal],
+                PaymentAllocationStrategy::DefaultedLast,
+            );
+            assert_eq!(res, expected);
+        }
+    }
+
     mod is_status_up_to_date {
 
         use super::*;
@@ -920,6 +1652,7 @@ mod test {
                 defaulted_date: Some(defaulted_timestamp(now).into()),
                 liquidation_date: None,
                 effective: Utc::now().date_naive(),
+                penalty: None,
             }]
         }
 
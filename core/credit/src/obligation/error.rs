@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::primitives::UsdCents;
+
 #[derive(Error, Debug)]
 pub enum ObligationError {
     #[error("ObligationError - AuthorizationError: {0}")]
@@ -22,6 +24,10 @@ pub enum ObligationError {
     PaymentAllocationError(#[from] crate::payment_allocation::error::PaymentAllocationError),
     #[error("ObligationError - PaymentAmountGreaterThanOutstandingObligations")]
     PaymentAmountGreaterThanOutstandingObligations,
+    #[error("ObligationError - PayoffAmountMismatch: outstanding {0}, paid {1}")]
+    PayoffAmountMismatch(UsdCents, UsdCents),
+    #[error("ObligationError - PayoffBlockedByLiquidation")]
+    PayoffBlockedByLiquidation,
     #[error("CoreCreditError - ObligationError: {0}")]
     CreditLedgerError(#[from] crate::ledger::error::CreditLedgerError),
 }
@@ -3,7 +3,7 @@ pub mod error;
 mod primitives;
 mod repo;
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use tracing::{Span, instrument};
 
@@ -14,14 +14,17 @@ use job::{JobId, Jobs};
 use outbox::OutboxEventMarker;
 
 use crate::{
-    CreditLedger, PaymentAllocation, PaymentAllocationId, PaymentAllocationRepo,
+    PaymentAllocation, PaymentAllocationId, PaymentAllocationRepo,
     event::CoreCreditEvent,
     jobs::obligation_due,
-    liquidation_process::{LiquidationProcess, LiquidationProcessRepo},
+    liquidation_process::{LiquidationProceedsData, LiquidationProcess, LiquidationProcessRepo},
+    payment_allocation::NewPaymentAllocation,
     primitives::{
-        CoreCreditAction, CoreCreditObject, CreditFacilityId, ObligationId, PaymentId, UsdCents,
+        CalaAccountId, CoreCreditAction, CoreCreditObject, CreditFacilityId, EffectiveDate, LedgerTxId,
+        LiquidationProcessId, ObligationId, ObligationType, PaymentId, Satoshis, UsdCents,
     },
     publisher::CreditFacilityPublisher,
+    terms::{PaymentAllocationStrategy, TermValues},
 };
 
 pub use entity::Obligation;
@@ -43,7 +46,6 @@ where
     repo: Arc<ObligationRepo<E>>,
     liquidation_process_repo: Arc<LiquidationProcessRepo<E>>,
     payment_allocation_repo: Arc<PaymentAllocationRepo<E>>,
-    ledger: Arc<CreditLedger>,
     jobs: Arc<Jobs>,
 }
 
@@ -58,7 +60,6 @@ where
             repo: self.repo.clone(),
             liquidation_process_repo: self.liquidation_process_repo.clone(),
             payment_allocation_repo: self.payment_allocation_repo.clone(),
-            ledger: self.ledger.clone(),
             jobs: self.jobs.clone(),
         }
     }
@@ -74,7 +75,6 @@ where
     pub(crate) fn new(
         pool: &sqlx::PgPool,
         authz: Arc<Perms>,
-        ledger: Arc<CreditLedger>,
         jobs: Arc<Jobs>,
         publisher: &CreditFacilityPublisher<E>,
     ) -> Self {
@@ -86,7 +86,6 @@ where
             repo: Arc::new(obligation_repo),
             liquidation_process_repo: Arc::new(liquidation_process_repo),
             jobs,
-            ledger,
             payment_allocation_repo: Arc::new(payment_allocation_repo),
         }
     }
@@ -154,6 +153,10 @@ where
         effective: chrono::NaiveDate,
     ) -> Result<(Obligation, Option<ObligationDueReallocationData>), ObligationError> {
         let mut obligation = self.repo.find_by_id(id).await?;
+        // a rescheduled obligation has its own job for the new due date
+        if obligation.lifecycle_dates().due != effective.into() {
+            return Ok((obligation, None));
+        }
 
         self.authz
             .audit()
@@ -205,6 +208,35 @@ where
         Ok(data)
     }
 
+    pub async fn accrue_penalty_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        id: ObligationId,
+        effective: chrono::NaiveDate,
+    ) -> Result<(Obligation, Option<Obligation>), ObligationError> {
+        let mut obligation = self.repo.find_by_id(id).await?;
+
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                op,
+                CoreCreditObject::obligation(id),
+                CoreCreditAction::OBLIGATION_ACCRUE_PENALTY,
+            )
+            .await
+            .map_err(authz::error::AuthorizationError::from)?;
+
+        let penalty_obligation =
+            if let Idempotent::Executed(new_obligation) = obligation.accrue_penalty(effective) {
+                self.repo.update_in_op(op, &mut obligation).await?;
+                Some(self.create_with_jobs_in_op(op, new_obligation).await?)
+            } else {
+                None
+            };
+
+        Ok((obligation, penalty_obligation))
+    }
+
     pub async fn start_liquidation_process_in_op(
         &self,
         op: &mut es_entity::DbOp<'_>,
@@ -240,6 +272,167 @@ where
         Ok((obligation, liquidation_process))
     }
 
+    #[instrument(
+        name = "credit.obligation.record_liquidation_collateral_sold_in_op",
+        skip(self, op),
+        err
+    )]
+    pub(crate) async fn record_liquidation_collateral_sold_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        liquidation_process_id: LiquidationProcessId,
+        collateral_sold: Satoshis,
+        proceeds: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Result<(LiquidationProcess, Option<LiquidationProceedsData>), ObligationError> {
+        let mut liquidation_process = self
+            .liquidation_process_repo
+            .find_by_id(liquidation_process_id)
+            .await?;
+
+        let data = if let Idempotent::Executed(data) =
+            liquidation_process.record_collateral_sold(collateral_sold, proceeds, effective)?
+        {
+            self.liquidation_process_repo
+                .update_in_op(op, &mut liquidation_process)
+                .await?;
+            Some(data)
+        } else {
+            None
+        };
+
+        Ok((liquidation_process, data))
+    }
+
+    #[instrument(
+        name = "credit.obligation.apply_liquidation_proceeds_in_op",
+        skip(self, op, liquidation_process),
+        fields(liquidation_process_id = %liquidation_process.id),
+        err
+    )]
+    pub(crate) async fn apply_liquidation_proceeds_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        liquidation_process: &mut LiquidationProcess,
+        payment_id: PaymentId,
+        strategy: PaymentAllocationStrategy,
+        effective: chrono::NaiveDate,
+    ) -> Result<AppliedLiquidationProceeds, ObligationError> {
+        let proceeds = liquidation_process.proceeds().ok_or(
+            crate::liquidation_process::error::LiquidationProcessError::CollateralSaleNotRecorded,
+        )?;
+
+        let mut obligations = self
+            .facility_obligations_in_op(op, liquidation_process.credit_facility_id)
+            .await?
+            .into_iter()
+            .filter(|o| o.is_in_liquidation())
+            .collect::<Vec<_>>();
+
+        let mut concluded = Vec::new();
+        for obligation in obligations.iter_mut() {
+            self.authz
+                .audit()
+                .record_system_entry_in_tx(
+                    op,
+                    CoreCreditObject::obligation(obligation.id),
+                    CoreCreditAction::OBLIGATION_UPDATE_STATUS,
+                )
+                .await
+                .map_err(authz::error::AuthorizationError::from)?;
+
+            let liquidation_process_id = obligation
+                .liquidation_process_id()
+                .expect("Obligation is in liquidation");
+            let _ = obligation.conclude_liquidation(liquidation_process_id);
+            concluded.push((
+                obligation.id,
+                liquidation_process_id,
+                obligation.outstanding(),
+            ));
+        }
+
+        let mut allocated = HashSet::new();
+        let new_allocations = allocate_to_obligations(
+            &mut obligations,
+            payment_id,
+            proceeds,
+            None,
+            strategy,
+            effective,
+            &mut allocated,
+        );
+
+        let mut shortfalls = Vec::new();
+        for obligation in obligations.iter_mut() {
+            if let Idempotent::Executed(shortfall) =
+                obligation.record_liquidation_shortfall(effective)
+            {
+                shortfalls.push(shortfall);
+            }
+            self.repo.update_in_op(op, obligation).await?;
+        }
+
+        let allocations = self
+            .payment_allocation_repo
+            .create_all_in_op(op, new_allocations)
+            .await?;
+        let proceeds_applied = allocations.iter().fold(UsdCents::ZERO, |c, a| c + a.amount);
+
+        let mut reserved_amount = UsdCents::ZERO;
+        for (obligation_id, liquidation_process_id, outstanding_before) in concluded {
+            let amount_applied = allocations
+                .iter()
+                .filter(|a| a.obligation_id == obligation_id)
+                .fold(UsdCents::ZERO, |c, a| c + a.amount);
+
+            if liquidation_process_id == liquidation_process.id {
+                reserved_amount += liquidation_process.initial_amount;
+                if liquidation_process
+                    .complete(
+                        payment_id,
+                        outstanding_before,
+                        amount_applied,
+                        proceeds_applied,
+                        effective,
+                    )?
+                    .did_execute()
+                {
+                    self.liquidation_process_repo
+                        .update_in_op(op, liquidation_process)
+                        .await?;
+                }
+            } else {
+                let mut other = self
+                    .liquidation_process_repo
+                    .find_by_id_in_op(&mut *op, liquidation_process_id)
+                    .await?;
+                reserved_amount += other.initial_amount;
+                if other
+                    .complete_alongside(payment_id, outstanding_before, amount_applied, effective)
+                    .did_execute()
+                {
+                    self.liquidation_process_repo
+                        .update_in_op(op, &mut other)
+                        .await?;
+                }
+            }
+        }
+
+        let surplus = (proceeds > proceeds_applied).then(|| LiquidationSurplusData {
+            tx_id: LedgerTxId::new(),
+            amount: proceeds - proceeds_applied,
+            effective,
+        });
+
+        Ok(AppliedLiquidationProceeds {
+            allocations,
+            shortfalls,
+            surplus,
+            reserved_amount,
+        })
+    }
+
     pub async fn find_by_id_without_audit(
         &self,
         id: ObligationId,
@@ -252,53 +445,301 @@ where
         skip(self, op),
         fields(n_new_allocations, n_facility_obligations, amount_allocated)
     )]
-    pub async fn allocate_payment_in_op(
+    pub(crate) async fn allocate_payment_in_op(
         &self,
-        mut op: es_entity::DbOp<'_>,
+        op: &mut es_entity::DbOp<'_>,
         credit_facility_id: CreditFacilityId,
         payment_id: PaymentId,
         amount: UsdCents,
+        funding_account_id: Option<CalaAccountId>,
+        strategy: PaymentAllocationStrategy,
         effective: chrono::NaiveDate,
-    ) -> Result<(), ObligationError> {
-        let span = Span::current();
+    ) -> Result<Vec<PaymentAllocation>, ObligationError> {
+        let obligations = self
+            .facility_obligations_in_op(op, credit_facility_id)
+            .await?;
+        self.allocate_to_obligations_in_op(
+            op,
+            obligations,
+            payment_id,
+            amount,
+            funding_account_id,
+            strategy,
+            effective,
+        )
+        .await
+    }
+
+    #[instrument(
+        name = "credit.obligation.allocate_payoff_in_op",
+        skip(self, op, new_obligations),
+        fields(n_new_allocations, n_facility_obligations, amount_allocated)
+    )]
+    pub(crate) async fn allocate_payoff_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        credit_facility_id: CreditFacilityId,
+        new_obligations: Vec<Obligation>,
+        payment_id: PaymentId,
+        amount: UsdCents,
+        strategy: PaymentAllocationStrategy,
+        effective: chrono::NaiveDate,
+    ) -> Result<Vec<PaymentAllocation>, ObligationError> {
         let mut obligations = self.facility_obligations(credit_facility_id).await?;
-        span.record("n_facility_obligations", obligations.len());
+        obligations.extend(new_obligations);
 
-        obligations.sort();
+        if obligations
+            .iter()
+            .any(|o| o.is_in_liquidation() && o.has_outstanding_balance())
+        {
+            return Err(ObligationError::PayoffBlockedByLiquidation);
+        }
 
-        let mut remaining = amount;
-        let mut new_allocations = Vec::new();
-        for obligation in obligations.iter_mut() {
-            if let es_entity::Idempotent::Executed(new_allocation) =
-                obligation.allocate_payment(remaining, payment_id, effective)
+        let outstanding = obligations
+            .iter()
+            .fold(UsdCents::ZERO, |acc, o| acc + o.outstanding());
+        if outstanding != amount {
+            return Err(ObligationError::PayoffAmountMismatch(outstanding, amount));
+        }
+
+        self.allocate_to_obligations_in_op(
+            op,
+            obligations,
+            payment_id,
+            amount,
+            None,
+            strategy,
+            effective,
+        )
+        .await
+    }
+
+    pub(crate) async fn principal_to_amortize(
+        &self,
+        credit_facility_id: CreditFacilityId,
+        installment_due_date: EffectiveDate,
+    ) -> Result<UsdCents, ObligationError> {
+        Ok(self
+            .facility_obligations(credit_facility_id)
+            .await?
+            .iter()
+            .filter(|o| o.is_amortizable_by(installment_due_date))
+            .fold(UsdCents::ZERO, |acc, o| acc + o.outstanding()))
+    }
+
+    pub(crate) async fn schedule_principal_installment_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        credit_facility_id: CreditFacilityId,
+        installment: PrincipalInstallment,
+    ) -> Result<Vec<Obligation>, ObligationError> {
+        let mut remaining = installment.amount;
+        let mut installments = Vec::new();
+        for mut obligation in self.facility_obligations(credit_facility_id).await? {
+            if remaining.is_zero() {
+                break;
+            }
+            if let Idempotent::Executed(new_obligation) =
+                obligation.schedule_principal_installment(&PrincipalInstallment {
+                    amount: remaining,
+                    ..installment.clone()
+                })
             {
-                self.repo.update_in_op(&mut op, obligation).await?;
-                remaining -= new_allocation.amount;
-                new_allocations.push(new_allocation);
-                if remaining == UsdCents::ZERO {
-                    break;
-                }
+                remaining -= new_obligation.amount;
+                self.repo.update_in_op(op, &mut obligation).await?;
+                installments.push(self.create_with_jobs_in_op(op, new_obligation).await?);
             }
         }
 
+        Ok(installments)
+    }
+
+    pub(crate) async fn outstanding_for_facility(
+        &self,
+        credit_facility_id: CreditFacilityId,
+    ) -> Result<UsdCents, ObligationError> {
+        Ok(self
+            .facility_obligations(credit_facility_id)
+            .await?
+            .iter()
+            .fold(UsdCents::ZERO, |acc, o| acc + o.outstanding()))
+    }
+
+    /// Moves the obligations of a facility that are not due yet onto amended terms.
+    /// Obligations falling due at the old maturity date or later fall due at the new one.
+    #[instrument(
+        name = "credit.obligation.reschedule_for_amended_terms_in_op",
+        skip(self, op, terms),
+        fields(n_rescheduled),
+        err
+    )]
+    pub(crate) async fn reschedule_for_amended_terms_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        credit_facility_id: CreditFacilityId,
+        terms: &TermValues,
+        old_maturity_date: EffectiveDate,
+        new_maturity_date: EffectiveDate,
+    ) -> Result<(), ObligationError> {
+        let mut n_rescheduled = 0;
+        for mut obligation in self
+            .facility_obligations_in_op(op, credit_facility_id)
+            .await?
+        {
+            if obligation.obligation_type == ObligationType::Penalty {
+                continue;
+            }
+
+            let dates = obligation.lifecycle_dates();
+            let due = if dates.due >= old_maturity_date || dates.due > new_maturity_date {
+                new_maturity_date
+            } else {
+                dates.due
+            };
+            let rescheduled = ObligationLifecycleDates {
+                due,
+                overdue: terms.get_overdue_date_from_due_date(due),
+                liquidation: terms.get_liquidation_date_from_due_date(due),
+                defaulted: dates.defaulted,
+            };
+            if obligation.reschedule(rescheduled).was_ignored() {
+                continue;
+            }
+            self.repo.update_in_op(op, &mut obligation).await?;
+
+            if due != dates.due {
+                self.jobs
+                    .create_and_spawn_at_in_op(
+                        op,
+                        JobId::new(),
+                        obligation_due::ObligationDueJobConfig::<Perms, E> {
+                            obligation_id: obligation.id,
+                            effective: obligation.due_at().date_naive(),
+                            _phantom: std::marker::PhantomData,
+                        },
+                        obligation.due_at(),
+                    )
+                    .await?;
+            }
+            n_rescheduled += 1;
+        }
+        Span::current().record("n_rescheduled", n_rescheduled);
+
+        Ok(())
+    }
+
+    /// Obligations of the facility with an outstanding balance, including those not
+    /// due yet, so that an escalated margin call liquidates the whole exposure.
+    pub(crate) async fn liquidatable_ids_for_facility(
+        &self,
+        credit_facility_id: CreditFacilityId,
+    ) -> Result<Vec<ObligationId>, ObligationError> {
+        Ok(self
+            .facility_obligations(credit_facility_id)
+            .await?
+            .iter()
+            .filter(|o| o.is_liquidatable())
+            .map(|o| o.id)
+            .collect())
+    }
+
+    /// Allocates several payments in turn against the facility's obligations.
+    /// Obligations are loaded and persisted once so that each payment sees the
+    /// allocations of the ones before it.
+    #[instrument(
+        name = "credit.obligation.allocate_payments_in_op",
+        skip(self, op, payments),
+        fields(n_new_allocations, n_facility_obligations, amount_allocated)
+    )]
+    pub(crate) async fn allocate_payments_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        credit_facility_id: CreditFacilityId,
+        payments: &[(PaymentId, UsdCents)],
+        funding_account_id: Option<CalaAccountId>,
+        strategy: PaymentAllocationStrategy,
+        effective: chrono::NaiveDate,
+    ) -> Result<Vec<PaymentAllocation>, ObligationError> {
+        let mut obligations = self
+            .facility_obligations_in_op(op, credit_facility_id)
+            .await?;
+        Span::current().record("n_facility_obligations", obligations.len());
+
+        let mut allocated = HashSet::new();
+        let mut new_allocations = Vec::new();
+        for (payment_id, amount) in payments {
+            new_allocations.extend(allocate_to_obligations(
+                &mut obligations,
+                *payment_id,
+                *amount,
+                funding_account_id,
+                strategy,
+                effective,
+                &mut allocated,
+            ));
+        }
+
+        self.persist_allocations_in_op(op, obligations, &allocated, new_allocations)
+            .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn allocate_to_obligations_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        mut obligations: Vec<Obligation>,
+        payment_id: PaymentId,
+        amount: UsdCents,
+        funding_account_id: Option<CalaAccountId>,
+        strategy: PaymentAllocationStrategy,
+        effective: chrono::NaiveDate,
+    ) -> Result<Vec<PaymentAllocation>, ObligationError> {
+        Span::current().record("n_facility_obligations", obligations.len());
+
+        let mut allocated = HashSet::new();
+        let new_allocations = allocate_to_obligations(
+            &mut obligations,
+            payment_id,
+            amount,
+            funding_account_id,
+            strategy,
+            effective,
+            &mut allocated,
+        );
+
+        self.persist_allocations_in_op(op, obligations, &allocated, new_allocations)
+            .await
+    }
+
+    async fn persist_allocations_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        obligations: Vec<Obligation>,
+        allocated: &HashSet<ObligationId>,
+        new_allocations: Vec<NewPaymentAllocation>,
+    ) -> Result<Vec<PaymentAllocation>, ObligationError> {
+        for mut obligation in obligations
+            .into_iter()
+            .filter(|o| allocated.contains(&o.id))
+        {
+            self.repo.update_in_op(op, &mut obligation).await?;
+        }
+
+        let span = Span::current();
         span.record("n_new_allocations", new_allocations.len());
 
         let allocations = self
             .payment_allocation_repo
-            .create_all_in_op(&mut op, new_allocations)
+            .create_all_in_op(op, new_allocations)
             .await?;
 
         let amount_allocated = allocations.iter().fold(UsdCents::ZERO, |c, a| c + a.amount);
-        tracing::Span::current().record(
+        span.record(
             "amount_allocated",
             tracing::field::display(amount_allocated),
         );
 
-        self.ledger
-            .record_payment_allocations(op, allocations)
-            .await?;
-
-        Ok(())
+        Ok(allocations)
     }
 
     pub(super) async fn find_allocation_by_id_without_audit(
@@ -374,4 +815,68 @@ where
 
         Ok(obligations)
     }
+
+    async fn facility_obligations_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        credit_facility_id: CreditFacilityId,
+    ) -> Result<Vec<Obligation>, ObligationError> {
+        let mut obligations = Vec::new();
+        let mut query = Default::default();
+        loop {
+            let mut res = self
+                .repo
+                .list_for_credit_facility_id_by_created_at_in_op(
+                    &mut *op,
+                    credit_facility_id,
+                    query,
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+
+            obligations.append(&mut res.entities);
+
+            if let Some(q) = res.into_next_query() {
+                query = q;
+            } else {
+                break;
+            };
+        }
+
+        Ok(obligations)
+    }
+}
+
+/// Allocates `amount` across `obligations` in allocation order, recording which
+/// obligations received an allocation so they can be persisted afterwards.
+fn allocate_to_obligations(
+    obligations: &mut [Obligation],
+    payment_id: PaymentId,
+    amount: UsdCents,
+    funding_account_id: Option<CalaAccountId>,
+    strategy: PaymentAllocationStrategy,
+    effective: chrono::NaiveDate,
+    allocated: &mut HashSet<ObligationId>,
+) -> Vec<NewPaymentAllocation> {
+    obligations.sort_by(|a, b| a.cmp_for_allocation(b, strategy));
+
+    let mut remaining = amount;
+    let mut new_allocations = Vec::new();
+    for obligation in obligations.iter_mut() {
+        if remaining == UsdCents::ZERO {
+            break;
+        }
+        if let es_entity::Idempotent::Executed(mut new_allocation) =
+            obligation.allocate_payment(remaining, payment_id, strategy, effective)
+        {
+            allocated.insert(obligation.id);
+            remaining -= new_allocation.amount;
+            if let Some(funding_account_id) = funding_account_id {
+                new_allocation.account_to_be_debited_id = funding_account_id;
+            }
+            new_allocations.push(new_allocation);
+        }
+    }
+
+    new_allocations
 }
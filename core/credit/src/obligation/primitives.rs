@@ -1,4 +1,8 @@
-use crate::primitives::*;
+use crate::{
+    payment_allocation::PaymentAllocation,
+    primitives::*,
+    terms::{AnnualRatePct, DayCountConvention},
+};
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -10,6 +14,26 @@ pub struct ObligationAccounts {
     pub account_to_be_credited_id: CalaAccountId,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ObligationPenaltyTerms {
+    pub annual_rate: AnnualRatePct,
+    #[serde(default)]
+    pub day_count_convention: DayCountConvention,
+    pub receivable_account_id: CalaAccountId,
+    pub income_account_id: CalaAccountId,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrincipalInstallment {
+    pub reference: String,
+    pub amount: UsdCents,
+    pub due_date: EffectiveDate,
+    pub overdue_date: Option<EffectiveDate>,
+    pub liquidation_date: Option<EffectiveDate>,
+    pub effective: chrono::NaiveDate,
+}
+
 pub struct ObligationDueReallocationData {
     pub tx_id: LedgerTxId,
     pub amount: UsdCents,
@@ -34,6 +58,27 @@ pub struct ObligationDefaultedReallocationData {
     pub effective: chrono::NaiveDate,
 }
 
+pub struct LiquidationShortfallData {
+    pub tx_id: LedgerTxId,
+    pub amount: UsdCents,
+    pub receivable_account_id: CalaAccountId,
+    pub defaulted_account_id: CalaAccountId,
+    pub effective: chrono::NaiveDate,
+}
+
+pub struct LiquidationSurplusData {
+    pub tx_id: LedgerTxId,
+    pub amount: UsdCents,
+    pub effective: chrono::NaiveDate,
+}
+
+pub struct AppliedLiquidationProceeds {
+    pub allocations: Vec<PaymentAllocation>,
+    pub shortfalls: Vec<LiquidationShortfallData>,
+    pub surplus: Option<LiquidationSurplusData>,
+    pub reserved_amount: UsdCents,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ObligationsAmounts {
     pub disbursed: UsdCents,
@@ -8,6 +8,8 @@ use es_entity::*;
 
 use crate::primitives::*;
 
+use super::error::PaymentError;
+
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -18,6 +20,20 @@ pub enum PaymentEvent {
         credit_facility_id: CreditFacilityId,
         amount: UsdCents,
     },
+    UnappliedAmountRecorded {
+        ledger_tx_id: LedgerTxId,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    },
+    UnappliedAmountApplied {
+        payment_allocation_id: PaymentAllocationId,
+        amount: UsdCents,
+    },
+    UnappliedAmountRefunded {
+        ledger_tx_id: LedgerTxId,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    },
 }
 
 #[derive(EsEntity, Builder)]
@@ -46,6 +62,9 @@ impl TryFromEvents<PaymentEvent> for Payment {
                         .credit_facility_id(*credit_facility_id)
                         .amount(*amount)
                 }
+                PaymentEvent::UnappliedAmountRecorded { .. } => (),
+                PaymentEvent::UnappliedAmountApplied { .. } => (),
+                PaymentEvent::UnappliedAmountRefunded { .. } => (),
             }
         }
         builder.events(events).build()
@@ -58,6 +77,109 @@ impl Payment {
             .entity_first_persisted_at()
             .expect("entity_first_persisted_at not found")
     }
+
+    pub fn unapplied_amount(&self) -> UsdCents {
+        self.events
+            .iter_all()
+            .filter_map(|e| match e {
+                PaymentEvent::UnappliedAmountRecorded { amount, .. } => Some(*amount),
+                _ => None,
+            })
+            .fold(UsdCents::ZERO, |acc, amount| acc + amount)
+    }
+
+    pub fn unapplied_remaining(&self) -> UsdCents {
+        self.events
+            .iter_all()
+            .fold(UsdCents::ZERO, |acc, e| match e {
+                PaymentEvent::UnappliedAmountRecorded { amount, .. } => acc + *amount,
+                PaymentEvent::UnappliedAmountApplied { amount, .. }
+                | PaymentEvent::UnappliedAmountRefunded { amount, .. } => acc - *amount,
+                _ => acc,
+            })
+    }
+
+    pub(crate) fn record_unapplied(
+        &mut self,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Idempotent<UnappliedPaymentData> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            PaymentEvent::UnappliedAmountRecorded { .. }
+        );
+        if amount.is_zero() {
+            return Idempotent::Ignored;
+        }
+
+        let tx_id = LedgerTxId::new();
+        self.events.push(PaymentEvent::UnappliedAmountRecorded {
+            ledger_tx_id: tx_id,
+            amount,
+            effective,
+        });
+
+        Idempotent::Executed(UnappliedPaymentData {
+            tx_id,
+            amount,
+            effective,
+        })
+    }
+
+    pub(crate) fn apply_unapplied(
+        &mut self,
+        payment_allocation_id: PaymentAllocationId,
+        amount: UsdCents,
+    ) -> Result<Idempotent<()>, PaymentError> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            PaymentEvent::UnappliedAmountApplied { payment_allocation_id: id, .. }
+                if *id == payment_allocation_id
+        );
+        let remaining = self.unapplied_remaining();
+        if amount > remaining {
+            return Err(PaymentError::AppliedAmountExceedsUnapplied(
+                self.id, amount, remaining,
+            ));
+        }
+
+        self.events.push(PaymentEvent::UnappliedAmountApplied {
+            payment_allocation_id,
+            amount,
+        });
+
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub(crate) fn refund_unapplied(
+        &mut self,
+        effective: chrono::NaiveDate,
+    ) -> Idempotent<UnappliedPaymentData> {
+        let amount = self.unapplied_remaining();
+        if amount.is_zero() {
+            return Idempotent::Ignored;
+        }
+
+        let tx_id = LedgerTxId::new();
+        self.events.push(PaymentEvent::UnappliedAmountRefunded {
+            ledger_tx_id: tx_id,
+            amount,
+            effective,
+        });
+
+        Idempotent::Executed(UnappliedPaymentData {
+            tx_id,
+            amount,
+            effective,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct UnappliedPaymentData {
+    pub tx_id: LedgerTxId,
+    pub amount: UsdCents,
+    pub effective: chrono::NaiveDate,
 }
 
 #[derive(Debug, Builder)]
@@ -86,3 +208,79 @@ impl IntoEvents<PaymentEvent> for NewPayment {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn payment_from(events: Vec<PaymentEvent>) -> Payment {
+        Payment::try_from_events(EntityEvents::init(PaymentId::new(), events)).unwrap()
+    }
+
+    fn initial_events() -> Vec<PaymentEvent> {
+        vec![PaymentEvent::Initialized {
+            id: PaymentId::new(),
+            credit_facility_id: CreditFacilityId::new(),
+            amount: UsdCents::from(1000),
+        }]
+    }
+
+    #[test]
+    fn record_unapplied_is_idempotent() {
+        let mut payment = payment_from(initial_events());
+        let effective = Utc::now().date_naive();
+
+        assert!(
+            payment
+                .record_unapplied(UsdCents::from(100), effective)
+                .did_execute()
+        );
+        assert!(
+            payment
+                .record_unapplied(UsdCents::from(100), effective)
+                .was_ignored()
+        );
+        assert_eq!(payment.unapplied_amount(), UsdCents::from(100));
+    }
+
+    #[test]
+    fn ignores_zero_unapplied() {
+        let mut payment = payment_from(initial_events());
+        assert!(
+            payment
+                .record_unapplied(UsdCents::ZERO, Utc::now().date_naive())
+                .was_ignored()
+        );
+    }
+
+    #[test]
+    fn remaining_reduced_by_application_and_refund() {
+        let mut payment = payment_from(initial_events());
+        let effective = Utc::now().date_naive();
+        let _ = payment.record_unapplied(UsdCents::from(100), effective);
+
+        let _ = payment
+            .apply_unapplied(PaymentAllocationId::new(), UsdCents::from(30))
+            .unwrap();
+        assert_eq!(payment.unapplied_remaining(), UsdCents::from(70));
+
+        let refund = payment.refund_unapplied(effective).unwrap();
+        assert_eq!(refund.amount, UsdCents::from(70));
+        assert_eq!(payment.unapplied_remaining(), UsdCents::ZERO);
+        assert_eq!(payment.unapplied_amount(), UsdCents::from(100));
+
+        assert!(payment.refund_unapplied(effective).was_ignored());
+    }
+
+    #[test]
+    fn errors_when_applying_more_than_unapplied_remaining() {
+        let mut payment = payment_from(initial_events());
+        let _ = payment.record_unapplied(UsdCents::from(100), Utc::now().date_naive());
+
+        assert!(matches!(
+            payment.apply_unapplied(PaymentAllocationId::new(), UsdCents::from(101)),
+            Err(PaymentError::AppliedAmountExceedsUnapplied(..))
+        ));
+        assert_eq!(payment.unapplied_remaining(), UsdCents::from(100));
+    }
+}
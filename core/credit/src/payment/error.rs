@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::primitives::{PaymentId, UsdCents};
+
 #[derive(Error, Debug)]
 pub enum PaymentError {
     #[error("PaymentError - Sqlx: {0}")]
@@ -14,6 +16,8 @@ pub enum PaymentError {
     ObligationError(#[from] crate::obligation::error::ObligationError),
     #[error("PaymentError - PaymentAllocationError: {0}")]
     PaymentAllocationError(#[from] crate::payment_allocation::error::PaymentAllocationError),
+    #[error("PaymentError - AppliedAmountExceedsUnapplied: {1} > {2} for {0}")]
+    AppliedAmountExceedsUnapplied(PaymentId, UsdCents, UsdCents),
 }
 
 es_entity::from_es_entity_error!(PaymentError);
@@ -7,9 +7,10 @@ use std::sync::Arc;
 use audit::AuditSvc;
 use authz::PermissionCheck;
 
-use crate::{CoreCreditAction, CoreCreditObject, primitives::*};
+use crate::{CoreCreditAction, CoreCreditObject, PaymentAllocation, primitives::*};
 
 pub use entity::Payment;
+pub(crate) use entity::UnappliedPaymentData;
 
 #[cfg(feature = "json-schema")]
 pub use entity::PaymentEvent;
@@ -69,4 +70,85 @@ where
 
         Ok(payment)
     }
+
+    pub(super) async fn record_unapplied_in_op(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        payment: &mut Payment,
+        amount: UsdCents,
+        effective: chrono::NaiveDate,
+    ) -> Result<Option<UnappliedPaymentData>, PaymentError> {
+        if let es_entity::Idempotent::Executed(data) = payment.record_unapplied(amount, effective) {
+            self.repo.update_in_op(db, payment).await?;
+            Ok(Some(data))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(super) async fn apply_unapplied_in_op(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        payment: &mut Payment,
+        allocations: &[PaymentAllocation],
+    ) -> Result<(), PaymentError> {
+        let payment_id = payment.id;
+        let mut updated = false;
+        for allocation in allocations.iter().filter(|a| a.payment_id == payment_id) {
+            updated |= payment
+                .apply_unapplied(allocation.id, allocation.amount)?
+                .did_execute();
+        }
+        if updated {
+            self.repo.update_in_op(db, payment).await?;
+        }
+
+        Ok(())
+    }
+
+    pub(super) async fn refund_unapplied_in_op(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        payment: &mut Payment,
+        effective: chrono::NaiveDate,
+    ) -> Result<Option<UnappliedPaymentData>, PaymentError> {
+        if let es_entity::Idempotent::Executed(data) = payment.refund_unapplied(effective) {
+            self.repo.update_in_op(db, payment).await?;
+            Ok(Some(data))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(super) async fn list_with_unapplied_remaining_for_facility(
+        &self,
+        credit_facility_id: CreditFacilityId,
+    ) -> Result<Vec<Payment>, PaymentError> {
+        let mut payments = Vec::new();
+        let mut query = Default::default();
+        loop {
+            let mut res = self
+                .repo
+                .list_for_credit_facility_id_by_created_at(
+                    credit_facility_id,
+                    query,
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+
+            payments.extend(
+                std::mem::take(&mut res.entities)
+                    .into_iter()
+                    .filter(|p| !p.unapplied_remaining().is_zero()),
+            );
+
+            if let Some(q) = res.into_next_query() {
+                query = q;
+            } else {
+                break;
+            };
+        }
+
+        Ok(payments)
+    }
 }
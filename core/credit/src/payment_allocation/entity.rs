@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use es_entity::*;
 
-use crate::primitives::*;
+use crate::{primitives::*, terms::PaymentAllocationStrategy};
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
@@ -24,6 +24,8 @@ pub enum PaymentAllocationEvent {
         amount: UsdCents,
         receivable_account_id: CalaAccountId,
         account_to_be_debited_id: CalaAccountId,
+        #[serde(default)]
+        allocation_strategy: PaymentAllocationStrategy,
         effective: chrono::NaiveDate,
     },
 }
@@ -32,6 +34,7 @@ pub enum PaymentAllocationEvent {
 #[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
 pub struct PaymentAllocation {
     pub id: PaymentAllocationId,
+    pub payment_id: PaymentId,
     pub obligation_id: ObligationId,
     pub payment_allocation_idx: usize,
     pub obligation_type: ObligationType,
@@ -40,6 +43,7 @@ pub struct PaymentAllocation {
     pub amount: UsdCents,
     pub account_to_be_debited_id: CalaAccountId,
     pub receivable_account_id: CalaAccountId,
+    pub allocation_strategy: PaymentAllocationStrategy,
     pub effective: chrono::NaiveDate,
 
     events: EntityEvents<PaymentAllocationEvent>,
@@ -63,6 +67,7 @@ impl TryFromEvents<PaymentAllocationEvent> for PaymentAllocation {
             match event {
                 PaymentAllocationEvent::Initialized {
                     id,
+                    payment_id,
                     obligation_id,
                     payment_allocation_idx,
                     obligation_type,
@@ -71,11 +76,13 @@ impl TryFromEvents<PaymentAllocationEvent> for PaymentAllocation {
                     amount,
                     account_to_be_debited_id,
                     receivable_account_id,
+                    allocation_strategy,
                     effective,
                     ..
                 } => {
                     builder = builder
                         .id(*id)
+                        .payment_id(*payment_id)
                         .obligation_id(*obligation_id)
                         .payment_allocation_idx(*payment_allocation_idx)
                         .obligation_type(*obligation_type)
@@ -84,6 +91,7 @@ impl TryFromEvents<PaymentAllocationEvent> for PaymentAllocation {
                         .amount(*amount)
                         .account_to_be_debited_id(*account_to_be_debited_id)
                         .receivable_account_id(*receivable_account_id)
+                        .allocation_strategy(*allocation_strategy)
                         .effective(*effective)
                 }
             }
@@ -111,6 +119,8 @@ pub struct NewPaymentAllocation {
     pub(crate) credit_facility_id: CreditFacilityId,
     pub(crate) receivable_account_id: CalaAccountId,
     pub(crate) account_to_be_debited_id: CalaAccountId,
+    #[builder(default)]
+    pub(crate) allocation_strategy: PaymentAllocationStrategy,
     pub(crate) effective: chrono::NaiveDate,
     #[builder(setter(into))]
     pub(crate) amount: UsdCents,
@@ -135,6 +145,7 @@ impl IntoEvents<PaymentAllocationEvent> for NewPaymentAllocation {
                 credit_facility_id: self.credit_facility_id,
                 amount: self.amount,
                 account_to_be_debited_id: self.account_to_be_debited_id,
+                allocation_strategy: self.allocation_strategy,
                 effective: self.effective,
                 receivable_account_id: self.receivable_account_id,
             }],
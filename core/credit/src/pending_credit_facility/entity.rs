@@ -14,7 +14,7 @@ use crate::{
         PendingCreditFacilityCreation,
     },
     primitives::*,
-    terms::TermValues,
+    terms::{DisbursalPolicy, TermValues},
 };
 
 use super::error::PendingCreditFacilityError;
@@ -36,14 +36,20 @@ pub enum PendingCreditFacilityEvent {
         amount: UsdCents,
         account_ids: PendingCreditFacilityAccountIds,
         disbursal_credit_account_id: CalaAccountId,
+        #[serde(default)]
+        disbursal_policy: DisbursalPolicy,
     },
     CollateralizationStateChanged {
         collateralization_state: PendingCreditFacilityCollateralizationState,
         collateral: Satoshis,
         price: PriceOfOneBTC,
+        #[serde(default)]
+        price_observation_id: Option<PriceObservationId>,
     },
     CollateralizationRatioChanged {
         collateralization_ratio: CollateralizationRatio,
+        #[serde(default)]
+        price_observation_id: Option<PriceObservationId>,
     },
     Completed {},
 }
@@ -61,6 +67,7 @@ pub struct PendingCreditFacility {
     pub collateral_id: CollateralId,
     pub amount: UsdCents,
     pub terms: TermValues,
+    pub disbursal_policy: DisbursalPolicy,
 
     events: EntityEvents<PendingCreditFacilityEvent>,
 }
@@ -97,19 +104,39 @@ impl PendingCreditFacility {
         PendingCreditFacilityStatus::PendingCollateralization
     }
 
+    /// Whether moving to `price` could change the collateralization state, judging by the
+    /// last recorded collateralization ratio.
+    pub(crate) fn collateralization_could_change(
+        &self,
+        price: PriceOfOneBTC,
+        buffer_cvl_pct: CVLPct,
+    ) -> bool {
+        let cvl = self.last_collateralization_ratio().cvl(price);
+        match self.last_collateralization_state() {
+            PendingCreditFacilityCollateralizationState::FullyCollateralized => {
+                cvl < self.terms.margin_call_cvl + buffer_cvl_pct
+            }
+            PendingCreditFacilityCollateralizationState::UnderCollateralized => {
+                cvl + buffer_cvl_pct >= self.terms.margin_call_cvl
+            }
+        }
+    }
+
     pub(crate) fn update_collateralization(
         &mut self,
-        price: PriceOfOneBTC,
+        price: &PriceObservation,
         balances: PendingCreditFacilityBalanceSummary,
     ) -> Idempotent<Option<PendingCreditFacilityCollateralizationState>> {
         if self.is_completed() {
             return Idempotent::Ignored;
         }
 
-        let ratio_changed = self.update_collateralization_ratio(&balances).did_execute();
+        let ratio_changed = self
+            .update_collateralization_ratio(&balances, price.id)
+            .did_execute();
 
         let is_fully_collateralized =
-            balances.facility_amount_cvl(price) >= self.terms.margin_call_cvl;
+            balances.facility_amount_cvl(price.price) >= self.terms.margin_call_cvl;
 
         let calculated_collateralization_state = if is_fully_collateralized {
             PendingCreditFacilityCollateralizationState::FullyCollateralized
@@ -122,7 +149,8 @@ impl PendingCreditFacility {
                 .push(PendingCreditFacilityEvent::CollateralizationStateChanged {
                     collateralization_state: calculated_collateralization_state,
                     collateral: balances.collateral(),
-                    price,
+                    price: price.price,
+                    price_observation_id: Some(price.id),
                 });
             Idempotent::Executed(Some(calculated_collateralization_state))
         } else if ratio_changed {
@@ -135,6 +163,7 @@ impl PendingCreditFacility {
     fn update_collateralization_ratio(
         &mut self,
         balance: &PendingCreditFacilityBalanceSummary,
+        price_observation_id: PriceObservationId,
     ) -> Idempotent<()> {
         let ratio = balance.current_collateralization_ratio();
 
@@ -145,6 +174,7 @@ impl PendingCreditFacility {
         self.events
             .push(PendingCreditFacilityEvent::CollateralizationRatioChanged {
                 collateralization_ratio: ratio,
+                price_observation_id: Some(price_observation_id),
             });
         Idempotent::Executed(())
     }
@@ -211,6 +241,7 @@ impl PendingCreditFacility {
             .collateral_id(self.collateral_id)
             .terms(self.terms)
             .amount(self.amount)
+            .disbursal_policy(self.disbursal_policy.clone())
             .activated_at(crate::time::now())
             .maturity_date(maturity_date);
 
@@ -271,6 +302,7 @@ impl TryFromEvents<PendingCreditFacilityEvent> for PendingCreditFacility {
                     account_ids,
                     disbursal_credit_account_id,
                     terms,
+                    disbursal_policy,
                     ..
                 } => {
                     builder = builder
@@ -284,6 +316,7 @@ impl TryFromEvents<PendingCreditFacilityEvent> for PendingCreditFacility {
                         .terms(*terms)
                         .account_ids(*account_ids)
                         .disbursal_credit_account_id(*disbursal_credit_account_id)
+                        .disbursal_policy(disbursal_policy.clone())
                 }
                 PendingCreditFacilityEvent::CollateralizationStateChanged { .. } => {}
                 PendingCreditFacilityEvent::CollateralizationRatioChanged { .. } => {}
@@ -315,12 +348,18 @@ pub struct NewPendingCreditFacility {
     disbursal_credit_account_id: CalaAccountId,
     terms: TermValues,
     amount: UsdCents,
+    #[builder(default)]
+    disbursal_policy: DisbursalPolicy,
 }
 
 impl NewPendingCreditFacility {
     pub fn builder() -> NewPendingCreditFacilityBuilder {
         NewPendingCreditFacilityBuilder::default()
     }
+
+    pub(super) fn status(&self) -> PendingCreditFacilityStatus {
+        PendingCreditFacilityStatus::PendingCollateralization
+    }
 }
 
 impl IntoEvents<PendingCreditFacilityEvent> for NewPendingCreditFacility {
@@ -339,6 +378,7 @@ impl IntoEvents<PendingCreditFacilityEvent> for NewPendingCreditFacility {
                 amount: self.amount,
                 account_ids: self.account_ids,
                 disbursal_credit_account_id: self.disbursal_credit_account_id,
+                disbursal_policy: self.disbursal_policy,
             }],
         )
     }
@@ -396,6 +436,7 @@ mod test {
             terms: default_terms(),
             account_ids: PendingCreditFacilityAccountIds::new(),
             disbursal_credit_account_id: CalaAccountId::new(),
+            disbursal_policy: DisbursalPolicy::default(),
         }]
     }
 
@@ -236,10 +236,10 @@ where
             .get_pending_credit_facility_balance(pending_facility.account_ids)
             .await?;
 
-        let price = self.price.usd_cents_per_btc().await?;
+        let price = self.price.current_observation().await?;
 
         if pending_facility
-            .update_collateralization(price, balances)
+            .update_collateralization(&price, balances)
             .did_execute()
         {
             self.repo
@@ -253,8 +253,8 @@ where
 
     pub(super) async fn update_collateralization_from_price(
         &self,
+        price: &PriceObservation,
     ) -> Result<(), PendingCreditFacilityError> {
-        let price = self.price.usd_cents_per_btc().await?;
         let mut has_next_page = true;
         let mut after: Option<PendingCreditFacilitiesByCollateralizationRatioCursor> = None;
         while has_next_page {
@@ -279,7 +279,10 @@ where
             let mut at_least_one = false;
 
             for pending_facility in pending_credit_facilities.entities.iter_mut() {
-                if pending_facility.status() == PendingCreditFacilityStatus::Completed {
+                if pending_facility.status() == PendingCreditFacilityStatus::Completed
+                    || !pending_facility
+                        .collateralization_could_change(price.price, CVLPct::UPGRADE_BUFFER)
+                {
                     continue;
                 }
                 let balances = self
@@ -297,8 +300,6 @@ where
 
             if at_least_one {
                 op.commit().await?;
-            } else {
-                break;
             }
         }
         Ok(())
@@ -365,6 +366,15 @@ where
         self.repo.find_by_id(id.into()).await
     }
 
+    pub(crate) async fn pending_collateralization_exists_for_customer(
+        &self,
+        customer_id: crate::primitives::CustomerId,
+    ) -> Result<bool, PendingCreditFacilityError> {
+        self.repo
+            .pending_collateralization_exists_for_customer(customer_id)
+            .await
+    }
+
     #[instrument(name = "credit.pending_credit_facility.find_by_id", skip(self, sub))]
     pub async fn find_by_id(
         &self,
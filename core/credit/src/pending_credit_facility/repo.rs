@@ -27,6 +27,11 @@ use super::{entity::*, error::PendingCreditFacilityError};
             list_for,
             update(accessor = "last_collateralization_state()")
         ),
+        status(
+            ty = "PendingCreditFacilityStatus",
+            create(accessor = "status()"),
+            update(accessor = "status()")
+        ),
     ),
     tbl_prefix = "core"
 )]
@@ -60,6 +65,23 @@ where
             publisher: publisher.clone(),
         }
     }
+
+    pub async fn pending_collateralization_exists_for_customer(
+        &self,
+        customer_id: CustomerId,
+    ) -> Result<bool, PendingCreditFacilityError> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                SELECT 1 FROM core_pending_credit_facilities
+                WHERE customer_id = $1 AND status = $2
+            ) AS "exists!""#,
+            customer_id as CustomerId,
+            PendingCreditFacilityStatus::PendingCollateralization as PendingCreditFacilityStatus,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(exists)
+    }
 }
 
 mod facility_collateralization_state_sqlx {
@@ -99,3 +121,41 @@ mod facility_collateralization_state_sqlx {
         }
     }
 }
+
+mod pending_facility_status_sqlx {
+    use sqlx::{Type, postgres::*};
+
+    use crate::primitives::PendingCreditFacilityStatus;
+
+    impl Type<Postgres> for PendingCreditFacilityStatus {
+        fn type_info() -> PgTypeInfo {
+            <String as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <String as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl sqlx::Encode<'_, Postgres> for PendingCreditFacilityStatus {
+        fn encode_by_ref(
+            &self,
+            buf: &mut PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            <String as sqlx::Encode<'_, Postgres>>::encode(self.to_string(), buf)
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for PendingCreditFacilityStatus {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let s = <String as sqlx::Decode<Postgres>>::decode(value)?;
+            Ok(s.parse().map_err(|e: strum::ParseError| Box::new(e))?)
+        }
+    }
+
+    impl PgHasArrayType for PendingCreditFacilityStatus {
+        fn array_type_info() -> PgTypeInfo {
+            <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+        }
+    }
+}
@@ -19,7 +19,7 @@ pub use cala_ledger::primitives::{
 pub use core_custody::WalletId as CustodyWalletId;
 pub use core_customer::{CustomerId, CustomerType};
 pub use core_money::*;
-pub use core_price::PriceOfOneBTC;
+pub use core_price::{PriceObservation, PriceObservationId, PriceOfOneBTC};
 pub use governance::ApprovalProcessId;
 pub use public_id::PublicId;
 
@@ -36,7 +36,9 @@ es_entity::entity_id! {
     CollateralId,
     ObligationId,
     LiquidationProcessId,
+    MarginCallId,
     InterestAccrualCycleId,
+    CollateralReturnId,
     TermsTemplateId;
 
     CreditFacilityProposalId => PendingCreditFacilityId,
@@ -47,10 +49,12 @@ es_entity::entity_id! {
     CreditFacilityId => governance::ApprovalProcessId,
     CreditFacilityProposalId => governance::ApprovalProcessId,
     DisbursalId => governance::ApprovalProcessId,
+    CollateralReturnId => governance::ApprovalProcessId,
 
     CreditFacilityId => job::JobId,
     InterestAccrualCycleId => job::JobId,
     ObligationId => job::JobId,
+    MarginCallId => job::JobId,
 
     DisbursalId => LedgerTxId,
     PaymentAllocationId => LedgerTxId,
@@ -73,6 +77,7 @@ pub enum ObligationStatus {
 pub enum ObligationType {
     Disbursal,
     Interest,
+    Penalty,
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
@@ -80,6 +85,7 @@ pub enum ObligationType {
 pub enum BalanceUpdatedType {
     Disbursal,
     InterestAccrual,
+    PenaltyAccrual,
 }
 
 impl From<ObligationType> for BalanceUpdatedType {
@@ -87,6 +93,7 @@ impl From<ObligationType> for BalanceUpdatedType {
         match obligation_type {
             ObligationType::Disbursal => Self::Disbursal,
             ObligationType::Interest => Self::InterestAccrual,
+            ObligationType::Penalty => Self::PenaltyAccrual,
         }
     }
 }
@@ -275,6 +282,14 @@ impl CoreCreditAction {
         CoreCreditAction::CreditFacility(CreditFacilityAction::UpdateCollateral);
     pub const CREDIT_FACILITY_UPDATE_COLLATERALIZATION_STATE: Self =
         CoreCreditAction::CreditFacility(CreditFacilityAction::UpdateCollateralizationState);
+    pub const CREDIT_FACILITY_REFUND_UNAPPLIED_PAYMENTS: Self =
+        CoreCreditAction::CreditFacility(CreditFacilityAction::RefundUnappliedPayments);
+    pub const CREDIT_FACILITY_AMEND_TERMS: Self =
+        CoreCreditAction::CreditFacility(CreditFacilityAction::AmendTerms);
+    pub const CREDIT_FACILITY_PAY_OFF: Self =
+        CoreCreditAction::CreditFacility(CreditFacilityAction::PayOff);
+    pub const CREDIT_FACILITY_RETURN_COLLATERAL: Self =
+        CoreCreditAction::CreditFacility(CreditFacilityAction::ReturnCollateral);
 
     pub const CHART_OF_ACCOUNTS_INTEGRATION_CONFIG_READ: Self =
         CoreCreditAction::ChartOfAccountsIntegrationConfig(
@@ -297,6 +312,10 @@ impl CoreCreditAction {
         CoreCreditAction::Obligation(ObligationAction::RecordPaymentAllocation);
     pub const OBLIGATION_RECORD_PAYMENT_WITH_DATE: Self =
         CoreCreditAction::Obligation(ObligationAction::RecordPaymentAllocationWithDate);
+    pub const OBLIGATION_RECORD_LIQUIDATION_EXECUTION: Self =
+        CoreCreditAction::Obligation(ObligationAction::RecordLiquidationExecution);
+    pub const OBLIGATION_ACCRUE_PENALTY: Self =
+        CoreCreditAction::Obligation(ObligationAction::AccruePenalty);
 
     pub const TERMS_TEMPLATE_CREATE: Self =
         CoreCreditAction::TermsTemplate(TermsTemplateAction::Create);
@@ -375,6 +394,10 @@ pub enum CreditFacilityAction {
     RecordInterest,
     Complete,
     UpdateCollateralizationState,
+    RefundUnappliedPayments,
+    AmendTerms,
+    PayOff,
+    ReturnCollateral,
 }
 
 impl ActionPermission for CreditFacilityAction {
@@ -387,7 +410,11 @@ impl ActionPermission for CreditFacilityAction {
             | Self::UpdateCollateral
             | Self::RecordInterest
             | Self::Complete
-            | Self::UpdateCollateralizationState => PERMISSION_SET_CREDIT_WRITER,
+            | Self::UpdateCollateralizationState
+            | Self::RefundUnappliedPayments
+            | Self::AmendTerms
+            | Self::PayOff
+            | Self::ReturnCollateral => PERMISSION_SET_CREDIT_WRITER,
         }
     }
 }
@@ -451,13 +478,18 @@ pub enum ObligationAction {
     UpdateStatus,
     RecordPaymentAllocation,
     RecordPaymentAllocationWithDate,
+    RecordLiquidationExecution,
+    AccruePenalty,
 }
 
 impl ActionPermission for ObligationAction {
     fn permission_set(&self) -> &'static str {
         match self {
             Self::Read => PERMISSION_SET_CREDIT_VIEWER,
-            Self::UpdateStatus | Self::RecordPaymentAllocation => PERMISSION_SET_CREDIT_WRITER,
+            Self::UpdateStatus
+            | Self::RecordPaymentAllocation
+            | Self::RecordLiquidationExecution
+            | Self::AccruePenalty => PERMISSION_SET_CREDIT_WRITER,
             Self::RecordPaymentAllocationWithDate => PERMISSION_SET_CREDIT_PAYMENT_DATE,
         }
     }
@@ -603,6 +635,18 @@ impl Default for CollateralizationRatio {
     }
 }
 
+impl CollateralizationRatio {
+    /// Estimates the CVL at `price` without the rounding applied when computing it from balances.
+    pub fn cvl(&self, price: PriceOfOneBTC) -> CVLPct {
+        match self {
+            Self::Finite(ratio) => CVLPct::Finite(
+                ratio * Decimal::from(price.into_inner().into_inner()) / Decimal::from(1_000_000),
+            ),
+            Self::Infinite => CVLPct::Infinite,
+        }
+    }
+}
+
 #[derive(
     Debug,
     Default,
@@ -646,6 +690,51 @@ pub enum PendingCreditFacilityCollateralizationState {
     UnderCollateralized,
 }
 
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Eq,
+    strum::Display,
+    strum::EnumString,
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum MarginCallStatus {
+    #[default]
+    Open,
+    Cured,
+    Escalated,
+}
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Eq,
+    strum::Display,
+    strum::EnumString,
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum CollateralReturnStatus {
+    #[default]
+    PendingApproval,
+    Denied,
+    PendingTransfer,
+    AwaitingConfirmation,
+    Confirmed,
+    Failed,
+}
+
 pub struct CollateralUpdate {
     pub tx_id: LedgerTxId,
     pub abs_diff: Satoshis,
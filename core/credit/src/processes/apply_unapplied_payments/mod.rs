@@ -0,0 +1,143 @@
+mod job;
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use outbox::OutboxEventMarker;
+
+use crate::{
+    credit_facility::CreditFacilities,
+    error::CoreCreditError,
+    event::CoreCreditEvent,
+    ledger::CreditLedger,
+    obligation::Obligations,
+    payment::Payments,
+    primitives::{CoreCreditAction, CoreCreditObject, CreditFacilityId},
+};
+
+pub use job::*;
+
+pub struct ApplyUnappliedPayments<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    credit_facilities: Arc<CreditFacilities<Perms, E>>,
+    obligations: Arc<Obligations<Perms, E>>,
+    payments: Arc<Payments<Perms>>,
+    ledger: Arc<CreditLedger>,
+    audit: Arc<Perms::Audit>,
+}
+
+impl<Perms, E> Clone for ApplyUnappliedPayments<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            credit_facilities: self.credit_facilities.clone(),
+            obligations: self.obligations.clone(),
+            payments: self.payments.clone(),
+            ledger: self.ledger.clone(),
+            audit: self.audit.clone(),
+        }
+    }
+}
+
+impl<Perms, E> ApplyUnappliedPayments<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub fn new(
+        credit_facilities: Arc<CreditFacilities<Perms, E>>,
+        obligations: Arc<Obligations<Perms, E>>,
+        payments: Arc<Payments<Perms>>,
+        ledger: Arc<CreditLedger>,
+        audit: Arc<Perms::Audit>,
+    ) -> Self {
+        Self {
+            credit_facilities,
+            obligations,
+            payments,
+            ledger,
+            audit,
+        }
+    }
+
+    #[es_entity::retry_on_concurrent_modification(any_error = true)]
+    #[instrument(name = "credit.apply_unapplied_payments.execute", skip(self))]
+    pub async fn execute(
+        &self,
+        id: impl es_entity::RetryableInto<CreditFacilityId>,
+    ) -> Result<(), CoreCreditError> {
+        let id = id.into();
+        let mut payments = self
+            .payments
+            .list_with_unapplied_remaining_for_facility(id)
+            .await?;
+        if payments.is_empty() {
+            return Ok(());
+        }
+
+        let credit_facility = self.credit_facilities.find_by_id_without_audit(id).await?;
+
+        let mut db = self.credit_facilities.begin_op().await?;
+        self.audit
+            .record_system_entry_in_tx(
+                &mut db,
+                CoreCreditObject::credit_facility(id),
+                CoreCreditAction::OBLIGATION_RECORD_PAYMENT,
+            )
+            .await?;
+
+        let effective = crate::time::now().date_naive();
+        let unapplied = payments
+            .iter()
+            .map(|p| (p.id, p.unapplied_remaining()))
+            .collect::<Vec<_>>();
+        let allocations = self
+            .obligations
+            .allocate_payments_in_op(
+                &mut db,
+                id,
+                &unapplied,
+                Some(credit_facility.account_ids.unapplied_payments_account_id),
+                credit_facility.terms.payment_allocation_strategy,
+                effective,
+            )
+            .await?;
+
+        for payment in payments.iter_mut() {
+            self.payments
+                .apply_unapplied_in_op(&mut db, payment, &allocations)
+                .await?;
+        }
+
+        if allocations.is_empty() {
+            return Ok(());
+        }
+
+        self.ledger
+            .record_payment_allocations(db, allocations)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+mod job;
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use governance::{ApprovalProcessType, GovernanceAction, GovernanceEvent, GovernanceObject};
+use outbox::OutboxEventMarker;
+
+use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+
+use crate::{
+    CoreCreditAction, CoreCreditError, CoreCreditEvent, CoreCreditObject,
+    collateral_return::{CollateralReturn, CollateralReturns},
+    primitives::ApprovalProcessId,
+};
+
+pub use job::*;
+pub const APPROVE_COLLATERAL_RETURN_PROCESS: ApprovalProcessType =
+    ApprovalProcessType::new("collateral-return");
+
+pub struct ApproveCollateralReturn<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    collateral_returns: Arc<CollateralReturns<Perms, E>>,
+}
+
+impl<Perms, E> Clone for ApproveCollateralReturn<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            collateral_returns: self.collateral_returns.clone(),
+        }
+    }
+}
+
+impl<Perms, E> ApproveCollateralReturn<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub fn new(collateral_returns: Arc<CollateralReturns<Perms, E>>) -> Self {
+        Self { collateral_returns }
+    }
+
+    #[es_entity::retry_on_concurrent_modification(any_error = true)]
+    #[instrument(name = "credit_facility.approve_collateral_return", skip(self))]
+    pub async fn execute(
+        &self,
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+    ) -> Result<CollateralReturn, CoreCreditError> {
+        Ok(self
+            .collateral_returns
+            .conclude_approval_process(approval_process_id, approved)
+            .await?)
+    }
+}
@@ -0,0 +1,76 @@
+mod job;
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use governance::{ApprovalProcessType, GovernanceAction, GovernanceEvent, GovernanceObject};
+use outbox::OutboxEventMarker;
+
+use core_custody::{CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+
+use crate::{
+    CoreCreditAction, CoreCreditError, CoreCreditEvent, CoreCreditObject, CreditFacility,
+    credit_facility::CreditFacilities,
+    primitives::{ApprovalProcessId, CreditFacilityId},
+};
+
+pub use job::*;
+pub const APPROVE_CREDIT_FACILITY_TERMS_AMENDMENT_PROCESS: ApprovalProcessType =
+    ApprovalProcessType::new("credit-facility-terms-amendment");
+
+pub struct ApproveTermsAmendment<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    credit_facilities: Arc<CreditFacilities<Perms, E>>,
+}
+
+impl<Perms, E> Clone for ApproveTermsAmendment<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            credit_facilities: self.credit_facilities.clone(),
+        }
+    }
+}
+
+impl<Perms, E> ApproveTermsAmendment<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCreditAction> + From<GovernanceAction> + From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreCreditObject> + From<GovernanceObject> + From<CoreCustodyObject>,
+    E: OutboxEventMarker<GovernanceEvent>
+        + OutboxEventMarker<CoreCreditEvent>
+        + OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub fn new(credit_facilities: Arc<CreditFacilities<Perms, E>>) -> Self {
+        Self { credit_facilities }
+    }
+
+    #[es_entity::retry_on_concurrent_modification(any_error = true)]
+    #[instrument(name = "credit_facility.approve_terms_amendment", skip(self))]
+    pub async fn execute(
+        &self,
+        credit_facility_id: CreditFacilityId,
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+    ) -> Result<CreditFacility, CoreCreditError> {
+        Ok(self
+            .credit_facilities
+            .conclude_terms_amendment(credit_facility_id, approval_process_id, approved)
+            .await?)
+    }
+}
@@ -1,3 +1,6 @@
 pub mod activate_credit_facility;
+pub mod apply_unapplied_payments;
+pub mod approve_collateral_return;
 pub mod approve_credit_facility_proposal;
 pub mod approve_disbursal;
+pub mod approve_terms_amendment;
@@ -18,6 +18,7 @@ use crate::{
     liquidation_process::{
         LiquidationProcess, LiquidationProcessEvent, error::LiquidationProcessError,
     },
+    margin_call::{MarginCall, MarginCallEvent, error::MarginCallError},
     obligation::{Obligation, ObligationEvent, error::ObligationError},
     payment_allocation::{
         PaymentAllocation, PaymentAllocationEvent, error::PaymentAllocationError,
@@ -76,12 +77,24 @@ where
                     id: entity.id,
                     completed_at: event.recorded_at,
                 }),
+                TermsAmended {
+                    old_terms,
+                    new_terms,
+                    new_maturity_date,
+                    ..
+                } => Some(CoreCreditEvent::FacilityTermsAmended {
+                    id: entity.id,
+                    old_terms: *old_terms,
+                    new_terms: *new_terms,
+                    maturity_date: *new_maturity_date,
+                    amended_at: event.recorded_at,
+                }),
                 CollateralizationStateChanged {
                     collateralization_state: state,
                     collateral,
                     outstanding,
                     price,
-                    ..
+                    price_observation_id,
                 } => Some(CoreCreditEvent::FacilityCollateralizationChanged {
                     id: entity.id,
                     state: *state,
@@ -90,6 +103,7 @@ where
                     collateral: *collateral,
                     outstanding: *outstanding,
                     price: *price,
+                    price_observation_id: *price_observation_id,
                 }),
 
                 _ => None,
@@ -153,6 +167,18 @@ where
                     action,
                     ledger_tx_id,
                     ..
+                }
+                | UpdatedViaLiquidation {
+                    abs_diff,
+                    action,
+                    ledger_tx_id,
+                    ..
+                }
+                | ReleasedOnCompletion {
+                    abs_diff,
+                    action,
+                    ledger_tx_id,
+                    ..
                 } => Some(CoreCreditEvent::FacilityCollateralUpdated {
                     ledger_tx_id: *ledger_tx_id,
                     abs_diff: *abs_diff,
@@ -316,6 +342,28 @@ where
                     credit_facility_id: entity.credit_facility_id,
                     amount: *amount,
                 }),
+                PrincipalInstallmentScheduled {
+                    installment_obligation_id,
+                    amount,
+                    ..
+                } => Some(CoreCreditEvent::ObligationPrincipalInstallmentScheduled {
+                    id: entity.id,
+                    installment_obligation_id: *installment_obligation_id,
+                    credit_facility_id: entity.credit_facility_id,
+                    amount: *amount,
+                }),
+                Rescheduled {
+                    due_date,
+                    overdue_date,
+                    defaulted_date,
+                    ..
+                } => Some(CoreCreditEvent::ObligationRescheduled {
+                    id: entity.id,
+                    credit_facility_id: entity.credit_facility_id,
+                    due_at: *due_date,
+                    overdue_at: *overdue_date,
+                    defaulted_at: *defaulted_date,
+                }),
                 Completed { .. } => Some(CoreCreditEvent::ObligationCompleted {
                     id: entity.id,
                     credit_facility_id: entity.credit_facility_id,
@@ -355,10 +403,85 @@ where
                     ledger_tx_id: *ledger_tx_id,
                     recorded_at: event.recorded_at,
                 },
-                Completed { .. } => CoreCreditEvent::LiquidationProcessConcluded {
+                CollateralSold {
+                    ledger_tx_id,
+                    collateral_sold,
+                    proceeds,
+                    execution_price,
+                    effective,
+                } => CoreCreditEvent::LiquidationCollateralSold {
+                    id: entity.id,
+                    obligation_id: entity.obligation_id,
+                    credit_facility_id: entity.credit_facility_id,
+                    ledger_tx_id: *ledger_tx_id,
+                    collateral_sold: *collateral_sold,
+                    proceeds: *proceeds,
+                    execution_price: *execution_price,
+                    recorded_at: event.recorded_at,
+                    effective: *effective,
+                },
+                Completed {
+                    amount_applied,
+                    shortfall,
+                    surplus,
+                    ..
+                } => CoreCreditEvent::LiquidationProcessConcluded {
                     id: entity.id,
                     obligation_id: entity.obligation_id,
                     credit_facility_id: entity.credit_facility_id,
+                    amount_applied: *amount_applied,
+                    shortfall: *shortfall,
+                    surplus: *surplus,
+                },
+            })
+            .collect::<Vec<_>>();
+        self.outbox
+            .publish_all_persisted(op, publish_events)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn publish_margin_call(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        entity: &MarginCall,
+        new_events: es_entity::LastPersisted<'_, MarginCallEvent>,
+    ) -> Result<(), MarginCallError> {
+        use MarginCallEvent::*;
+        let publish_events = new_events
+            .map(|event| match &event.event {
+                Initialized {
+                    id,
+                    credit_facility_id,
+                    collateral_required,
+                    repayment_required,
+                    cure_deadline,
+                    effective,
+                    ..
+                } => CoreCreditEvent::MarginCallOpened {
+                    id: *id,
+                    credit_facility_id: *credit_facility_id,
+                    collateral_required: *collateral_required,
+                    repayment_required: *repayment_required,
+                    cure_deadline: *cure_deadline,
+                    recorded_at: event.recorded_at,
+                    effective: *effective,
+                },
+                Cured { effective } => CoreCreditEvent::MarginCallCured {
+                    id: entity.id,
+                    credit_facility_id: entity.credit_facility_id,
+                    recorded_at: event.recorded_at,
+                    effective: *effective,
+                },
+                Escalated {
+                    obligation_ids,
+                    effective,
+                } => CoreCreditEvent::MarginCallEscalated {
+                    id: entity.id,
+                    credit_facility_id: entity.credit_facility_id,
+                    obligation_ids: obligation_ids.clone(),
+                    recorded_at: event.recorded_at,
+                    effective: *effective,
                 },
             })
             .collect::<Vec<_>>();
@@ -7,6 +7,7 @@ use crate::primitives::*;
 pub enum RepaymentType {
     Disbursal,
     Interest,
+    Penalty,
 }
 
 impl From<&ObligationType> for RepaymentType {
@@ -14,10 +15,23 @@ impl From<&ObligationType> for RepaymentType {
         match value {
             ObligationType::Disbursal => Self::Disbursal,
             ObligationType::Interest => Self::Interest,
+            ObligationType::Penalty => Self::Penalty,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RepaymentInstallment {
+    pub principal: UsdCents,
+    pub interest: UsdCents,
+}
+
+impl RepaymentInstallment {
+    pub fn total(&self) -> UsdCents {
+        self.principal + self.interest
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub struct CreditFacilityRepaymentPlanEntry {
@@ -34,6 +48,9 @@ pub struct CreditFacilityRepaymentPlanEntry {
 
     pub recorded_at: DateTime<Utc>,
     pub effective: chrono::NaiveDate,
+
+    #[serde(default)]
+    pub installment: Option<RepaymentInstallment>,
 }
 
 impl PartialOrd for CreditFacilityRepaymentPlanEntry {
@@ -48,6 +65,12 @@ impl Ord for CreditFacilityRepaymentPlanEntry {
             match (self.repayment_type, other.repayment_type) {
                 (RepaymentType::Interest, RepaymentType::Disbursal) => std::cmp::Ordering::Less,
                 (RepaymentType::Disbursal, RepaymentType::Interest) => std::cmp::Ordering::Greater,
+                (RepaymentType::Penalty, RepaymentType::Interest | RepaymentType::Disbursal) => {
+                    std::cmp::Ordering::Less
+                }
+                (RepaymentType::Interest | RepaymentType::Disbursal, RepaymentType::Penalty) => {
+                    std::cmp::Ordering::Greater
+                }
                 _ => std::cmp::Ordering::Equal,
             }
         })
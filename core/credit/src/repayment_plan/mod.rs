@@ -19,6 +19,8 @@ pub struct CreditFacilityRepaymentPlan {
     activated_at: Option<DateTime<Utc>>,
     last_interest_accrual_at: Option<DateTime<Utc>>,
     last_updated_on_sequence: EventSequence,
+    #[serde(default)]
+    obligations: Vec<CreditFacilityRepaymentPlanEntry>,
 
     pub entries: Vec<CreditFacilityRepaymentPlanEntry>,
 }
@@ -29,6 +31,10 @@ impl CreditFacilityRepaymentPlan {
     }
 
     fn existing_obligations(&self) -> Vec<CreditFacilityRepaymentPlanEntry> {
+        if !self.obligations.is_empty() {
+            return self.obligations.clone();
+        }
+
         self.entries
             .iter()
             .filter(|entry| entry.is_not_upcoming())
@@ -59,6 +65,7 @@ impl CreditFacilityRepaymentPlan {
                 defaulted_at: None,
                 recorded_at: activated_at,
                 effective: activated_at.date_naive(),
+                installment: None,
             })
         }
         disbursals.push(CreditFacilityRepaymentPlanEntry {
@@ -74,14 +81,15 @@ impl CreditFacilityRepaymentPlan {
             defaulted_at: None,
             recorded_at: activated_at,
             effective: activated_at.date_naive(),
+            installment: None,
         });
 
         disbursals
     }
 
-    fn planned_interest_accruals(
+    fn planned_repayments(
         &self,
-        updated_entries: &[CreditFacilityRepaymentPlanEntry],
+        updated_entries: &mut [CreditFacilityRepaymentPlanEntry],
     ) -> Vec<CreditFacilityRepaymentPlanEntry> {
         let terms = self.terms.expect("Missing FacilityCreated event");
         let activated_at = self.activated_at();
@@ -101,7 +109,13 @@ impl CreditFacilityRepaymentPlan {
                     .truncate(maturity_date.start_of_day())
             };
 
-        let disbursed_outstanding = updated_entries
+        let mut periods = vec![];
+        while let Some(period) = next_interest_period {
+            next_interest_period = period.next().truncate(maturity_date.start_of_day());
+            periods.push(period);
+        }
+
+        let mut disbursed_outstanding = updated_entries
             .iter()
             .filter_map(|entry| match entry {
                 CreditFacilityRepaymentPlanEntry {
@@ -113,30 +127,115 @@ impl CreditFacilityRepaymentPlan {
             })
             .fold(UsdCents::ZERO, |acc, outstanding| acc + outstanding);
 
-        let mut planned_interest_entries = vec![];
-        while let Some(period) = next_interest_period {
-            let interest = terms
-                .annual_rate
-                .interest_for_time_period(disbursed_outstanding, period.days());
+        let mut planned_entries = vec![];
+        let n_periods = periods.len();
+        for (idx, period) in periods.into_iter().enumerate() {
+            let due_at = EffectiveDate::from(period.end);
+            let interest = terms.interest_for_period(disbursed_outstanding, &period);
 
-            planned_interest_entries.push(CreditFacilityRepaymentPlanEntry {
+            planned_entries.push(CreditFacilityRepaymentPlanEntry {
                 repayment_type: RepaymentType::Interest,
                 obligation_id: None,
                 status: RepaymentStatus::Upcoming,
                 initial: interest,
                 outstanding: interest,
 
-                due_at: EffectiveDate::from(period.end),
+                due_at,
                 overdue_at: None,
                 defaulted_at: None,
                 recorded_at: period.end,
                 effective: period.end.date_naive(),
+                installment: None,
             });
 
-            next_interest_period = period.next().truncate(maturity_date.start_of_day());
+            let Some(schedule) = terms.amortization else {
+                continue;
+            };
+            let principal_to_amortize = updated_entries
+                .iter()
+                .filter(|entry| Self::is_amortizable_by(entry, due_at))
+                .fold(UsdCents::ZERO, |acc, entry| acc + entry.outstanding);
+            let principal = schedule.principal_installment_for_period(
+                principal_to_amortize,
+                interest,
+                (n_periods - idx) as u32,
+                &period,
+            );
+            if principal.is_zero() || principal_to_amortize.is_zero() {
+                continue;
+            }
+
+            let mut remaining = principal;
+            for entry in updated_entries
+                .iter_mut()
+                .filter(|entry| Self::is_amortizable_by(entry, due_at))
+            {
+                let carved = std::cmp::min(remaining, entry.outstanding);
+                entry.initial -= carved;
+                entry.outstanding -= carved;
+                remaining -= carved;
+            }
+            disbursed_outstanding -= principal;
+
+            planned_entries.push(CreditFacilityRepaymentPlanEntry {
+                repayment_type: RepaymentType::Disbursal,
+                obligation_id: None,
+                status: RepaymentStatus::Upcoming,
+                initial: principal,
+                outstanding: principal,
+
+                due_at,
+                overdue_at: None,
+                defaulted_at: None,
+                recorded_at: period.end,
+                effective: period.end.date_naive(),
+                installment: None,
+            });
+        }
+
+        planned_entries
+    }
+
+    fn is_amortizable_by(entry: &CreditFacilityRepaymentPlanEntry, due_at: EffectiveDate) -> bool {
+        entry.repayment_type == RepaymentType::Disbursal
+            && matches!(
+                entry.status,
+                RepaymentStatus::Upcoming | RepaymentStatus::NotYetDue
+            )
+            && entry.due_at > due_at
+            && !entry.outstanding.is_zero()
+    }
+
+    fn split_installments(&mut self) {
+        if self.terms.and_then(|terms| terms.amortization).is_none() {
+            return;
         }
 
-        planned_interest_entries
+        let entries = self.entries.clone();
+        for entry in self.entries.iter_mut() {
+            if entry.repayment_type == RepaymentType::Penalty {
+                continue;
+            }
+
+            let installment = entries
+                .iter()
+                .filter(|other| other.due_at == entry.due_at)
+                .fold(
+                    RepaymentInstallment {
+                        principal: UsdCents::ZERO,
+                        interest: UsdCents::ZERO,
+                    },
+                    |mut installment, other| {
+                        match other.repayment_type {
+                            RepaymentType::Disbursal => installment.principal += other.initial,
+                            RepaymentType::Interest => installment.interest += other.initial,
+                            RepaymentType::Penalty => (),
+                        }
+                        installment
+                    },
+                );
+            entry.installment = Some(installment);
+        }
     }
 
     pub(super) fn process_event(
@@ -156,6 +255,9 @@ impl CreditFacilityRepaymentPlan {
             CoreCreditEvent::FacilityActivated { activated_at, .. } => {
                 self.activated_at = Some(*activated_at);
             }
+            CoreCreditEvent::FacilityTermsAmended { new_terms, .. } => {
+                self.terms = Some(*new_terms);
+            }
             CoreCreditEvent::ObligationCreated {
                 id,
                 obligation_type,
@@ -180,6 +282,7 @@ impl CreditFacilityRepaymentPlan {
                     defaulted_at: defaulted_at.map(EffectiveDate::from),
                     recorded_at: *recorded_at,
                     effective: *effective,
+                    installment: None,
                 };
                 if *obligation_type == ObligationType::Interest {
                     let effective = EffectiveDate::from(*effective);
@@ -208,6 +311,7 @@ impl CreditFacilityRepaymentPlan {
                     defaulted_at: None,
                     recorded_at: *recorded_at,
                     effective: *effective,
+                    installment: None,
                 };
 
                 let effective = EffectiveDate::from(*effective);
@@ -228,6 +332,43 @@ impl CreditFacilityRepaymentPlan {
                     return false;
                 }
             }
+            CoreCreditEvent::ObligationPrincipalInstallmentScheduled {
+                id: obligation_id,
+                amount,
+                ..
+            } => {
+                if let Some(idx) = existing_obligations
+                    .iter()
+                    .position(|entry| entry.obligation_id == Some(*obligation_id))
+                {
+                    let entry = &mut existing_obligations[idx];
+                    entry.initial -= *amount;
+                    entry.outstanding -= *amount;
+                    // a fully split obligation lives on in its installments only
+                    if entry.initial.is_zero() {
+                        existing_obligations.remove(idx);
+                    }
+                } else {
+                    return false;
+                }
+            }
+            CoreCreditEvent::ObligationRescheduled {
+                id: obligation_id,
+                due_at,
+                overdue_at,
+                defaulted_at,
+                ..
+            } => {
+                if let Some(entry) = existing_obligations.iter_mut().find_map(|entry| {
+                    (entry.obligation_id == Some(*obligation_id)).then_some(entry)
+                }) {
+                    entry.due_at = *due_at;
+                    entry.overdue_at = *overdue_at;
+                    entry.defaulted_at = *defaulted_at;
+                } else {
+                    return false;
+                }
+            }
             CoreCreditEvent::ObligationDue {
                 id: obligation_id, ..
             }
@@ -258,19 +399,18 @@ impl CreditFacilityRepaymentPlan {
             _ => return false,
         };
 
-        let updated_entries = if !existing_obligations.is_empty() {
+        self.obligations = existing_obligations.clone();
+        let mut updated_entries = if !existing_obligations.is_empty() {
             existing_obligations
         } else {
             self.planned_disbursals()
         };
 
-        let planned_interest_entries = self.planned_interest_accruals(&updated_entries);
+        let planned_entries = self.planned_repayments(&mut updated_entries);
 
-        self.entries = updated_entries
-            .into_iter()
-            .chain(planned_interest_entries)
-            .collect();
+        self.entries = updated_entries.into_iter().chain(planned_entries).collect();
         self.entries.sort();
+        self.split_installments();
 
         true
     }
@@ -282,7 +422,10 @@ mod tests {
 
     use rust_decimal_macros::dec;
 
-    use crate::terms::{FacilityDuration, InterestInterval, ObligationDuration, OneTimeFeeRatePct};
+    use crate::terms::{
+        AmortizationSchedule, FacilityDuration, InterestInterval, ObligationDuration,
+        OneTimeFeeRatePct,
+    };
 
     use super::*;
 
@@ -388,6 +531,10 @@ mod tests {
                     repayment_type: RepaymentType::Interest,
                     ..
                 } => res.interest_unpaid += 1,
+                CreditFacilityRepaymentPlanEntry {
+                    repayment_type: RepaymentType::Penalty,
+                    ..
+                } => (),
             }
         }
 
@@ -437,6 +584,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_terms_amended_to_extend_maturity() {
+        let mut plan = initial_plan_no_structuring_fee();
+
+        let mut new_terms = terms(0);
+        new_terms.duration = FacilityDuration::Months(6);
+        let events = vec![
+            CoreCreditEvent::FacilityActivated {
+                id: CreditFacilityId::new(),
+                activation_tx_id: LedgerTxId::new(),
+                activated_at: default_start_date(),
+                amount: default_facility_amount(),
+            },
+            CoreCreditEvent::FacilityTermsAmended {
+                id: CreditFacilityId::new(),
+                old_terms: terms(0),
+                new_terms,
+                maturity_date: new_terms.maturity_date(default_start_date()),
+                amended_at: default_start_date(),
+            },
+        ];
+        process_events(&mut plan, events);
+
+        let counts = count_entries(&plan);
+        assert_eq!(
+            counts,
+            EntriesCount {
+                interest_unpaid: 0,
+                interest_paid: 0,
+                interest_upcoming: 7,
+                disbursals_unpaid: 0,
+                disbursals_paid: 0,
+                disbursals_upcoming: 1,
+            }
+        );
+    }
+
     #[test]
     fn with_zero_structuring_fee_and_first_accrual() {
         let mut plan = initial_plan_no_structuring_fee();
@@ -882,4 +1066,143 @@ mod tests {
             }
         );
     }
+
+    fn amortizing_terms() -> TermValues {
+        let mut terms = terms(0);
+        terms.amortization = Some(AmortizationSchedule::StraightLine);
+        terms
+    }
+
+    fn upcoming_principal(plan: &CreditFacilityRepaymentPlan) -> Vec<UsdCents> {
+        plan.entries
+            .iter()
+            .filter(|e| {
+                e.repayment_type == RepaymentType::Disbursal
+                    && e.status == RepaymentStatus::Upcoming
+            })
+            .map(|e| e.initial)
+            .collect()
+    }
+
+    #[test]
+    fn amortizing_facility_projects_principal_installments() {
+        let mut plan = plan(amortizing_terms());
+        process_events(
+            &mut plan,
+            vec![CoreCreditEvent::FacilityActivated {
+                id: CreditFacilityId::new(),
+                activation_tx_id: LedgerTxId::new(),
+                activated_at: default_start_date(),
+                amount: default_facility_amount(),
+            }],
+        );
+
+        assert_eq!(
+            upcoming_principal(&plan),
+            vec![UsdCents::from(250_000_00); 4]
+        );
+
+        let first_due_at = plan.entries[0].due_at;
+        let first_interest = plan
+            .entries
+            .iter()
+            .find(|e| e.due_at == first_due_at && e.repayment_type == RepaymentType::Interest)
+            .unwrap()
+            .initial;
+        assert_eq!(
+            plan.entries[0].installment,
+            Some(RepaymentInstallment {
+                principal: UsdCents::from(250_000_00),
+                interest: first_interest,
+            })
+        );
+    }
+
+    #[test]
+    fn amortizing_facility_with_principal_installment_scheduled() {
+        let mut plan = plan(amortizing_terms());
+
+        let first_period = InterestInterval::EndOfMonth.period_from(default_start_date());
+        let maturity_date = amortizing_terms().maturity_date(default_start_date());
+        let disbursal_obligation_id = ObligationId::new();
+        let installment_obligation_id = ObligationId::new();
+        let events = vec![
+            CoreCreditEvent::FacilityActivated {
+                id: CreditFacilityId::new(),
+                activation_tx_id: LedgerTxId::new(),
+                activated_at: default_start_date(),
+                amount: default_facility_amount(),
+            },
+            CoreCreditEvent::ObligationCreated {
+                id: disbursal_obligation_id,
+                obligation_type: ObligationType::Disbursal,
+                credit_facility_id: CreditFacilityId::new(),
+                amount: UsdCents::from(100_000_00),
+                due_at: maturity_date,
+                overdue_at: None,
+                defaulted_at: None,
+                recorded_at: default_start_date(),
+                effective: default_start_date().date_naive(),
+            },
+            CoreCreditEvent::ObligationCreated {
+                id: ObligationId::new(),
+                obligation_type: ObligationType::Interest,
+                credit_facility_id: CreditFacilityId::new(),
+                amount: UsdCents::from(1_000_00),
+                due_at: EffectiveDate::from(first_period.end),
+                overdue_at: None,
+                defaulted_at: None,
+                recorded_at: first_period.end,
+                effective: first_period.end.date_naive(),
+            },
+            CoreCreditEvent::ObligationPrincipalInstallmentScheduled {
+                id: disbursal_obligation_id,
+                installment_obligation_id,
+                credit_facility_id: CreditFacilityId::new(),
+                amount: UsdCents::from(25_000_00),
+            },
+            CoreCreditEvent::ObligationCreated {
+                id: installment_obligation_id,
+                obligation_type: ObligationType::Disbursal,
+                credit_facility_id: CreditFacilityId::new(),
+                amount: UsdCents::from(25_000_00),
+                due_at: EffectiveDate::from(first_period.end),
+                overdue_at: None,
+                defaulted_at: None,
+                recorded_at: first_period.end,
+                effective: first_period.end.date_naive(),
+            },
+            CoreCreditEvent::ObligationDue {
+                id: installment_obligation_id,
+                credit_facility_id: CreditFacilityId::new(),
+                obligation_type: ObligationType::Disbursal,
+                amount: UsdCents::from(25_000_00),
+            },
+        ];
+        process_events(&mut plan, events);
+
+        assert_eq!(
+            upcoming_principal(&plan),
+            vec![UsdCents::from(25_000_00); 2]
+        );
+        let disbursal = plan
+            .entries
+            .iter()
+            .find(|e| e.obligation_id == Some(disbursal_obligation_id))
+            .unwrap();
+        assert_eq!(disbursal.initial, UsdCents::from(25_000_00));
+        let installment = plan
+            .entries
+            .iter()
+            .find(|e| e.obligation_id == Some(installment_obligation_id))
+            .unwrap();
+        assert_eq!(installment.status, RepaymentStatus::Due);
+        assert_eq!(
+            installment.installment,
+            Some(RepaymentInstallment {
+                principal: UsdCents::from(25_000_00),
+                interest: UsdCents::from(1_000_00),
+            })
+        );
+    }
 }
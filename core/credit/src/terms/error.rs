@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::primitives::CVLPct;
+use crate::primitives::{CVLPct, UsdCents};
 
 #[derive(Error, Debug)]
 pub enum TermsError {
@@ -19,6 +19,12 @@ pub enum TermsError {
         "TermsError - MarginCallBelowLiquidationLimit: margin_call_cvl {0} <= liquidation_cvl {1}"
     )]
     MarginCallBelowLiquidationLimit(CVLPct, CVLPct),
+    #[error("TermsError - EmptyDisbursalSchedule")]
+    EmptyDisbursalSchedule,
+    #[error("TermsError - ZeroScheduledDisbursal")]
+    ZeroScheduledDisbursal,
+    #[error("TermsError - ScheduledDisbursalsExceedFacilityAmount: {0} > {1}")]
+    ScheduledDisbursalsExceedFacilityAmount(UsdCents, UsdCents),
     #[error("TermsError - UninitializedField: {0}")]
     UninitializedField(#[from] derive_builder::UninitializedFieldError),
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Datelike, TimeZone, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
 use derive_builder::{Builder, UninitializedFieldError};
 use rust_decimal::{Decimal, prelude::*};
 use rust_decimal_macros::dec;
@@ -18,7 +18,8 @@ use crate::{
 
 use super::error::TermsError;
 
-const NUMBER_OF_DAYS_IN_YEAR: u64 = 365;
+const NUMBER_OF_DAYS_IN_YEAR: u32 = 365;
+const NUMBER_OF_DAYS_IN_BANKING_YEAR: u32 = 360;
 const SHORT_TERM_DURATION_MONTHS_THRESHOLD: u32 = 12;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -30,8 +31,30 @@ async_graphql::scalar!(AnnualRatePct);
 
 impl AnnualRatePct {
     pub fn interest_for_time_period(&self, principal: UsdCents, days: u32) -> UsdCents {
-        let cents = principal.to_usd() * Decimal::from(days) * self.0
-            / Decimal::from(NUMBER_OF_DAYS_IN_YEAR);
+        self.interest_for_day_counts(principal, [(days, NUMBER_OF_DAYS_IN_YEAR)])
+    }
+
+    pub fn interest_for_days(
+        &self,
+        principal: UsdCents,
+        first_day: NaiveDate,
+        last_day: NaiveDate,
+        convention: DayCountConvention,
+    ) -> UsdCents {
+        self.interest_for_day_counts(principal, convention.day_counts(first_day, last_day))
+    }
+
+    fn interest_for_day_counts(
+        &self,
+        principal: UsdCents,
+        day_counts: impl IntoIterator<Item = (u32, u32)>,
+    ) -> UsdCents {
+        let cents = day_counts
+            .into_iter()
+            .map(|(days, days_in_year)| {
+                principal.to_usd() * Decimal::from(days) * self.0 / Decimal::from(days_in_year)
+            })
+            .fold(Decimal::ZERO, |acc, cents| acc + cents);
 
         UsdCents::from(
             cents
@@ -174,6 +197,22 @@ impl InterestPeriod {
     pub fn days(&self) -> u32 {
         self.end.day() - self.start.day() + 1
     }
+
+    /// Share of `amount` attributable to the days covered by the period, for a
+    /// period starting part way through its interval.
+    pub fn prorate(&self, amount: UsdCents) -> UsdCents {
+        let interval_days = match self.interval {
+            InterestInterval::EndOfMonth => self.interval.end_date_starting_at(self.start).day(),
+            InterestInterval::EndOfDay => 1,
+        };
+        if self.days() >= interval_days {
+            return amount;
+        }
+
+        UsdCents::from(
+            (amount.into_inner() * u64::from(self.days())).div_ceil(u64::from(interval_days)),
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -222,6 +261,209 @@ impl InterestInterval {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DayCountConvention {
+    #[default]
+    Actual365Fixed,
+    Actual360,
+    ActualActual,
+    Thirty360,
+}
+
+impl DayCountConvention {
+    /// Days counted for the accrual from `first_day` through `last_day` (inclusive), split
+    /// into `(days, days_in_year)` pairs so that leap years can be weighted separately.
+    fn day_counts(&self, first_day: NaiveDate, last_day: NaiveDate) -> Vec<(u32, u32)> {
+        let end = last_day.succ_opt().expect("should return the next day");
+        let actual_days = |start: NaiveDate, end: NaiveDate| {
+            u32::try_from((end - start).num_days()).expect("should not be a negative period")
+        };
+
+        match self {
+            Self::Actual365Fixed => vec![(actual_days(first_day, end), NUMBER_OF_DAYS_IN_YEAR)],
+            Self::Actual360 => vec![(actual_days(first_day, end), NUMBER_OF_DAYS_IN_BANKING_YEAR)],
+            Self::ActualActual => {
+                let mut day_counts = vec![];
+                let mut start = first_day;
+                while start < end {
+                    let next_year = NaiveDate::from_ymd_opt(start.year() + 1, 1, 1)
+                        .expect("should return a valid date");
+                    let days_in_year = next_year
+                        .pred_opt()
+                        .expect("should return a valid date")
+                        .ordinal();
+                    let period_end = next_year.min(end);
+                    day_counts.push((actual_days(start, period_end), days_in_year));
+                    start = period_end;
+                }
+                day_counts
+            }
+            Self::Thirty360 => {
+                let start_day = first_day.day().min(30);
+                let end_day = if end.day() == 31 && start_day == 30 {
+                    30
+                } else {
+                    end.day()
+                };
+                let days = 360 * (end.year() - first_day.year())
+                    + 30 * (end.month() as i32 - first_day.month() as i32)
+                    + (end_day as i32 - start_day as i32);
+                vec![(
+                    u32::try_from(days).expect("should not be a negative period"),
+                    NUMBER_OF_DAYS_IN_BANKING_YEAR,
+                )]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentAllocationStrategy {
+    #[default]
+    InterestFirst,
+    OverdueFirst,
+    PrincipalFirst,
+    DefaultedLast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AmortizationSchedule {
+    Annuity,
+    StraightLine,
+}
+
+impl AmortizationSchedule {
+    /// Principal to fall due at the end of the current cycle, given the principal not yet
+    /// scheduled, the interest of the cycle and the number of cycles left including this one.
+    pub fn principal_installment(
+        &self,
+        principal_outstanding: UsdCents,
+        interest: UsdCents,
+        remaining_installments: u32,
+    ) -> UsdCents {
+        if remaining_installments <= 1 || principal_outstanding.is_zero() {
+            return principal_outstanding;
+        }
+
+        let principal = principal_outstanding.into_inner();
+        let straight_line = principal.div_ceil(u64::from(remaining_installments));
+        match self {
+            Self::StraightLine => UsdCents::from(straight_line),
+            Self::Annuity if interest.is_zero() => UsdCents::from(straight_line),
+            Self::Annuity => {
+                let principal = Decimal::from(principal);
+                let interest = Decimal::from(interest.into_inner());
+                let rate = interest / principal;
+                let growth = (0..remaining_installments)
+                    .fold(Decimal::ONE, |acc, _| acc * (Decimal::ONE + rate));
+                let installment = principal * rate * growth / (growth - Decimal::ONE);
+
+                let principal_part = (installment - interest)
+                    .round_dp_with_strategy(0, RoundingStrategy::AwayFromZero)
+                    .max(Decimal::ZERO)
+                    .min(principal);
+                UsdCents::from(
+                    principal_part
+                        .to_u64()
+                        .expect("should return a valid integer"),
+                )
+            }
+        }
+    }
+
+    /// As `principal_installment`, prorated when the cycle starts part way through its
+    /// interval. The final installment always settles the remaining principal.
+    pub fn principal_installment_for_period(
+        &self,
+        principal_outstanding: UsdCents,
+        interest: UsdCents,
+        remaining_installments: u32,
+        period: &InterestPeriod,
+    ) -> UsdCents {
+        let installment =
+            self.principal_installment(principal_outstanding, interest, remaining_installments);
+        if remaining_installments <= 1 {
+            return installment;
+        }
+        period.prorate(installment)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct ScheduledDisbursal {
+    pub date: NaiveDate,
+    pub amount: UsdCents,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum DisbursalPolicy {
+    #[default]
+    OnDemand,
+    FullAtActivation,
+    Scheduled(Vec<ScheduledDisbursal>),
+}
+
+impl DisbursalPolicy {
+    pub fn validate(&self, facility_amount: UsdCents) -> Result<(), TermsError> {
+        let Self::Scheduled(schedule) = self else {
+            return Ok(());
+        };
+
+        if schedule.is_empty() {
+            return Err(TermsError::EmptyDisbursalSchedule);
+        }
+        if schedule.iter().any(|d| d.amount.is_zero()) {
+            return Err(TermsError::ZeroScheduledDisbursal);
+        }
+
+        let total = schedule
+            .iter()
+            .fold(UsdCents::ZERO, |acc, d| acc + d.amount);
+        if total > facility_amount {
+            return Err(TermsError::ScheduledDisbursalsExceedFacilityAmount(
+                total,
+                facility_amount,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Drawdowns to be executed automatically, ordered by date. A full disbursal
+    /// draws everything left after the structuring fee on the activation date.
+    pub fn drawdowns(
+        &self,
+        activated_at: DateTime<Utc>,
+        disbursable: UsdCents,
+    ) -> Vec<ScheduledDisbursal> {
+        match self {
+            Self::OnDemand => vec![],
+            Self::FullAtActivation if disbursable.is_zero() => vec![],
+            Self::FullAtActivation => vec![ScheduledDisbursal {
+                date: activated_at.date_naive(),
+                amount: disbursable,
+            }],
+            Self::Scheduled(schedule) => {
+                let mut schedule = schedule.clone();
+                schedule.sort_by_key(|d| d.date);
+                schedule
+            }
+        }
+    }
+}
+
 #[derive(Builder, Debug, Serialize, Deserialize, Clone, Copy)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[builder(build_fn(validate = "Self::validate", error = "TermsError"))]
@@ -248,9 +490,36 @@ pub struct TermValues {
     pub margin_call_cvl: CVLPct,
     #[builder(setter(into))]
     pub initial_cvl: CVLPct,
+    #[builder(default)]
+    #[serde(default)]
+    pub payment_allocation_strategy: PaymentAllocationStrategy,
+    #[builder(default, setter(into))]
+    #[serde(default)]
+    pub prepayment_fee_rate: Option<OneTimeFeeRatePct>,
+    #[builder(default, setter(into))]
+    #[serde(default)]
+    pub penalty_annual_rate: Option<AnnualRatePct>,
+    #[builder(default, setter(into))]
+    #[serde(default)]
+    pub amortization: Option<AmortizationSchedule>,
+    #[builder(default)]
+    #[serde(default)]
+    pub day_count_convention: DayCountConvention,
+    #[builder(
+        default = "TermValues::DEFAULT_MARGIN_CALL_CURE_DURATION",
+        setter(into)
+    )]
+    #[serde(default = "default_margin_call_cure_duration")]
+    pub margin_call_cure_duration: ObligationDuration,
+}
+
+fn default_margin_call_cure_duration() -> ObligationDuration {
+    TermValues::DEFAULT_MARGIN_CALL_CURE_DURATION
 }
 
 impl TermValues {
+    pub const DEFAULT_MARGIN_CALL_CURE_DURATION: ObligationDuration = ObligationDuration::Days(3);
+
     pub fn maturity_date(&self, start_date: DateTime<Utc>) -> EffectiveDate {
         self.duration.maturity_date(start_date)
     }
@@ -276,6 +545,21 @@ impl TermValues {
         total >= self.margin_call_cvl
     }
 
+    pub fn interest_for_period(&self, principal: UsdCents, period: &InterestPeriod) -> UsdCents {
+        self.annual_rate.interest_for_days(
+            principal,
+            period.start.date_naive(),
+            period.end.date_naive(),
+            self.day_count_convention,
+        )
+    }
+
+    pub fn prepayment_fee(&self, principal: UsdCents) -> UsdCents {
+        self.prepayment_fee_rate
+            .map(|rate| rate.apply(principal))
+            .unwrap_or(UsdCents::ZERO)
+    }
+
     pub fn builder() -> TermValuesBuilder {
         TermValuesBuilder::default()
     }
@@ -377,6 +661,14 @@ impl TermValues {
             .map(|d| d.end_date(due_date))
     }
 
+    pub(crate) fn margin_call_cure_deadline(&self, opened_at: DateTime<Utc>) -> DateTime<Utc> {
+        match self.margin_call_cure_duration {
+            ObligationDuration::Days(days) => opened_at
+                .checked_add_days(chrono::Days::new(days))
+                .expect("should return a cure deadline"),
+        }
+    }
+
     pub(crate) fn get_overdue_date_from_due_date(
         &self,
         due_date: EffectiveDate,
@@ -513,6 +805,18 @@ mod test {
         assert_eq!(required_collateral, sats);
     }
 
+    #[test]
+    fn prepayment_fee() {
+        let principal = UsdCents::from(100000);
+        assert_eq!(terms().prepayment_fee(principal), UsdCents::ZERO);
+
+        let terms = TermValues {
+            prepayment_fee_rate: Some(OneTimeFeeRatePct(dec!(2))),
+            ..terms()
+        };
+        assert_eq!(terms.prepayment_fee(principal), UsdCents::from(2000));
+    }
+
     #[test]
     fn days() {
         let start_date = "2024-12-03T14:00:00Z".parse::<DateTime<Utc>>().unwrap();
@@ -567,6 +871,227 @@ mod test {
         assert_eq!(interest, UsdCents::from(757));
     }
 
+    #[test]
+    fn actual_360_interest() {
+        let annual_rate = AnnualRatePct(dec!(12));
+        let principal = UsdCents::try_from_usd(dec!(1000)).unwrap();
+        let first_day = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+
+        let interest = annual_rate.interest_for_days(
+            principal,
+            first_day,
+            last_day,
+            DayCountConvention::Actual360,
+        );
+        assert_eq!(interest, UsdCents::from(1000));
+
+        let interest = annual_rate.interest_for_days(
+            principal,
+            first_day,
+            last_day,
+            DayCountConvention::Actual365Fixed,
+        );
+        assert_eq!(
+            interest,
+            annual_rate.interest_for_time_period(principal, 30)
+        );
+    }
+
+    #[test]
+    fn actual_actual_day_counts_in_leap_year() {
+        let convention = DayCountConvention::ActualActual;
+
+        let first_day = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(convention.day_counts(first_day, last_day), vec![(29, 366)]);
+
+        let first_day = NaiveDate::from_ymd_opt(2023, 2, 1).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(2023, 2, 28).unwrap();
+        assert_eq!(convention.day_counts(first_day, last_day), vec![(28, 365)]);
+    }
+
+    #[test]
+    fn actual_actual_day_counts_across_year_end() {
+        let first_day = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+        let last_day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert_eq!(
+            DayCountConvention::ActualActual.day_counts(first_day, last_day),
+            vec![(1, 365), (1, 366)]
+        );
+
+        let annual_rate = AnnualRatePct(dec!(12));
+        let principal = UsdCents::try_from_usd(dec!(1000)).unwrap();
+        let interest = annual_rate.interest_for_days(
+            principal,
+            first_day,
+            last_day,
+            DayCountConvention::ActualActual,
+        );
+        assert_eq!(interest, UsdCents::from(66));
+    }
+
+    #[test]
+    fn thirty_360_day_counts_at_month_end() {
+        let convention = DayCountConvention::Thirty360;
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            convention.day_counts(day(2024, 1, 1), day(2024, 1, 31)),
+            vec![(30, 360)]
+        );
+        assert_eq!(
+            convention.day_counts(day(2023, 2, 1), day(2023, 2, 28)),
+            vec![(30, 360)]
+        );
+        assert_eq!(
+            convention.day_counts(day(2024, 2, 1), day(2024, 2, 29)),
+            vec![(30, 360)]
+        );
+        assert_eq!(
+            convention.day_counts(day(2024, 1, 30), day(2024, 1, 30)),
+            vec![(0, 360)]
+        );
+        assert_eq!(
+            convention.day_counts(day(2024, 1, 31), day(2024, 1, 31)),
+            vec![(1, 360)]
+        );
+        assert_eq!(
+            convention.day_counts(day(2024, 2, 29), day(2024, 2, 29)),
+            vec![(2, 360)]
+        );
+        assert_eq!(
+            convention.day_counts(day(2024, 1, 15), day(2024, 3, 14)),
+            vec![(60, 360)]
+        );
+    }
+
+    #[test]
+    fn thirty_360_daily_accruals_add_up_to_a_month() {
+        let annual_rate = AnnualRatePct(dec!(12));
+        let principal = UsdCents::try_from_usd(dec!(3600)).unwrap();
+        let first_day = NaiveDate::from_ymd_opt(2023, 2, 1).unwrap();
+
+        let daily_total = first_day
+            .iter_days()
+            .take(28)
+            .map(|day| {
+                annual_rate.interest_for_days(principal, day, day, DayCountConvention::Thirty360)
+            })
+            .fold(UsdCents::ZERO, |acc, interest| acc + interest);
+        assert_eq!(daily_total, UsdCents::from(3600));
+    }
+
+    #[test]
+    fn scheduled_disbursals_cannot_exceed_facility_amount() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let policy = DisbursalPolicy::Scheduled(vec![
+            ScheduledDisbursal {
+                date,
+                amount: UsdCents::from(600),
+            },
+            ScheduledDisbursal {
+                date,
+                amount: UsdCents::from(500),
+            },
+        ]);
+
+        assert!(policy.validate(UsdCents::from(1100)).is_ok());
+        assert!(matches!(
+            policy.validate(UsdCents::from(1000)),
+            Err(TermsError::ScheduledDisbursalsExceedFacilityAmount(_, _))
+        ));
+        assert!(matches!(
+            DisbursalPolicy::Scheduled(vec![]).validate(UsdCents::from(1000)),
+            Err(TermsError::EmptyDisbursalSchedule)
+        ));
+    }
+
+    #[test]
+    fn drawdowns() {
+        let activated_at = "2024-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let disbursable = UsdCents::from(1000);
+
+        assert!(
+            DisbursalPolicy::OnDemand
+                .drawdowns(activated_at, disbursable)
+                .is_empty()
+        );
+        assert_eq!(
+            DisbursalPolicy::FullAtActivation.drawdowns(activated_at, disbursable),
+            vec![ScheduledDisbursal {
+                date: activated_at.date_naive(),
+                amount: disbursable,
+            }]
+        );
+
+        let first = ScheduledDisbursal {
+            date: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            amount: UsdCents::from(300),
+        };
+        let second = ScheduledDisbursal {
+            date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            amount: UsdCents::from(700),
+        };
+        assert_eq!(
+            DisbursalPolicy::Scheduled(vec![second, first]).drawdowns(activated_at, disbursable),
+            vec![first, second]
+        );
+    }
+
+    #[test]
+    fn straight_line_principal_installment() {
+        let principal = UsdCents::try_from_usd(dec!(1000)).unwrap();
+        let installment = AmortizationSchedule::StraightLine.principal_installment(
+            principal,
+            UsdCents::from(1000),
+            12,
+        );
+        assert_eq!(installment, UsdCents::from(8334));
+    }
+
+    #[test]
+    fn annuity_principal_installment() {
+        let principal = UsdCents::try_from_usd(dec!(1000)).unwrap();
+        let installment = AmortizationSchedule::Annuity.principal_installment(
+            principal,
+            UsdCents::from(1000),
+            12,
+        );
+        assert_eq!(installment, UsdCents::from(7885));
+    }
+
+    #[test]
+    fn principal_installment_prorated_for_partial_period() {
+        let principal = UsdCents::try_from_usd(dec!(1000)).unwrap();
+        let start = "2024-04-21T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let period = InterestInterval::EndOfMonth.period_from(start);
+
+        let installment = AmortizationSchedule::StraightLine.principal_installment_for_period(
+            principal,
+            UsdCents::from(1000),
+            4,
+            &period,
+        );
+        assert_eq!(installment, UsdCents::from(8334));
+
+        let last = AmortizationSchedule::StraightLine.principal_installment_for_period(
+            principal,
+            UsdCents::from(1000),
+            1,
+            &period,
+        );
+        assert_eq!(last, principal);
+    }
+
+    #[test]
+    fn last_principal_installment_is_remaining_principal() {
+        let principal = UsdCents::try_from_usd(dec!(1000)).unwrap();
+        let installment =
+            AmortizationSchedule::Annuity.principal_installment(principal, UsdCents::from(1000), 1);
+        assert_eq!(installment, principal);
+    }
+
     #[test]
     fn maturity_date() {
         let start_date = "2024-12-03T14:00:00Z".parse::<DateTime<Utc>>().unwrap();
@@ -865,6 +1390,7 @@ mod test {
             due_interest_outstanding: UsdCents::ZERO,
             overdue_interest_outstanding: UsdCents::ZERO,
             interest_defaulted: UsdCents::ZERO,
+            penalty_outstanding: UsdCents::ZERO,
         }
     }
 
@@ -902,4 +1428,36 @@ mod test {
         let amount = UsdCents::try_from_usd(dec!(80_000)).unwrap();
         assert!(terms.is_disbursal_allowed(balance, amount, price));
     }
+
+    #[test]
+    fn margin_call_cure_deadline_from_terms() {
+        let opened_at = "2024-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let terms = default_terms();
+        assert_eq!(
+            terms.margin_call_cure_deadline(opened_at),
+            "2024-01-18T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+
+        let mut terms = default_terms();
+        terms.margin_call_cure_duration = ObligationDuration::Days(7);
+        assert_eq!(
+            terms.margin_call_cure_deadline(opened_at),
+            "2024-01-22T12:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn margin_call_cure_duration_defaults_when_missing() {
+        let mut value = serde_json::to_value(default_terms()).unwrap();
+        value
+            .as_object_mut()
+            .unwrap()
+            .remove("margin_call_cure_duration");
+        let terms: TermValues = serde_json::from_value(value).unwrap();
+        assert!(matches!(
+            terms.margin_call_cure_duration,
+            ObligationDuration::Days(3)
+        ));
+    }
 }
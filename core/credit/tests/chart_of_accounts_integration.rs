@@ -21,19 +21,9 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
     let storage = Storage::new(&StorageConfig::default());
     let document_storage = DocumentStorage::new(&pool, &storage);
 
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
     let public_ids = public_id::PublicIds::new(&pool);
     let customers =
         core_customer::Customers::new(&pool, &authz, &outbox, document_storage, public_ids);
-    let custody =
-        core_custody::CoreCustody::init(&pool, &authz, helpers::custody_config(), &outbox).await?;
-    let price = core_price::Price::new();
-
-    let cala_config = CalaLedgerConfig::builder()
-        .pool(pool.clone())
-        .exec_migrations(false)
-        .build()?;
-    let cala = CalaLedger::init(cala_config).await?;
     let jobs = job::Jobs::init(
         job::JobSvcConfig::builder()
             .pool(pool.clone())
@@ -41,6 +31,17 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
             .unwrap(),
     )
     .await?;
+    let custody =
+        core_custody::CoreCustody::init(&pool, &authz, helpers::custody_config(), &jobs, &outbox)
+            .await?;
+    let price = core_price::Price::new(&pool);
+
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let governance = governance::Governance::new(&pool, &authz, &jobs, &outbox);
 
     let journal_id = helpers::init_journal(&cala).await?;
     let public_ids = PublicIds::new(&pool);
@@ -124,6 +125,8 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
                 chart_of_account_in_liquidation_parent_code: "3".parse().unwrap(),
                 chart_of_account_interest_income_parent_code: "7".parse().unwrap(),
                 chart_of_account_fee_income_parent_code: "8".parse().unwrap(),
+                chart_of_account_penalty_income_parent_code: "7".parse().unwrap(),
+                chart_of_account_penalty_receivable_parent_code: "6".parse().unwrap(),
                 chart_of_account_short_term_individual_disbursed_receivable_parent_code: "1".parse().unwrap(),
                 chart_of_account_short_term_government_entity_disbursed_receivable_parent_code:
                     "2".parse().unwrap(),
@@ -237,6 +240,8 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
                 chart_of_account_in_liquidation_parent_code: "3".parse().unwrap(),
                 chart_of_account_interest_income_parent_code: "7".parse().unwrap(),
                 chart_of_account_fee_income_parent_code: "8".parse().unwrap(),
+                chart_of_account_penalty_income_parent_code: "7".parse().unwrap(),
+                chart_of_account_penalty_receivable_parent_code: "6".parse().unwrap(),
                 chart_of_account_short_term_individual_disbursed_receivable_parent_code: "1".parse().unwrap(),
                 chart_of_account_short_term_government_entity_disbursed_receivable_parent_code:
                     "2".parse().unwrap(),
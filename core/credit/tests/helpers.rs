@@ -14,6 +14,7 @@ pub fn custody_config() -> CustodyConfig {
         },
         deprecated_encryption_key: None,
         custody_providers: Default::default(),
+        ..Default::default()
     }
 }
 
@@ -147,6 +148,7 @@ pub mod event {
     use core_credit::CoreCreditEvent;
     use core_custody::CoreCustodyEvent;
     use core_customer::CoreCustomerEvent;
+    use core_price::CorePriceEvent;
     use governance::GovernanceEvent;
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -155,6 +157,7 @@ pub mod event {
         CoreCredit(CoreCreditEvent),
         CoreCustody(CoreCustodyEvent),
         CoreCustomer(CoreCustomerEvent),
+        CorePrice(CorePriceEvent),
         Governance(GovernanceEvent),
     }
 
@@ -180,4 +183,5 @@ pub mod event {
     impl_event_marker!(CoreCreditEvent, CoreCredit);
     impl_event_marker!(CoreCustodyEvent, CoreCustody);
     impl_event_marker!(CoreCustomerEvent, CoreCustomer);
+    impl_event_marker!(CorePriceEvent, CorePrice);
 }
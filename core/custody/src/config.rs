@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 
 use super::{DeprecatedEncryptionKey, EncryptionConfig, custodian::CustodyProviderConfig};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde_with::serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CustodyConfig {
     #[serde(skip)]
@@ -14,4 +15,24 @@ pub struct CustodyConfig {
 
     #[serde(default)]
     pub custody_providers: CustodyProviderConfig,
+
+    /// How often wallet balances are compared against the custodian, in case a webhook was missed.
+    #[serde(default = "default_wallet_reconciliation_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub wallet_reconciliation_interval: std::time::Duration,
+}
+
+impl Default for CustodyConfig {
+    fn default() -> Self {
+        Self {
+            encryption: Default::default(),
+            deprecated_encryption_key: None,
+            custody_providers: Default::default(),
+            wallet_reconciliation_interval: default_wallet_reconciliation_interval(),
+        }
+    }
+}
+
+fn default_wallet_reconciliation_interval() -> std::time::Duration {
+    std::time::Duration::from_secs(60 * 60)
 }
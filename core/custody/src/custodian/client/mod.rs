@@ -1,7 +1,7 @@
 pub mod error;
 
 use async_trait::async_trait;
-use bitgo::TransferState;
+use bitgo::{TransferState, TransferType};
 use bytes::Bytes;
 use chrono::Utc;
 
@@ -9,7 +9,7 @@ use core_money::Satoshis;
 
 use error::CustodianClientError;
 
-use crate::primitives::{ExternalWallet, WalletNetwork};
+use crate::primitives::{ExternalTransfer, ExternalWallet, WalletNetwork};
 
 use super::notification::CustodianNotification;
 
@@ -23,12 +23,30 @@ pub trait CustodianClient: Send {
     /// This call may or may not create new wallet.
     async fn initialize_wallet(&self, label: &str) -> Result<ExternalWallet, CustodianClientError>;
 
+    /// Requests a transfer of `amount` from the wallet to `address`.
+    /// The outcome is reported asynchronously via webhook notifications
+    /// referencing the returned transfer.
+    async fn send_from_wallet(
+        &self,
+        external_wallet_id: &str,
+        address: &str,
+        amount: Satoshis,
+        reference: &str,
+    ) -> Result<ExternalTransfer, CustodianClientError>;
+
+    /// Fetches the confirmed balance of the wallet as reported by the custodian.
+    /// Returns `None` if the custodian cannot report balances.
+    async fn fetch_wallet_balance(
+        &self,
+        external_wallet_id: &str,
+    ) -> Result<Option<Satoshis>, CustodianClientError>;
+
     /// Validates and parses webhook.
     async fn process_webhook(
         &self,
         headers: &http::HeaderMap,
         payload: Bytes,
-    ) -> Result<Option<CustodianNotification>, CustodianClientError>;
+    ) -> Result<Vec<CustodianNotification>, CustodianClientError>;
 }
 
 #[async_trait]
@@ -55,16 +73,42 @@ impl CustodianClient for bitgo::BitgoClient {
         })
     }
 
+    async fn send_from_wallet(
+        &self,
+        external_wallet_id: &str,
+        address: &str,
+        amount: Satoshis,
+        reference: &str,
+    ) -> Result<ExternalTransfer, CustodianClientError> {
+        let (response, full_response) = self
+            .send_coins(external_wallet_id, address, amount.into_inner(), reference)
+            .await?;
+
+        Ok(ExternalTransfer {
+            external_id: response.transfer.id,
+            full_response,
+        })
+    }
+
+    async fn fetch_wallet_balance(
+        &self,
+        external_wallet_id: &str,
+    ) -> Result<Option<Satoshis>, CustodianClientError> {
+        let (wallet, _) = self.get_wallet(external_wallet_id).await?;
+
+        Ok(Some(wallet.confirmed_balance.into()))
+    }
+
     async fn process_webhook(
         &self,
         headers: &http::HeaderMap,
         payload: Bytes,
-    ) -> Result<Option<CustodianNotification>, CustodianClientError> {
+    ) -> Result<Vec<CustodianNotification>, CustodianClientError> {
         let notification = self.validate_webhook_notification(headers, &payload)?;
 
         use bitgo::Notification;
 
-        let custodian_notification = match notification {
+        let custodian_notifications = match notification {
             Notification::Transfer(transfer) if transfer.state == TransferState::Confirmed => {
                 let transfer = self
                     .get_transfer(&transfer.transfer, &transfer.wallet)
@@ -75,19 +119,40 @@ impl CustodianClient for bitgo::BitgoClient {
 
                     let changed_at = transfer.confirmed_time.unwrap_or_else(Utc::now);
 
-                    Some(CustodianNotification::WalletBalanceChanged {
+                    let mut notifications = vec![CustodianNotification::WalletBalanceChanged {
                         external_wallet_id: transfer.wallet,
                         new_balance: wallet.confirmed_balance.into(),
                         changed_at,
-                    })
+                    }];
+
+                    if transfer.transfer_type == TransferType::Send {
+                        notifications.push(CustodianNotification::TransferConfirmed {
+                            external_transfer_id: transfer.id,
+                            confirmed_at: changed_at,
+                        });
+                    }
+
+                    notifications
                 } else {
-                    None
+                    vec![]
                 }
             }
-            _ => None,
+            Notification::Transfer(transfer)
+                if transfer.transfer_type == TransferType::Send
+                    && matches!(
+                        transfer.state,
+                        TransferState::Failed | TransferState::Rejected | TransferState::Removed
+                    ) =>
+            {
+                vec![CustodianNotification::TransferFailed {
+                    external_transfer_id: transfer.transfer,
+                    failed_at: Utc::now(),
+                }]
+            }
+            _ => vec![],
         };
 
-        Ok(custodian_notification)
+        Ok(custodian_notifications)
     }
 }
 
@@ -110,16 +175,46 @@ impl CustodianClient for komainu::KomainuClient {
         })
     }
 
+    async fn send_from_wallet(
+        &self,
+        external_wallet_id: &str,
+        address: &str,
+        amount: Satoshis,
+        reference: &str,
+    ) -> Result<ExternalTransfer, CustodianClientError> {
+        let _ = self
+            .create_transaction_request(external_wallet_id, address, amount.to_btc(), reference)
+            .await?;
+
+        // Webhooks only identify the transaction, which carries the reference as its note.
+        Ok(ExternalTransfer {
+            external_id: reference.to_owned(),
+            full_response: serde_json::Value::Null,
+        })
+    }
+
+    async fn fetch_wallet_balance(
+        &self,
+        external_wallet_id: &str,
+    ) -> Result<Option<Satoshis>, CustodianClientError> {
+        let wallet = self.get_wallet(external_wallet_id).await?;
+
+        Ok(Some(Satoshis::try_from_btc(wallet.balance.total)?))
+    }
+
     async fn process_webhook(
         &self,
         headers: &http::HeaderMap,
         payload: Bytes,
-    ) -> Result<Option<CustodianNotification>, CustodianClientError> {
+    ) -> Result<Vec<CustodianNotification>, CustodianClientError> {
         let notification = self.validate_webhook_notification(headers, &payload)?;
 
-        use komainu::{EntityType, EventType, Notification};
+        use komainu::{
+            EntityType, EventType, Notification, RequestEntity, RequestStatus,
+            TransactionDirection, TransactionStatus,
+        };
 
-        let custodian_notification = match notification {
+        let custodian_notifications = match notification {
             Notification {
                 event_type: EventType::BalanceUpdated,
                 entity: EntityType::Wallet,
@@ -127,19 +222,79 @@ impl CustodianClient for komainu::KomainuClient {
             } => {
                 let wallet = self.get_wallet(&wallet_id).await?;
 
-                let new_balance = Satoshis::try_from_btc(wallet.balance.available)?;
+                // `available` excludes funds locked by pending transactions that are
+                // still held on chain, so track the confirmed `total` like for BitGo.
+                let new_balance = Satoshis::try_from_btc(wallet.balance.total)?;
 
                 let changed_at = wallet.balance.balance_updated_at.unwrap_or_else(Utc::now);
 
-                Some(CustodianNotification::WalletBalanceChanged {
+                vec![CustodianNotification::WalletBalanceChanged {
                     external_wallet_id: wallet.id,
                     new_balance,
                     changed_at,
-                })
+                }]
+            }
+            Notification {
+                event_type: EventType::RequestUpdated,
+                entity: EntityType::Request,
+                entity_id: request_id,
+            } => {
+                let request = self.get_request(&request_id).await?;
+
+                match (request.entity, request.status) {
+                    (
+                        RequestEntity::Transaction,
+                        RequestStatus::Rejected
+                        | RequestStatus::Cancelled
+                        | RequestStatus::Expired
+                        | RequestStatus::Blocked,
+                    ) => {
+                        let transaction = self.get_transaction(&request.entity_id).await?;
+
+                        if transaction.note.is_empty() {
+                            vec![]
+                        } else {
+                            vec![CustodianNotification::TransferFailed {
+                                external_transfer_id: transaction.note,
+                                failed_at: request.updated_at,
+                            }]
+                        }
+                    }
+                    _ => vec![],
+                }
             }
+            Notification {
+                event_type: EventType::TransactionUpdated,
+                entity: EntityType::Transaction,
+                entity_id: transaction_id,
+            } => {
+                let transaction = self.get_transaction(&transaction_id).await?;
+
+                // Transfers are tracked by the reference sent as the transaction note.
+                let reference = (transaction.direction == TransactionDirection::Out
+                    && !transaction.note.is_empty())
+                .then_some(transaction.note);
+
+                match (reference, transaction.status) {
+                    (Some(reference), TransactionStatus::Confirmed) => {
+                        vec![CustodianNotification::TransferConfirmed {
+                            external_transfer_id: reference,
+                            confirmed_at: Utc::now(),
+                        }]
+                    }
+                    (Some(reference), TransactionStatus::Failed) => {
+                        vec![CustodianNotification::TransferFailed {
+                            external_transfer_id: reference,
+                            failed_at: Utc::now(),
+                        }]
+                    }
+                    _ => vec![],
+                }
+            }
+            _ => vec![],
         };
 
-        Ok(custodian_notification)
+        Ok(custodian_notifications)
     }
 }
 
@@ -158,6 +313,12 @@ pub mod mock {
         balance: u64,
     }
 
+    #[derive(Deserialize)]
+    struct TransferStateChanged {
+        transfer: String,
+        confirmed: bool,
+    }
+
     #[async_trait]
     impl CustodianClient for CustodianMock {
         async fn verify_client(&self) -> Result<(), CustodianClientError> {
@@ -175,19 +336,55 @@ pub mod mock {
                 full_response: serde_json::Value::Null,
             })
         }
+        async fn send_from_wallet(
+            &self,
+            _external_wallet_id: &str,
+            _address: &str,
+            _amount: Satoshis,
+            reference: &str,
+        ) -> Result<ExternalTransfer, CustodianClientError> {
+            Ok(ExternalTransfer {
+                external_id: reference.to_owned(),
+                full_response: serde_json::Value::Null,
+            })
+        }
+
+        async fn fetch_wallet_balance(
+            &self,
+            _external_wallet_id: &str,
+        ) -> Result<Option<Satoshis>, CustodianClientError> {
+            Ok(None)
+        }
+
         async fn process_webhook(
             &self,
             _headers: &http::HeaderMap,
             payload: Bytes,
-        ) -> Result<Option<CustodianNotification>, CustodianClientError> {
+        ) -> Result<Vec<CustodianNotification>, CustodianClientError> {
             if let Ok(WalletBalanceChanged { wallet, balance }) = serde_json::from_slice(&payload) {
-                Ok(Some(CustodianNotification::WalletBalanceChanged {
+                Ok(vec![CustodianNotification::WalletBalanceChanged {
                     external_wallet_id: wallet,
                     new_balance: balance.into(),
                     changed_at: Utc::now(),
-                }))
+                }])
+            } else if let Ok(TransferStateChanged {
+                transfer,
+                confirmed,
+            }) = serde_json::from_slice(&payload)
+            {
+                Ok(vec![if confirmed {
+                    CustodianNotification::TransferConfirmed {
+                        external_transfer_id: transfer,
+                        confirmed_at: Utc::now(),
+                    }
+                } else {
+                    CustodianNotification::TransferFailed {
+                        external_transfer_id: transfer,
+                        failed_at: Utc::now(),
+                    }
+                }])
             } else {
-                Ok(None)
+                Ok(vec![])
             }
         }
     }
@@ -8,4 +8,12 @@ pub enum CustodianNotification {
         new_balance: Satoshis,
         changed_at: DateTime<Utc>,
     },
+    TransferConfirmed {
+        external_transfer_id: String,
+        confirmed_at: DateTime<Utc>,
+    },
+    TransferFailed {
+        external_transfer_id: String,
+        failed_at: DateTime<Utc>,
+    },
 }
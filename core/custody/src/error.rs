@@ -14,8 +14,12 @@ pub enum CoreCustodyError {
     Custodian(#[from] crate::custodian::error::CustodianError),
     #[error("CoreCustodyError - CustodianClientError: {0}")]
     CustodianClient(#[from] crate::custodian::client::error::CustodianClientError),
+    #[error("CoreCustodyError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
     #[error("CoreCustodyError - WalletError: {0}")]
     Wallet(#[from] crate::wallet::error::WalletError),
+    #[error("CoreCustodyError - ConversionError: {0}")]
+    ConversionError(#[from] core_money::ConversionError),
 }
 
 es_entity::from_es_entity_error!(CoreCustodyError);
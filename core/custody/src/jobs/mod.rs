@@ -0,0 +1,3 @@
+mod wallet_reconciliation;
+
+pub use wallet_reconciliation::*;
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use std::time::Duration;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{CoreCustody, CoreCustodyAction, CoreCustodyEvent, CoreCustodyObject};
+
+#[serde_with::serde_as]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WalletReconciliationJobConfig<Perms, E> {
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub job_interval: Duration,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> JobConfig for WalletReconciliationJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCustodyEvent>,
+{
+    type Initializer = WalletReconciliationInit<Perms, E>;
+}
+
+pub struct WalletReconciliationInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCustodyEvent>,
+{
+    custody: CoreCustody<Perms, E>,
+}
+
+impl<Perms, E> WalletReconciliationInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCustodyEvent>,
+{
+    pub fn new(custody: &CoreCustody<Perms, E>) -> Self {
+        Self {
+            custody: custody.clone(),
+        }
+    }
+}
+
+const WALLET_RECONCILIATION_JOB: JobType = JobType::new("cron.custody-wallet-reconciliation");
+
+impl<Perms, E> JobInitializer for WalletReconciliationInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCustodyEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        WALLET_RECONCILIATION_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(WalletReconciliationJobRunner::<Perms, E> {
+            config: job.config()?,
+            custody: self.custody.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+pub struct WalletReconciliationJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCustodyEvent>,
+{
+    config: WalletReconciliationJobConfig<Perms, E>,
+    custody: CoreCustody<Perms, E>,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for WalletReconciliationJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreCustodyAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreCustodyObject>,
+    E: OutboxEventMarker<CoreCustodyEvent>,
+{
+    #[instrument(name = "custody.wallet_reconciliation.run", skip_all, err)]
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        self.custody.reconcile_wallet_balances().await?;
+
+        Ok(JobCompletion::RescheduleIn(self.config.job_interval))
+    }
+}
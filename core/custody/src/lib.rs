@@ -5,9 +5,11 @@ mod config;
 pub mod custodian;
 pub mod error;
 mod event;
+mod jobs;
 mod primitives;
 mod publisher;
 pub mod wallet;
+mod wallet_reconciliation_repo;
 mod webhook_notification_repo;
 
 use chrono::{DateTime, Utc};
@@ -23,12 +25,16 @@ use audit::AuditSvc;
 use authz::PermissionCheck;
 use core_money::Satoshis;
 
+use custodian::client::CustodianClient;
 pub use custodian::*;
 pub use wallet::*;
+pub use wallet_reconciliation_repo::WalletReconciliation;
+use wallet_reconciliation_repo::*;
 use webhook_notification_repo::*;
 
 pub use config::CustodyConfig;
 use error::CoreCustodyError;
+use jobs::{WalletReconciliationInit, WalletReconciliationJobConfig};
 pub use primitives::*;
 
 #[cfg(feature = "json-schema")]
@@ -46,6 +52,7 @@ where
     webhooks: WebhookNotificationRepo,
     config: CustodyConfig,
     wallets: WalletRepo<E>,
+    reconciliations: WalletReconciliationRepo,
     pool: sqlx::PgPool,
     outbox: Outbox<E>,
 }
@@ -61,6 +68,7 @@ where
         pool: &sqlx::PgPool,
         authz: &Perms,
         config: CustodyConfig,
+        jobs: &job::Jobs,
         outbox: &Outbox<E>,
     ) -> Result<Self, CoreCustodyError> {
         let custody = Self {
@@ -69,6 +77,7 @@ where
             webhooks: WebhookNotificationRepo::new(pool),
             config,
             wallets: WalletRepo::new(pool, &CustodyPublisher::new(outbox)),
+            reconciliations: WalletReconciliationRepo::new(pool),
             pool: pool.clone(),
             outbox: outbox.clone(),
         };
@@ -79,6 +88,15 @@ where
                 .await?;
         }
 
+        jobs.add_initializer_and_spawn_unique(
+            WalletReconciliationInit::new(&custody),
+            WalletReconciliationJobConfig {
+                job_interval: custody.config.wallet_reconciliation_interval,
+                _phantom: std::marker::PhantomData,
+            },
+        )
+        .await?;
+
         Ok(custody)
     }
 
@@ -333,6 +351,33 @@ where
         Ok(wallet)
     }
 
+    #[instrument(name = "custody.send_from_wallet", skip(self), err)]
+    pub async fn send_from_wallet(
+        &self,
+        wallet_id: WalletId,
+        address: &str,
+        amount: Satoshis,
+        reference: &str,
+    ) -> Result<ExternalTransfer, CoreCustodyError> {
+        self.authz
+            .audit()
+            .record_system_entry(
+                CoreCustodyObject::wallet(wallet_id),
+                CoreCustodyAction::WALLET_SEND,
+            )
+            .await?;
+
+        let wallet = self.wallets.find_by_id(wallet_id).await?;
+        let custodian = self.custodians.find_by_id(wallet.custodian_id).await?;
+
+        let transfer = custodian
+            .custodian_client(self.config.encryption.key, &self.config.custody_providers)?
+            .send_from_wallet(&wallet.external_wallet_id, address, amount, reference)
+            .await?;
+
+        Ok(transfer)
+    }
+
     #[instrument(name = "custody.handle_webhook", skip(self), err)]
     pub async fn handle_webhook(
         &self,
@@ -353,12 +398,16 @@ where
             .persist(custodian_id, &uri, &headers, &payload)
             .await?;
 
-        if let Ok(custodian) = custodian
-            && let Some(notification) = custodian
-                .custodian_client(self.config.encryption.key, &self.config.custody_providers)?
-                .process_webhook(&headers, payload)
-                .await?
-        {
+        let Ok(custodian) = custodian else {
+            return Ok(());
+        };
+
+        let notifications = custodian
+            .custodian_client(self.config.encryption.key, &self.config.custody_providers)?
+            .process_webhook(&headers, payload)
+            .await?;
+
+        for notification in notifications {
             match notification {
                 CustodianNotification::WalletBalanceChanged {
                     external_wallet_id,
@@ -368,12 +417,178 @@ where
                     self.update_wallet_balance(external_wallet_id, new_balance, changed_at)
                         .await?;
                 }
+                CustodianNotification::TransferConfirmed {
+                    external_transfer_id,
+                    confirmed_at,
+                } => {
+                    self.publish_transfer_outcome(CoreCustodyEvent::TransferConfirmed {
+                        external_transfer_id,
+                        confirmed_at,
+                    })
+                    .await?;
+                }
+                CustodianNotification::TransferFailed {
+                    external_transfer_id,
+                    failed_at,
+                } => {
+                    self.publish_transfer_outcome(CoreCustodyEvent::TransferFailed {
+                        external_transfer_id,
+                        failed_at,
+                    })
+                    .await?;
+                }
             }
         }
 
         Ok(())
     }
 
+    #[instrument(name = "custody.list_wallet_reconciliations", skip(self), err)]
+    pub async fn list_wallet_reconciliations(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        wallet_id: impl Into<WalletId> + std::fmt::Debug,
+    ) -> Result<Vec<WalletReconciliation>, CoreCustodyError> {
+        let wallet_id = wallet_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreCustodyObject::wallet(wallet_id),
+                CoreCustodyAction::WALLET_READ_RECONCILIATIONS,
+            )
+            .await?;
+
+        self.reconciliations.list_for_wallet(wallet_id).await
+    }
+
+    /// Compares every wallet's balance with the one reported by its custodian and
+    /// adopts the custodian's balance on mismatch, as a fallback for missed webhooks.
+    /// Wallets whose custodian cannot be reached are skipped until the next run.
+    #[instrument(name = "custody.reconcile_wallet_balances", skip(self), err)]
+    pub(crate) async fn reconcile_wallet_balances(&self) -> Result<(), CoreCustodyError> {
+        self.authz
+            .audit()
+            .record_system_entry(
+                CoreCustodyObject::all_wallets(),
+                CoreCustodyAction::WALLET_RECONCILE,
+            )
+            .await?;
+
+        let mut wallets_by_custodian = std::collections::HashMap::<_, Vec<Wallet>>::new();
+        for wallet in self.wallets.list_all().await? {
+            wallets_by_custodian
+                .entry(wallet.custodian_id)
+                .or_default()
+                .push(wallet);
+        }
+
+        for custodian in self.custodians.list_all().await? {
+            let Some(wallets) = wallets_by_custodian.remove(&custodian.id) else {
+                continue;
+            };
+
+            let custodian_id = custodian.id;
+            let client = match custodian
+                .custodian_client(self.config.encryption.key, &self.config.custody_providers)
+            {
+                Ok(client) => client,
+                Err(e) => {
+                    tracing::warn!(%custodian_id, error = %e, "could not build custodian client");
+                    continue;
+                }
+            };
+
+            self.reconcile_wallets_with(client, wallets).await;
+        }
+
+        Ok(())
+    }
+
+    /// Failures are logged per wallet so that one wallet cannot hold up the others.
+    async fn reconcile_wallets_with(&self, client: Box<dyn CustodianClient>, wallets: Vec<Wallet>) {
+        for wallet in wallets {
+            let wallet_id = wallet.id;
+            let custodian_balance = match client
+                .fetch_wallet_balance(&wallet.external_wallet_id)
+                .await
+            {
+                Ok(Some(balance)) => balance,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(%wallet_id, error = %e, "could not fetch wallet balance");
+                    continue;
+                }
+            };
+
+            if custodian_balance == wallet.balance() {
+                continue;
+            }
+
+            if let Err(e) = self
+                .reconcile_wallet_balance(wallet, custodian_balance)
+                .await
+            {
+                tracing::warn!(%wallet_id, error = %e, "could not reconcile wallet balance");
+            }
+        }
+    }
+
+    #[instrument(
+        name = "custody.reconcile_wallet_balance",
+        skip(self, wallet),
+        fields(wallet_id = %wallet.id),
+        err
+    )]
+    async fn reconcile_wallet_balance(
+        &self,
+        mut wallet: Wallet,
+        custodian_balance: Satoshis,
+    ) -> Result<(), CoreCustodyError> {
+        let mut db = self.wallets.begin_op().await?;
+
+        self.reconciliations
+            .record_mismatch_in_op(
+                &mut db,
+                wallet.id,
+                wallet.custodian_id,
+                wallet.balance(),
+                custodian_balance,
+            )
+            .await?;
+
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                &mut db,
+                CoreCustodyObject::wallet(wallet.id),
+                CoreCustodyAction::WALLET_UPDATE,
+            )
+            .await?;
+
+        if wallet
+            .update_balance(custodian_balance, Utc::now())
+            .did_execute()
+        {
+            self.wallets.update_in_op(&mut db, &mut wallet).await?;
+        }
+
+        db.commit().await?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "custody.publish_transfer_outcome", skip(self), err)]
+    async fn publish_transfer_outcome(
+        &self,
+        event: CoreCustodyEvent,
+    ) -> Result<(), CoreCustodyError> {
+        let mut db = DbOp::init(&self.pool).await?;
+        self.outbox.publish_persisted(&mut db, event).await?;
+        db.commit().await?;
+
+        Ok(())
+    }
+
     #[instrument(name = "custody.update_wallet_balance", skip(self), err)]
     async fn update_wallet_balance(
         &self,
@@ -421,6 +636,7 @@ where
             custodians: self.custodians.clone(),
             webhooks: self.webhooks.clone(),
             wallets: self.wallets.clone(),
+            reconciliations: self.reconciliations.clone(),
             pool: self.pool.clone(),
             config: self.config.clone(),
             outbox: self.outbox.clone(),
@@ -24,6 +24,12 @@ pub struct ExternalWallet {
     pub full_response: serde_json::Value,
 }
 
+#[derive(Debug, Clone)]
+pub struct ExternalTransfer {
+    pub external_id: String,
+    pub full_response: serde_json::Value,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum WalletNetwork {
@@ -32,6 +38,20 @@ pub enum WalletNetwork {
     Mainnet,
 }
 
+impl WalletNetwork {
+    pub fn is_valid_address(&self, address: &str) -> bool {
+        let network = match self {
+            Self::Testnet3 => bitcoin::Network::Testnet,
+            Self::Testnet4 => bitcoin::Network::Testnet4,
+            Self::Mainnet => bitcoin::Network::Bitcoin,
+        };
+
+        address
+            .parse::<bitcoin::Address<bitcoin::address::NetworkUnchecked>>()
+            .is_ok_and(|address| address.is_valid_for_network(network))
+    }
+}
+
 pub const PERMISSION_SET_CUSTODY_VIEWER: &str = "custody_viewer";
 pub const PERMISSION_SET_CUSTODY_WRITER: &str = "custody_writer";
 
@@ -51,6 +71,10 @@ impl CoreCustodyAction {
         CoreCustodyAction::Custodian(CustodianAction::CreateWallet);
 
     pub const WALLET_UPDATE: Self = CoreCustodyAction::Wallet(WalletAction::Update);
+    pub const WALLET_SEND: Self = CoreCustodyAction::Wallet(WalletAction::Send);
+    pub const WALLET_RECONCILE: Self = CoreCustodyAction::Wallet(WalletAction::Reconcile);
+    pub const WALLET_READ_RECONCILIATIONS: Self =
+        CoreCustodyAction::Wallet(WalletAction::ReadReconciliations);
 
     pub fn actions() -> Vec<ActionMapping> {
         use CoreCustodyActionDiscriminants::*;
@@ -121,12 +145,16 @@ impl From<CustodianAction> for CoreCustodyAction {
 #[strum(serialize_all = "kebab-case")]
 pub enum WalletAction {
     Update,
+    Send,
+    Reconcile,
+    ReadReconciliations,
 }
 
 impl ActionPermission for WalletAction {
     fn permission_set(&self) -> &'static str {
         match self {
-            Self::Update => PERMISSION_SET_CUSTODY_WRITER,
+            Self::Update | Self::Send | Self::Reconcile => PERMISSION_SET_CUSTODY_WRITER,
+            Self::ReadReconciliations => PERMISSION_SET_CUSTODY_VIEWER,
         }
     }
 }
@@ -157,6 +185,10 @@ impl CoreCustodyObject {
         CoreCustodyObject::Custodian(AllOrOne::ById(id))
     }
 
+    pub const fn all_wallets() -> Self {
+        CoreCustodyObject::Wallet(AllOrOne::All)
+    }
+
     pub const fn wallet(id: WalletId) -> Self {
         CoreCustodyObject::Wallet(AllOrOne::ById(id))
     }
@@ -195,3 +227,18 @@ impl core::str::FromStr for CoreCustodyObject {
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_address_against_network() {
+        let testnet = "tb1qplx6wllreywl3nadc7wh6waah58xq7p48857qh";
+
+        assert!(WalletNetwork::Testnet3.is_valid_address(testnet));
+        assert!(WalletNetwork::Testnet4.is_valid_address(testnet));
+        assert!(!WalletNetwork::Mainnet.is_valid_address(testnet));
+        assert!(!WalletNetwork::Testnet3.is_valid_address("not-an-address"));
+    }
+}
@@ -39,6 +39,17 @@ pub struct Wallet {
 }
 
 impl Wallet {
+    pub fn balance(&self) -> Satoshis {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|event| match event {
+                WalletEvent::BalanceChanged { new_balance, .. } => Some(*new_balance),
+                _ => None,
+            })
+            .unwrap_or(Satoshis::ZERO)
+    }
+
     pub fn update_balance(
         &mut self,
         new_balance: Satoshis,
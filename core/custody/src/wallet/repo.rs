@@ -35,6 +35,20 @@ where
         }
     }
 
+    pub async fn list_all(&self) -> Result<Vec<Wallet>, WalletError> {
+        let mut wallets = Vec::new();
+        let mut next = Some(PaginatedQueryArgs::default());
+
+        while let Some(query) = next.take() {
+            let mut ret = self.list_by_id(query, Default::default()).await?;
+
+            wallets.append(&mut ret.entities);
+            next = ret.into_next_query();
+        }
+
+        Ok(wallets)
+    }
+
     async fn publish(
         &self,
         op: &mut impl es_entity::AtomicOperation,
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use core_money::{ConversionError, Satoshis};
+
+use crate::{error::*, primitives::*};
+
+/// A wallet balance that disagreed with the one reported by the custodian.
+#[derive(Debug, Clone)]
+pub struct WalletReconciliation {
+    pub wallet_id: WalletId,
+    pub custodian_id: CustodianId,
+    pub recorded_balance: Satoshis,
+    pub custodian_balance: Satoshis,
+    pub reconciled_at: DateTime<Utc>,
+}
+
+/// Append-only report of reconciliation mismatches.
+#[derive(Clone)]
+pub(crate) struct WalletReconciliationRepo {
+    pool: PgPool,
+}
+
+impl WalletReconciliationRepo {
+    pub(crate) fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    #[instrument(
+        name = "custody.wallet_reconciliation.record_mismatch_in_op",
+        skip(self, op),
+        err
+    )]
+    pub async fn record_mismatch_in_op(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        wallet_id: WalletId,
+        custodian_id: CustodianId,
+        recorded_balance: Satoshis,
+        custodian_balance: Satoshis,
+    ) -> Result<(), CoreCustodyError> {
+        sqlx::query!(
+            r#"
+              INSERT INTO core_wallet_reconciliations
+                (wallet_id, custodian_id, recorded_balance, custodian_balance)
+              VALUES ($1, $2, $3, $4)
+            "#,
+            wallet_id as WalletId,
+            custodian_id as CustodianId,
+            i64::try_from(recorded_balance.into_inner()).map_err(|_| ConversionError::Overflow)?,
+            i64::try_from(custodian_balance.into_inner()).map_err(|_| ConversionError::Overflow)?,
+        )
+        .execute(op.as_executor())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_for_wallet(
+        &self,
+        wallet_id: WalletId,
+    ) -> Result<Vec<WalletReconciliation>, CoreCustodyError> {
+        let rows = sqlx::query!(
+            r#"
+              SELECT wallet_id AS "wallet_id: WalletId", custodian_id AS "custodian_id: CustodianId",
+                recorded_balance, custodian_balance, reconciled_at
+              FROM core_wallet_reconciliations
+              WHERE wallet_id = $1
+              ORDER BY reconciled_at DESC
+            "#,
+            wallet_id as WalletId,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(WalletReconciliation {
+                    wallet_id: row.wallet_id,
+                    custodian_id: row.custodian_id,
+                    recorded_balance: satoshis_from_column(row.recorded_balance)?,
+                    custodian_balance: satoshis_from_column(row.custodian_balance)?,
+                    reconciled_at: row.reconciled_at,
+                })
+            })
+            .collect()
+    }
+}
+
+fn satoshis_from_column(value: i64) -> Result<Satoshis, ConversionError> {
+    u64::try_from(value)
+        .map(Satoshis::from)
+        .map_err(|_| ConversionError::Overflow)
+}
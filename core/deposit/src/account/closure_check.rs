@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::primitives::DepositAccountHolderId;
+
+/// Checks owned by other modules that must pass before a deposit account can be closed.
+#[async_trait]
+pub trait DepositAccountClosureCheck: Send + Sync {
+    async fn holder_has_open_credit_facilities(
+        &self,
+        holder_id: DepositAccountHolderId,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+}
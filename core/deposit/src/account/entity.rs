@@ -1,4 +1,6 @@
+use chrono::NaiveDate;
 use derive_builder::Builder;
+use rust_decimal::{Decimal, RoundingStrategy, prelude::ToPrimitive};
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,6 +9,8 @@ use es_entity::*;
 
 use crate::{ledger::*, primitives::*};
 
+use super::error::DepositAccountError;
+
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -22,6 +26,34 @@ pub enum DepositAccountEvent {
     AccountStatusUpdated {
         status: DepositAccountStatus,
     },
+    Unfrozen {},
+    Closed {},
+    InterestAccrued {
+        ledger_tx_id: CalaTransactionId,
+        amount: UsdCents,
+        #[serde(default)]
+        accrued_cents: Decimal,
+        accrued_on: NaiveDate,
+    },
+    InterestCapitalized {
+        ledger_tx_id: CalaTransactionId,
+        amount: UsdCents,
+        period_end: NaiveDate,
+    },
+}
+
+pub struct DepositInterestAccrual {
+    pub tx_id: CalaTransactionId,
+    pub deposit_account_id: CalaAccountId,
+    pub amount: UsdCents,
+    pub effective: NaiveDate,
+}
+
+pub struct DepositInterestCapitalization {
+    pub tx_id: CalaTransactionId,
+    pub deposit_account_id: CalaAccountId,
+    pub amount: UsdCents,
+    pub effective: NaiveDate,
 }
 
 #[derive(EsEntity, Builder)]
@@ -44,10 +76,13 @@ impl DepositAccount {
     }
 
     pub fn update_status(&mut self, status: DepositAccountStatus) -> Idempotent<()> {
+        if self.is_closed() {
+            return Idempotent::Ignored;
+        }
         idempotency_guard!(
             self.events.iter_all().rev(),
             DepositAccountEvent::AccountStatusUpdated { status: existing_status, .. } if existing_status == &status,
-            => DepositAccountEvent::AccountStatusUpdated { .. }
+            => DepositAccountEvent::AccountStatusUpdated { .. } | DepositAccountEvent::Unfrozen { .. }
         );
         self.events
             .push(DepositAccountEvent::AccountStatusUpdated { status });
@@ -58,6 +93,165 @@ impl DepositAccount {
     pub fn freeze(&mut self) -> Idempotent<()> {
         self.update_status(DepositAccountStatus::Frozen)
     }
+
+    pub fn unfreeze(&mut self) -> Result<Idempotent<()>, DepositAccountError> {
+        if self.is_closed() {
+            return Err(DepositAccountError::DepositAccountClosed);
+        }
+        if self.status != DepositAccountStatus::Frozen {
+            return Ok(Idempotent::Ignored);
+        }
+        self.events.push(DepositAccountEvent::Unfrozen {});
+        self.status = DepositAccountStatus::Active;
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub fn close(&mut self) -> Result<Idempotent<()>, DepositAccountError> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            DepositAccountEvent::Closed { .. }
+        );
+        if self.status == DepositAccountStatus::Frozen {
+            return Err(DepositAccountError::DepositAccountFrozen);
+        }
+        self.events.push(DepositAccountEvent::Closed {});
+        self.status = DepositAccountStatus::Closed;
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.status == DepositAccountStatus::Closed
+    }
+
+    /// The last day interest was accrued on, if any.
+    pub fn interest_accrued_through(&self) -> Option<NaiveDate> {
+        self.events.iter_all().rev().find_map(|e| match e {
+            DepositAccountEvent::InterestAccrued { accrued_on, .. } => Some(*accrued_on),
+            _ => None,
+        })
+    }
+
+    pub fn accrue_interest(
+        &mut self,
+        day: NaiveDate,
+        annual_rate: DepositInterestRatePct,
+        settled_balance: UsdCents,
+    ) -> Idempotent<DepositInterestAccrual> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            DepositAccountEvent::InterestAccrued { accrued_on, .. } if accrued_on >= &day
+        );
+        if self.status != DepositAccountStatus::Active {
+            return Idempotent::Ignored;
+        }
+        let accrued_cents = annual_rate.daily_interest(settled_balance);
+        if accrued_cents.is_zero() {
+            return Idempotent::Ignored;
+        }
+
+        let total_cents = self.uncapitalized_accrued_cents() + accrued_cents;
+        let amount = UsdCents::from(
+            total_cents
+                .round_dp_with_strategy(0, RoundingStrategy::ToZero)
+                .to_u64()
+                .expect("should return a valid integer"),
+        ) - self.uncapitalized_interest();
+
+        let ledger_tx_id = CalaTransactionId::new();
+        self.events.push(DepositAccountEvent::InterestAccrued {
+            ledger_tx_id,
+            amount,
+            accrued_cents,
+            accrued_on: day,
+        });
+
+        Idempotent::Executed(DepositInterestAccrual {
+            tx_id: ledger_tx_id,
+            deposit_account_id: self.account_ids.deposit_account_id,
+            amount,
+            effective: day,
+        })
+    }
+
+    /// The day accrued on or the period end recorded with the interest posted by `tx_id`.
+    pub fn interest_date_for_tx(&self, tx_id: CalaTransactionId) -> Option<NaiveDate> {
+        self.events.iter_all().find_map(|e| match e {
+            DepositAccountEvent::InterestAccrued {
+                ledger_tx_id,
+                accrued_on,
+                ..
+            } if *ledger_tx_id == tx_id => Some(*accrued_on),
+            DepositAccountEvent::InterestCapitalized {
+                ledger_tx_id,
+                period_end,
+                ..
+            } if *ledger_tx_id == tx_id => Some(*period_end),
+            _ => None,
+        })
+    }
+
+    /// Unrounded interest accrued since the last capitalization, in cents.
+    fn uncapitalized_accrued_cents(&self) -> Decimal {
+        self.events
+            .iter_all()
+            .rev()
+            .take_while(|e| !matches!(e, DepositAccountEvent::InterestCapitalized { .. }))
+            .filter_map(|e| match e {
+                DepositAccountEvent::InterestAccrued {
+                    amount,
+                    accrued_cents,
+                    ..
+                } if accrued_cents.is_zero() => Some(Decimal::from(amount.into_inner())),
+                DepositAccountEvent::InterestAccrued { accrued_cents, .. } => Some(*accrued_cents),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Interest posted to the ledger since the last capitalization. Daily postings
+    /// carry over fractions of a cent, so this is the rounded down total accrued.
+    pub fn uncapitalized_interest(&self) -> UsdCents {
+        self.events
+            .iter_all()
+            .rev()
+            .take_while(|e| !matches!(e, DepositAccountEvent::InterestCapitalized { .. }))
+            .filter_map(|e| match e {
+                DepositAccountEvent::InterestAccrued { amount, .. } => Some(*amount),
+                _ => None,
+            })
+            .fold(UsdCents::ZERO, |acc, amount| acc + amount)
+    }
+
+    pub fn capitalize_interest(
+        &mut self,
+        period_end: NaiveDate,
+    ) -> Idempotent<DepositInterestCapitalization> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            DepositAccountEvent::InterestCapitalized { period_end: existing_period_end, .. } if existing_period_end >= &period_end
+        );
+        if self.status != DepositAccountStatus::Active {
+            return Idempotent::Ignored;
+        }
+        let amount = self.uncapitalized_interest();
+        if amount.is_zero() {
+            return Idempotent::Ignored;
+        }
+
+        let ledger_tx_id = CalaTransactionId::new();
+        self.events.push(DepositAccountEvent::InterestCapitalized {
+            ledger_tx_id,
+            amount,
+            period_end,
+        });
+
+        Idempotent::Executed(DepositInterestCapitalization {
+            tx_id: ledger_tx_id,
+            deposit_account_id: self.account_ids.deposit_account_id,
+            amount,
+            effective: period_end,
+        })
+    }
 }
 
 impl TryFromEvents<DepositAccountEvent> for DepositAccount {
@@ -83,6 +277,14 @@ impl TryFromEvents<DepositAccountEvent> for DepositAccount {
                 DepositAccountEvent::AccountStatusUpdated { status, .. } => {
                     builder = builder.status(*status);
                 }
+                DepositAccountEvent::Unfrozen { .. } => {
+                    builder = builder.status(DepositAccountStatus::Active);
+                }
+                DepositAccountEvent::Closed { .. } => {
+                    builder = builder.status(DepositAccountStatus::Closed);
+                }
+                DepositAccountEvent::InterestAccrued { .. } => (),
+                DepositAccountEvent::InterestCapitalized { .. } => (),
             }
         }
         builder.events(events).build()
@@ -132,9 +334,17 @@ mod tests {
     use es_entity::{EntityEvents, TryFromEvents as _};
     use public_id::PublicId;
 
-    use crate::{DepositAccountHolderId, DepositAccountId, DepositAccountStatus};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    use crate::{
+        DepositAccountHolderId, DepositAccountId, DepositAccountStatus, DepositInterestRatePct,
+        UsdCents,
+    };
 
-    use super::{DepositAccount, DepositAccountEvent, DepositAccountLedgerAccountIds};
+    use super::{
+        DepositAccount, DepositAccountError, DepositAccountEvent, DepositAccountLedgerAccountIds,
+    };
 
     fn initial_events() -> Vec<DepositAccountEvent> {
         let id = DepositAccountId::new();
@@ -179,4 +389,117 @@ mod tests {
                 .did_execute()
         );
     }
+
+    #[test]
+    fn unfreeze_restores_active_status() {
+        let mut account = DepositAccount::try_from_events(EntityEvents::init(
+            DepositAccountId::new(),
+            initial_events(),
+        ))
+        .unwrap();
+        assert!(account.unfreeze().unwrap().was_ignored());
+
+        let _ = account.freeze();
+        assert!(account.unfreeze().unwrap().did_execute());
+        assert_eq!(account.status, DepositAccountStatus::Active);
+        assert!(account.unfreeze().unwrap().was_ignored());
+
+        assert!(account.freeze().did_execute());
+        assert_eq!(account.status, DepositAccountStatus::Frozen);
+    }
+
+    #[test]
+    fn close_account() {
+        let mut account = DepositAccount::try_from_events(EntityEvents::init(
+            DepositAccountId::new(),
+            initial_events(),
+        ))
+        .unwrap();
+
+        let _ = account.freeze();
+        assert!(matches!(
+            account.close(),
+            Err(DepositAccountError::DepositAccountFrozen)
+        ));
+
+        let _ = account.unfreeze().unwrap();
+        assert!(account.close().unwrap().did_execute());
+        assert!(account.is_closed());
+        assert!(account.close().unwrap().was_ignored());
+
+        assert!(
+            account
+                .update_status(DepositAccountStatus::Active)
+                .was_ignored()
+        );
+        assert!(matches!(
+            account.unfreeze(),
+            Err(DepositAccountError::DepositAccountClosed)
+        ));
+    }
+
+    #[test]
+    fn accrues_and_capitalizes_interest() {
+        let mut account = DepositAccount::try_from_events(EntityEvents::init(
+            DepositAccountId::new(),
+            initial_events(),
+        ))
+        .unwrap();
+        let _ = account.update_status(DepositAccountStatus::Active);
+        let day = NaiveDate::from_ymd_opt(2025, 1, 30).unwrap();
+        let rate = DepositInterestRatePct::from(dec!(5));
+        let balance = UsdCents::from(3_650_000);
+
+        assert_eq!(account.interest_accrued_through(), None);
+        let accrual = account.accrue_interest(day, rate, balance).unwrap();
+        assert_eq!(accrual.amount, UsdCents::from(500));
+        assert_eq!(account.interest_date_for_tx(accrual.tx_id), Some(day));
+        assert!(account.accrue_interest(day, rate, balance).was_ignored());
+        assert_eq!(account.interest_accrued_through(), Some(day));
+
+        let next_day = day.succ_opt().unwrap();
+        assert!(
+            account
+                .accrue_interest(next_day, rate, balance)
+                .did_execute()
+        );
+        assert!(account.accrue_interest(day, rate, balance).was_ignored());
+        assert_eq!(account.uncapitalized_interest(), UsdCents::from(1000));
+
+        let capitalization = account.capitalize_interest(next_day).unwrap();
+        assert_eq!(capitalization.amount, UsdCents::from(1000));
+        assert_eq!(
+            account.interest_date_for_tx(capitalization.tx_id),
+            Some(next_day)
+        );
+        assert_eq!(account.uncapitalized_interest(), UsdCents::ZERO);
+        assert!(account.capitalize_interest(next_day).was_ignored());
+    }
+
+    #[test]
+    fn carries_fractional_interest_to_capitalization() {
+        let mut account = DepositAccount::try_from_events(EntityEvents::init(
+            DepositAccountId::new(),
+            initial_events(),
+        ))
+        .unwrap();
+        let _ = account.update_status(DepositAccountStatus::Active);
+        let rate = DepositInterestRatePct::from(dec!(5));
+        let balance = UsdCents::from(100_000);
+
+        let mut posted = UsdCents::ZERO;
+        for day in NaiveDate::from_ymd_opt(2025, 4, 1)
+            .unwrap()
+            .iter_days()
+            .take(30)
+        {
+            posted += account.accrue_interest(day, rate, balance).unwrap().amount;
+        }
+        assert_eq!(posted, UsdCents::from(410));
+        assert_eq!(account.uncapitalized_interest(), UsdCents::from(410));
+
+        let period_end = NaiveDate::from_ymd_opt(2025, 4, 30).unwrap();
+        let capitalization = account.capitalize_interest(period_end).unwrap();
+        assert_eq!(capitalization.amount, UsdCents::from(410));
+    }
 }
@@ -8,6 +8,10 @@ pub enum DepositAccountError {
     EsEntityError(es_entity::EsEntityError),
     #[error("CommitteeError - CursorDestructureError: {0}")]
     CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("DepositAccountError - DepositAccountFrozen")]
+    DepositAccountFrozen,
+    #[error("DepositAccountError - DepositAccountClosed")]
+    DepositAccountClosed,
 }
 
 es_entity::from_es_entity_error!(DepositAccountError);
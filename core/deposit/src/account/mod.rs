@@ -1,7 +1,9 @@
+mod closure_check;
 mod entity;
 pub mod error;
 mod repo;
 
+pub use closure_check::DepositAccountClosureCheck;
 pub use entity::DepositAccount;
 #[cfg(feature = "json-schema")]
 pub use entity::DepositAccountEvent;
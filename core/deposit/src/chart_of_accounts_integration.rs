@@ -2,10 +2,12 @@ use serde::{Deserialize, Serialize};
 
 use core_accounting::{AccountCode, ChartId};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ChartOfAccountsIntegrationConfig {
     pub chart_of_accounts_id: ChartId,
     pub chart_of_accounts_omnibus_parent_code: AccountCode,
+    #[serde(default)]
+    pub chart_of_accounts_interest_expense_parent_code: Option<AccountCode>,
     pub chart_of_accounts_individual_deposit_accounts_parent_code: AccountCode,
     pub chart_of_accounts_government_entity_deposit_accounts_parent_code: AccountCode,
     pub chart_of_account_private_company_deposit_accounts_parent_code: AccountCode,
@@ -19,3 +21,71 @@ pub struct ChartOfAccountsIntegrationConfig {
     pub chart_of_account_frozen_financial_institution_deposit_accounts_parent_code: AccountCode,
     pub chart_of_account_frozen_non_domiciled_individual_deposit_accounts_parent_code: AccountCode,
 }
+
+impl ChartOfAccountsIntegrationConfig {
+    /// Whether `new` only adds the interest expense parent to a config set before it existed.
+    pub(crate) fn is_completed_by(&self, new: &Self) -> bool {
+        self.chart_of_accounts_interest_expense_parent_code
+            .is_none()
+            && new.chart_of_accounts_interest_expense_parent_code.is_some()
+            && *new
+                == Self {
+                    chart_of_accounts_interest_expense_parent_code: new
+                        .chart_of_accounts_interest_expense_parent_code
+                        .clone(),
+                    ..self.clone()
+                }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(interest_expense_parent_code: Option<&str>) -> ChartOfAccountsIntegrationConfig {
+        let code = |code: &str| code.parse::<AccountCode>().unwrap();
+        ChartOfAccountsIntegrationConfig {
+            chart_of_accounts_id: ChartId::new(),
+            chart_of_accounts_omnibus_parent_code: code("1"),
+            chart_of_accounts_interest_expense_parent_code: interest_expense_parent_code.map(code),
+            chart_of_accounts_individual_deposit_accounts_parent_code: code("2"),
+            chart_of_accounts_government_entity_deposit_accounts_parent_code: code("2"),
+            chart_of_account_private_company_deposit_accounts_parent_code: code("2"),
+            chart_of_account_bank_deposit_accounts_parent_code: code("2"),
+            chart_of_account_financial_institution_deposit_accounts_parent_code: code("2"),
+            chart_of_account_non_domiciled_individual_deposit_accounts_parent_code: code("2"),
+            chart_of_accounts_frozen_individual_deposit_accounts_parent_code: code("3"),
+            chart_of_accounts_frozen_government_entity_deposit_accounts_parent_code: code("3"),
+            chart_of_account_frozen_private_company_deposit_accounts_parent_code: code("3"),
+            chart_of_account_frozen_bank_deposit_accounts_parent_code: code("3"),
+            chart_of_account_frozen_financial_institution_deposit_accounts_parent_code: code("3"),
+            chart_of_account_frozen_non_domiciled_individual_deposit_accounts_parent_code: code(
+                "3",
+            ),
+        }
+    }
+
+    #[test]
+    fn completed_by_adding_interest_expense_parent() {
+        let existing = config(None);
+        let new = ChartOfAccountsIntegrationConfig {
+            chart_of_accounts_interest_expense_parent_code: Some("4".parse().unwrap()),
+            ..existing.clone()
+        };
+        assert!(existing.is_completed_by(&new));
+    }
+
+    #[test]
+    fn not_completed_by_other_changes() {
+        let existing = config(None);
+        let new = ChartOfAccountsIntegrationConfig {
+            chart_of_accounts_interest_expense_parent_code: Some("4".parse().unwrap()),
+            chart_of_accounts_omnibus_parent_code: "5".parse().unwrap(),
+            ..existing.clone()
+        };
+        assert!(!existing.is_completed_by(&new));
+
+        let existing = config(Some("4"));
+        assert!(!existing.is_completed_by(&existing.clone()));
+    }
+}
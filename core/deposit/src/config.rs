@@ -0,0 +1,78 @@
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::{DepositAccountType, DepositInterestRatePct, UsdCents};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct DepositConfig {
+    #[serde(default = "default_transfer_approval_threshold")]
+    pub transfer_approval_threshold: UsdCents,
+    #[serde(default)]
+    pub interest_rates: DepositInterestRates,
+}
+
+impl Default for DepositConfig {
+    fn default() -> Self {
+        DepositConfig {
+            transfer_approval_threshold: default_transfer_approval_threshold(),
+            interest_rates: DepositInterestRates::default(),
+        }
+    }
+}
+
+fn default_transfer_approval_threshold() -> UsdCents {
+    UsdCents::from(1_000_000)
+}
+
+/// Annual interest rate paid on deposit accounts of each type. Accounts of a type
+/// without a rate do not earn interest.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct DepositInterestRates {
+    #[serde(default)]
+    pub individual: Option<DepositInterestRatePct>,
+    #[serde(default)]
+    pub government_entity: Option<DepositInterestRatePct>,
+    #[serde(default)]
+    pub private_company: Option<DepositInterestRatePct>,
+    #[serde(default)]
+    pub bank: Option<DepositInterestRatePct>,
+    #[serde(default)]
+    pub financial_institution: Option<DepositInterestRatePct>,
+    #[serde(default)]
+    pub non_domiciled_company: Option<DepositInterestRatePct>,
+}
+
+impl DepositInterestRates {
+    pub fn for_account_type(
+        &self,
+        account_type: DepositAccountType,
+    ) -> Option<DepositInterestRatePct> {
+        match account_type {
+            DepositAccountType::Individual => self.individual,
+            DepositAccountType::GovernmentEntity => self.government_entity,
+            DepositAccountType::PrivateCompany => self.private_company,
+            DepositAccountType::Bank => self.bank,
+            DepositAccountType::FinancialInstitution => self.financial_institution,
+            DepositAccountType::NonDomiciledCompany => self.non_domiciled_company,
+        }
+    }
+
+    pub(crate) fn any_negative(&self) -> bool {
+        [
+            self.individual,
+            self.government_entity,
+            self.private_company,
+            self.bank,
+            self.financial_institution,
+            self.non_domiciled_company,
+        ]
+        .iter()
+        .flatten()
+        .any(|rate| rate.is_negative())
+    }
+}
@@ -14,6 +14,8 @@ pub enum CoreDepositError {
     DepositError(#[from] crate::deposit::error::DepositError),
     #[error("CoreDepositError - WithdrawalError: {0}")]
     WithdrawalError(#[from] crate::withdrawal::error::WithdrawalError),
+    #[error("CoreDepositError - TransferError: {0}")]
+    TransferError(#[from] crate::transfer::error::TransferError),
     #[error("CoreDepositError - DepositLedgerError: {0}")]
     DepositLedgerError(#[from] crate::ledger::error::DepositLedgerError),
     #[error("CoreDepositError - GovernanceError: {0}")]
@@ -38,10 +40,22 @@ pub enum CoreDepositError {
     DepositAccountInactive,
     #[error("CoreDepositError - DepositAccountFrozen")]
     DepositAccountFrozen,
+    #[error("CoreDepositError - DepositAccountClosed")]
+    DepositAccountClosed,
+    #[error("CoreDepositError - DepositAccountHasBalance")]
+    DepositAccountHasBalance,
+    #[error("CoreDepositError - DepositAccountHasOpenCreditFacility")]
+    DepositAccountHasOpenCreditFacility,
+    #[error("CoreDepositError - ClosureCheckError: {0}")]
+    ClosureCheckError(Box<dyn std::error::Error + Send + Sync>),
+    #[error("CoreDepositError - NegativeInterestRate")]
+    NegativeInterestRate,
     #[error("CoreDepositError - WithdrawalBuilderError: {0}")]
     WithdrawalBuilderError(#[from] super::NewWithdrawalBuilderError),
     #[error("CoreDepositError - DepositBuilderError: {0}")]
     DepositBuilderError(#[from] super::NewDepositBuilderError),
+    #[error("CoreDepositError - TransferBuilderError: {0}")]
+    TransferBuilderError(#[from] super::NewTransferBuilderError),
     #[error("CoreDepositError - PublicIdError: {0}")]
     PublicIdError(#[from] public_id::PublicIdError),
 }
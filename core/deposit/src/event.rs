@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use super::primitives::{DepositAccountHolderId, DepositAccountId, DepositId, WithdrawalId};
+use super::primitives::{
+    DepositAccountHolderId, DepositAccountId, DepositId, TransferId, WithdrawalId,
+};
 use core_money::UsdCents;
 
 #[derive(Debug, Serialize, Deserialize, strum::AsRefStr)]
@@ -25,8 +27,22 @@ pub enum CoreDepositEvent {
         deposit_account_id: DepositAccountId,
         amount: UsdCents,
     },
+    TransferExecuted {
+        id: TransferId,
+        from_account_id: DepositAccountId,
+        to_account_id: DepositAccountId,
+        amount: UsdCents,
+    },
     DepositAccountFrozen {
         id: DepositAccountId,
         account_holder_id: DepositAccountHolderId,
     },
+    DepositAccountUnfrozen {
+        id: DepositAccountId,
+        account_holder_id: DepositAccountHolderId,
+    },
+    DepositAccountClosed {
+        id: DepositAccountId,
+        account_holder_id: DepositAccountHolderId,
+    },
 }
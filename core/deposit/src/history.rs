@@ -1,7 +1,10 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::primitives::{CalaEntryId, CalaTransactionId as CalaTxId};
+use crate::{
+    account::DepositAccount,
+    primitives::{CalaEntryId, CalaTransactionId as CalaTxId, UsdCents},
+};
 
 pub enum DepositAccountHistoryEntry {
     Deposit(DepositEntry),
@@ -9,6 +12,9 @@ pub enum DepositAccountHistoryEntry {
     CancelledWithdrawal(WithdrawalEntry),
     Disbursal(DisbursalEntry),
     Payment(PaymentEntry),
+    Transfer(TransferEntry),
+    InterestAccrual(InterestAccrualEntry),
+    InterestPayment(InterestPaymentEntry),
     Unknown(UnknownEntry),
     Ignored,
 }
@@ -36,6 +42,28 @@ pub struct PaymentEntry {
     pub recorded_at: DateTime<Utc>,
 }
 
+pub struct TransferEntry {
+    pub tx_id: CalaTxId,
+    pub entry_id: CalaEntryId,
+    pub recorded_at: DateTime<Utc>,
+}
+
+pub struct InterestAccrualEntry {
+    pub tx_id: CalaTxId,
+    pub entry_id: CalaEntryId,
+    pub recorded_at: DateTime<Utc>,
+    pub amount: UsdCents,
+    pub accrued_on: NaiveDate,
+}
+
+pub struct InterestPaymentEntry {
+    pub tx_id: CalaTxId,
+    pub entry_id: CalaEntryId,
+    pub recorded_at: DateTime<Utc>,
+    pub amount: UsdCents,
+    pub period_end: NaiveDate,
+}
+
 pub struct UnknownEntry {
     pub tx_id: CalaTxId,
     pub entry_id: CalaEntryId,
@@ -47,10 +75,15 @@ const INITIATE_WITHDRAW: &str = "INITIATE_WITHDRAW_SETTLED_DR";
 const CANCEL_WITHDRAW: &str = "CANCEL_WITHDRAW_SETTLED_CR";
 const CONFIRM_DISBURSAL: &str = "CONFIRM_DISBURSAL_SETTLED_CR";
 const RECORD_PAYMENT_ALLOCATION: &str = "RECORD_PAYMENT_ALLOCATION_DR";
+const TRANSFER_DEBIT: &str = "TRANSFER_DR";
+const TRANSFER_CREDIT: &str = "TRANSFER_CR";
+const ACCRUE_DEPOSIT_INTEREST: &str = "ACCRUE_DEPOSIT_INTEREST_CR";
+const CAPITALIZE_DEPOSIT_INTEREST: &str = "CAPITALIZE_DEPOSIT_INTEREST_SETTLED_CR";
 
 const IGNORE_INITIATE_WITHDRAW_PENDING: &str = "INITIATE_WITHDRAW_PENDING_CR";
 const IGNORE_CONFIRM_WITHDRAWAL_PENDING: &str = "CONFIRM_WITHDRAW_PENDING_DR";
 const IGNORE_CANCEL_WITHDRAW_PENDING: &str = "CANCEL_WITHDRAW_PENDING_DR";
+const IGNORE_CAPITALIZE_DEPOSIT_INTEREST_PENDING: &str = "CAPITALIZE_DEPOSIT_INTEREST_PENDING_DR";
 
 impl From<cala_ledger::entry::Entry> for DepositAccountHistoryEntry {
     fn from(entry: cala_ledger::entry::Entry) -> Self {
@@ -80,10 +113,36 @@ impl From<cala_ledger::entry::Entry> for DepositAccountHistoryEntry {
                 entry_id: entry.id,
                 recorded_at: entry.created_at(),
             }),
+            TRANSFER_DEBIT | TRANSFER_CREDIT => {
+                DepositAccountHistoryEntry::Transfer(TransferEntry {
+                    tx_id: entry.values().transaction_id,
+                    entry_id: entry.id,
+                    recorded_at: entry.created_at(),
+                })
+            }
+            ACCRUE_DEPOSIT_INTEREST => {
+                DepositAccountHistoryEntry::InterestAccrual(InterestAccrualEntry {
+                    tx_id: entry.values().transaction_id,
+                    entry_id: entry.id,
+                    recorded_at: entry.created_at(),
+                    amount: interest_amount(&entry),
+                    accrued_on: entry.created_at().date_naive(),
+                })
+            }
+            CAPITALIZE_DEPOSIT_INTEREST => {
+                DepositAccountHistoryEntry::InterestPayment(InterestPaymentEntry {
+                    tx_id: entry.values().transaction_id,
+                    entry_id: entry.id,
+                    recorded_at: entry.created_at(),
+                    amount: interest_amount(&entry),
+                    period_end: entry.created_at().date_naive(),
+                })
+            }
 
             IGNORE_CONFIRM_WITHDRAWAL_PENDING => DepositAccountHistoryEntry::Ignored,
             IGNORE_INITIATE_WITHDRAW_PENDING => DepositAccountHistoryEntry::Ignored,
             IGNORE_CANCEL_WITHDRAW_PENDING => DepositAccountHistoryEntry::Ignored,
+            IGNORE_CAPITALIZE_DEPOSIT_INTEREST_PENDING => DepositAccountHistoryEntry::Ignored,
 
             _ => DepositAccountHistoryEntry::Unknown(UnknownEntry {
                 tx_id: entry.values().transaction_id,
@@ -94,6 +153,32 @@ impl From<cala_ledger::entry::Entry> for DepositAccountHistoryEntry {
     }
 }
 
+fn interest_amount(entry: &cala_ledger::entry::Entry) -> UsdCents {
+    UsdCents::try_from_usd(entry.values().units).expect("interest entries have positive units")
+}
+
+impl DepositAccountHistoryEntry {
+    /// Replaces the recorded day of interest entries with the day the interest was
+    /// accrued on or the end of the period it was capitalized for.
+    pub(crate) fn with_interest_dates(self, account: &DepositAccount) -> Self {
+        match self {
+            Self::InterestAccrual(mut entry) => {
+                if let Some(day) = account.interest_date_for_tx(entry.tx_id) {
+                    entry.accrued_on = day;
+                }
+                Self::InterestAccrual(entry)
+            }
+            Self::InterestPayment(mut entry) => {
+                if let Some(day) = account.interest_date_for_tx(entry.tx_id) {
+                    entry.period_end = day;
+                }
+                Self::InterestPayment(entry)
+            }
+            entry => entry,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DepositAccountHistoryCursor {
     pub entry_id: CalaEntryId,
@@ -123,6 +208,18 @@ impl From<&DepositAccountHistoryEntry> for DepositAccountHistoryCursor {
                 entry_id: entry.entry_id,
                 created_at: entry.recorded_at,
             },
+            DepositAccountHistoryEntry::Transfer(entry) => Self {
+                entry_id: entry.entry_id,
+                created_at: entry.recorded_at,
+            },
+            DepositAccountHistoryEntry::InterestAccrual(entry) => Self {
+                entry_id: entry.entry_id,
+                created_at: entry.recorded_at,
+            },
+            DepositAccountHistoryEntry::InterestPayment(entry) => Self {
+                entry_id: entry.entry_id,
+                created_at: entry.recorded_at,
+            },
             DepositAccountHistoryEntry::Unknown(entry) => Self {
                 entry_id: entry.entry_id,
                 created_at: entry.recorded_at,
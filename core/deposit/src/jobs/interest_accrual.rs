@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use es_entity::Idempotent;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    account::{DepositAccount, DepositAccountRepo},
+    config::DepositInterestRates,
+    event::CoreDepositEvent,
+    ledger::DepositLedger,
+    primitives::DepositAccountStatus,
+};
+
+const ACCOUNTS_PAGE_SIZE: usize = 100;
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+struct DepositInterestAccrualState {
+    /// Interest has been accrued for every account up to and including this day.
+    accrued_through: Option<NaiveDate>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct DepositInterestAccrualJobConfig<E> {
+    _phantom: std::marker::PhantomData<E>,
+}
+impl<E> DepositInterestAccrualJobConfig<E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<E> JobConfig for DepositInterestAccrualJobConfig<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    type Initializer = DepositInterestAccrualInit<E>;
+}
+
+pub(crate) struct DepositInterestAccrualInit<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    deposit_accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    interest_rates: DepositInterestRates,
+}
+
+impl<E> DepositInterestAccrualInit<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        deposit_accounts: &DepositAccountRepo<E>,
+        ledger: &DepositLedger,
+        interest_rates: &DepositInterestRates,
+    ) -> Self {
+        Self {
+            deposit_accounts: deposit_accounts.clone(),
+            ledger: ledger.clone(),
+            interest_rates: interest_rates.clone(),
+        }
+    }
+}
+
+const DEPOSIT_INTEREST_ACCRUAL_JOB: JobType = JobType::new("cron.deposit-interest-accrual");
+impl<E> JobInitializer for DepositInterestAccrualInit<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        DEPOSIT_INTEREST_ACCRUAL_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(DepositInterestAccrualJobRunner::<E> {
+            deposit_accounts: self.deposit_accounts.clone(),
+            ledger: self.ledger.clone(),
+            interest_rates: self.interest_rates.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+pub(crate) struct DepositInterestAccrualJobRunner<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    deposit_accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    interest_rates: DepositInterestRates,
+}
+
+impl<E> DepositInterestAccrualJobRunner<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    /// Accrues every day from `from` (or the day after the account last accrued, if
+    /// later) up to and including `until`.
+    async fn accrue_interest(
+        &self,
+        mut account: DepositAccount,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if account.status != DepositAccountStatus::Active {
+            return Ok(());
+        }
+        let Some(annual_rate) = self
+            .ledger
+            .deposit_account_type(account.id)
+            .await?
+            .and_then(|account_type| self.interest_rates.for_account_type(account_type))
+        else {
+            return Ok(());
+        };
+
+        let from = account
+            .interest_accrued_through()
+            .and_then(|day| day.succ_opt())
+            .map_or(from, |next| next.max(from));
+        for day in from.iter_days().take_while(|day| *day <= until) {
+            let balance = self.ledger.settled_balance_as_of(account.id, day).await?;
+            if let Idempotent::Executed(accrual) =
+                account.accrue_interest(day, annual_rate, balance)
+            {
+                let mut op = self.deposit_accounts.begin_op().await?;
+                self.deposit_accounts
+                    .update_in_op(&mut op, &mut account)
+                    .await?;
+                if accrual.amount.is_zero() {
+                    op.commit().await?;
+                } else {
+                    self.ledger
+                        .record_interest_accrual(op, account.id, accrual)
+                        .await?;
+                }
+            }
+
+            if is_last_day_of_month(day)
+                && let Idempotent::Executed(capitalization) = account.capitalize_interest(day)
+            {
+                let mut op = self.deposit_accounts.begin_op().await?;
+                self.deposit_accounts
+                    .update_in_op(&mut op, &mut account)
+                    .await?;
+                self.ledger
+                    .record_interest_capitalization(op, account.id, capitalization)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<E> JobRunner for DepositInterestAccrualJobRunner<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    #[instrument(name = "deposit.job.interest-accrual", skip(self, current_job))]
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let today = crate::time::now().date_naive();
+        let yesterday = today.pred_opt().expect("should have a previous day");
+
+        let mut state = current_job
+            .execution_state::<DepositInterestAccrualState>()?
+            .unwrap_or_default();
+        let from = state
+            .accrued_through
+            .and_then(|day| day.succ_opt())
+            .unwrap_or(yesterday);
+
+        if from <= yesterday {
+            let mut all_accrued = true;
+            let mut query = es_entity::PaginatedQueryArgs {
+                first: ACCOUNTS_PAGE_SIZE,
+                after: None,
+            };
+            loop {
+                let ret = self
+                    .deposit_accounts
+                    .list_by_created_at(query, es_entity::ListDirection::Ascending)
+                    .await?;
+                for account in ret.entities {
+                    let id = account.id;
+                    if let Err(e) = self.accrue_interest(account, from, yesterday).await {
+                        all_accrued = false;
+                        tracing::error!(
+                            deposit_account_id = %id,
+                            error = %e,
+                            "could not accrue deposit interest"
+                        );
+                    }
+                }
+                if !ret.has_next_page {
+                    break;
+                }
+                query = es_entity::PaginatedQueryArgs {
+                    first: ACCOUNTS_PAGE_SIZE,
+                    after: ret.end_cursor,
+                };
+            }
+
+            // Failed accounts are retried from the same day on the next run.
+            if all_accrued {
+                state.accrued_through = Some(yesterday);
+                current_job.update_execution_state(&state).await?;
+            }
+        }
+
+        let next_run_at = (today + chrono::Days::new(1))
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight should be valid")
+            .and_utc();
+        Ok(JobCompletion::RescheduleAt(next_run_at))
+    }
+}
+
+fn is_last_day_of_month(day: NaiveDate) -> bool {
+    day.succ_opt()
+        .map(|next_day| next_day.month() != day.month())
+        .unwrap_or(true)
+}
@@ -0,0 +1 @@
+pub mod interest_accrual;
@@ -32,6 +32,8 @@ pub enum DepositLedgerError {
         "DepositLedgerError - NonAccountMemberFoundInAccountSet: Found non-Account typed member in account set {0}"
     )]
     NonAccountMemberFoundInAccountSet(String),
+    #[error("DepositLedgerError - MalformedIntegrationMetadata: {0}")]
+    MalformedIntegrationMetadata(serde_json::Error),
     #[error("DepositLedgerError - JournalIdMismatch: Account sets have wrong JournalId")]
     JournalIdMismatch,
 }
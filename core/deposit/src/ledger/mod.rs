@@ -18,12 +18,12 @@ use cala_ledger::{
 };
 
 use crate::{
-    DepositAccount, DepositAccountBalance, DepositReversalData, LedgerOmnibusAccountIds,
-    WithdrawalReversalData,
+    DepositAccount, DepositAccountBalance, DepositInterestAccrual, DepositInterestCapitalization,
+    DepositReversalData, LedgerOmnibusAccountIds, WithdrawalReversalData,
     chart_of_accounts_integration::ChartOfAccountsIntegrationConfig,
     primitives::{
-        CalaAccountId, CalaAccountSetId, DEPOSIT_ACCOUNT_ENTITY_TYPE, DepositAccountType,
-        DepositId, UsdCents, WithdrawalId,
+        CalaAccountId, CalaAccountSetId, DEPOSIT_ACCOUNT_ENTITY_TYPE, DepositAccountId,
+        DepositAccountType, DepositId, TransferId, UsdCents, WithdrawalId,
     },
 };
 
@@ -74,6 +74,10 @@ pub const DEPOSIT_OMNIBUS_ACCOUNT_SET_NAME: &str = "Deposit Omnibus Account Set"
 pub const DEPOSIT_OMNIBUS_ACCOUNT_SET_REF: &str = "deposit-omnibus-account-set";
 pub const DEPOSIT_OMNIBUS_ACCOUNT_REF: &str = "deposit-omnibus-account";
 
+pub const DEPOSIT_INTEREST_EXPENSE_ACCOUNT_SET_NAME: &str = "Deposit Interest Expense Account Set";
+pub const DEPOSIT_INTEREST_EXPENSE_ACCOUNT_SET_REF: &str = "deposit-interest-expense-account-set";
+pub const DEPOSIT_INTEREST_EXPENSE_ACCOUNT_REF: &str = "deposit-interest-expense-account";
+
 pub const DEPOSITS_VELOCITY_CONTROL_ID: uuid::Uuid =
     uuid::uuid!("00000000-0000-0000-0000-000000000001");
 
@@ -108,6 +112,28 @@ impl DepositAccountSets {
     fn account_set_id_for_config(&self) -> CalaAccountSetId {
         self.individual.id
     }
+
+    fn account_type_for(&self, account_set_id: CalaAccountSetId) -> Option<DepositAccountType> {
+        [
+            (self.individual.id, DepositAccountType::Individual),
+            (
+                self.government_entity.id,
+                DepositAccountType::GovernmentEntity,
+            ),
+            (self.private_company.id, DepositAccountType::PrivateCompany),
+            (self.bank.id, DepositAccountType::Bank),
+            (
+                self.financial_institution.id,
+                DepositAccountType::FinancialInstitution,
+            ),
+            (
+                self.non_domiciled_individual.id,
+                DepositAccountType::NonDomiciledCompany,
+            ),
+        ]
+        .into_iter()
+        .find_map(|(id, account_type)| (id == account_set_id).then_some(account_type))
+    }
 }
 
 #[derive(Clone)]
@@ -117,6 +143,7 @@ pub struct DepositLedger {
     deposit_account_sets: DepositAccountSets,
     frozen_deposit_account_sets: DepositAccountSets,
     deposit_omnibus_account_ids: LedgerOmnibusAccountIds,
+    interest_expense_account_ids: LedgerOmnibusAccountIds,
     usd: Currency,
     deposit_control_id: VelocityControlId,
 }
@@ -133,6 +160,10 @@ impl DepositLedger {
         templates::RevertWithdraw::init(cala).await?;
         templates::RevertDeposit::init(cala).await?;
         templates::FreezeAccount::init(cala).await?;
+        templates::UnfreezeAccount::init(cala).await?;
+        templates::Transfer::init(cala).await?;
+        templates::AccrueDepositInterest::init(cala).await?;
+        templates::CapitalizeDepositInterest::init(cala).await?;
 
         let deposits_normal_balance_type = DebitOrCredit::Credit;
 
@@ -254,6 +285,16 @@ impl DepositLedger {
         )
         .await?;
 
+        let interest_expense_account_ids = Self::find_or_create_omnibus_account(
+            cala,
+            journal_id,
+            format!("{journal_id}:{DEPOSIT_INTEREST_EXPENSE_ACCOUNT_SET_REF}"),
+            format!("{journal_id}:{DEPOSIT_INTEREST_EXPENSE_ACCOUNT_REF}"),
+            DEPOSIT_INTEREST_EXPENSE_ACCOUNT_SET_NAME.to_string(),
+            DebitOrCredit::Debit,
+        )
+        .await?;
+
         let overdraft_prevention_id = velocity::OverdraftPrevention::init(cala).await?;
 
         let deposit_control_id = Self::create_deposit_control(cala).await?;
@@ -323,6 +364,7 @@ impl DepositLedger {
                 },
             },
             deposit_omnibus_account_ids,
+            interest_expense_account_ids,
             deposit_control_id,
             usd: Currency::USD,
         })
@@ -504,6 +546,99 @@ impl DepositLedger {
         Ok(())
     }
 
+    pub async fn transfer(
+        &self,
+        op: es_entity::DbOp<'_>,
+        entity_id: TransferId,
+        tx_id: impl Into<TransactionId>,
+        amount: UsdCents,
+        debit_account_id: impl Into<AccountId>,
+        credit_account_id: impl Into<AccountId>,
+    ) -> Result<(), DepositLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        let params = templates::TransferParams {
+            entity_id: entity_id.into(),
+            journal_id: self.journal_id,
+            currency: self.usd,
+            amount: amount.to_usd(),
+            debit_account_id: debit_account_id.into(),
+            credit_account_id: credit_account_id.into(),
+        };
+        self.cala
+            .post_transaction_in_op(&mut op, tx_id.into(), templates::TRANSFER_CODE, params)
+            .await?;
+
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn record_interest_accrual(
+        &self,
+        op: es_entity::DbOp<'_>,
+        entity_id: DepositAccountId,
+        accrual: DepositInterestAccrual,
+    ) -> Result<(), DepositLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        let params = templates::AccrueDepositInterestParams {
+            entity_id: entity_id.into(),
+            journal_id: self.journal_id,
+            currency: self.usd,
+            amount: accrual.amount.to_usd(),
+            interest_expense_account_id: self.interest_expense_account_ids.account_id,
+            deposit_account_id: accrual.deposit_account_id,
+            effective: accrual.effective,
+        };
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                accrual.tx_id,
+                templates::ACCRUE_DEPOSIT_INTEREST_CODE,
+                params,
+            )
+            .await?;
+
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn record_interest_capitalization(
+        &self,
+        op: es_entity::DbOp<'_>,
+        entity_id: DepositAccountId,
+        capitalization: DepositInterestCapitalization,
+    ) -> Result<(), DepositLedgerError> {
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        let params = templates::CapitalizeDepositInterestParams {
+            entity_id: entity_id.into(),
+            journal_id: self.journal_id,
+            currency: self.usd,
+            amount: capitalization.amount.to_usd(),
+            interest_expense_account_id: self.interest_expense_account_ids.account_id,
+            deposit_account_id: capitalization.deposit_account_id,
+            effective: capitalization.effective,
+        };
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                capitalization.tx_id,
+                templates::CAPITALIZE_DEPOSIT_INTEREST_CODE,
+                params,
+            )
+            .await?;
+
+        op.commit().await?;
+        Ok(())
+    }
+
     pub async fn initiate_withdrawal(
         &self,
         op: es_entity::DbOp<'_>,
@@ -632,6 +767,41 @@ impl DepositLedger {
         Ok(())
     }
 
+    pub async fn unfreeze_account_in_op(
+        &self,
+        op: es_entity::DbOp<'_>,
+        account: &DepositAccount,
+    ) -> Result<(), DepositLedgerError> {
+        let frozen_balance = self
+            .balance(account.account_ids.frozen_deposit_account_id)
+            .await?;
+
+        let mut op = self
+            .cala
+            .ledger_operation_from_db_op(op.with_db_time().await?);
+
+        let params = templates::UnfreezeAccountParams {
+            journal_id: self.journal_id,
+            account_id: account.account_ids.deposit_account_id,
+            frozen_accounts_account_id: account.account_ids.frozen_deposit_account_id,
+            amount: frozen_balance.settled.to_usd(),
+            currency: self.usd,
+        };
+
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                TransactionId::new(),
+                templates::UNFREEZE_ACCOUNT_CODE,
+                params,
+            )
+            .await?;
+
+        op.commit().await?;
+
+        Ok(())
+    }
+
     pub async fn confirm_withdrawal(
         &self,
         op: es_entity::DbOp<'_>,
@@ -715,6 +885,41 @@ impl DepositLedger {
         }
     }
 
+    pub async fn settled_balance_as_of(
+        &self,
+        account_id: impl Into<AccountId>,
+        day: chrono::NaiveDate,
+    ) -> Result<UsdCents, DepositLedgerError> {
+        match self
+            .cala
+            .balances()
+            .effective()
+            .find_cumulative(self.journal_id, account_id.into(), self.usd, day)
+            .await
+        {
+            Ok(balances) => Ok(UsdCents::try_from_usd(balances.settled())?),
+            Err(cala_ledger::balance::error::BalanceError::NotFound(..)) => Ok(UsdCents::ZERO),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The type a deposit account was opened with, from the account set it was created in.
+    pub async fn deposit_account_type(
+        &self,
+        account_id: impl Into<AccountId>,
+    ) -> Result<Option<DepositAccountType>, DepositLedgerError> {
+        let parents = self
+            .cala
+            .account_sets()
+            .find_where_member(account_id.into(), Default::default())
+            .await?
+            .entities;
+
+        Ok(parents
+            .iter()
+            .find_map(|parent| self.deposit_account_sets.account_type_for(parent.id())))
+    }
+
     pub async fn create_deposit_accounts(
         &self,
         op: es_entity::DbOp<'_>,
@@ -881,8 +1086,8 @@ impl DepositLedger {
             .find(self.deposit_account_sets.account_set_id_for_config())
             .await?;
         if let Some(meta) = account_set.values().metadata.as_ref() {
-            let meta: ChartOfAccountsIntegrationMeta =
-                serde_json::from_value(meta.clone()).expect("Could not deserialize metadata");
+            let meta: ChartOfAccountsIntegrationMeta = serde_json::from_value(meta.clone())
+                .map_err(DepositLedgerError::MalformedIntegrationMetadata)?;
             Ok(Some(meta.config))
         } else {
             Ok(None)
@@ -899,28 +1104,38 @@ impl DepositLedger {
         old_parent_id_getter: F,
     ) -> Result<(), DepositLedgerError>
     where
-        F: FnOnce(ChartOfAccountsIntegrationMeta) -> CalaAccountSetId,
+        F: FnOnce(ChartOfAccountsIntegrationMeta) -> Option<CalaAccountSetId>,
     {
         let mut internal_account_set = account_sets
             .remove(&internal_account_set_id)
             .expect("internal account set not found");
 
+        let mut already_attached = false;
         if let Some(old_meta) = internal_account_set.values().metadata.as_ref() {
-            let old_meta: ChartOfAccountsIntegrationMeta =
-                serde_json::from_value(old_meta.clone()).expect("Could not deserialize metadata");
-            let old_parent_account_set_id = old_parent_id_getter(old_meta);
-            if old_parent_account_set_id != parent_account_set_id {
-                self.cala
-                    .account_sets()
-                    .remove_member_in_op(op, old_parent_account_set_id, internal_account_set_id)
-                    .await?;
+            let old_meta: ChartOfAccountsIntegrationMeta = serde_json::from_value(old_meta.clone())
+                .map_err(DepositLedgerError::MalformedIntegrationMetadata)?;
+            match old_parent_id_getter(old_meta) {
+                Some(old_parent_account_set_id)
+                    if old_parent_account_set_id == parent_account_set_id =>
+                {
+                    already_attached = true;
+                }
+                Some(old_parent_account_set_id) => {
+                    self.cala
+                        .account_sets()
+                        .remove_member_in_op(op, old_parent_account_set_id, internal_account_set_id)
+                        .await?;
+                }
+                None => (),
             }
         }
 
-        self.cala
-            .account_sets()
-            .add_member_in_op(op, parent_account_set_id, internal_account_set_id)
-            .await?;
+        if !already_attached {
+            self.cala
+                .account_sets()
+                .add_member_in_op(op, parent_account_set_id, internal_account_set_id)
+                .await?;
+        }
         let mut update = AccountSetUpdate::default();
         update
             .metadata(new_meta)
@@ -940,7 +1155,10 @@ impl DepositLedger {
     ) -> Result<(), DepositLedgerError> {
         let mut op = self.cala.begin_operation().await?;
 
-        let mut account_set_ids = vec![self.deposit_omnibus_account_ids.account_set_id];
+        let mut account_set_ids = vec![
+            self.deposit_omnibus_account_ids.account_set_id,
+            self.interest_expense_account_ids.account_set_id,
+        ];
         account_set_ids.extend(self.deposit_account_sets.account_set_ids());
         account_set_ids.extend(self.frozen_deposit_account_sets.account_set_ids());
 
@@ -954,6 +1172,7 @@ impl DepositLedger {
             config: _,
             audit_info: _,
             omnibus_parent_account_set_id,
+            interest_expense_parent_account_set_id,
             individual_deposit_accounts_parent_account_set_id,
             government_entity_deposit_accounts_parent_account_set_id,
             private_company_deposit_accounts_parent_account_set_id,
@@ -974,17 +1193,30 @@ impl DepositLedger {
             self.deposit_omnibus_account_ids.account_set_id,
             *omnibus_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.omnibus_parent_account_set_id,
+            |meta| Some(meta.omnibus_parent_account_set_id),
         )
         .await?;
 
+        if let Some(interest_expense_parent_account_set_id) = interest_expense_parent_account_set_id
+        {
+            self.attach_charts_account_set(
+                &mut op,
+                &mut account_sets,
+                self.interest_expense_account_ids.account_set_id,
+                *interest_expense_parent_account_set_id,
+                &charts_integration_meta,
+                |meta| meta.interest_expense_parent_account_set_id,
+            )
+            .await?;
+        }
+
         self.attach_charts_account_set(
             &mut op,
             &mut account_sets,
             self.deposit_account_sets.individual.id,
             *individual_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.individual_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.individual_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -994,7 +1226,7 @@ impl DepositLedger {
             self.deposit_account_sets.government_entity.id,
             *government_entity_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.government_entity_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.government_entity_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1004,7 +1236,7 @@ impl DepositLedger {
             self.deposit_account_sets.private_company.id,
             *private_company_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.private_company_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.private_company_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1014,7 +1246,7 @@ impl DepositLedger {
             self.deposit_account_sets.bank.id,
             *bank_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.bank_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.bank_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1024,7 +1256,7 @@ impl DepositLedger {
             self.deposit_account_sets.financial_institution.id,
             *financial_institution_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.financial_institution_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.financial_institution_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1034,7 +1266,7 @@ impl DepositLedger {
             self.deposit_account_sets.non_domiciled_individual.id,
             *non_domiciled_individual_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.non_domiciled_individual_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.non_domiciled_individual_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1044,7 +1276,7 @@ impl DepositLedger {
             self.frozen_deposit_account_sets.individual.id,
             *frozen_individual_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.frozen_individual_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.frozen_individual_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1054,7 +1286,7 @@ impl DepositLedger {
             self.frozen_deposit_account_sets.government_entity.id,
             *frozen_government_entity_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.frozen_government_entity_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.frozen_government_entity_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1064,7 +1296,7 @@ impl DepositLedger {
             self.frozen_deposit_account_sets.private_company.id,
             *frozen_private_company_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.frozen_private_company_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.frozen_private_company_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1074,7 +1306,7 @@ impl DepositLedger {
             self.frozen_deposit_account_sets.bank.id,
             *frozen_bank_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.frozen_bank_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.frozen_bank_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1084,7 +1316,7 @@ impl DepositLedger {
             self.frozen_deposit_account_sets.financial_institution.id,
             *frozen_financial_institution_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.frozen_financial_institution_deposit_accounts_parent_account_set_id,
+            |meta| Some(meta.frozen_financial_institution_deposit_accounts_parent_account_set_id),
         )
         .await?;
 
@@ -1094,7 +1326,9 @@ impl DepositLedger {
             self.frozen_deposit_account_sets.non_domiciled_individual.id,
             *frozen_non_domiciled_individual_deposit_accounts_parent_account_set_id,
             &charts_integration_meta,
-            |meta| meta.frozen_non_domiciled_individual_deposit_accounts_parent_account_set_id,
+            |meta| {
+                Some(meta.frozen_non_domiciled_individual_deposit_accounts_parent_account_set_id)
+            },
         )
         .await?;
 
@@ -1110,6 +1344,8 @@ pub struct ChartOfAccountsIntegrationMeta {
     pub audit_info: AuditInfo,
 
     pub omnibus_parent_account_set_id: CalaAccountSetId,
+    #[serde(default)]
+    pub interest_expense_parent_account_set_id: Option<CalaAccountSetId>,
 
     pub individual_deposit_accounts_parent_account_set_id: CalaAccountSetId,
     pub government_entity_deposit_accounts_parent_account_set_id: CalaAccountSetId,
@@ -1,15 +1,23 @@
+mod accrue_deposit_interest;
 mod cancel_withdraw;
+mod capitalize_deposit_interest;
 mod confirm_withdraw;
 mod freeze_account;
 mod initiate_withdraw;
 mod record_deposit;
 mod revert_deposit;
 mod revert_withdraw;
+mod transfer;
+mod unfreeze_account;
 
+pub use accrue_deposit_interest::*;
 pub use cancel_withdraw::*;
+pub use capitalize_deposit_interest::*;
 pub use confirm_withdraw::*;
 pub use freeze_account::*;
 pub use initiate_withdraw::*;
 pub use record_deposit::*;
 pub use revert_deposit::*;
 pub use revert_withdraw::*;
+pub use transfer::*;
+pub use unfreeze_account::*;
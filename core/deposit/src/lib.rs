@@ -3,17 +3,20 @@
 
 mod account;
 mod chart_of_accounts_integration;
+mod config;
 mod deposit;
 mod deposit_account_balance;
 pub mod error;
 mod event;
 mod for_subject;
 mod history;
+mod jobs;
 mod ledger;
 mod primitives;
 mod processes;
 mod publisher;
 mod time;
+mod transfer;
 mod withdrawal;
 
 use deposit_account_cursor::DepositAccountsByCreatedAtCursor;
@@ -23,14 +26,15 @@ use audit::AuditSvc;
 use authz::PermissionCheck;
 use cala_ledger::CalaLedger;
 use core_accounting::Chart;
-use governance::{Governance, GovernanceEvent};
+use governance::{ApprovalProcessContext, Governance, GovernanceEvent};
 use job::Jobs;
 use outbox::{Outbox, OutboxEventMarker};
 use public_id::PublicIds;
 
-pub use account::DepositAccount;
 use account::*;
+pub use account::{DepositAccount, DepositAccountClosureCheck};
 pub use chart_of_accounts_integration::ChartOfAccountsIntegrationConfig;
+pub use config::*;
 use deposit::*;
 pub use deposit::{Deposit, DepositsByCreatedAtCursor};
 pub use deposit_account_balance::DepositAccountBalance;
@@ -38,11 +42,18 @@ use error::*;
 pub use event::*;
 pub use for_subject::DepositsForSubject;
 pub use history::{DepositAccountHistoryCursor, DepositAccountHistoryEntry};
+use jobs::interest_accrual::{DepositInterestAccrualInit, DepositInterestAccrualJobConfig};
 use ledger::*;
 pub use primitives::*;
 pub use processes::approval::APPROVE_WITHDRAWAL_PROCESS;
 use processes::approval::{ApproveWithdrawal, WithdrawApprovalInit, WithdrawApprovalJobConfig};
+pub use processes::transfer_approval::APPROVE_TRANSFER_PROCESS;
+use processes::transfer_approval::{
+    ApproveTransfer, TransferApprovalInit, TransferApprovalJobConfig,
+};
 use publisher::DepositPublisher;
+use transfer::*;
+pub use transfer::{Transfer, TransferStatus, TransfersByCreatedAtCursor};
 use withdrawal::*;
 pub use withdrawal::{Withdrawal, WithdrawalStatus, WithdrawalsByCreatedAtCursor};
 
@@ -50,6 +61,7 @@ pub use withdrawal::{Withdrawal, WithdrawalStatus, WithdrawalsByCreatedAtCursor}
 pub mod event_schema {
     pub use crate::account::DepositAccountEvent;
     pub use crate::deposit::DepositEvent;
+    pub use crate::transfer::TransferEvent;
     pub use crate::withdrawal::WithdrawalEvent;
 }
 
@@ -61,13 +73,16 @@ where
     deposit_accounts: DepositAccountRepo<E>,
     deposits: DepositRepo<E>,
     withdrawals: WithdrawalRepo<E>,
+    transfers: TransferRepo<E>,
     approve_withdrawal: ApproveWithdrawal<Perms, E>,
+    approve_transfer: ApproveTransfer<Perms, E>,
     ledger: DepositLedger,
     cala: CalaLedger,
     authz: Perms,
     governance: Governance<Perms, E>,
     outbox: Outbox<E>,
     public_ids: PublicIds,
+    config: DepositConfig,
 }
 
 impl<Perms, E> Clone for CoreDeposit<Perms, E>
@@ -80,13 +95,16 @@ where
             deposit_accounts: self.deposit_accounts.clone(),
             deposits: self.deposits.clone(),
             withdrawals: self.withdrawals.clone(),
+            transfers: self.transfers.clone(),
             ledger: self.ledger.clone(),
             cala: self.cala.clone(),
             authz: self.authz.clone(),
             governance: self.governance.clone(),
             approve_withdrawal: self.approve_withdrawal.clone(),
+            approve_transfer: self.approve_transfer.clone(),
             outbox: self.outbox.clone(),
             public_ids: self.public_ids.clone(),
+            config: self.config.clone(),
         }
     }
 }
@@ -109,40 +127,62 @@ where
         cala: &CalaLedger,
         journal_id: CalaJournalId,
         public_ids: &PublicIds,
+        config: DepositConfig,
     ) -> Result<Self, CoreDepositError> {
         let publisher = DepositPublisher::new(outbox);
         let accounts = DepositAccountRepo::new(pool, &publisher);
         let deposits = DepositRepo::new(pool, &publisher);
         let withdrawals = WithdrawalRepo::new(pool, &publisher);
+        let transfers = TransferRepo::new(pool, &publisher);
+        if config.interest_rates.any_negative() {
+            return Err(CoreDepositError::NegativeInterestRate);
+        }
+
         let ledger = DepositLedger::init(cala, journal_id).await?;
 
         let approve_withdrawal = ApproveWithdrawal::new(&withdrawals, authz.audit(), governance);
+        let approve_transfer = ApproveTransfer::new(&transfers, authz.audit(), governance);
 
         jobs.add_initializer_and_spawn_unique(
             WithdrawApprovalInit::new(outbox, &approve_withdrawal),
             WithdrawApprovalJobConfig::<Perms, E>::new(),
         )
         .await?;
+        jobs.add_initializer_and_spawn_unique(
+            TransferApprovalInit::new(outbox, &approve_transfer),
+            TransferApprovalJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+        jobs.add_initializer_and_spawn_unique(
+            DepositInterestAccrualInit::new(&accounts, &ledger, &config.interest_rates),
+            DepositInterestAccrualJobConfig::<E>::new(),
+        )
+        .await?;
 
-        match governance.init_policy(APPROVE_WITHDRAWAL_PROCESS).await {
-            Err(governance::error::GovernanceError::PolicyError(
-                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
-            )) => (),
-            Err(e) => return Err(e.into()),
-            _ => (),
+        for process_type in [APPROVE_WITHDRAWAL_PROCESS, APPROVE_TRANSFER_PROCESS] {
+            match governance.init_policy(process_type).await {
+                Err(governance::error::GovernanceError::PolicyError(
+                    governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+                )) => (),
+                Err(e) => return Err(e.into()),
+                _ => (),
+            }
         }
 
         let res = Self {
             deposit_accounts: accounts,
             deposits,
             withdrawals,
+            transfers,
             authz: authz.clone(),
             outbox: outbox.clone(),
             governance: governance.clone(),
             cala: cala.clone(),
             approve_withdrawal,
+            approve_transfer,
             ledger,
             public_ids: public_ids.clone(),
+            config,
         };
         Ok(res)
     }
@@ -298,12 +338,26 @@ where
             )
             .await?;
 
-        let history = self
+        let mut history = self
             .ledger
             .account_history::<DepositAccountHistoryEntry, DepositAccountHistoryCursor>(
                 account_id, query,
             )
             .await?;
+        if history.entities.iter().any(|entry| {
+            matches!(
+                entry,
+                DepositAccountHistoryEntry::InterestAccrual(_)
+                    | DepositAccountHistoryEntry::InterestPayment(_)
+            )
+        }) {
+            let account = self.deposit_accounts.find_by_id(account_id).await?;
+            history.entities = history
+                .entities
+                .into_iter()
+                .map(|entry| entry.with_interest_dates(&account))
+                .collect();
+        }
         Ok(history)
     }
 
@@ -385,6 +439,7 @@ where
                 withdrawal_id,
                 withdrawal_id.to_string(),
                 APPROVE_WITHDRAWAL_PROCESS,
+                ApprovalProcessContext::for_amount(amount),
             )
             .await?;
         let withdrawal = self
@@ -398,6 +453,108 @@ where
         Ok(withdrawal)
     }
 
+    #[instrument(name = "deposit.initiate_transfer", skip(self), err)]
+    pub async fn initiate_transfer(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        from_account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        to_account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        amount: UsdCents,
+        reference: Option<String>,
+    ) -> Result<Transfer, CoreDepositError> {
+        let from_account_id = from_account_id.into();
+        let to_account_id = to_account_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_transfers(),
+                CoreDepositAction::TRANSFER_INITIATE,
+            )
+            .await?;
+        self.check_account_active(from_account_id).await?;
+        self.check_account_active(to_account_id).await?;
+
+        let transfer_id = TransferId::new();
+        let mut new_transfer = NewTransfer::builder();
+        new_transfer
+            .id(transfer_id)
+            .from_account_id(from_account_id)
+            .to_account_id(to_account_id)
+            .amount(amount)
+            .reference(reference);
+        let requires_approval = amount > self.config.transfer_approval_threshold;
+        if requires_approval {
+            new_transfer.approval_process_id(transfer_id);
+        }
+        let new_transfer = new_transfer.build()?;
+
+        let mut op = self.transfers.begin_op().await?;
+        if requires_approval {
+            self.governance
+                .start_process(
+                    &mut op,
+                    transfer_id,
+                    transfer_id.to_string(),
+                    APPROVE_TRANSFER_PROCESS,
+                    ApprovalProcessContext::for_amount(amount),
+                )
+                .await?;
+        }
+        let mut transfer = self.transfers.create_in_op(&mut op, new_transfer).await?;
+
+        if requires_approval {
+            op.commit().await?;
+            return Ok(transfer);
+        }
+
+        self.execute_transfer_in_op(op, &mut transfer).await?;
+        Ok(transfer)
+    }
+
+    #[instrument(name = "deposit.execute_transfer", skip(self), err)]
+    pub async fn execute_transfer(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        transfer_id: impl Into<TransferId> + std::fmt::Debug,
+    ) -> Result<Transfer, CoreDepositError> {
+        let id = transfer_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::transfer(id),
+                CoreDepositAction::TRANSFER_EXECUTE,
+            )
+            .await?;
+        let mut transfer = self.transfers.find_by_id(id).await?;
+        self.check_account_active(transfer.from_account_id).await?;
+        self.check_account_active(transfer.to_account_id).await?;
+
+        let op = self.transfers.begin_op().await?;
+        self.execute_transfer_in_op(op, &mut transfer).await?;
+        Ok(transfer)
+    }
+
+    async fn execute_transfer_in_op(
+        &self,
+        mut op: es_entity::DbOp<'_>,
+        transfer: &mut Transfer,
+    ) -> Result<(), CoreDepositError> {
+        if let es_entity::Idempotent::Executed(tx_id) = transfer.execute()? {
+            self.transfers.update_in_op(&mut op, transfer).await?;
+            self.ledger
+                .transfer(
+                    op,
+                    transfer.id,
+                    tx_id,
+                    transfer.amount,
+                    transfer.from_account_id,
+                    transfer.to_account_id,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
     #[instrument(name = "deposit.revert_deposit", skip(self), err)]
     pub async fn revert_deposit(
         &self,
@@ -563,6 +720,75 @@ where
         Ok(account)
     }
 
+    #[instrument(name = "deposit.unfreeze_account", skip(self), err)]
+    pub async fn unfreeze_account(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<DepositAccount, CoreDepositError> {
+        let account_id = account_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::deposit_account(account_id),
+                CoreDepositAction::DEPOSIT_ACCOUNT_UNFREEZE,
+            )
+            .await?;
+
+        let mut account = self.deposit_accounts.find_by_id(account_id).await?;
+
+        if account.unfreeze()?.was_ignored() {
+            return Ok(account);
+        }
+
+        let mut op = self.deposit_accounts.begin_op().await?;
+        self.deposit_accounts
+            .update_in_op(&mut op, &mut account)
+            .await?;
+
+        self.ledger.unfreeze_account_in_op(op, &account).await?;
+
+        Ok(account)
+    }
+
+    #[instrument(name = "deposit.close_account", skip(self, closure_check), err)]
+    pub async fn close_account(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        closure_check: &impl DepositAccountClosureCheck,
+    ) -> Result<DepositAccount, CoreDepositError> {
+        let account_id = account_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::deposit_account(account_id),
+                CoreDepositAction::DEPOSIT_ACCOUNT_CLOSE,
+            )
+            .await?;
+
+        let mut account = self.deposit_accounts.find_by_id(account_id).await?;
+
+        let balance = self.ledger.balance(account.id).await?;
+        if !balance.is_zero() {
+            return Err(CoreDepositError::DepositAccountHasBalance);
+        }
+
+        if closure_check
+            .holder_has_open_credit_facilities(account.account_holder_id)
+            .await
+            .map_err(CoreDepositError::ClosureCheckError)?
+        {
+            return Err(CoreDepositError::DepositAccountHasOpenCreditFacility);
+        }
+
+        if account.close()?.did_execute() {
+            self.deposit_accounts.update(&mut account).await?;
+        }
+
+        Ok(account)
+    }
+
     #[instrument(name = "deposit.account_balance", skip(self), err)]
     pub async fn account_balance(
         &self,
@@ -627,6 +853,28 @@ where
         }
     }
 
+    #[instrument(name = "deposit.find_transfer_by_id", skip(self), err)]
+    pub async fn find_transfer_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<TransferId> + std::fmt::Debug,
+    ) -> Result<Option<Transfer>, CoreDepositError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::transfer(id),
+                CoreDepositAction::TRANSFER_READ,
+            )
+            .await?;
+
+        match self.transfers.find_by_id(id).await {
+            Ok(transfer) => Ok(Some(transfer)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     #[instrument(name = "deposit.find_deposit_by_public_id", skip(self), err)]
     pub async fn find_deposit_by_public_id(
         &self,
@@ -699,6 +947,14 @@ where
         Ok(self.withdrawals.find_all(ids).await?)
     }
 
+    #[instrument(name = "deposit.find_all_transfers", skip(self), err)]
+    pub async fn find_all_transfers<T: From<Transfer>>(
+        &self,
+        ids: &[TransferId],
+    ) -> Result<std::collections::HashMap<TransferId, T>, CoreDepositError> {
+        Ok(self.transfers.find_all(ids).await?)
+    }
+
     #[instrument(name = "deposit.find_all_deposits", skip(self), err)]
     pub async fn find_all_deposits<T: From<Deposit>>(
         &self,
@@ -807,6 +1063,43 @@ where
             .entities)
     }
 
+    #[instrument(name = "deposit.list_transfers_for_account", skip(self), err)]
+    pub async fn list_transfers_for_account(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<Vec<Transfer>, CoreDepositError> {
+        let account_id = account_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_transfers(),
+                CoreDepositAction::TRANSFER_LIST,
+            )
+            .await?;
+        let mut transfers = self
+            .transfers
+            .list_for_from_account_id_by_created_at(
+                account_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities;
+        transfers.extend(
+            self.transfers
+                .list_for_to_account_id_by_created_at(
+                    account_id,
+                    Default::default(),
+                    es_entity::ListDirection::Descending,
+                )
+                .await?
+                .entities,
+        );
+        transfers.sort_by_key(|transfer| std::cmp::Reverse(transfer.created_at()));
+        Ok(transfers)
+    }
+
     #[instrument(
         name = "deposit.list_accounts_by_created_at_for_account_holder",
         skip(self),
@@ -868,11 +1161,11 @@ where
             return Err(CoreDepositError::ChartIdMismatch);
         }
 
-        if self
+        if let Some(existing) = self
             .ledger
             .get_chart_of_accounts_integration_config()
             .await?
-            .is_some()
+            && !existing.is_completed_by(&config)
         {
             return Err(CoreDepositError::DepositConfigAlreadyExists);
         }
@@ -926,6 +1219,11 @@ where
 
         let omnibus_parent_account_set_id =
             chart.account_set_id_from_code(&config.chart_of_accounts_omnibus_parent_code)?;
+        let interest_expense_parent_account_set_id = config
+            .chart_of_accounts_interest_expense_parent_code
+            .as_ref()
+            .map(|code| chart.account_set_id_from_code(code))
+            .transpose()?;
 
         let audit_info = self
             .authz
@@ -940,6 +1238,7 @@ where
             audit_info,
             config: config.clone(),
             omnibus_parent_account_set_id,
+            interest_expense_parent_account_set_id,
             individual_deposit_accounts_parent_account_set_id,
             government_entity_deposit_accounts_parent_account_set_id,
             private_company_deposit_accounts_parent_account_set_id,
@@ -969,6 +1268,7 @@ where
         match account.status {
             DepositAccountStatus::Inactive => Err(CoreDepositError::DepositAccountInactive),
             DepositAccountStatus::Frozen => Err(CoreDepositError::DepositAccountFrozen),
+            DepositAccountStatus::Closed => Err(CoreDepositError::DepositAccountClosed),
             DepositAccountStatus::Active => Ok(()),
         }
     }
@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -20,6 +21,7 @@ es_entity::entity_id! {
     DepositAccountHolderId,
     DepositAccountId,
     WithdrawalId,
+    TransferId,
     ChartOfAccountsIntegrationConfigId,
     DepositId;
 
@@ -30,7 +32,9 @@ es_entity::entity_id! {
     DepositId => CalaTransactionId,
     WithdrawalId => CalaTransactionId,
     WithdrawalId => public_id::PublicIdTargetId,
-    WithdrawalId => ApprovalProcessId
+    WithdrawalId => ApprovalProcessId,
+    TransferId => CalaTransactionId,
+    TransferId => ApprovalProcessId
 }
 
 pub use core_money::UsdCents;
@@ -41,12 +45,15 @@ pub const DEPOSIT_TRANSACTION_ENTITY_TYPE: core_accounting::EntityType =
     core_accounting::EntityType::new("Deposit");
 pub const WITHDRAWAL_TRANSACTION_ENTITY_TYPE: core_accounting::EntityType =
     core_accounting::EntityType::new("Withdrawal");
+pub const TRANSFER_TRANSACTION_ENTITY_TYPE: core_accounting::EntityType =
+    core_accounting::EntityType::new("Transfer");
 
 pub type DepositAccountAllOrOne = AllOrOne<DepositAccountId>;
 pub type DepositAccountByHolderAllOrOne = AllOrOne<DepositAccountHolderId>;
 pub type DepositAllOrOne = AllOrOne<DepositId>;
 pub type ChartOfAccountsIntegrationConfigAllOrOne = AllOrOne<ChartOfAccountsIntegrationConfigId>;
 pub type WithdrawalAllOrOne = AllOrOne<WithdrawalId>;
+pub type TransferAllOrOne = AllOrOne<TransferId>;
 
 pub const PERMISSION_SET_DEPOSIT_VIEWER: &str = "deposit_viewer";
 pub const PERMISSION_SET_DEPOSIT_WRITER: &str = "deposit_writer";
@@ -73,6 +80,7 @@ pub enum CoreDepositObject {
     Deposit(DepositAllOrOne),
     ChartOfAccountsIntegrationConfig(ChartOfAccountsIntegrationConfigAllOrOne),
     Withdrawal(WithdrawalAllOrOne),
+    Transfer(TransferAllOrOne),
 }
 
 impl CoreDepositObject {
@@ -100,6 +108,14 @@ impl CoreDepositObject {
         CoreDepositObject::Withdrawal(AllOrOne::ById(id))
     }
 
+    pub fn all_transfers() -> Self {
+        CoreDepositObject::Transfer(AllOrOne::All)
+    }
+
+    pub fn transfer(id: TransferId) -> Self {
+        CoreDepositObject::Transfer(AllOrOne::ById(id))
+    }
+
     pub fn chart_of_accounts_integration() -> Self {
         CoreDepositObject::ChartOfAccountsIntegrationConfig(AllOrOne::All)
     }
@@ -113,6 +129,7 @@ impl Display for CoreDepositObject {
             DepositAccount(obj_ref) => write!(f, "{discriminant}/{obj_ref}"),
             Deposit(obj_ref) => write!(f, "{discriminant}/{obj_ref}"),
             Withdrawal(obj_ref) => write!(f, "{discriminant}/{obj_ref}"),
+            Transfer(obj_ref) => write!(f, "{discriminant}/{obj_ref}"),
             ChartOfAccountsIntegrationConfig(obj_ref) => write!(f, "{discriminant}/{obj_ref}"),
         }
     }
@@ -143,6 +160,12 @@ impl FromStr for CoreDepositObject {
                     .map_err(|_| "could not parse CoreDepositObject")?;
                 CoreDepositObject::Withdrawal(obj_ref)
             }
+            Transfer => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CoreDepositObject")?;
+                CoreDepositObject::Transfer(obj_ref)
+            }
             ChartOfAccountsIntegrationConfig => {
                 let obj_ref = id
                     .parse()
@@ -162,6 +185,7 @@ pub enum CoreDepositAction {
     Deposit(DepositAction),
     ChartOfAccountsIntegrationConfig(ChartOfAccountsIntegrationConfigAction),
     Withdrawal(WithdrawalAction),
+    Transfer(TransferAction),
 }
 
 impl CoreDepositAction {
@@ -177,6 +201,10 @@ impl CoreDepositAction {
         CoreDepositAction::DepositAccount(DepositAccountAction::List);
     pub const DEPOSIT_ACCOUNT_FREEZE: Self =
         CoreDepositAction::DepositAccount(DepositAccountAction::Freeze);
+    pub const DEPOSIT_ACCOUNT_UNFREEZE: Self =
+        CoreDepositAction::DepositAccount(DepositAccountAction::Unfreeze);
+    pub const DEPOSIT_ACCOUNT_CLOSE: Self =
+        CoreDepositAction::DepositAccount(DepositAccountAction::Close);
 
     pub const DEPOSIT_CREATE: Self = CoreDepositAction::Deposit(DepositAction::Create);
     pub const DEPOSIT_READ: Self = CoreDepositAction::Deposit(DepositAction::Read);
@@ -201,6 +229,13 @@ impl CoreDepositAction {
     pub const WITHDRAWAL_LIST: Self = CoreDepositAction::Withdrawal(WithdrawalAction::List);
     pub const WITHDRAWAL_REVERT: Self = CoreDepositAction::Withdrawal(WithdrawalAction::Revert);
 
+    pub const TRANSFER_INITIATE: Self = CoreDepositAction::Transfer(TransferAction::Initiate);
+    pub const TRANSFER_EXECUTE: Self = CoreDepositAction::Transfer(TransferAction::Execute);
+    pub const TRANSFER_CONCLUDE_APPROVAL_PROCESS: Self =
+        CoreDepositAction::Transfer(TransferAction::ConcludeApprovalProcess);
+    pub const TRANSFER_READ: Self = CoreDepositAction::Transfer(TransferAction::Read);
+    pub const TRANSFER_LIST: Self = CoreDepositAction::Transfer(TransferAction::List);
+
     pub fn actions() -> Vec<ActionMapping> {
         use CoreDepositActionDiscriminants::*;
         use strum::VariantArray;
@@ -218,6 +253,7 @@ impl CoreDepositAction {
                     ChartOfAccountsIntegrationConfigAction
                 ),
                 Withdrawal => map_action!(deposit, Withdrawal, WithdrawalAction),
+                Transfer => map_action!(deposit, Transfer, TransferAction),
             })
             .collect()
     }
@@ -232,6 +268,7 @@ impl Display for CoreDepositAction {
             Deposit(action) => action.fmt(f),
             ChartOfAccountsIntegrationConfig(action) => action.fmt(f),
             Withdrawal(action) => action.fmt(f),
+            Transfer(action) => action.fmt(f),
         }
     }
 }
@@ -249,6 +286,7 @@ impl FromStr for CoreDepositAction {
                 CoreDepositAction::from(action.parse::<ChartOfAccountsIntegrationConfigAction>()?)
             }
             Withdrawal => CoreDepositAction::from(action.parse::<WithdrawalAction>()?),
+            Transfer => CoreDepositAction::from(action.parse::<TransferAction>()?),
         };
 
         Ok(res)
@@ -265,6 +303,8 @@ pub enum DepositAccountAction {
     Read,
     List,
     Freeze,
+    Unfreeze,
+    Close,
 }
 
 impl ActionPermission for DepositAccountAction {
@@ -273,8 +313,8 @@ impl ActionPermission for DepositAccountAction {
             Self::Read | Self::List | Self::ReadBalance | Self::ReadTxHistory => {
                 PERMISSION_SET_DEPOSIT_VIEWER
             }
-            Self::Create | Self::UpdateStatus => PERMISSION_SET_DEPOSIT_WRITER,
-            Self::Freeze => PERMISSION_SET_DEPOSIT_FREEZE,
+            Self::Create | Self::UpdateStatus | Self::Close => PERMISSION_SET_DEPOSIT_WRITER,
+            Self::Freeze | Self::Unfreeze => PERMISSION_SET_DEPOSIT_FREEZE,
         }
     }
 }
@@ -340,6 +380,33 @@ impl From<WithdrawalAction> for CoreDepositAction {
     }
 }
 
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString, strum::VariantArray)]
+#[strum(serialize_all = "kebab-case")]
+pub enum TransferAction {
+    Initiate,
+    Execute,
+    ConcludeApprovalProcess,
+    Read,
+    List,
+}
+
+impl ActionPermission for TransferAction {
+    fn permission_set(&self) -> &'static str {
+        match self {
+            Self::Read | Self::List => PERMISSION_SET_DEPOSIT_VIEWER,
+            Self::Initiate | Self::Execute | Self::ConcludeApprovalProcess => {
+                PERMISSION_SET_DEPOSIT_WRITER
+            }
+        }
+    }
+}
+
+impl From<TransferAction> for CoreDepositAction {
+    fn from(action: TransferAction) -> Self {
+        CoreDepositAction::Transfer(action)
+    }
+}
+
 #[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString, strum::VariantArray)]
 #[strum(serialize_all = "kebab-case")]
 pub enum ChartOfAccountsIntegrationConfigAction {
@@ -370,9 +437,10 @@ pub enum DepositAccountStatus {
     Inactive,
     Active,
     Frozen,
+    Closed,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DepositAccountType {
     Individual,
     GovernmentEntity,
@@ -403,3 +471,36 @@ pub enum DepositStatus {
     Confirmed,
     Reverted,
 }
+
+const NUMBER_OF_DAYS_IN_YEAR: u32 = 365;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(transparent)]
+pub struct DepositInterestRatePct(Decimal);
+#[cfg(feature = "graphql")]
+async_graphql::scalar!(DepositInterestRatePct);
+
+impl DepositInterestRatePct {
+    pub const ZERO: Self = Self(Decimal::ZERO);
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.is_sign_negative() && !self.0.is_zero()
+    }
+
+    /// Interest for one day on `balance`, in cents and not rounded, so that
+    /// fractions of a cent can add up over the capitalization period.
+    pub fn daily_interest(&self, balance: UsdCents) -> Decimal {
+        balance.to_usd() * self.0 / Decimal::from(NUMBER_OF_DAYS_IN_YEAR)
+    }
+}
+
+impl From<Decimal> for DepositInterestRatePct {
+    fn from(value: Decimal) -> Self {
+        DepositInterestRatePct(value)
+    }
+}
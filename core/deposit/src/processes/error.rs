@@ -10,6 +10,8 @@ pub enum ProcessError {
     Sqlx(#[from] sqlx::Error),
     #[error("ProcessError - WithdrawalError: {0}")]
     WithdrawalError(#[from] crate::withdrawal::error::WithdrawalError),
+    #[error("ProcessError - TransferError: {0}")]
+    TransferError(#[from] crate::transfer::error::TransferError),
     #[error("ProcessError - AuditError: {0}")]
     AuditError(#[from] audit::error::AuditError),
 }
@@ -1,2 +1,3 @@
 pub mod approval;
 pub mod error;
+pub mod transfer_approval;
@@ -0,0 +1,92 @@
+mod job;
+
+use authz::PermissionCheck;
+use governance::{ApprovalProcessType, GovernanceAction, GovernanceEvent, GovernanceObject};
+use tracing::instrument;
+
+use audit::AuditSvc;
+use governance::Governance;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    CoreDepositAction, CoreDepositObject,
+    event::CoreDepositEvent,
+    primitives::TransferId,
+    transfer::{Transfer, error::TransferError, repo::TransferRepo},
+};
+
+pub use job::*;
+
+pub const APPROVE_TRANSFER_PROCESS: ApprovalProcessType = ApprovalProcessType::new("transfer");
+
+pub struct ApproveTransfer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
+{
+    repo: TransferRepo<E>,
+    audit: Perms::Audit,
+    governance: Governance<Perms, E>,
+}
+impl<Perms, E> Clone for ApproveTransfer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            repo: self.repo.clone(),
+            audit: self.audit.clone(),
+            governance: self.governance.clone(),
+        }
+    }
+}
+
+impl<Perms, E> ApproveTransfer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreDepositAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreDepositObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        repo: &TransferRepo<E>,
+        audit: &Perms::Audit,
+        governance: &Governance<Perms, E>,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            audit: audit.clone(),
+            governance: governance.clone(),
+        }
+    }
+
+    #[es_entity::retry_on_concurrent_modification]
+    #[instrument(name = "core_deposit.transfer_approval.execute", skip(self))]
+    pub async fn execute(
+        &self,
+        id: impl es_entity::RetryableInto<TransferId>,
+        approved: bool,
+    ) -> Result<Transfer, TransferError> {
+        let id = id.into();
+        let mut transfer = self.repo.find_by_id(id).await?;
+        if transfer.is_approved_or_denied().is_some() {
+            return Ok(transfer);
+        }
+        let mut op = self.repo.begin_op().await?;
+        self.audit
+            .record_system_entry_in_tx(
+                &mut op,
+                CoreDepositObject::transfer(id),
+                CoreDepositAction::TRANSFER_CONCLUDE_APPROVAL_PROCESS,
+            )
+            .await?;
+        if transfer.approval_process_concluded(approved).did_execute() {
+            self.repo.update_in_op(&mut op, &mut transfer).await?;
+            op.commit().await?;
+        }
+        Ok(transfer)
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     DepositAccountStatus,
     account::{DepositAccount, DepositAccountEvent, error::DepositAccountError},
     deposit::{Deposit, DepositEvent, error::DepositError},
+    transfer::{Transfer, TransferEvent, error::TransferError},
     withdrawal::{Withdrawal, WithdrawalEvent, error::WithdrawalError},
 };
 
@@ -56,6 +57,14 @@ where
                     id: entity.id,
                     account_holder_id: entity.account_holder_id,
                 }),
+                Unfrozen { .. } => Some(CoreDepositEvent::DepositAccountUnfrozen {
+                    id: entity.id,
+                    account_holder_id: entity.account_holder_id,
+                }),
+                Closed { .. } => Some(CoreDepositEvent::DepositAccountClosed {
+                    id: entity.id,
+                    account_holder_id: entity.account_holder_id,
+                }),
                 _ => None,
             })
             .collect::<Vec<_>>();
@@ -114,4 +123,28 @@ where
             .await?;
         Ok(())
     }
+
+    pub async fn publish_transfer(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        entity: &Transfer,
+        new_events: es_entity::LastPersisted<'_, TransferEvent>,
+    ) -> Result<(), TransferError> {
+        use TransferEvent::*;
+        let publish_events = new_events
+            .filter_map(|event| match &event.event {
+                Executed { .. } => Some(CoreDepositEvent::TransferExecuted {
+                    id: entity.id,
+                    from_account_id: entity.from_account_id,
+                    to_account_id: entity.to_account_id,
+                    amount: entity.amount,
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.outbox
+            .publish_all_persisted(op, publish_events)
+            .await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,310 @@
+use derive_builder::Builder;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use crate::primitives::{
+    ApprovalProcessId, CalaTransactionId, DepositAccountId, TransferId, UsdCents,
+};
+
+use super::error::TransferError;
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub enum TransferStatus {
+    PendingApproval,
+    PendingExecution,
+    Executed,
+    Denied,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "TransferId")]
+pub enum TransferEvent {
+    Initialized {
+        id: TransferId,
+        from_account_id: DepositAccountId,
+        to_account_id: DepositAccountId,
+        amount: UsdCents,
+        reference: String,
+        approval_process_id: Option<ApprovalProcessId>,
+        status: TransferStatus,
+    },
+    ApprovalProcessConcluded {
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+        status: TransferStatus,
+    },
+    Executed {
+        ledger_tx_id: CalaTransactionId,
+        status: TransferStatus,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct Transfer {
+    pub id: TransferId,
+    pub from_account_id: DepositAccountId,
+    pub to_account_id: DepositAccountId,
+    pub amount: UsdCents,
+    pub reference: String,
+    #[builder(default)]
+    pub approval_process_id: Option<ApprovalProcessId>,
+
+    events: EntityEvents<TransferEvent>,
+}
+
+impl Transfer {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for transfer")
+    }
+
+    pub fn requires_approval(&self) -> bool {
+        self.approval_process_id.is_some()
+    }
+
+    pub fn is_approved_or_denied(&self) -> Option<bool> {
+        self.events.iter_all().find_map(|e| {
+            if let TransferEvent::ApprovalProcessConcluded { approved, .. } = e {
+                Some(*approved)
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn status(&self) -> TransferStatus {
+        self.events
+            .iter_all()
+            .rev()
+            .map(|e| match e {
+                TransferEvent::Initialized { status, .. } => *status,
+                TransferEvent::ApprovalProcessConcluded { status, .. } => *status,
+                TransferEvent::Executed { status, .. } => *status,
+            })
+            .next()
+            .expect("status should always exist")
+    }
+
+    pub fn ledger_tx_ids(&self) -> Vec<CalaTransactionId> {
+        self.events
+            .iter_all()
+            .filter_map(|e| match e {
+                TransferEvent::Executed { ledger_tx_id, .. } => Some(*ledger_tx_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn approval_process_concluded(&mut self, approved: bool) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            TransferEvent::ApprovalProcessConcluded { .. }
+        );
+        let Some(approval_process_id) = self.approval_process_id else {
+            return Idempotent::Ignored;
+        };
+        let status = if approved {
+            TransferStatus::PendingExecution
+        } else {
+            TransferStatus::Denied
+        };
+        self.events.push(TransferEvent::ApprovalProcessConcluded {
+            approval_process_id,
+            approved,
+            status,
+        });
+        Idempotent::Executed(())
+    }
+
+    pub fn execute(&mut self) -> Result<Idempotent<CalaTransactionId>, TransferError> {
+        idempotency_guard!(self.events.iter_all(), TransferEvent::Executed { .. });
+
+        if self.requires_approval() && self.is_approved_or_denied() != Some(true) {
+            return Err(TransferError::NotApproved(self.id));
+        }
+
+        let ledger_tx_id = CalaTransactionId::from(self.id);
+        self.events.push(TransferEvent::Executed {
+            ledger_tx_id,
+            status: TransferStatus::Executed,
+        });
+
+        Ok(Idempotent::Executed(ledger_tx_id))
+    }
+}
+
+impl TryFromEvents<TransferEvent> for Transfer {
+    fn try_from_events(events: EntityEvents<TransferEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = TransferBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                TransferEvent::Initialized {
+                    id,
+                    from_account_id,
+                    to_account_id,
+                    amount,
+                    reference,
+                    approval_process_id,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .from_account_id(*from_account_id)
+                        .to_account_id(*to_account_id)
+                        .amount(*amount)
+                        .reference(reference.clone())
+                        .approval_process_id(*approval_process_id)
+                }
+                TransferEvent::ApprovalProcessConcluded { .. } => (),
+                TransferEvent::Executed { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct NewTransfer {
+    #[builder(setter(into))]
+    pub(super) id: TransferId,
+    #[builder(setter(into))]
+    pub(super) from_account_id: DepositAccountId,
+    #[builder(setter(into))]
+    pub(super) to_account_id: DepositAccountId,
+    #[builder(setter(into))]
+    pub(super) amount: UsdCents,
+    #[builder(setter(strip_option, into), default)]
+    pub(super) approval_process_id: Option<ApprovalProcessId>,
+    reference: Option<String>,
+}
+
+impl NewTransfer {
+    pub fn builder() -> NewTransferBuilder {
+        NewTransferBuilder::default()
+    }
+
+    pub(super) fn reference(&self) -> String {
+        match self.reference.as_deref() {
+            None => self.id.to_string(),
+            Some("") => self.id.to_string(),
+            Some(reference) => reference.to_string(),
+        }
+    }
+}
+
+impl NewTransferBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(amount) = self.amount
+            && amount.is_zero()
+        {
+            return Err("Transfer amount cannot be zero".to_string());
+        }
+        match (self.from_account_id, self.to_account_id) {
+            (Some(from), Some(to)) if from == to => {
+                Err("Cannot transfer to the same account".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IntoEvents<TransferEvent> for NewTransfer {
+    fn into_events(self) -> EntityEvents<TransferEvent> {
+        EntityEvents::init(
+            self.id,
+            [TransferEvent::Initialized {
+                reference: self.reference(),
+                id: self.id,
+                from_account_id: self.from_account_id,
+                to_account_id: self.to_account_id,
+                amount: self.amount,
+                approval_process_id: self.approval_process_id,
+                status: if self.approval_process_id.is_some() {
+                    TransferStatus::PendingApproval
+                } else {
+                    TransferStatus::PendingExecution
+                },
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transfer(approval_process_id: Option<ApprovalProcessId>) -> Transfer {
+        let mut builder = NewTransfer::builder();
+        builder
+            .id(TransferId::new())
+            .from_account_id(DepositAccountId::new())
+            .to_account_id(DepositAccountId::new())
+            .amount(UsdCents::ONE)
+            .reference(None);
+        if let Some(id) = approval_process_id {
+            builder.approval_process_id(id);
+        }
+        Transfer::try_from_events(builder.build().unwrap().into_events()).unwrap()
+    }
+
+    #[test]
+    fn errors_when_transferring_to_same_account() {
+        let account_id = DepositAccountId::new();
+        let transfer = NewTransfer::builder()
+            .id(TransferId::new())
+            .from_account_id(account_id)
+            .to_account_id(account_id)
+            .amount(UsdCents::ONE)
+            .reference(None)
+            .build();
+
+        assert!(matches!(
+            transfer,
+            Err(NewTransferBuilderError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn executes_transfer_without_approval() {
+        let mut transfer = transfer(None);
+        assert_eq!(transfer.status(), TransferStatus::PendingExecution);
+
+        assert!(transfer.execute().unwrap().did_execute());
+        assert_eq!(transfer.status(), TransferStatus::Executed);
+        assert!(transfer.execute().unwrap().was_ignored());
+    }
+
+    #[test]
+    fn cannot_execute_before_approval() {
+        let mut transfer = transfer(Some(ApprovalProcessId::new()));
+        assert_eq!(transfer.status(), TransferStatus::PendingApproval);
+        assert!(matches!(
+            transfer.execute(),
+            Err(TransferError::NotApproved(_))
+        ));
+
+        assert!(transfer.approval_process_concluded(true).did_execute());
+        assert!(transfer.execute().unwrap().did_execute());
+    }
+
+    #[test]
+    fn cannot_execute_denied_transfer() {
+        let mut transfer = transfer(Some(ApprovalProcessId::new()));
+        let _ = transfer.approval_process_concluded(false);
+        assert_eq!(transfer.status(), TransferStatus::Denied);
+        assert!(matches!(
+            transfer.execute(),
+            Err(TransferError::NotApproved(_))
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+mod entity;
+pub mod error;
+pub mod repo;
+
+#[cfg(feature = "json-schema")]
+pub use entity::TransferEvent;
+pub(super) use entity::*;
+pub use entity::{Transfer, TransferStatus};
+pub use repo::transfer_cursor::TransfersByCreatedAtCursor;
+pub(super) use repo::*;
@@ -0,0 +1,69 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    event::CoreDepositEvent,
+    primitives::{ApprovalProcessId, DepositAccountId, TransferId},
+    publisher::DepositPublisher,
+};
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo)]
+#[es_repo(
+    entity = "Transfer",
+    err = "TransferError",
+    columns(
+        from_account_id(ty = "DepositAccountId", list_for, update(persist = false)),
+        to_account_id(ty = "DepositAccountId", list_for, update(persist = false)),
+        approval_process_id(ty = "Option<ApprovalProcessId>", update(persist = false)),
+        reference(ty = "String", create(accessor = "reference()"))
+    ),
+    tbl_prefix = "core",
+    post_persist_hook = "publish"
+)]
+pub struct TransferRepo<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    publisher: DepositPublisher<E>,
+
+    pool: PgPool,
+}
+
+impl<E> Clone for TransferRepo<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            publisher: self.publisher.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<E> TransferRepo<E>
+where
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(pool: &PgPool, publisher: &DepositPublisher<E>) -> Self {
+        Self {
+            pool: pool.clone(),
+            publisher: publisher.clone(),
+        }
+    }
+
+    async fn publish(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        entity: &Transfer,
+        new_events: es_entity::LastPersisted<'_, TransferEvent>,
+    ) -> Result<(), TransferError> {
+        self.publisher
+            .publish_transfer(op, entity, new_events)
+            .await
+    }
+}
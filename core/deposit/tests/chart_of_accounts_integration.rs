@@ -16,7 +16,6 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
 
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
 
     let cala_config = CalaLedgerConfig::builder()
         .pool(pool.clone())
@@ -30,6 +29,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
             .unwrap(),
     )
     .await?;
+    let governance = governance::Governance::new(&pool, &authz, &jobs, &outbox);
 
     let journal_id = helpers::init_journal(&cala).await?;
     let public_ids = public_id::PublicIds::new(&pool);
@@ -43,6 +43,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
         &cala,
         journal_id,
         &public_ids,
+        DepositConfig::default(),
     )
     .await?;
 
@@ -68,6 +69,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
         5,Financial Institution Deposit Accounts
         6,Non Domiciled Individual Deposit Accounts
         8,Frozen Deposit Accounts
+        9,Interest Expense
         "#
     .to_string();
     let chart_id = chart.id;
@@ -90,6 +92,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
             ChartOfAccountsIntegrationConfig {
                 chart_of_accounts_id: chart_id,
                 chart_of_accounts_omnibus_parent_code: "2".parse().unwrap(),
+                chart_of_accounts_interest_expense_parent_code: Some("9".parse().unwrap()),
                 chart_of_accounts_individual_deposit_accounts_parent_code: "1".parse().unwrap(),
                 chart_of_accounts_government_entity_deposit_accounts_parent_code: "7"
                     .parse()
@@ -149,6 +152,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
         5,Other Financial Institution Deposit Accounts
         6,Other Non Domiciled Individual Deposit Accounts
         8,Other Frozen Deposit Accounts
+        9,Other Interest Expense
         "#
     .to_string();
     let chart_id = chart.id;
@@ -164,6 +168,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
             ChartOfAccountsIntegrationConfig {
                 chart_of_accounts_id: chart_id,
                 chart_of_accounts_omnibus_parent_code: "2".parse().unwrap(),
+                chart_of_accounts_interest_expense_parent_code: Some("9".parse().unwrap()),
                 chart_of_accounts_individual_deposit_accounts_parent_code: "1".parse().unwrap(),
                 chart_of_accounts_government_entity_deposit_accounts_parent_code: "7"
                     .parse()
@@ -13,7 +13,6 @@ async fn deposit() -> anyhow::Result<()> {
 
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
 
     let cala_config = CalaLedgerConfig::builder()
         .pool(pool.clone())
@@ -27,6 +26,7 @@ async fn deposit() -> anyhow::Result<()> {
             .unwrap(),
     )
     .await?;
+    let governance = governance::Governance::new(&pool, &authz, &jobs, &outbox);
 
     let journal_id = helpers::init_journal(&cala).await?;
     let public_ids = public_id::PublicIds::new(&pool);
@@ -40,6 +40,7 @@ async fn deposit() -> anyhow::Result<()> {
         &cala,
         journal_id,
         &public_ids,
+        DepositConfig::default(),
     )
     .await?;
 
@@ -78,7 +79,6 @@ async fn revert_deposit() -> anyhow::Result<()> {
 
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
 
     let cala_config = CalaLedgerConfig::builder()
         .pool(pool.clone())
@@ -92,6 +92,7 @@ async fn revert_deposit() -> anyhow::Result<()> {
             .unwrap(),
     )
     .await?;
+    let governance = governance::Governance::new(&pool, &authz, &jobs, &outbox);
 
     let journal_id = helpers::init_journal(&cala).await?;
     let public_ids = public_id::PublicIds::new(&pool);
@@ -105,6 +106,7 @@ async fn revert_deposit() -> anyhow::Result<()> {
         &cala,
         journal_id,
         &public_ids,
+        DepositConfig::default(),
     )
     .await?;
 
@@ -142,3 +144,77 @@ async fn revert_deposit() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+struct OpenCreditFacilities(bool);
+
+#[async_trait::async_trait]
+impl DepositAccountClosureCheck for OpenCreditFacilities {
+    async fn holder_has_open_credit_facilities(
+        &self,
+        _holder_id: DepositAccountHolderId,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.0)
+    }
+}
+
+#[tokio::test]
+async fn close_account_with_open_credit_facility() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
+    let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
+
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let jobs = job::Jobs::init(
+        job::JobSvcConfig::builder()
+            .pool(pool.clone())
+            .build()
+            .unwrap(),
+    )
+    .await?;
+    let governance = governance::Governance::new(&pool, &authz, &jobs, &outbox);
+
+    let journal_id = helpers::init_journal(&cala).await?;
+    let public_ids = public_id::PublicIds::new(&pool);
+
+    let deposit = CoreDeposit::init(
+        &pool,
+        &authz,
+        &outbox,
+        &governance,
+        &jobs,
+        &cala,
+        journal_id,
+        &public_ids,
+        DepositConfig::default(),
+    )
+    .await?;
+
+    let account = deposit
+        .create_account(
+            &DummySubject,
+            DepositAccountHolderId::new(),
+            true,
+            DepositAccountType::Individual,
+        )
+        .await?;
+
+    let res = deposit
+        .close_account(&DummySubject, account.id, &OpenCreditFacilities(true))
+        .await;
+    assert!(matches!(
+        res,
+        Err(error::CoreDepositError::DepositAccountHasOpenCreditFacility)
+    ));
+
+    let account = deposit
+        .close_account(&DummySubject, account.id, &OpenCreditFacilities(false))
+        .await?;
+    assert_eq!(account.status, DepositAccountStatus::Closed);
+
+    Ok(())
+}
@@ -14,7 +14,6 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
 
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
 
     let cala_config = CalaLedgerConfig::builder()
         .pool(pool.clone())
@@ -28,6 +27,7 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
             .unwrap(),
     )
     .await?;
+    let governance = governance::Governance::new(&pool, &authz, &jobs, &outbox);
 
     let journal_id = helpers::init_journal(&cala).await?;
     let public_ids = public_id::PublicIds::new(&pool);
@@ -41,6 +41,7 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
         &cala,
         journal_id,
         &public_ids,
+        DepositConfig::default(),
     )
     .await?;
 
@@ -55,6 +55,7 @@ pub struct Document {
     pub filename: String,
     pub content_type: String,
     pub(super) path_in_storage: String,
+    pub document_type: DocumentType,
     pub reference_id: ReferenceId,
     pub status: DocumentStatus,
     events: EntityEvents<DocumentEvent>,
@@ -122,6 +123,7 @@ impl TryFromEvents<DocumentEvent> for Document {
             match event {
                 DocumentEvent::Initialized {
                     id,
+                    document_type,
                     sanitized_filename,
                     content_type,
                     path_in_storage,
@@ -133,6 +135,7 @@ impl TryFromEvents<DocumentEvent> for Document {
                         .filename(sanitized_filename.clone())
                         .content_type(content_type.clone())
                         .path_in_storage(path_in_storage.clone())
+                        .document_type(document_type.clone())
                         .reference_id(*reference_id)
                         .status(DocumentStatus::New);
                 }
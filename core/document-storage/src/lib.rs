@@ -6,6 +6,7 @@ pub mod error;
 mod primitives;
 mod repo;
 
+pub use cloud_storage::ChunkedUpload;
 use cloud_storage::Storage;
 use es_entity::ListDirection;
 use std::collections::HashMap;
@@ -112,6 +113,43 @@ impl DocumentStorage {
         Ok(())
     }
 
+    #[instrument(
+        name = "document_storage.start_chunked_upload",
+        skip(self, document),
+        fields(document_id = %document.id),
+        err
+    )]
+    pub async fn start_chunked_upload(
+        &self,
+        document: &Document,
+    ) -> Result<ChunkedUpload, DocumentStorageError> {
+        let upload = self
+            .storage
+            .start_chunked_upload(&document.path_in_storage, &document.content_type)
+            .await?;
+        Ok(upload)
+    }
+
+    #[instrument(
+        name = "document_storage.finish_chunked_upload",
+        skip(self, upload, document),
+        fields(document_id = %document.id),
+        err
+    )]
+    pub async fn finish_chunked_upload(
+        &self,
+        upload: ChunkedUpload,
+        document: &mut Document,
+    ) -> Result<(), DocumentStorageError> {
+        upload.finish().await?;
+
+        if document.upload_file().did_execute() {
+            self.repo.update(document).await?;
+        }
+
+        Ok(())
+    }
+
     #[instrument(name = "document_storage.create_and_upload", skip(self, content), err)]
     pub async fn create_and_upload(
         &self,
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
@@ -7,7 +8,12 @@ use std::collections::HashSet;
 
 use es_entity::*;
 
-use crate::{policy::ApprovalRules, primitives::*};
+use crate::{
+    policy::{ApprovalExpiry, ApprovalExpiryAction, ApprovalRules},
+    primitives::*,
+};
+
+pub const APPROVAL_PROCESS_EXPIRED_REASON: &str = "Approval process expired";
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(JsonSchema))]
@@ -20,19 +26,50 @@ pub enum ApprovalProcessEvent {
         process_type: ApprovalProcessType,
         rules: ApprovalRules,
         target_ref: String,
+        expiry: Option<ApprovalExpiry>,
+        expires_at: Option<DateTime<Utc>>,
+        remind_at: Option<DateTime<Utc>>,
     },
     Approved {
         approver_id: CommitteeMemberId,
+        #[serde(default)]
+        stage: usize,
+        #[serde(default)]
+        delegate_id: Option<CommitteeMemberId>,
     },
     Denied {
         denier_id: CommitteeMemberId,
         reason: String,
+        #[serde(default)]
+        stage: usize,
+        #[serde(default)]
+        delegate_id: Option<CommitteeMemberId>,
+    },
+    StageApproved {
+        stage: usize,
     },
+    ReminderSent {
+        pending_voters: Vec<CommitteeMemberId>,
+    },
+    Escalated {
+        #[serde(default)]
+        stage: usize,
+        rules: ApprovalRules,
+        expires_at: DateTime<Utc>,
+        remind_at: Option<DateTime<Utc>>,
+    },
+    Expired,
     Concluded {
         approved: bool,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalProcessExpiryOutcome {
+    Denied,
+    Escalated,
+}
+
 #[derive(EsEntity, Builder)]
 #[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
 pub struct ApprovalProcess {
@@ -40,6 +77,14 @@ pub struct ApprovalProcess {
     pub process_type: ApprovalProcessType,
     pub policy_id: PolicyId,
     pub rules: ApprovalRules,
+    #[builder(default)]
+    pub current_stage: usize,
+    #[builder(default)]
+    pub expiry: Option<ApprovalExpiry>,
+    #[builder(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[builder(default)]
+    pub remind_at: Option<DateTime<Utc>>,
     events: EntityEvents<ApprovalProcessEvent>,
 }
 
@@ -55,11 +100,149 @@ impl ApprovalProcess {
             .iter_all()
             .filter_map(|event| match event {
                 ApprovalProcessEvent::Denied { reason, .. } => Some(reason.as_str()),
+                ApprovalProcessEvent::Expired => Some(APPROVAL_PROCESS_EXPIRED_REASON),
                 _ => None,
             })
             .next()
     }
 
+    pub fn is_escalated(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|event| matches!(event, ApprovalProcessEvent::Escalated { .. }))
+    }
+
+    fn reminder_sent_in_current_stage(&self) -> bool {
+        self.events
+            .iter_all()
+            .rev()
+            .take_while(|event| {
+                !matches!(
+                    event,
+                    ApprovalProcessEvent::Escalated { .. }
+                        | ApprovalProcessEvent::StageApproved { .. }
+                )
+            })
+            .any(|event| matches!(event, ApprovalProcessEvent::ReminderSent { .. }))
+    }
+
+    fn escalated_in_current_stage(&self) -> bool {
+        self.events
+            .iter_all()
+            .rev()
+            .take_while(|event| !matches!(event, ApprovalProcessEvent::StageApproved { .. }))
+            .any(|event| matches!(event, ApprovalProcessEvent::Escalated { .. }))
+    }
+
+    /// Rules that the votes of the current stage are evaluated against.
+    pub fn current_rules(&self) -> ApprovalRules {
+        self.rules.stage(self.current_stage)
+    }
+
+    pub fn n_stages(&self) -> usize {
+        self.rules.n_stages()
+    }
+
+    fn is_final_stage(&self) -> bool {
+        self.current_stage + 1 >= self.n_stages()
+    }
+
+    /// The next point in time at which a reminder or the expiry needs to be handled.
+    pub fn next_deadline(&self) -> Option<DateTime<Utc>> {
+        if self.status().is_concluded() {
+            return None;
+        }
+        let remind_at = self
+            .remind_at
+            .filter(|_| !self.reminder_sent_in_current_stage());
+        match (remind_at, self.expires_at) {
+            (Some(remind_at), Some(expires_at)) => Some(remind_at.min(expires_at)),
+            (remind_at, expires_at) => remind_at.or(expires_at),
+        }
+    }
+
+    pub fn pending_voters(&self, eligible: &HashSet<CommitteeMemberId>) -> Vec<CommitteeMemberId> {
+        let voted = self.voted_members();
+        eligible
+            .iter()
+            .filter(|id| !voted.contains(id))
+            .copied()
+            .collect()
+    }
+
+    pub(crate) fn send_reminder(
+        &mut self,
+        eligible: &HashSet<CommitteeMemberId>,
+        now: DateTime<Utc>,
+    ) -> Idempotent<Vec<CommitteeMemberId>> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ApprovalProcessEvent::Concluded { .. },
+        );
+        if self.reminder_sent_in_current_stage() {
+            return Idempotent::Ignored;
+        }
+        match self.remind_at {
+            Some(remind_at) if remind_at <= now => (),
+            _ => return Idempotent::Ignored,
+        }
+
+        let pending_voters = self.pending_voters(eligible);
+        if pending_voters.is_empty() {
+            return Idempotent::Ignored;
+        }
+
+        self.events.push(ApprovalProcessEvent::ReminderSent {
+            pending_voters: pending_voters.clone(),
+        });
+        Idempotent::Executed(pending_voters)
+    }
+
+    pub(crate) fn expire(
+        &mut self,
+        now: DateTime<Utc>,
+    ) -> Idempotent<ApprovalProcessExpiryOutcome> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ApprovalProcessEvent::Concluded { .. },
+        );
+        let (Some(expiry), Some(expires_at)) = (self.expiry, self.expires_at) else {
+            return Idempotent::Ignored;
+        };
+        if now < expires_at {
+            return Idempotent::Ignored;
+        }
+
+        match expiry.action {
+            ApprovalExpiryAction::Escalate {
+                committee_id,
+                threshold,
+            } if !self.escalated_in_current_stage() => {
+                let rules = self
+                    .rules
+                    .escalate_stage(self.current_stage, committee_id, threshold);
+                let expires_at = expiry.expires_at(now);
+                let remind_at = expiry.remind_at(now);
+                self.rules = rules.clone();
+                self.expires_at = Some(expires_at);
+                self.remind_at = remind_at;
+                self.events.push(ApprovalProcessEvent::Escalated {
+                    stage: self.current_stage,
+                    rules,
+                    expires_at,
+                    remind_at,
+                });
+                Idempotent::Executed(ApprovalProcessExpiryOutcome::Escalated)
+            }
+            _ => {
+                self.events.push(ApprovalProcessEvent::Expired);
+                self.events
+                    .push(ApprovalProcessEvent::Concluded { approved: false });
+                Idempotent::Executed(ApprovalProcessExpiryOutcome::Denied)
+            }
+        }
+    }
+
     pub fn member_voted_at(
         &self,
         member_id: CommitteeMemberId,
@@ -86,8 +269,9 @@ impl ApprovalProcess {
         }
     }
 
+    /// Committee voting in the current stage.
     pub fn committee_id(&self) -> Option<CommitteeId> {
-        self.rules.committee_id()
+        self.current_rules().committee_id()
     }
 
     pub fn can_member_vote(
@@ -95,9 +279,45 @@ impl ApprovalProcess {
         member_id: CommitteeMemberId,
         eligible: HashSet<CommitteeMemberId>,
     ) -> bool {
-        eligible.contains(&member_id)
-            && !self.approvers().contains(&member_id)
-            && !self.deniers().contains(&member_id)
+        eligible.contains(&member_id) && !self.voted_members().contains(&member_id)
+    }
+
+    /// Members who approved an earlier stage can not count towards a later one.
+    fn stage_eligible(&self, eligible: &HashSet<CommitteeMemberId>) -> HashSet<CommitteeMemberId> {
+        let voted = self.voted_members();
+        let current = self.approvers();
+        eligible
+            .iter()
+            .filter(|id| current.contains(id) || !voted.contains(id))
+            .copied()
+            .collect()
+    }
+
+    fn current_stage_outcome(&self, eligible: &HashSet<CommitteeMemberId>) -> Option<bool> {
+        self.current_rules().is_approved_or_denied(
+            &self.stage_eligible(eligible),
+            &self.approvers(),
+            &self.deniers(),
+        )
+    }
+
+    pub(crate) fn advance_stage(
+        &mut self,
+        eligible: &HashSet<CommitteeMemberId>,
+    ) -> Idempotent<usize> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ApprovalProcessEvent::Concluded { .. },
+        );
+        if self.is_final_stage() || self.current_stage_outcome(eligible) != Some(true) {
+            return Idempotent::Ignored;
+        }
+
+        self.events.push(ApprovalProcessEvent::StageApproved {
+            stage: self.current_stage,
+        });
+        self.current_stage += 1;
+        Idempotent::Executed(self.current_stage)
     }
 
     pub(crate) fn check_concluded(
@@ -108,9 +328,9 @@ impl ApprovalProcess {
             self.events.iter_all(),
             ApprovalProcessEvent::Concluded { .. },
         );
-        if let Some(approved) =
-            self.rules
-                .is_approved_or_denied(&eligible, &self.approvers(), &self.deniers())
+        if let Some(approved) = self
+            .current_stage_outcome(&eligible)
+            .filter(|approved| !approved || self.is_final_stage())
         {
             let reason = self
                 .events
@@ -147,6 +367,25 @@ impl ApprovalProcess {
         &mut self,
         eligible_members: &HashSet<CommitteeMemberId>,
         approver_id: CommitteeMemberId,
+    ) -> Idempotent<()> {
+        self.record_approval(eligible_members, approver_id, None)
+    }
+
+    /// Casts the vote of `delegator_id` through a delegation to `delegate_id`.
+    pub(crate) fn approve_on_behalf(
+        &mut self,
+        eligible_members: &HashSet<CommitteeMemberId>,
+        delegator_id: CommitteeMemberId,
+        delegate_id: CommitteeMemberId,
+    ) -> Idempotent<()> {
+        self.record_approval(eligible_members, delegator_id, Some(delegate_id))
+    }
+
+    fn record_approval(
+        &mut self,
+        eligible_members: &HashSet<CommitteeMemberId>,
+        approver_id: CommitteeMemberId,
+        delegate_id: Option<CommitteeMemberId>,
     ) -> Idempotent<()> {
         use ApprovalProcessEvent::*;
         idempotency_guard!(
@@ -159,8 +398,11 @@ impl ApprovalProcess {
             return Idempotent::Ignored;
         }
 
-        self.events
-            .push(ApprovalProcessEvent::Approved { approver_id });
+        self.events.push(ApprovalProcessEvent::Approved {
+            approver_id,
+            stage: self.current_stage,
+            delegate_id,
+        });
 
         Idempotent::Executed(())
     }
@@ -170,6 +412,26 @@ impl ApprovalProcess {
         eligible_members: &HashSet<CommitteeMemberId>,
         denier_id: CommitteeMemberId,
         reason: String,
+    ) -> Idempotent<()> {
+        self.record_denial(eligible_members, denier_id, reason, None)
+    }
+
+    pub(crate) fn deny_on_behalf(
+        &mut self,
+        eligible_members: &HashSet<CommitteeMemberId>,
+        delegator_id: CommitteeMemberId,
+        delegate_id: CommitteeMemberId,
+        reason: String,
+    ) -> Idempotent<()> {
+        self.record_denial(eligible_members, delegator_id, reason, Some(delegate_id))
+    }
+
+    fn record_denial(
+        &mut self,
+        eligible_members: &HashSet<CommitteeMemberId>,
+        denier_id: CommitteeMemberId,
+        reason: String,
+        delegate_id: Option<CommitteeMemberId>,
     ) -> Idempotent<()> {
         use ApprovalProcessEvent::*;
         idempotency_guard!(
@@ -182,26 +444,75 @@ impl ApprovalProcess {
             return Idempotent::Ignored;
         }
 
-        self.events
-            .push(ApprovalProcessEvent::Denied { denier_id, reason });
+        self.events.push(ApprovalProcessEvent::Denied {
+            denier_id,
+            reason,
+            stage: self.current_stage,
+            delegate_id,
+        });
 
         Idempotent::Executed(())
     }
 
+    /// Approvers of the current stage.
     pub fn approvers(&self) -> HashSet<CommitteeMemberId> {
+        self.stage_approvers(self.current_stage)
+    }
+
+    /// Deniers of the current stage.
+    pub fn deniers(&self) -> HashSet<CommitteeMemberId> {
+        self.stage_deniers(self.current_stage)
+    }
+
+    pub fn stage_approvers(&self, stage_idx: usize) -> HashSet<CommitteeMemberId> {
         self.events
             .iter_all()
             .filter_map(|event| match event {
-                ApprovalProcessEvent::Approved { approver_id, .. } => Some(*approver_id),
+                ApprovalProcessEvent::Approved {
+                    approver_id, stage, ..
+                } if *stage == stage_idx => Some(*approver_id),
                 _ => None,
             })
             .collect()
     }
 
-    pub fn deniers(&self) -> HashSet<CommitteeMemberId> {
+    pub fn stage_deniers(&self, stage_idx: usize) -> HashSet<CommitteeMemberId> {
         self.events
             .iter_all()
             .filter_map(|event| match event {
+                ApprovalProcessEvent::Denied {
+                    denier_id, stage, ..
+                } if *stage == stage_idx => Some(*denier_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The delegate that cast the vote of `member_id`, if it was not cast by the member itself.
+    pub fn member_vote_delegate(&self, member_id: CommitteeMemberId) -> Option<CommitteeMemberId> {
+        self.events
+            .iter_all()
+            .find_map(|event| match event {
+                ApprovalProcessEvent::Approved {
+                    approver_id: id,
+                    delegate_id,
+                    ..
+                }
+                | ApprovalProcessEvent::Denied {
+                    denier_id: id,
+                    delegate_id,
+                    ..
+                } if *id == member_id => Some(*delegate_id),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    fn voted_members(&self) -> HashSet<CommitteeMemberId> {
+        self.events
+            .iter_all()
+            .filter_map(|event| match event {
+                ApprovalProcessEvent::Approved { approver_id, .. } => Some(*approver_id),
                 ApprovalProcessEvent::Denied { denier_id, .. } => Some(*denier_id),
                 _ => None,
             })
@@ -219,16 +530,38 @@ impl TryFromEvents<ApprovalProcessEvent> for ApprovalProcess {
                     process_type,
                     policy_id,
                     rules,
+                    expiry,
+                    expires_at,
+                    remind_at,
                     ..
                 } => {
                     builder = builder
                         .id(*id)
                         .process_type(process_type.clone())
                         .policy_id(*policy_id)
-                        .rules(*rules);
+                        .rules(rules.clone())
+                        .expiry(*expiry)
+                        .expires_at(*expires_at)
+                        .remind_at(*remind_at);
                 }
                 ApprovalProcessEvent::Approved { .. } => {}
                 ApprovalProcessEvent::Denied { .. } => {}
+                ApprovalProcessEvent::StageApproved { stage } => {
+                    builder = builder.current_stage(stage + 1);
+                }
+                ApprovalProcessEvent::ReminderSent { .. } => {}
+                ApprovalProcessEvent::Escalated {
+                    rules,
+                    expires_at,
+                    remind_at,
+                    ..
+                } => {
+                    builder = builder
+                        .rules(rules.clone())
+                        .expires_at(Some(*expires_at))
+                        .remind_at(*remind_at);
+                }
+                ApprovalProcessEvent::Expired => {}
                 ApprovalProcessEvent::Concluded { .. } => {}
             }
         }
@@ -245,6 +578,12 @@ pub struct NewApprovalProcess {
     pub(super) rules: ApprovalRules,
     #[builder(setter(into))]
     pub(super) target_ref: String,
+    #[builder(default)]
+    pub(super) expiry: Option<ApprovalExpiry>,
+    #[builder(default)]
+    pub(super) expires_at: Option<DateTime<Utc>>,
+    #[builder(default)]
+    pub(super) remind_at: Option<DateTime<Utc>>,
 }
 
 impl NewApprovalProcess {
@@ -267,6 +606,9 @@ impl IntoEvents<ApprovalProcessEvent> for NewApprovalProcess {
                 process_type: self.process_type,
                 rules: self.rules,
                 target_ref: self.target_ref,
+                expiry: self.expiry,
+                expires_at: self.expires_at,
+                remind_at: self.remind_at,
             }],
         )
     }
@@ -276,6 +618,8 @@ impl IntoEvents<ApprovalProcessEvent> for NewApprovalProcess {
 mod tests {
     use super::*;
 
+    use crate::policy::ApprovalStage;
+
     fn init_events(rules: ApprovalRules) -> EntityEvents<ApprovalProcessEvent> {
         EntityEvents::init(
             ApprovalProcessId::new(),
@@ -285,10 +629,50 @@ mod tests {
                 process_type: ApprovalProcessType::from_owned("type".to_string()),
                 rules,
                 target_ref: "target_ref".to_string(),
+                expiry: None,
+                expires_at: None,
+                remind_at: None,
             }],
         )
     }
 
+    fn expiring_process(action: ApprovalExpiryAction, now: DateTime<Utc>) -> ApprovalProcess {
+        expiring_process_with_rules(
+            ApprovalRules::CommitteeThreshold {
+                threshold: 2,
+                committee_id: CommitteeId::new(),
+            },
+            action,
+            now,
+        )
+    }
+
+    fn expiring_process_with_rules(
+        rules: ApprovalRules,
+        action: ApprovalExpiryAction,
+        now: DateTime<Utc>,
+    ) -> ApprovalProcess {
+        let expiry = ApprovalExpiry {
+            ttl_hours: 48,
+            remind_after_hours: Some(24),
+            action,
+        };
+        ApprovalProcess::try_from_events(EntityEvents::init(
+            ApprovalProcessId::new(),
+            [ApprovalProcessEvent::Initialized {
+                id: ApprovalProcessId::new(),
+                policy_id: PolicyId::new(),
+                process_type: ApprovalProcessType::from_owned("type".to_string()),
+                rules,
+                target_ref: "target_ref".to_string(),
+                expiry: Some(expiry),
+                expires_at: Some(expiry.expires_at(now)),
+                remind_at: expiry.remind_at(now),
+            }],
+        ))
+        .expect("Could not build approval process")
+    }
+
     #[test]
     fn approve() {
         let mut process =
@@ -394,4 +778,229 @@ mod tests {
         let eligible: HashSet<_> = [denier].iter().copied().collect();
         assert!(process.deny(&eligible, denier, String::new()).was_ignored());
     }
+
+    #[test]
+    fn sends_reminder_to_pending_voters_once() {
+        let now = Utc::now();
+        let mut process = expiring_process(ApprovalExpiryAction::Deny, now);
+        let voted = CommitteeMemberId::new();
+        let pending = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [voted, pending].into_iter().collect();
+        let _ = process.approve(&eligible, voted);
+
+        assert!(process.send_reminder(&eligible, now).was_ignored());
+        assert_eq!(process.next_deadline(), process.remind_at);
+
+        let later = now + chrono::Duration::hours(25);
+        assert_eq!(
+            process.send_reminder(&eligible, later).unwrap(),
+            vec![pending]
+        );
+        assert!(process.send_reminder(&eligible, later).was_ignored());
+        assert_eq!(process.next_deadline(), process.expires_at);
+    }
+
+    #[test]
+    fn expiry_denies_process() {
+        let now = Utc::now();
+        let mut process = expiring_process(ApprovalExpiryAction::Deny, now);
+
+        assert!(process.expire(now).was_ignored());
+        let later = now + chrono::Duration::hours(48);
+        assert_eq!(
+            process.expire(later).unwrap(),
+            ApprovalProcessExpiryOutcome::Denied
+        );
+        assert_eq!(process.status(), ApprovalProcessStatus::Denied);
+        assert_eq!(
+            process.denied_reason(),
+            Some(APPROVAL_PROCESS_EXPIRED_REASON)
+        );
+        assert_eq!(process.next_deadline(), None);
+    }
+
+    #[test]
+    fn expiry_escalates_then_denies() {
+        let now = Utc::now();
+        let secondary = CommitteeId::new();
+        let mut process = expiring_process(
+            ApprovalExpiryAction::Escalate {
+                committee_id: secondary,
+                threshold: 1,
+            },
+            now,
+        );
+
+        let expired_at = now + chrono::Duration::hours(48);
+        assert_eq!(
+            process.expire(expired_at).unwrap(),
+            ApprovalProcessExpiryOutcome::Escalated
+        );
+        assert_eq!(process.committee_id(), Some(secondary));
+        assert_eq!(process.status(), ApprovalProcessStatus::InProgress);
+        assert_eq!(
+            process.expires_at,
+            Some(expired_at + chrono::Duration::hours(48))
+        );
+
+        let escalation_expired_at = expired_at + chrono::Duration::hours(48);
+        assert_eq!(
+            process.expire(escalation_expired_at).unwrap(),
+            ApprovalProcessExpiryOutcome::Denied
+        );
+        assert_eq!(process.status(), ApprovalProcessStatus::Denied);
+    }
+
+    #[test]
+    fn expiry_escalates_only_current_stage() {
+        let now = Utc::now();
+        let risk = CommitteeId::new();
+        let credit = CommitteeId::new();
+        let secondary = CommitteeId::new();
+        let mut process = expiring_process_with_rules(
+            ApprovalRules::MultiStage {
+                stages: vec![
+                    ApprovalStage {
+                        committee_id: risk,
+                        threshold: 1,
+                    },
+                    ApprovalStage {
+                        committee_id: credit,
+                        threshold: 2,
+                    },
+                ],
+            },
+            ApprovalExpiryAction::Escalate {
+                committee_id: secondary,
+                threshold: 1,
+            },
+            now,
+        );
+
+        let expired_at = now + chrono::Duration::hours(48);
+        assert_eq!(
+            process.expire(expired_at).unwrap(),
+            ApprovalProcessExpiryOutcome::Escalated
+        );
+        assert_eq!(process.n_stages(), 2);
+        assert_eq!(process.committee_id(), Some(secondary));
+        assert_eq!(
+            process.rules.stage(1),
+            ApprovalRules::CommitteeThreshold {
+                committee_id: credit,
+                threshold: 2,
+            }
+        );
+
+        let member = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [member].into_iter().collect();
+        assert!(process.approve(&eligible, member).did_execute());
+        assert_eq!(process.advance_stage(&eligible).unwrap(), 1);
+        assert_eq!(process.committee_id(), Some(credit));
+
+        let stage_expired_at = expired_at + chrono::Duration::hours(48);
+        assert_eq!(
+            process.expire(stage_expired_at).unwrap(),
+            ApprovalProcessExpiryOutcome::Escalated
+        );
+        assert_eq!(process.committee_id(), Some(secondary));
+        assert_eq!(process.status(), ApprovalProcessStatus::InProgress);
+
+        let rules = process.rules.clone();
+        let process = ApprovalProcess::try_from_events(process.events)
+            .expect("Could not build approval process");
+        assert_eq!(process.rules, rules);
+        assert_eq!(process.current_stage, 1);
+    }
+
+    fn two_stage_process(first: CommitteeId, second: CommitteeId) -> ApprovalProcess {
+        ApprovalProcess::try_from_events(init_events(ApprovalRules::MultiStage {
+            stages: vec![
+                ApprovalStage {
+                    committee_id: first,
+                    threshold: 1,
+                },
+                ApprovalStage {
+                    committee_id: second,
+                    threshold: 1,
+                },
+            ],
+        }))
+        .expect("Could not build approval process")
+    }
+
+    #[test]
+    fn multi_stage_concludes_after_last_stage() {
+        let risk = CommitteeId::new();
+        let credit = CommitteeId::new();
+        let mut process = two_stage_process(risk, credit);
+        let risk_member = CommitteeMemberId::new();
+        let credit_member = CommitteeMemberId::new();
+        let risk_eligible: HashSet<_> = [risk_member].into_iter().collect();
+        let credit_eligible: HashSet<_> = [risk_member, credit_member].into_iter().collect();
+
+        assert_eq!(process.committee_id(), Some(risk));
+        assert!(process.approve(&risk_eligible, risk_member).did_execute());
+        assert!(process.check_concluded(risk_eligible.clone()).was_ignored());
+        assert_eq!(process.advance_stage(&risk_eligible).unwrap(), 1);
+        assert_eq!(process.committee_id(), Some(credit));
+        assert!(process.approvers().is_empty());
+
+        assert!(!process.can_member_vote(risk_member, credit_eligible.clone()));
+        assert!(process.approve(&credit_eligible, risk_member).was_ignored());
+        assert!(
+            process
+                .approve(&credit_eligible, credit_member)
+                .did_execute()
+        );
+        assert!(process.advance_stage(&credit_eligible).was_ignored());
+        assert_eq!(
+            process.check_concluded(credit_eligible).unwrap(),
+            (true, None)
+        );
+        assert_eq!(process.status(), ApprovalProcessStatus::Approved);
+        assert_eq!(process.stage_approvers(0), risk_eligible);
+    }
+
+    #[test]
+    fn multi_stage_denial_concludes_immediately() {
+        let mut process = two_stage_process(CommitteeId::new(), CommitteeId::new());
+        let denier = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [denier].into_iter().collect();
+
+        assert!(
+            process
+                .deny(&eligible, denier, "too risky".to_string())
+                .did_execute()
+        );
+        assert!(process.advance_stage(&eligible).was_ignored());
+        assert_eq!(
+            process.check_concluded(eligible).unwrap(),
+            (false, Some("too risky".to_string()))
+        );
+        assert_eq!(process.current_stage, 0);
+    }
+
+    #[test]
+    fn approve_on_behalf_counts_for_delegator() {
+        let mut process =
+            ApprovalProcess::try_from_events(init_events(ApprovalRules::CommitteeThreshold {
+                threshold: 1,
+                committee_id: CommitteeId::new(),
+            }))
+            .expect("Could not build approval process");
+        let delegator = CommitteeMemberId::new();
+        let delegate = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [delegator].into_iter().collect();
+
+        assert!(process.approve(&eligible, delegate).was_ignored());
+        assert!(
+            process
+                .approve_on_behalf(&eligible, delegator, delegate)
+                .did_execute()
+        );
+        assert!(process.approve(&eligible, delegator).was_ignored());
+        assert_eq!(process.member_vote_delegate(delegator), Some(delegate));
+        assert_eq!(process.check_concluded(eligible).unwrap(), (true, None));
+    }
 }
@@ -4,7 +4,10 @@ mod repo;
 
 #[cfg(feature = "json-schema")]
 pub use entity::ApprovalProcessEvent;
-pub use entity::{ApprovalProcess, NewApprovalProcess};
+pub use entity::{
+    APPROVAL_PROCESS_EXPIRED_REASON, ApprovalProcess, ApprovalProcessExpiryOutcome,
+    NewApprovalProcess,
+};
 pub use repo::approval_process_cursor;
 
 pub(crate) use repo::ApprovalProcessRepo;
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 #[cfg(feature = "json-schema")]
 use schemars::JsonSchema;
@@ -19,6 +20,24 @@ pub enum CommitteeEvent {
     Initialized { id: CommitteeId, name: String },
     MemberAdded { member_id: CommitteeMemberId },
     MemberRemoved { member_id: CommitteeMemberId },
+    VotesDelegated { delegation: VoteDelegation },
+    VoteDelegationRevoked { delegator_id: CommitteeMemberId },
+}
+
+/// Allows `delegate_id` to vote on behalf of `delegator_id` between `starts_at` and `ends_at`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+pub struct VoteDelegation {
+    pub delegator_id: CommitteeMemberId,
+    pub delegate_id: CommitteeMemberId,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl VoteDelegation {
+    pub fn is_active_at(&self, at: DateTime<Utc>) -> bool {
+        self.starts_at <= at && at < self.ends_at
+    }
 }
 
 #[derive(EsEntity, Builder)]
@@ -49,12 +68,106 @@ impl Committee {
         Ok(())
     }
 
-    pub(crate) fn remove_member(&mut self, member_id: CommitteeMemberId) {
+    pub(crate) fn remove_member(&mut self, member_id: CommitteeMemberId) -> Idempotent<()> {
         if !self.members().contains(&member_id) {
-            return;
+            return Idempotent::Ignored;
         }
         self.events
             .push(CommitteeEvent::MemberRemoved { member_id });
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn delegate_votes(
+        &mut self,
+        delegation: VoteDelegation,
+    ) -> Result<Idempotent<()>, CommitteeError> {
+        if self.delegation_of(delegation.delegator_id) == Some(delegation) {
+            return Ok(Idempotent::Ignored);
+        }
+        if !self.members().contains(&delegation.delegator_id) {
+            return Err(CommitteeError::MemberNotFound(delegation.delegator_id));
+        }
+        if delegation.delegator_id == delegation.delegate_id {
+            return Err(CommitteeError::SelfDelegation(delegation.delegator_id));
+        }
+        if self.members().contains(&delegation.delegate_id) {
+            return Err(CommitteeError::DelegateIsMember(delegation.delegate_id));
+        }
+        if delegation.ends_at <= delegation.starts_at {
+            return Err(CommitteeError::InvalidDelegationPeriod);
+        }
+
+        self.events
+            .push(CommitteeEvent::VotesDelegated { delegation });
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub(crate) fn revoke_vote_delegation(
+        &mut self,
+        delegator_id: CommitteeMemberId,
+    ) -> Idempotent<()> {
+        if self.delegation_of(delegator_id).is_none() {
+            return Idempotent::Ignored;
+        }
+        self.events
+            .push(CommitteeEvent::VoteDelegationRevoked { delegator_id });
+        Idempotent::Executed(())
+    }
+
+    fn delegation_of(&self, delegator_id: CommitteeMemberId) -> Option<VoteDelegation> {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|event| match event {
+                CommitteeEvent::VotesDelegated { delegation }
+                    if delegation.delegator_id == delegator_id =>
+                {
+                    Some(Some(*delegation))
+                }
+                CommitteeEvent::VoteDelegationRevoked { delegator_id: id }
+                | CommitteeEvent::MemberRemoved { member_id: id }
+                    if *id == delegator_id =>
+                {
+                    Some(None)
+                }
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Delegations that have not been revoked, including ones that are not yet or no longer active.
+    pub fn delegations(&self) -> Vec<VoteDelegation> {
+        let mut delegators = Vec::new();
+        for event in self.events.iter_all() {
+            if let CommitteeEvent::VotesDelegated { delegation } = event
+                && !delegators.contains(&delegation.delegator_id)
+            {
+                delegators.push(delegation.delegator_id);
+            }
+        }
+        delegators
+            .into_iter()
+            .filter_map(|member_id| self.delegation_of(member_id))
+            .collect()
+    }
+
+    /// Members whose votes `delegate_id` may cast at the given time. A delegate that
+    /// has since joined the committee only casts its own vote.
+    pub fn delegators_for(
+        &self,
+        delegate_id: CommitteeMemberId,
+        at: DateTime<Utc>,
+    ) -> HashSet<CommitteeMemberId> {
+        if self.members().contains(&delegate_id) {
+            return HashSet::new();
+        }
+        self.delegations()
+            .into_iter()
+            .filter(|delegation| {
+                delegation.delegate_id == delegate_id && delegation.is_active_at(at)
+            })
+            .map(|delegation| delegation.delegator_id)
+            .collect()
     }
 
     pub fn n_members(&self) -> usize {
@@ -93,6 +206,8 @@ impl TryFromEvents<CommitteeEvent> for Committee {
                 }
                 CommitteeEvent::MemberAdded { .. } => {}
                 CommitteeEvent::MemberRemoved { .. } => {}
+                CommitteeEvent::VotesDelegated { .. } => {}
+                CommitteeEvent::VoteDelegationRevoked { .. } => {}
             }
         }
         builder.events(events).build()
@@ -123,3 +238,92 @@ impl IntoEvents<CommitteeEvent> for NewCommittee {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn committee_with_members(members: &[CommitteeMemberId]) -> Committee {
+        let id = CommitteeId::new();
+        let mut events = vec![CommitteeEvent::Initialized {
+            id,
+            name: "committee".to_string(),
+        }];
+        events.extend(members.iter().map(|member_id| CommitteeEvent::MemberAdded {
+            member_id: *member_id,
+        }));
+        Committee::try_from_events(EntityEvents::init(id, events)).unwrap()
+    }
+
+    #[test]
+    fn delegation_is_active_within_period() {
+        let delegator = CommitteeMemberId::new();
+        let delegate = CommitteeMemberId::new();
+        let mut committee = committee_with_members(&[delegator]);
+        let now = Utc::now();
+        let delegation = VoteDelegation {
+            delegator_id: delegator,
+            delegate_id: delegate,
+            starts_at: now,
+            ends_at: now + chrono::Duration::days(7),
+        };
+
+        assert!(committee.delegate_votes(delegation).unwrap().did_execute());
+        assert!(committee.delegate_votes(delegation).unwrap().was_ignored());
+        assert!(committee.delegators_for(delegate, now).contains(&delegator));
+        assert!(
+            committee
+                .delegators_for(delegate, now + chrono::Duration::days(7))
+                .is_empty()
+        );
+
+        assert!(committee.revoke_vote_delegation(delegator).did_execute());
+        assert!(committee.delegations().is_empty());
+    }
+
+    #[test]
+    fn delegation_requires_member() {
+        let mut committee = committee_with_members(&[]);
+        let now = Utc::now();
+        let res = committee.delegate_votes(VoteDelegation {
+            delegator_id: CommitteeMemberId::new(),
+            delegate_id: CommitteeMemberId::new(),
+            starts_at: now,
+            ends_at: now + chrono::Duration::days(1),
+        });
+        assert!(matches!(res, Err(CommitteeError::MemberNotFound(_))));
+    }
+
+    #[test]
+    fn delegation_to_fellow_member_rejected() {
+        let delegator = CommitteeMemberId::new();
+        let member = CommitteeMemberId::new();
+        let mut committee = committee_with_members(&[delegator, member]);
+        let now = Utc::now();
+        let res = committee.delegate_votes(VoteDelegation {
+            delegator_id: delegator,
+            delegate_id: member,
+            starts_at: now,
+            ends_at: now + chrono::Duration::days(1),
+        });
+        assert!(matches!(res, Err(CommitteeError::DelegateIsMember(id)) if id == member));
+    }
+
+    #[test]
+    fn delegate_joining_committee_only_votes_for_itself() {
+        let delegator = CommitteeMemberId::new();
+        let delegate = CommitteeMemberId::new();
+        let mut committee = committee_with_members(&[delegator]);
+        let now = Utc::now();
+        let delegation = VoteDelegation {
+            delegator_id: delegator,
+            delegate_id: delegate,
+            starts_at: now,
+            ends_at: now + chrono::Duration::days(7),
+        };
+        assert!(committee.delegate_votes(delegation).unwrap().did_execute());
+
+        committee.add_member(delegate).unwrap();
+        assert!(committee.delegators_for(delegate, now).is_empty());
+    }
+}
@@ -10,6 +10,14 @@ pub enum CommitteeError {
     CursorDestructureError(#[from] es_entity::CursorDestructureError),
     #[error("CommitteeError - MemberAlreadyAdded: {0}")]
     MemberAlreadyAdded(crate::primitives::CommitteeMemberId),
+    #[error("CommitteeError - MemberNotFound: {0}")]
+    MemberNotFound(crate::primitives::CommitteeMemberId),
+    #[error("CommitteeError - SelfDelegation: {0}")]
+    SelfDelegation(crate::primitives::CommitteeMemberId),
+    #[error("CommitteeError - DelegateIsMember: {0}")]
+    DelegateIsMember(crate::primitives::CommitteeMemberId),
+    #[error("CommitteeError - InvalidDelegationPeriod")]
+    InvalidDelegationPeriod,
 }
 
 es_entity::from_es_entity_error!(CommitteeError);
@@ -4,7 +4,7 @@ mod repo;
 
 #[cfg(feature = "json-schema")]
 pub use entity::CommitteeEvent;
-pub use entity::{Committee, NewCommittee};
+pub use entity::{Committee, NewCommittee, VoteDelegation};
 pub use repo::committee_cursor;
 
 pub(super) use repo::CommitteeRepo;
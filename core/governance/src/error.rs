@@ -14,6 +14,8 @@ pub enum GovernanceError {
     ApprovalProcessError(#[from] crate::approval_process::error::ApprovalProcessError),
     #[error("GovernanceError - Audit: {0}")]
     AuditError(#[from] audit::error::AuditError),
+    #[error("GovernanceError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
     #[error("GovernanceError - SubjectIsNotCommitteeMember")]
     SubjectIsNotCommitteeMember,
 }
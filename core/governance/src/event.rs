@@ -12,4 +12,16 @@ pub enum GovernanceEvent {
         denied_reason: Option<String>,
         target_ref: String,
     },
+    ApprovalProcessEscalated {
+        id: ApprovalProcessId,
+        process_type: ApprovalProcessType,
+        committee_id: CommitteeId,
+        target_ref: String,
+    },
+    ApprovalProcessReminderSent {
+        id: ApprovalProcessId,
+        process_type: ApprovalProcessType,
+        pending_voters: Vec<CommitteeMemberId>,
+        target_ref: String,
+    },
 }
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{Governance, event::GovernanceEvent, primitives::*};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ApprovalProcessDeadlineJobConfig<Perms, E> {
+    pub process_id: ApprovalProcessId,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> ApprovalProcessDeadlineJobConfig<Perms, E> {
+    pub fn new(process_id: ApprovalProcessId) -> Self {
+        Self {
+            process_id,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Perms, E> JobConfig for ApprovalProcessDeadlineJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    type Initializer = ApprovalProcessDeadlineInit<Perms, E>;
+}
+
+pub struct ApprovalProcessDeadlineInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: serde::de::DeserializeOwned + serde::Serialize + Send + Sync + 'static + Unpin,
+{
+    governance: Governance<Perms, E>,
+}
+
+impl<Perms, E> ApprovalProcessDeadlineInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: serde::de::DeserializeOwned + serde::Serialize + Send + Sync + 'static + Unpin,
+{
+    pub fn new(governance: &Governance<Perms, E>) -> Self {
+        Self {
+            governance: governance.clone(),
+        }
+    }
+}
+
+const APPROVAL_PROCESS_DEADLINE_JOB: JobType = JobType::new("task.approval-process-deadline");
+impl<Perms, E> JobInitializer for ApprovalProcessDeadlineInit<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        APPROVAL_PROCESS_DEADLINE_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(ApprovalProcessDeadlineJobRunner::<Perms, E> {
+            config: job.config()?,
+            governance: self.governance.clone(),
+        }))
+    }
+}
+
+pub struct ApprovalProcessDeadlineJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: serde::de::DeserializeOwned + serde::Serialize + Send + Sync + 'static + Unpin,
+{
+    config: ApprovalProcessDeadlineJobConfig<Perms, E>,
+    governance: Governance<Perms, E>,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for ApprovalProcessDeadlineJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        match self
+            .governance
+            .handle_process_deadline(self.config.process_id)
+            .await?
+        {
+            Some(next_deadline) => Ok(JobCompletion::RescheduleAt(next_deadline)),
+            None => Ok(JobCompletion::Complete),
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod approval_process_deadline;
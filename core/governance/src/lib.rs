@@ -5,6 +5,7 @@ mod approval_process;
 mod committee;
 pub mod error;
 mod event;
+mod jobs;
 mod policy;
 mod primitives;
 
@@ -14,12 +15,16 @@ use std::collections::{HashMap, HashSet};
 
 use audit::AuditSvc;
 use authz::PermissionCheck;
+use job::{JobId, Jobs};
 use outbox::{Outbox, OutboxEventMarker};
 
 pub use approval_process::{error as approval_process_error, *};
 pub use committee::{error as committee_error, *};
 use error::*;
 pub use event::*;
+use jobs::approval_process_deadline::{
+    ApprovalProcessDeadlineInit, ApprovalProcessDeadlineJobConfig,
+};
 pub use policy::{error as policy_error, *};
 pub use primitives::*;
 
@@ -39,6 +44,7 @@ where
     policy_repo: PolicyRepo,
     process_repo: ApprovalProcessRepo,
     authz: Perms,
+    jobs: Jobs,
     outbox: Outbox<E>,
 }
 
@@ -53,6 +59,7 @@ where
             policy_repo: self.policy_repo.clone(),
             process_repo: self.process_repo.clone(),
             authz: self.authz.clone(),
+            jobs: self.jobs.clone(),
             outbox: self.outbox.clone(),
         }
     }
@@ -65,18 +72,21 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
     E: OutboxEventMarker<GovernanceEvent>,
 {
-    pub fn new(pool: &sqlx::PgPool, authz: &Perms, outbox: &Outbox<E>) -> Self {
+    pub fn new(pool: &sqlx::PgPool, authz: &Perms, jobs: &Jobs, outbox: &Outbox<E>) -> Self {
         let committee_repo = CommitteeRepo::new(pool);
         let policy_repo = PolicyRepo::new(pool);
         let process_repo = ApprovalProcessRepo::new(pool);
 
-        Self {
+        let governance = Self {
             committee_repo,
             policy_repo,
             process_repo,
             authz: authz.clone(),
+            jobs: jobs.clone(),
             outbox: outbox.clone(),
-        }
+        };
+        jobs.add_initializer(ApprovalProcessDeadlineInit::new(&governance));
+        governance
     }
 
     pub async fn init_policy(
@@ -186,6 +196,127 @@ where
         Ok(policy)
     }
 
+    #[instrument(name = "governance.assign_stages_to_policy", skip(self), err)]
+    pub async fn assign_stages_to_policy(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        stages: Vec<ApprovalStage>,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        let committee_ids: Vec<_> = stages.iter().map(|stage| stage.committee_id).collect();
+        let committee_sizes = self
+            .committee_repo
+            .find_all::<Committee>(&committee_ids)
+            .await?
+            .into_iter()
+            .map(|(id, committee)| (id, committee.n_members()))
+            .collect();
+
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        if policy
+            .assign_stages(stages, &committee_sizes)?
+            .did_execute()
+        {
+            let mut db_tx = self.policy_repo.begin_op().await?;
+            self.policy_repo
+                .update_in_op(&mut db_tx, &mut policy)
+                .await?;
+            db_tx.commit().await?;
+        }
+
+        Ok(policy)
+    }
+
+    #[instrument(name = "governance.update_policy_approval_bands", skip(self), err)]
+    pub async fn update_policy_approval_bands(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        bands: Vec<ApprovalBand>,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        let committee_ids: Vec<_> = bands
+            .iter()
+            .flat_map(|band| band.rules.committee_ids())
+            .collect();
+        let committee_sizes = self
+            .committee_repo
+            .find_all::<Committee>(&committee_ids)
+            .await?
+            .into_iter()
+            .map(|(id, committee)| (id, committee.n_members()))
+            .collect();
+
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        if policy.update_bands(bands, &committee_sizes)?.did_execute() {
+            let mut db_tx = self.policy_repo.begin_op().await?;
+            self.policy_repo
+                .update_in_op(&mut db_tx, &mut policy)
+                .await?;
+            db_tx.commit().await?;
+        }
+
+        Ok(policy)
+    }
+
+    #[instrument(name = "governance.update_policy_expiry", skip(self), err)]
+    pub async fn update_policy_expiry(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        expiry: Option<ApprovalExpiry>,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        let mut committee_sizes = HashMap::new();
+        if let Some(ApprovalExpiry {
+            action: ApprovalExpiryAction::Escalate { committee_id, .. },
+            ..
+        }) = expiry
+        {
+            let committee = self.committee_repo.find_by_id(committee_id).await?;
+            committee_sizes.insert(committee.id, committee.n_members());
+        }
+
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        if policy
+            .update_expiry(expiry, &committee_sizes)?
+            .did_execute()
+        {
+            let mut db_tx = self.policy_repo.begin_op().await?;
+            self.policy_repo
+                .update_in_op(&mut db_tx, &mut policy)
+                .await?;
+            db_tx.commit().await?;
+        }
+
+        Ok(policy)
+    }
+
     #[instrument(name = "governance.find_all_policies", skip(self), err)]
     pub async fn find_all_policies<T: From<Policy>>(
         &self,
@@ -201,6 +332,7 @@ where
         id: impl Into<ApprovalProcessId> + std::fmt::Debug,
         target_ref: String,
         process_type: ApprovalProcessType,
+        context: ApprovalProcessContext,
     ) -> Result<ApprovalProcess, GovernanceError> {
         let policy = self.policy_repo.find_by_process_type(process_type).await?;
         self.authz
@@ -210,7 +342,7 @@ where
                 GovernanceAction::APPROVAL_PROCESS_CREATE,
             )
             .await?;
-        let new_process = policy.spawn_process(id.into(), target_ref);
+        let new_process = policy.spawn_process(id.into(), target_ref, &context, chrono::Utc::now());
         let mut process = self.process_repo.create_in_op(db, new_process).await?;
         let eligible = self.eligible_voters_for_process(&process).await?;
         if self
@@ -219,9 +351,104 @@ where
         {
             self.process_repo.update_in_op(db, &mut process).await?;
         }
+        if let Some(deadline) = process.next_deadline() {
+            self.jobs
+                .create_and_spawn_at_in_op(
+                    db,
+                    JobId::new(),
+                    ApprovalProcessDeadlineJobConfig::<Perms, E>::new(process.id),
+                    deadline,
+                )
+                .await?;
+        }
         Ok(process)
     }
 
+    #[instrument(name = "governance.handle_process_deadline", skip(self), err)]
+    pub(crate) async fn handle_process_deadline(
+        &self,
+        process_id: ApprovalProcessId,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, GovernanceError> {
+        let mut process = self.process_repo.find_by_id(process_id).await?;
+        let now = chrono::Utc::now();
+        let eligible = self.eligible_voters_for_process(&process).await?;
+
+        let mut db = self.process_repo.begin_op().await?;
+        let mut updated = false;
+
+        if let es_entity::Idempotent::Executed(pending_voters) =
+            process.send_reminder(&eligible, now)
+        {
+            self.outbox
+                .publish_persisted(
+                    &mut db,
+                    GovernanceEvent::ApprovalProcessReminderSent {
+                        id: process.id,
+                        process_type: process.process_type.clone(),
+                        pending_voters,
+                        target_ref: process.target_ref().to_string(),
+                    },
+                )
+                .await?;
+            updated = true;
+        }
+
+        if let es_entity::Idempotent::Executed(outcome) = process.expire(now) {
+            self.authz
+                .audit()
+                .record_system_entry_in_tx(
+                    &mut db,
+                    GovernanceObject::approval_process(process.id),
+                    GovernanceAction::APPROVAL_PROCESS_EXPIRE,
+                )
+                .await?;
+            match outcome {
+                ApprovalProcessExpiryOutcome::Denied => {
+                    self.outbox
+                        .publish_persisted(
+                            &mut db,
+                            GovernanceEvent::ApprovalProcessConcluded {
+                                id: process.id,
+                                approved: false,
+                                denied_reason: process.denied_reason().map(String::from),
+                                process_type: process.process_type.clone(),
+                                target_ref: process.target_ref().to_string(),
+                            },
+                        )
+                        .await?;
+                }
+                ApprovalProcessExpiryOutcome::Escalated => {
+                    self.outbox
+                        .publish_persisted(
+                            &mut db,
+                            GovernanceEvent::ApprovalProcessEscalated {
+                                id: process.id,
+                                process_type: process.process_type.clone(),
+                                committee_id: process
+                                    .committee_id()
+                                    .expect("escalated process has a committee"),
+                                target_ref: process.target_ref().to_string(),
+                            },
+                        )
+                        .await?;
+                    let eligible = self.eligible_voters_for_process(&process).await?;
+                    self.maybe_fire_concluded_event(db.begin().await?, eligible, &mut process)
+                        .await?;
+                }
+            }
+            updated = true;
+        }
+
+        if updated {
+            self.process_repo
+                .update_in_op(&mut db, &mut process)
+                .await?;
+        }
+        db.commit().await?;
+
+        Ok(process.next_deadline())
+    }
+
     #[instrument(name = "governance.approve_process", skip(self), err)]
     pub async fn approve_process(
         &self,
@@ -243,10 +470,40 @@ where
         let member_id = CommitteeMemberId::try_from(sub)
             .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
         let mut process = self.process_repo.find_by_id(process_id).await?;
-        let eligible = self.eligible_voters_for_process(&process).await?;
-
-        if process.approve(&eligible, member_id).did_execute() {
+        let committee = self.committee_for_process(&process).await?;
+        let eligible = committee
+            .as_ref()
+            .map(Committee::members)
+            .unwrap_or_default();
+        let delegators = committee
+            .as_ref()
+            .map(|committee| committee.delegators_for(member_id, chrono::Utc::now()))
+            .unwrap_or_default();
+
+        let voted = process.approve(&eligible, member_id).did_execute();
+        let voted_on_behalf: Vec<_> = delegators
+            .into_iter()
+            .filter(|delegator_id| {
+                process
+                    .approve_on_behalf(&eligible, *delegator_id, member_id)
+                    .did_execute()
+            })
+            .collect();
+
+        if voted || !voted_on_behalf.is_empty() {
             let mut db = self.policy_repo.begin_op().await?;
+            if !voted_on_behalf.is_empty() {
+                self.authz
+                    .audit()
+                    .record_entry_in_tx(
+                        &mut db,
+                        sub,
+                        GovernanceObject::approval_process(process.id),
+                        GovernanceAction::APPROVAL_PROCESS_VOTE_ON_BEHALF,
+                        true,
+                    )
+                    .await?;
+            }
             self.maybe_fire_concluded_event(db.begin().await?, eligible, &mut process)
                 .await?;
             self.process_repo
@@ -280,16 +537,42 @@ where
         let member_id = CommitteeMemberId::try_from(sub)
             .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
         let mut process = self.process_repo.find_by_id(process_id).await?;
-        let eligible = if let Some(committee_id) = process.committee_id() {
-            self.committee_repo
-                .find_by_id(committee_id)
-                .await?
-                .members()
-        } else {
-            HashSet::new()
-        };
-        if process.deny(&eligible, member_id, reason).did_execute() {
+        let committee = self.committee_for_process(&process).await?;
+        let eligible = committee
+            .as_ref()
+            .map(Committee::members)
+            .unwrap_or_default();
+        let delegators = committee
+            .as_ref()
+            .map(|committee| committee.delegators_for(member_id, chrono::Utc::now()))
+            .unwrap_or_default();
+
+        let voted = process
+            .deny(&eligible, member_id, reason.clone())
+            .did_execute();
+        let voted_on_behalf: Vec<_> = delegators
+            .into_iter()
+            .filter(|delegator_id| {
+                process
+                    .deny_on_behalf(&eligible, *delegator_id, member_id, reason.clone())
+                    .did_execute()
+            })
+            .collect();
+
+        if voted || !voted_on_behalf.is_empty() {
             let mut db = self.policy_repo.begin_op().await?;
+            if !voted_on_behalf.is_empty() {
+                self.authz
+                    .audit()
+                    .record_entry_in_tx(
+                        &mut db,
+                        sub,
+                        GovernanceObject::approval_process(process.id),
+                        GovernanceAction::APPROVAL_PROCESS_VOTE_ON_BEHALF,
+                        true,
+                    )
+                    .await?;
+            }
             self.maybe_fire_concluded_event(db.begin().await?, eligible, &mut process)
                 .await?;
             self.process_repo
@@ -330,10 +613,12 @@ where
         Ok(committee)
     }
 
+    /// Returns `true` if the process was advanced to a later stage or concluded and
+    /// needs to be persisted.
     async fn maybe_fire_concluded_event(
         &self,
         mut op: es_entity::DbOp<'_>,
-        eligible: HashSet<CommitteeMemberId>,
+        mut eligible: HashSet<CommitteeMemberId>,
         process: &mut ApprovalProcess,
     ) -> Result<bool, GovernanceError> {
         self.authz
@@ -345,6 +630,12 @@ where
             )
             .await?;
 
+        let mut advanced = false;
+        while process.advance_stage(&eligible).did_execute() {
+            advanced = true;
+            eligible = self.eligible_voters_for_process(process).await?;
+        }
+
         if let es_entity::Idempotent::Executed((approved, denied_reason)) =
             process.check_concluded(eligible)
         {
@@ -365,7 +656,7 @@ where
             return Ok(true);
         }
 
-        Ok(false)
+        Ok(advanced)
     }
 
     #[instrument(name = "governance.add_member_to_committee", skip(self), err)]
@@ -408,8 +699,118 @@ where
             .await?;
 
         let mut committee = self.committee_repo.find_by_id(committee_id).await?;
-        committee.remove_member(member_id.into());
-        self.committee_repo.update(&mut committee).await?;
+        if committee.remove_member(member_id.into()).did_execute() {
+            self.committee_repo.update(&mut committee).await?;
+        }
+
+        Ok(committee)
+    }
+
+    /// Removes `member_id` from every committee it belongs to, e.g. when the underlying
+    /// user is deactivated. Returns the committees the member was removed from.
+    #[instrument(name = "governance.remove_member_from_all_committees", skip(self), err)]
+    pub async fn remove_member_from_all_committees(
+        &self,
+        member_id: impl Into<CommitteeMemberId> + std::fmt::Debug,
+    ) -> Result<Vec<CommitteeId>, GovernanceError> {
+        let member_id = member_id.into();
+        let mut removed_from = Vec::new();
+        let mut has_next_page = true;
+        let mut after: Option<committee::committee_cursor::CommitteesByCreatedAtCursor> = None;
+        while has_next_page {
+            let mut committees = self
+                .committee_repo
+                .list_by_created_at(
+                    es_entity::PaginatedQueryArgs::<
+                        committee::committee_cursor::CommitteesByCreatedAtCursor,
+                    > {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+            (after, has_next_page) = (committees.end_cursor, committees.has_next_page);
+
+            let mut op = self.committee_repo.begin_op().await?;
+            for committee in committees.entities.iter_mut() {
+                if committee.remove_member(member_id).did_execute() {
+                    self.authz
+                        .audit()
+                        .record_system_entry_in_tx(
+                            &mut op,
+                            GovernanceObject::committee(committee.id),
+                            GovernanceAction::COMMITTEE_REMOVE_MEMBER,
+                        )
+                        .await?;
+                    self.committee_repo.update_in_op(&mut op, committee).await?;
+                    removed_from.push(committee.id);
+                }
+            }
+            op.commit().await?;
+        }
+
+        Ok(removed_from)
+    }
+
+    #[instrument(name = "governance.delegate_committee_votes", skip(self), err)]
+    pub async fn delegate_committee_votes(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        committee_id: impl Into<CommitteeId> + std::fmt::Debug,
+        delegator_id: impl Into<CommitteeMemberId> + std::fmt::Debug,
+        delegate_id: impl Into<CommitteeMemberId> + std::fmt::Debug,
+        starts_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Committee, GovernanceError> {
+        let committee_id = committee_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::committee(committee_id),
+                GovernanceAction::COMMITTEE_DELEGATE_VOTES,
+            )
+            .await?;
+
+        let mut committee = self.committee_repo.find_by_id(committee_id).await?;
+        if committee
+            .delegate_votes(VoteDelegation {
+                delegator_id: delegator_id.into(),
+                delegate_id: delegate_id.into(),
+                starts_at,
+                ends_at,
+            })?
+            .did_execute()
+        {
+            self.committee_repo.update(&mut committee).await?;
+        }
+
+        Ok(committee)
+    }
+
+    #[instrument(name = "governance.revoke_committee_vote_delegation", skip(self), err)]
+    pub async fn revoke_committee_vote_delegation(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        committee_id: impl Into<CommitteeId> + std::fmt::Debug,
+        delegator_id: impl Into<CommitteeMemberId> + std::fmt::Debug,
+    ) -> Result<Committee, GovernanceError> {
+        let committee_id = committee_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::committee(committee_id),
+                GovernanceAction::COMMITTEE_DELEGATE_VOTES,
+            )
+            .await?;
+
+        let mut committee = self.committee_repo.find_by_id(committee_id).await?;
+        if committee
+            .revoke_vote_delegation(delegator_id.into())
+            .did_execute()
+        {
+            self.committee_repo.update(&mut committee).await?;
+        }
 
         Ok(committee)
     }
@@ -545,24 +946,37 @@ where
         if let Some(committee) = committee {
             let member_id = CommitteeMemberId::try_from(sub)
                 .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
-            Ok(process.can_member_vote(member_id, committee.members()))
+            let eligible = committee.members();
+            Ok(process.can_member_vote(member_id, eligible.clone())
+                || committee
+                    .delegators_for(member_id, chrono::Utc::now())
+                    .into_iter()
+                    .any(|delegator_id| process.can_member_vote(delegator_id, eligible.clone())))
         } else {
             Ok(false)
         }
     }
 
-    async fn eligible_voters_for_process(
+    async fn committee_for_process(
         &self,
         process: &ApprovalProcess,
-    ) -> Result<HashSet<CommitteeMemberId>, GovernanceError> {
+    ) -> Result<Option<Committee>, GovernanceError> {
         let res = if let Some(committee_id) = process.committee_id() {
-            self.committee_repo
-                .find_by_id(committee_id)
-                .await?
-                .members()
+            Some(self.committee_repo.find_by_id(committee_id).await?)
         } else {
-            HashSet::new()
+            None
         };
         Ok(res)
     }
+
+    async fn eligible_voters_for_process(
+        &self,
+        process: &ApprovalProcess,
+    ) -> Result<HashSet<CommitteeMemberId>, GovernanceError> {
+        Ok(self
+            .committee_for_process(process)
+            .await?
+            .map(|committee| committee.members())
+            .unwrap_or_default())
+    }
 }
@@ -3,9 +3,17 @@ use derive_builder::Builder;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use std::collections::HashMap;
+
 use es_entity::*;
 
-use super::{error::PolicyError, rules::ApprovalRules};
+use super::{
+    error::PolicyError,
+    rules::{
+        ApprovalBand, ApprovalExpiry, ApprovalExpiryAction, ApprovalProcessContext, ApprovalRules,
+        ApprovalStage,
+    },
+};
 use crate::{approval_process::NewApprovalProcess, primitives::*};
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +29,12 @@ pub enum PolicyEvent {
     ApprovalRulesUpdated {
         rules: ApprovalRules,
     },
+    ApprovalBandsUpdated {
+        bands: Vec<ApprovalBand>,
+    },
+    ExpiryUpdated {
+        expiry: Option<ApprovalExpiry>,
+    },
 }
 
 #[derive(EsEntity, Builder)]
@@ -29,6 +43,10 @@ pub struct Policy {
     pub id: PolicyId,
     pub process_type: ApprovalProcessType,
     pub rules: ApprovalRules,
+    #[builder(default)]
+    pub bands: Vec<ApprovalBand>,
+    #[builder(default)]
+    pub expiry: Option<ApprovalExpiry>,
     events: EntityEvents<PolicyEvent>,
 }
 
@@ -43,17 +61,30 @@ impl Policy {
             .expect("No events for policy")
     }
 
+    pub fn rules_for(&self, context: &ApprovalProcessContext) -> ApprovalRules {
+        self.bands
+            .iter()
+            .find(|band| band.matches(context))
+            .map(|band| band.rules.clone())
+            .unwrap_or_else(|| self.rules.clone())
+    }
+
     pub(crate) fn spawn_process(
         &self,
         id: ApprovalProcessId,
         target_ref: String,
+        context: &ApprovalProcessContext,
+        now: chrono::DateTime<chrono::Utc>,
     ) -> NewApprovalProcess {
         NewApprovalProcess::builder()
             .id(id)
             .target_ref(target_ref)
             .policy_id(self.id)
             .process_type(self.process_type.clone())
-            .rules(self.rules)
+            .rules(self.rules_for(context))
+            .expiry(self.expiry)
+            .expires_at(self.expiry.map(|expiry| expiry.expires_at(now)))
+            .remind_at(self.expiry.and_then(|expiry| expiry.remind_at(now)))
             .build()
             .expect("failed to build new approval process")
     }
@@ -85,12 +116,142 @@ impl Policy {
             committee_id,
         };
 
+        self.events.push(PolicyEvent::ApprovalRulesUpdated {
+            rules: self.rules.clone(),
+        });
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub fn assign_stages(
+        &mut self,
+        stages: Vec<ApprovalStage>,
+        committee_sizes: &HashMap<CommitteeId, usize>,
+    ) -> Result<Idempotent<()>, PolicyError> {
+        if stages.is_empty() {
+            return Err(PolicyError::NoApprovalStages);
+        }
+        let rules = ApprovalRules::MultiStage { stages };
+
+        if self.rules == rules {
+            return Ok(Idempotent::Ignored);
+        }
+
+        validate_rules(&rules, committee_sizes)?;
+
+        self.rules = rules;
+        self.events.push(PolicyEvent::ApprovalRulesUpdated {
+            rules: self.rules.clone(),
+        });
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub fn update_bands(
+        &mut self,
+        bands: Vec<ApprovalBand>,
+        committee_sizes: &HashMap<CommitteeId, usize>,
+    ) -> Result<Idempotent<()>, PolicyError> {
+        if self.bands == bands {
+            return Ok(Idempotent::Ignored);
+        }
+
+        for band in bands.iter() {
+            validate_rules(&band.rules, committee_sizes)?;
+        }
+
+        for (idx, band) in bands.iter().enumerate() {
+            let shadowed = bands[..idx].iter().any(|earlier| {
+                earlier.customer_type == band.customer_type
+                    && match (earlier.amount_below, band.amount_below) {
+                        (None, _) => true,
+                        (Some(earlier), Some(current)) => earlier >= current,
+                        (Some(_), None) => false,
+                    }
+            });
+            if shadowed {
+                return Err(PolicyError::ApprovalBandsNotAscending);
+            }
+        }
+
+        self.bands = bands.clone();
         self.events
-            .push(PolicyEvent::ApprovalRulesUpdated { rules: self.rules });
+            .push(PolicyEvent::ApprovalBandsUpdated { bands });
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub fn update_expiry(
+        &mut self,
+        expiry: Option<ApprovalExpiry>,
+        committee_sizes: &HashMap<CommitteeId, usize>,
+    ) -> Result<Idempotent<()>, PolicyError> {
+        if self.expiry == expiry {
+            return Ok(Idempotent::Ignored);
+        }
+
+        if let Some(expiry) = expiry {
+            if expiry.ttl_hours == 0 {
+                return Err(PolicyError::InvalidExpiry(
+                    "ttl must be at least one hour".to_string(),
+                ));
+            }
+            if let Some(remind_after_hours) = expiry.remind_after_hours
+                && remind_after_hours >= expiry.ttl_hours
+            {
+                return Err(PolicyError::InvalidExpiry(
+                    "reminder must be sent before the process expires".to_string(),
+                ));
+            }
+            if let ApprovalExpiryAction::Escalate {
+                committee_id,
+                threshold,
+            } = expiry.action
+            {
+                if threshold < 1 {
+                    return Err(PolicyError::PolicyThresholdTooLow(committee_id, threshold));
+                }
+                let n_members = committee_sizes.get(&committee_id).copied().unwrap_or(0);
+                if threshold > n_members {
+                    return Err(PolicyError::PolicyThresholdTooHigh(committee_id, threshold));
+                }
+            }
+        }
+
+        self.expiry = expiry;
+        self.events.push(PolicyEvent::ExpiryUpdated { expiry });
         Ok(Idempotent::Executed(()))
     }
 }
 
+fn validate_rules(
+    rules: &ApprovalRules,
+    committee_sizes: &HashMap<CommitteeId, usize>,
+) -> Result<(), PolicyError> {
+    let stages = match rules {
+        ApprovalRules::CommitteeThreshold {
+            committee_id,
+            threshold,
+        } => vec![ApprovalStage {
+            committee_id: *committee_id,
+            threshold: *threshold,
+        }],
+        ApprovalRules::MultiStage { stages } => stages.clone(),
+        ApprovalRules::SystemAutoApprove => vec![],
+    };
+    for ApprovalStage {
+        committee_id,
+        threshold,
+    } in stages
+    {
+        if threshold < 1 {
+            return Err(PolicyError::PolicyThresholdTooLow(committee_id, threshold));
+        }
+        let n_members = committee_sizes.get(&committee_id).copied().unwrap_or(0);
+        if threshold > n_members {
+            return Err(PolicyError::PolicyThresholdTooHigh(committee_id, threshold));
+        }
+    }
+    Ok(())
+}
+
 impl TryFromEvents<PolicyEvent> for Policy {
     fn try_from_events(events: EntityEvents<PolicyEvent>) -> Result<Self, EsEntityError> {
         let mut builder = PolicyBuilder::default();
@@ -105,9 +266,15 @@ impl TryFromEvents<PolicyEvent> for Policy {
                     builder = builder
                         .id(*id)
                         .process_type(process_type.clone())
-                        .rules(*rules)
+                        .rules(rules.clone())
                 }
-                PolicyEvent::ApprovalRulesUpdated { rules, .. } => builder = builder.rules(*rules),
+                PolicyEvent::ApprovalRulesUpdated { rules, .. } => {
+                    builder = builder.rules(rules.clone())
+                }
+                PolicyEvent::ApprovalBandsUpdated { bands } => {
+                    builder = builder.bands(bands.clone())
+                }
+                PolicyEvent::ExpiryUpdated { expiry } => builder = builder.expiry(*expiry),
             }
         }
         builder.events(events).build()
@@ -204,4 +371,147 @@ mod test {
 
         assert!(matches!(res, Err(PolicyError::PolicyThresholdTooLow(_, _))));
     }
+
+    #[test]
+    fn selects_rules_by_amount_band() {
+        let mut policy = Policy::try_from_events(init_events()).unwrap();
+        let operations = CommitteeId::new();
+        let credit = CommitteeId::new();
+        let committee_sizes = HashMap::from([(operations, 3), (credit, 5)]);
+        let bands = vec![
+            ApprovalBand {
+                amount_below: Some(UsdCents::from(1_000_000)),
+                customer_type: None,
+                rules: ApprovalRules::SystemAutoApprove,
+            },
+            ApprovalBand {
+                amount_below: Some(UsdCents::from(25_000_000)),
+                customer_type: None,
+                rules: ApprovalRules::CommitteeThreshold {
+                    committee_id: operations,
+                    threshold: 2,
+                },
+            },
+            ApprovalBand {
+                amount_below: None,
+                customer_type: None,
+                rules: ApprovalRules::CommitteeThreshold {
+                    committee_id: credit,
+                    threshold: 3,
+                },
+            },
+        ];
+        assert!(
+            policy
+                .update_bands(bands, &committee_sizes)
+                .unwrap()
+                .did_execute()
+        );
+
+        let rules_for =
+            |cents: u64| policy.rules_for(&ApprovalProcessContext::for_amount(cents.into()));
+        assert_eq!(rules_for(999_999), ApprovalRules::SystemAutoApprove);
+        assert_eq!(
+            rules_for(1_000_000),
+            ApprovalRules::CommitteeThreshold {
+                committee_id: operations,
+                threshold: 2
+            }
+        );
+        assert_eq!(
+            rules_for(25_000_000),
+            ApprovalRules::CommitteeThreshold {
+                committee_id: credit,
+                threshold: 3
+            }
+        );
+        assert_eq!(
+            policy.rules_for(&ApprovalProcessContext::default()),
+            ApprovalRules::CommitteeThreshold {
+                committee_id: credit,
+                threshold: 3
+            }
+        );
+    }
+
+    #[test]
+    fn selects_rules_by_customer_type() {
+        let mut policy = Policy::try_from_events(init_events()).unwrap();
+        let committee_id = CommitteeId::new();
+        let committee_sizes = HashMap::from([(committee_id, 1)]);
+        let bank_rules = ApprovalRules::CommitteeThreshold {
+            committee_id,
+            threshold: 1,
+        };
+        let bands = vec![ApprovalBand {
+            amount_below: None,
+            customer_type: Some("Bank".to_string()),
+            rules: bank_rules.clone(),
+        }];
+        let _ = policy.update_bands(bands, &committee_sizes).unwrap();
+
+        let context = ApprovalProcessContext::for_amount(UsdCents::ONE);
+        assert_eq!(
+            policy.rules_for(&context.clone().with_customer_type("Bank")),
+            bank_rules
+        );
+        assert_eq!(policy.rules_for(&context), ApprovalRules::SystemAutoApprove);
+    }
+
+    #[test]
+    fn error_when_bands_are_not_ascending() {
+        let mut policy = Policy::try_from_events(init_events()).unwrap();
+        let bands = vec![
+            ApprovalBand {
+                amount_below: Some(UsdCents::from(100)),
+                customer_type: None,
+                rules: ApprovalRules::SystemAutoApprove,
+            },
+            ApprovalBand {
+                amount_below: Some(UsdCents::from(50)),
+                customer_type: None,
+                rules: ApprovalRules::SystemAutoApprove,
+            },
+        ];
+        let res = policy.update_bands(bands, &HashMap::new());
+
+        assert!(matches!(res, Err(PolicyError::ApprovalBandsNotAscending)));
+    }
+
+    #[test]
+    fn assign_stages() {
+        let mut policy = Policy::try_from_events(init_events()).unwrap();
+        let risk = CommitteeId::new();
+        let credit = CommitteeId::new();
+        let committee_sizes = HashMap::from([(risk, 2), (credit, 3)]);
+        let stages = vec![
+            ApprovalStage {
+                committee_id: risk,
+                threshold: 1,
+            },
+            ApprovalStage {
+                committee_id: credit,
+                threshold: 4,
+            },
+        ];
+        assert!(matches!(
+            policy.assign_stages(stages.clone(), &committee_sizes),
+            Err(PolicyError::PolicyThresholdTooHigh(id, 4)) if id == credit
+        ));
+        assert!(matches!(
+            policy.assign_stages(vec![], &committee_sizes),
+            Err(PolicyError::NoApprovalStages)
+        ));
+
+        let mut stages = stages;
+        stages[1].threshold = 2;
+        assert!(
+            policy
+                .assign_stages(stages.clone(), &committee_sizes)
+                .unwrap()
+                .did_execute()
+        );
+        assert_eq!(policy.rules, ApprovalRules::MultiStage { stages });
+        assert_eq!(policy.committee_id(), Some(risk));
+    }
 }
@@ -14,6 +14,12 @@ pub enum PolicyError {
     PolicyThresholdTooHigh(crate::primitives::CommitteeId, usize),
     #[error("PolicyError - Threshold {1} too low for committee {0}")]
     PolicyThresholdTooLow(crate::primitives::CommitteeId, usize),
+    #[error("PolicyError - ApprovalBandsNotAscending")]
+    ApprovalBandsNotAscending,
+    #[error("PolicyError - NoApprovalStages")]
+    NoApprovalStages,
+    #[error("PolicyError - InvalidExpiry: {0}")]
+    InvalidExpiry(String),
 }
 
 es_entity::from_es_entity_error!(PolicyError);
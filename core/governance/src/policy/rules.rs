@@ -1,11 +1,20 @@
 use serde::{Deserialize, Serialize};
 
+use chrono::{DateTime, Utc};
+
 use std::collections::HashSet;
 
-use crate::primitives::CommitteeId;
+use crate::primitives::{CommitteeId, UsdCents};
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ApprovalStage {
+    pub committee_id: CommitteeId,
+    pub threshold: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ApprovalRules {
     CommitteeThreshold {
@@ -13,13 +22,75 @@ pub enum ApprovalRules {
         threshold: usize,
     },
     SystemAutoApprove,
+    /// Stages are approved sequentially, each by its own committee.
+    MultiStage {
+        stages: Vec<ApprovalStage>,
+    },
 }
 
 impl ApprovalRules {
+    /// The committee voting first. For multi-stage rules use [`Self::stage`] to get
+    /// the committee of a later stage.
     pub fn committee_id(&self) -> Option<CommitteeId> {
         match self {
             ApprovalRules::CommitteeThreshold { committee_id, .. } => Some(*committee_id),
             ApprovalRules::SystemAutoApprove => None,
+            ApprovalRules::MultiStage { stages } => stages.first().map(|stage| stage.committee_id),
+        }
+    }
+
+    pub fn committee_ids(&self) -> Vec<CommitteeId> {
+        match self {
+            ApprovalRules::MultiStage { stages } => {
+                stages.iter().map(|stage| stage.committee_id).collect()
+            }
+            rules => rules.committee_id().into_iter().collect(),
+        }
+    }
+
+    pub fn n_stages(&self) -> usize {
+        match self {
+            ApprovalRules::MultiStage { stages } => stages.len(),
+            _ => 1,
+        }
+    }
+
+    /// Single-committee rules that apply while the process is in the given stage.
+    pub fn stage(&self, idx: usize) -> ApprovalRules {
+        match self {
+            ApprovalRules::MultiStage { stages } => match stages.get(idx).or(stages.last()) {
+                Some(stage) => ApprovalRules::CommitteeThreshold {
+                    committee_id: stage.committee_id,
+                    threshold: stage.threshold,
+                },
+                None => ApprovalRules::SystemAutoApprove,
+            },
+            rules => rules.clone(),
+        }
+    }
+
+    /// Rules with the committee and threshold of the given stage replaced, keeping
+    /// the other stages.
+    pub fn escalate_stage(
+        &self,
+        idx: usize,
+        committee_id: CommitteeId,
+        threshold: usize,
+    ) -> ApprovalRules {
+        match self {
+            ApprovalRules::MultiStage { stages } if !stages.is_empty() => {
+                let mut stages = stages.clone();
+                let idx = idx.min(stages.len() - 1);
+                stages[idx] = ApprovalStage {
+                    committee_id,
+                    threshold,
+                };
+                ApprovalRules::MultiStage { stages }
+            }
+            _ => ApprovalRules::CommitteeThreshold {
+                committee_id,
+                threshold,
+            },
         }
     }
 
@@ -44,11 +115,92 @@ impl ApprovalRules {
             {
                 Some(false)
             }
+            ApprovalRules::MultiStage { .. } => self.stage(0).is_approved_or_denied(
+                eligible_members,
+                approving_members,
+                denying_members,
+            ),
             _ => None,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApprovalExpiryAction {
+    Deny,
+    Escalate {
+        committee_id: CommitteeId,
+        threshold: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ApprovalExpiry {
+    pub ttl_hours: u32,
+    pub remind_after_hours: Option<u32>,
+    pub action: ApprovalExpiryAction,
+}
+
+impl ApprovalExpiry {
+    pub fn expires_at(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        from + chrono::Duration::hours(self.ttl_hours.into())
+    }
+
+    pub fn remind_at(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.remind_after_hours
+            .map(|hours| from + chrono::Duration::hours(hours.into()))
+    }
+}
+
+/// Inputs used to pick the band of a policy when spawning an approval process.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalProcessContext {
+    pub amount: Option<UsdCents>,
+    pub customer_type: Option<String>,
+}
+
+impl ApprovalProcessContext {
+    pub fn for_amount(amount: UsdCents) -> Self {
+        Self {
+            amount: Some(amount),
+            customer_type: None,
+        }
+    }
+
+    pub fn with_customer_type(mut self, customer_type: impl Into<String>) -> Self {
+        self.customer_type = Some(customer_type.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ApprovalBand {
+    /// Exclusive upper bound of the band. `None` means unbounded.
+    pub amount_below: Option<UsdCents>,
+    pub customer_type: Option<String>,
+    pub rules: ApprovalRules,
+}
+
+impl ApprovalBand {
+    pub fn matches(&self, context: &ApprovalProcessContext) -> bool {
+        let amount_matches = match (self.amount_below, context.amount) {
+            (None, _) => true,
+            (Some(below), Some(amount)) => amount < below,
+            (Some(_), None) => false,
+        };
+        let customer_type_matches = match (&self.customer_type, &context.customer_type) {
+            (None, _) => true,
+            (Some(expected), Some(actual)) => expected == actual,
+            (Some(_), None) => false,
+        };
+        amount_matches && customer_type_matches
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +310,33 @@ mod tests {
             "Should be denied when threshold exceeds eligible set size"
         );
     }
+
+    #[test]
+    fn test_multi_stage_rules() {
+        let risk = CommitteeId::new();
+        let credit = CommitteeId::new();
+        let rules = ApprovalRules::MultiStage {
+            stages: vec![
+                ApprovalStage {
+                    committee_id: risk,
+                    threshold: 1,
+                },
+                ApprovalStage {
+                    committee_id: credit,
+                    threshold: 2,
+                },
+            ],
+        };
+
+        assert_eq!(rules.n_stages(), 2);
+        assert_eq!(rules.committee_id(), Some(risk));
+        assert_eq!(rules.committee_ids(), vec![risk, credit]);
+        assert_eq!(
+            rules.stage(1),
+            ApprovalRules::CommitteeThreshold {
+                committee_id: credit,
+                threshold: 2,
+            }
+        );
+    }
 }
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, fmt::Display, str::FromStr};
 
 use authz::{ActionPermission, AllOrOne, action_description::*, map_action};
+pub use core_money::UsdCents;
+
 es_entity::entity_id! { ApprovalProcessId, CommitteeId, PolicyId, CommitteeMemberId }
 
 #[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
@@ -59,6 +61,8 @@ impl GovernanceAction {
     pub const COMMITTEE_ADD_MEMBER: Self = GovernanceAction::Committee(CommitteeAction::AddMember);
     pub const COMMITTEE_REMOVE_MEMBER: Self =
         GovernanceAction::Committee(CommitteeAction::RemoveMember);
+    pub const COMMITTEE_DELEGATE_VOTES: Self =
+        GovernanceAction::Committee(CommitteeAction::DelegateVotes);
 
     pub const POLICY_CREATE: Self = GovernanceAction::Policy(PolicyAction::Create);
     pub const POLICY_READ: Self = GovernanceAction::Policy(PolicyAction::Read);
@@ -77,6 +81,10 @@ impl GovernanceAction {
         GovernanceAction::ApprovalProcess(ApprovalProcessAction::Deny);
     pub const APPROVAL_PROCESS_CONCLUDE: Self =
         GovernanceAction::ApprovalProcess(ApprovalProcessAction::Conclude);
+    pub const APPROVAL_PROCESS_EXPIRE: Self =
+        GovernanceAction::ApprovalProcess(ApprovalProcessAction::Expire);
+    pub const APPROVAL_PROCESS_VOTE_ON_BEHALF: Self =
+        GovernanceAction::ApprovalProcess(ApprovalProcessAction::VoteOnBehalf);
 
     pub fn actions() -> Vec<ActionMapping> {
         use GovernanceActionDiscriminants::*;
@@ -126,6 +134,7 @@ pub enum CommitteeAction {
     Create,
     AddMember,
     RemoveMember,
+    DelegateVotes,
     Read,
     List,
 }
@@ -135,7 +144,9 @@ impl ActionPermission for CommitteeAction {
         match self {
             Self::Read | Self::List => PERMISSION_SET_GOVERNANCE_VIEWER,
 
-            Self::Create | Self::AddMember | Self::RemoveMember => PERMISSION_SET_GOVERNANCE_WRITER,
+            Self::Create | Self::AddMember | Self::RemoveMember | Self::DelegateVotes => {
+                PERMISSION_SET_GOVERNANCE_WRITER
+            }
         }
     }
 }
@@ -168,6 +179,8 @@ pub enum ApprovalProcessAction {
     Approve,
     Deny,
     Conclude,
+    Expire,
+    VoteOnBehalf,
 }
 
 impl ActionPermission for ApprovalProcessAction {
@@ -175,9 +188,12 @@ impl ActionPermission for ApprovalProcessAction {
         match self {
             Self::Read | Self::List => PERMISSION_SET_GOVERNANCE_VIEWER,
 
-            Self::Create | Self::Approve | Self::Deny | Self::Conclude => {
-                PERMISSION_SET_GOVERNANCE_WRITER
-            }
+            Self::Create
+            | Self::Approve
+            | Self::Deny
+            | Self::Conclude
+            | Self::Expire
+            | Self::VoteOnBehalf => PERMISSION_SET_GOVERNANCE_WRITER,
         }
     }
 }
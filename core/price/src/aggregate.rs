@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+
+use core_money::UsdCents;
+
+use crate::{
+    config::PriceConfig, error::PriceError, primitives::PriceOfOneBTC, provider::PriceQuote,
+};
+
+/// Combines quotes of independent sources into a single price.
+///
+/// Quotes older than `max_staleness` are dropped, the median of the rest is computed
+/// and quotes deviating from it by more than `max_deviation_bps` are rejected as outliers.
+/// The median of the remaining quotes is returned as long as at least `min_sources` agree.
+pub(crate) fn aggregate_quotes(
+    quotes: &[PriceQuote],
+    config: &PriceConfig,
+    now: DateTime<Utc>,
+) -> Result<PriceOfOneBTC, PriceError> {
+    let max_staleness =
+        chrono::Duration::from_std(config.max_staleness).unwrap_or(chrono::Duration::MAX);
+    let fresh = quotes
+        .iter()
+        .filter(|quote| now.signed_duration_since(quote.observed_at) <= max_staleness)
+        .map(|quote| quote.price.into_inner().into_inner())
+        .collect::<Vec<_>>();
+    if fresh.len() < config.min_sources {
+        return Err(PriceError::StalePrice {
+            fresh: fresh.len(),
+            required: config.min_sources,
+        });
+    }
+
+    let reference = median(&fresh).ok_or(PriceError::NoPriceConsensus {
+        agreeing: 0,
+        required: config.min_sources,
+    })?;
+    let agreeing = fresh
+        .into_iter()
+        .filter(|price| {
+            u128::from(price.abs_diff(reference)) * 10_000
+                <= u128::from(config.max_deviation_bps) * u128::from(reference)
+        })
+        .collect::<Vec<_>>();
+    if agreeing.len() < config.min_sources {
+        return Err(PriceError::NoPriceConsensus {
+            agreeing: agreeing.len(),
+            required: config.min_sources,
+        });
+    }
+
+    let median = median(&agreeing).ok_or(PriceError::NoPriceConsensus {
+        agreeing: 0,
+        required: config.min_sources,
+    })?;
+    Ok(PriceOfOneBTC::new(UsdCents::from(median)))
+}
+
+fn median(prices: &[u64]) -> Option<u64> {
+    let mut prices = prices.to_vec();
+    prices.sort_unstable();
+    let mid = prices.len() / 2;
+    match prices.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(prices[mid]),
+        _ => Some(prices[mid - 1] + (prices[mid] - prices[mid - 1]) / 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PriceConfig {
+        PriceConfig {
+            min_sources: 2,
+            max_deviation_bps: 200,
+            max_staleness: std::time::Duration::from_secs(300),
+            ..Default::default()
+        }
+    }
+
+    fn quote(source: &str, cents: u64, age_secs: i64, now: DateTime<Utc>) -> PriceQuote {
+        PriceQuote {
+            source: source.to_string(),
+            price: PriceOfOneBTC::new(UsdCents::from(cents)),
+            observed_at: now - chrono::Duration::seconds(age_secs),
+        }
+    }
+
+    #[test]
+    fn median_of_agreeing_quotes() {
+        let now = Utc::now();
+        let quotes = vec![
+            quote("a", 10_000_000, 0, now),
+            quote("b", 10_100_000, 0, now),
+            quote("c", 9_950_000, 0, now),
+        ];
+        let price = aggregate_quotes(&quotes, &config(), now).unwrap();
+        assert_eq!(price, PriceOfOneBTC::new(UsdCents::from(10_000_000)));
+    }
+
+    #[test]
+    fn even_number_of_quotes_averages_middle() {
+        let now = Utc::now();
+        let quotes = vec![
+            quote("a", 10_000_000, 0, now),
+            quote("b", 10_100_000, 0, now),
+        ];
+        let price = aggregate_quotes(&quotes, &config(), now).unwrap();
+        assert_eq!(price, PriceOfOneBTC::new(UsdCents::from(10_050_000)));
+    }
+
+    #[test]
+    fn outlier_is_rejected() {
+        let now = Utc::now();
+        let quotes = vec![
+            quote("a", 10_000_000, 0, now),
+            quote("b", 10_020_000, 0, now),
+            quote("c", 5_000_000, 0, now),
+        ];
+        let price = aggregate_quotes(&quotes, &config(), now).unwrap();
+        assert_eq!(price, PriceOfOneBTC::new(UsdCents::from(10_010_000)));
+    }
+
+    #[test]
+    fn stale_quotes_are_ignored() {
+        let now = Utc::now();
+        let quotes = vec![
+            quote("a", 10_000_000, 0, now),
+            quote("b", 10_000_000, 301, now),
+        ];
+        let res = aggregate_quotes(&quotes, &config(), now);
+        assert!(matches!(
+            res,
+            Err(PriceError::StalePrice {
+                fresh: 1,
+                required: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn disagreeing_quotes_have_no_consensus() {
+        let now = Utc::now();
+        let quotes = vec![
+            quote("a", 10_000_000, 0, now),
+            quote("b", 12_000_000, 0, now),
+        ];
+        let res = aggregate_quotes(&quotes, &config(), now);
+        assert!(matches!(res, Err(PriceError::NoPriceConsensus { .. })));
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use core_money::UsdCents;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceProviderConfig {
+    Bitfinex,
+    Kraken,
+    Coinbase,
+    Fixed { usd_cents_per_btc: UsdCents },
+}
+
+#[serde_with::serde_as]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PriceConfig {
+    #[serde(default = "default_providers")]
+    pub providers: Vec<PriceProviderConfig>,
+    /// Minimum number of fresh, agreeing quotes required to publish a price.
+    #[serde(default = "default_min_sources")]
+    pub min_sources: usize,
+    /// Quotes deviating from the median by more than this are discarded as outliers.
+    #[serde(default = "default_max_deviation_bps")]
+    pub max_deviation_bps: u64,
+    /// Quotes older than this are not considered.
+    #[serde(default = "default_max_staleness")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub max_staleness: std::time::Duration,
+    #[serde(default = "default_cache_ttl")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub cache_ttl: std::time::Duration,
+    /// How often the price is polled for publishing updates.
+    #[serde(default = "default_update_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub update_interval: std::time::Duration,
+    /// Minimum move relative to the last published price for an update to be published.
+    #[serde(default = "default_update_threshold_bps")]
+    pub update_threshold_bps: u64,
+}
+
+impl Default for PriceConfig {
+    fn default() -> Self {
+        Self {
+            providers: default_providers(),
+            min_sources: default_min_sources(),
+            max_deviation_bps: default_max_deviation_bps(),
+            max_staleness: default_max_staleness(),
+            cache_ttl: default_cache_ttl(),
+            update_interval: default_update_interval(),
+            update_threshold_bps: default_update_threshold_bps(),
+        }
+    }
+}
+
+fn default_providers() -> Vec<PriceProviderConfig> {
+    vec![
+        PriceProviderConfig::Bitfinex,
+        PriceProviderConfig::Kraken,
+        PriceProviderConfig::Coinbase,
+    ]
+}
+
+fn default_min_sources() -> usize {
+    2
+}
+
+fn default_max_deviation_bps() -> u64 {
+    200
+}
+
+fn default_max_staleness() -> std::time::Duration {
+    std::time::Duration::from_secs(5 * 60)
+}
+
+fn default_cache_ttl() -> std::time::Duration {
+    std::time::Duration::from_secs(60)
+}
+
+fn default_update_interval() -> std::time::Duration {
+    std::time::Duration::from_secs(30)
+}
+
+fn default_update_threshold_bps() -> u64 {
+    50
+}
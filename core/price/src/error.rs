@@ -2,8 +2,24 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum PriceError {
-    #[error("PriceError - BfxClientError: {0}")]
-    BfxClientError(#[from] super::bfx_client::error::BfxClientError),
+    #[error("PriceError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("PriceError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
     #[error("PriceError - ConversionError: {0}")]
     ConversionError(#[from] core_money::ConversionError),
+    #[error("PriceError - StalePrice: only {fresh} fresh quotes, {required} required")]
+    StalePrice { fresh: usize, required: usize },
+    #[error("PriceError - NoPriceConsensus: only {agreeing} agreeing quotes, {required} required")]
+    NoPriceConsensus { agreeing: usize, required: usize },
+}
+
+impl PriceError {
+    /// The sources did not yield a price that is safe to act upon.
+    pub fn is_unreliable_price(&self) -> bool {
+        matches!(
+            self,
+            Self::StalePrice { .. } | Self::NoPriceConsensus { .. }
+        )
+    }
 }
@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+
+use crate::primitives::{PriceObservationId, PriceOfOneBTC};
+
+#[derive(Debug, Serialize, Deserialize, strum::AsRefStr)]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(tag = "type")]
+pub enum CorePriceEvent {
+    PriceUpdated {
+        observation_id: PriceObservationId,
+        price: PriceOfOneBTC,
+        previous_price: Option<PriceOfOneBTC>,
+        observed_at: DateTime<Utc>,
+    },
+}
@@ -0,0 +1,3 @@
+mod publish_price_updates;
+
+pub use publish_price_updates::*;
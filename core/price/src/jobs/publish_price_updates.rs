@@ -0,0 +1,197 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use std::time::Duration;
+
+use job::*;
+use outbox::{Outbox, OutboxEventMarker};
+
+use crate::{Price, event::CorePriceEvent, primitives::PriceOfOneBTC};
+
+#[serde_with::serde_as]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PublishPriceUpdatesJobConfig<E> {
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub job_interval: Duration,
+    pub threshold_bps: u64,
+    pub _phantom: std::marker::PhantomData<E>,
+}
+
+impl<E> JobConfig for PublishPriceUpdatesJobConfig<E>
+where
+    E: OutboxEventMarker<CorePriceEvent>,
+{
+    type Initializer = PublishPriceUpdatesInit<E>;
+}
+
+pub struct PublishPriceUpdatesInit<E>
+where
+    E: OutboxEventMarker<CorePriceEvent>,
+{
+    price: Price,
+    outbox: Outbox<E>,
+}
+
+impl<E> PublishPriceUpdatesInit<E>
+where
+    E: OutboxEventMarker<CorePriceEvent>,
+{
+    pub fn new(price: &Price, outbox: &Outbox<E>) -> Self {
+        Self {
+            price: price.clone(),
+            outbox: outbox.clone(),
+        }
+    }
+}
+
+const PUBLISH_PRICE_UPDATES_JOB: JobType = JobType::new("cron.publish-price-updates");
+
+impl<E> JobInitializer for PublishPriceUpdatesInit<E>
+where
+    E: OutboxEventMarker<CorePriceEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        PUBLISH_PRICE_UPDATES_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(PublishPriceUpdatesJobRunner::<E> {
+            config: job.config()?,
+            price: self.price.clone(),
+            outbox: self.outbox.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+#[derive(Default, Clone, Copy, Serialize, Deserialize)]
+struct PublishPriceUpdatesState {
+    last_published: Option<PriceOfOneBTC>,
+}
+
+pub struct PublishPriceUpdatesJobRunner<E>
+where
+    E: OutboxEventMarker<CorePriceEvent>,
+{
+    config: PublishPriceUpdatesJobConfig<E>,
+    price: Price,
+    outbox: Outbox<E>,
+}
+
+#[async_trait]
+impl<E> JobRunner for PublishPriceUpdatesJobRunner<E>
+where
+    E: OutboxEventMarker<CorePriceEvent>,
+{
+    #[instrument(name = "price.publish_price_updates.run", skip_all, err)]
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut state = current_job
+            .execution_state::<PublishPriceUpdatesState>()?
+            .unwrap_or_default();
+
+        let observation = match self.price.current_observation().await {
+            Ok(observation) => observation,
+            Err(e) if e.is_unreliable_price() => {
+                tracing::warn!(error = %e, "not publishing unreliable btc price");
+                return Ok(JobCompletion::RescheduleIn(self.config.job_interval));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if moved_beyond_threshold(
+            state.last_published,
+            observation.price,
+            self.config.threshold_bps,
+        ) {
+            let mut op = es_entity::DbOp::init(self.price.pool()).await?;
+            self.outbox
+                .publish_persisted(
+                    &mut op,
+                    CorePriceEvent::PriceUpdated {
+                        observation_id: observation.id,
+                        price: observation.price,
+                        previous_price: state.last_published,
+                        observed_at: observation.observed_at,
+                    },
+                )
+                .await?;
+            op.commit().await?;
+
+            state.last_published = Some(observation.price);
+            current_job.update_execution_state(state).await?;
+        }
+
+        Ok(JobCompletion::RescheduleIn(self.config.job_interval))
+    }
+}
+
+fn moved_beyond_threshold(
+    last_published: Option<PriceOfOneBTC>,
+    price: PriceOfOneBTC,
+    threshold_bps: u64,
+) -> bool {
+    let Some(last_published) = last_published else {
+        return true;
+    };
+    let last = u128::from(last_published.into_inner().into_inner());
+    let current = u128::from(price.into_inner().into_inner());
+
+    current.abs_diff(last) * 10_000 > u128::from(threshold_bps) * last
+}
+
+#[cfg(test)]
+mod tests {
+    use core_money::UsdCents;
+
+    use super::*;
+
+    fn price(cents: u64) -> PriceOfOneBTC {
+        PriceOfOneBTC::new(UsdCents::from(cents))
+    }
+
+    #[test]
+    fn first_price_is_always_published() {
+        assert!(moved_beyond_threshold(None, price(10_000_000), 50));
+    }
+
+    #[test]
+    fn small_moves_are_not_published() {
+        assert!(!moved_beyond_threshold(
+            Some(price(10_000_000)),
+            price(10_050_000),
+            50
+        ));
+        assert!(!moved_beyond_threshold(
+            Some(price(10_000_000)),
+            price(9_950_000),
+            50
+        ));
+    }
+
+    #[test]
+    fn moves_beyond_threshold_are_published() {
+        assert!(moved_beyond_threshold(
+            Some(price(10_000_000)),
+            price(10_050_001),
+            50
+        ));
+        assert!(moved_beyond_threshold(
+            Some(price(10_000_000)),
+            price(9_949_999),
+            50
+        ));
+    }
+}
@@ -1,50 +1,230 @@
 #![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
 #![cfg_attr(feature = "fail-on-warnings", deny(clippy::all))]
 
+mod aggregate;
 mod bfx_client;
+pub mod config;
 pub mod error;
+mod event;
+mod jobs;
 mod primitives;
+pub mod provider;
+mod repo;
 
-use cached::proc_macro::cached;
-use std::time::Duration;
+use chrono::{DateTime, Utc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use core_money::UsdCents;
+use outbox::{Outbox, OutboxEventMarker};
 
 use bfx_client::BfxClient;
+use config::{PriceConfig, PriceProviderConfig};
 use error::PriceError;
+pub use event::*;
+use jobs::{PublishPriceUpdatesInit, PublishPriceUpdatesJobConfig};
 pub use primitives::*;
+use provider::{CoinbaseClient, FixedPriceProvider, KrakenClient, PriceProvider, PriceQuote};
+use repo::PriceObservationRepo;
+
+#[derive(Default)]
+struct PriceState {
+    last_quotes: HashMap<String, PriceQuote>,
+    cached: Option<PriceObservation>,
+}
 
 #[derive(Clone)]
 pub struct Price {
-    bfx: BfxClient,
+    repo: PriceObservationRepo,
+    providers: Arc<Vec<Arc<dyn PriceProvider>>>,
+    config: PriceConfig,
+    state: Arc<Mutex<PriceState>>,
 }
 
 impl Price {
-    pub fn new() -> Self {
+    pub fn new(pool: &sqlx::PgPool) -> Self {
+        Self::from_config(pool, PriceConfig::default())
+    }
+
+    /// Builds the configured providers and spawns the job publishing
+    /// [`CorePriceEvent::PriceUpdated`] whenever the price moves beyond `update_threshold_bps`.
+    pub async fn init<E>(
+        pool: &sqlx::PgPool,
+        config: PriceConfig,
+        jobs: &job::Jobs,
+        outbox: &Outbox<E>,
+    ) -> Result<Self, PriceError>
+    where
+        E: OutboxEventMarker<CorePriceEvent>,
+    {
+        let job_config = PublishPriceUpdatesJobConfig {
+            job_interval: config.update_interval,
+            threshold_bps: config.update_threshold_bps,
+            _phantom: std::marker::PhantomData,
+        };
+        let price = Self::from_config(pool, config);
+        jobs.add_initializer_and_spawn_unique(
+            PublishPriceUpdatesInit::new(&price, outbox),
+            job_config,
+        )
+        .await?;
+
+        Ok(price)
+    }
+
+    pub fn from_config(pool: &sqlx::PgPool, config: PriceConfig) -> Self {
+        if std::env::var("BFX_LOCAL_PRICE").is_ok() {
+            let local = FixedPriceProvider::new(
+                "local",
+                PriceOfOneBTC::new(
+                    UsdCents::try_from_usd(rust_decimal_macros::dec!(100_000))
+                        .expect("valid local price"),
+                ),
+            );
+            return Self::with_providers(
+                pool,
+                vec![Arc::new(local)],
+                PriceConfig {
+                    min_sources: 1,
+                    ..config
+                },
+            );
+        }
+
+        let providers = config
+            .providers
+            .iter()
+            .map(|provider| -> Arc<dyn PriceProvider> {
+                match provider {
+                    PriceProviderConfig::Bitfinex => Arc::new(BfxClient::new()),
+                    PriceProviderConfig::Kraken => Arc::new(KrakenClient::new()),
+                    PriceProviderConfig::Coinbase => Arc::new(CoinbaseClient::new()),
+                    PriceProviderConfig::Fixed { usd_cents_per_btc } => Arc::new(
+                        FixedPriceProvider::new("fixed", PriceOfOneBTC::new(*usd_cents_per_btc)),
+                    ),
+                }
+            })
+            .collect();
+        Self::with_providers(pool, providers, config)
+    }
+
+    pub fn with_providers(
+        pool: &sqlx::PgPool,
+        providers: Vec<Arc<dyn PriceProvider>>,
+        config: PriceConfig,
+    ) -> Self {
         Self {
-            bfx: BfxClient::new(),
+            repo: PriceObservationRepo::new(pool),
+            providers: Arc::new(providers),
+            config,
+            state: Arc::new(Mutex::new(PriceState::default())),
         }
     }
 
+    /// Median price across all configured sources.
+    /// Fails with an unreliable price error if not enough fresh and agreeing quotes are available.
     pub async fn usd_cents_per_btc(&self) -> Result<PriceOfOneBTC, PriceError> {
-        usd_cents_per_btc_cached(&self.bfx).await
+        Ok(self.current_observation().await?.price)
     }
-}
 
-impl Default for Price {
-    fn default() -> Self {
-        Self::new()
+    /// Like [`Price::usd_cents_per_btc`] but returns the persisted observation,
+    /// so that decisions based on the price can reference it.
+    pub async fn current_observation(&self) -> Result<PriceObservation, PriceError> {
+        let now = Utc::now();
+        if let Some(cached) = self.state().cached.clone()
+            && now
+                .signed_duration_since(cached.observed_at)
+                .to_std()
+                .unwrap_or_default()
+                < self.config.cache_ttl
+        {
+            return Ok(cached);
+        }
+
+        let results = futures::future::join_all(
+            self.providers
+                .iter()
+                .map(|provider| provider.fetch_btc_usd()),
+        )
+        .await;
+
+        let mut observations = Vec::new();
+        let aggregated = {
+            let mut state = self.state();
+            for (provider, result) in self.providers.iter().zip(results) {
+                match result {
+                    Ok(quote) => {
+                        observations.push(PriceObservation {
+                            id: PriceObservationId::new(),
+                            source: quote.source.clone(),
+                            price: quote.price,
+                            observed_at: quote.observed_at,
+                        });
+                        state.last_quotes.insert(provider.name().to_string(), quote);
+                    }
+                    Err(e) => {
+                        tracing::warn!(source = provider.name(), error = %e, "failed to fetch btc price");
+                    }
+                }
+            }
+            let quotes = state.last_quotes.values().cloned().collect::<Vec<_>>();
+            aggregate::aggregate_quotes(&quotes, &self.config, now)
+                .map(|price| PriceObservation::aggregated(PriceObservationId::new(), price, now))
+        };
+        if let Ok(aggregated) = &aggregated {
+            observations.push(aggregated.clone());
+        }
+        self.repo.persist_all(&observations).await?;
+
+        let aggregated = aggregated?;
+        self.state().cached = Some(aggregated.clone());
+
+        Ok(aggregated)
+    }
+
+    /// The aggregated price that was current at `at`.
+    pub async fn price_at(
+        &self,
+        at: DateTime<Utc>,
+    ) -> Result<Option<PriceObservation>, PriceError> {
+        self.repo.find_latest_at(AGGREGATED_PRICE_SOURCE, at).await
     }
-}
 
-#[cached(time = 60, result = true, key = "()", convert = r#"{}"#)]
-async fn usd_cents_per_btc_cached(bfx: &BfxClient) -> Result<PriceOfOneBTC, PriceError> {
-    if std::env::var("BFX_LOCAL_PRICE").is_ok() {
-        return Ok(PriceOfOneBTC::new(UsdCents::try_from_usd(
-            rust_decimal_macros::dec!(100_000),
-        )?));
+    /// Aggregated prices observed in `from <= observed_at < until`, oldest first.
+    pub async fn prices_between(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<PriceObservation>, PriceError> {
+        self.repo
+            .list_between(AGGREGATED_PRICE_SOURCE, from, until)
+            .await
     }
 
-    let last_price = bfx.btc_usd_tick().await?.last_price;
-    Ok(PriceOfOneBTC::new(UsdCents::try_from_usd(last_price)?))
+    /// Quotes of a single provider observed in `from <= observed_at < until`, oldest first.
+    pub async fn quotes_between(
+        &self,
+        source: &str,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<PriceObservation>, PriceError> {
+        self.repo.list_between(source, from, until).await
+    }
+
+    pub async fn find_observation_by_id(
+        &self,
+        id: PriceObservationId,
+    ) -> Result<Option<PriceObservation>, PriceError> {
+        self.repo.find_by_id(id).await
+    }
+
+    pub(crate) fn pool(&self) -> &sqlx::PgPool {
+        self.repo.pool()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, PriceState> {
+        self.state.lock().expect("poisoned price state")
+    }
 }
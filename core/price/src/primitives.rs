@@ -1,8 +1,11 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::RoundingStrategy;
 use serde::{Deserialize, Serialize};
 
 use core_money::{Satoshis, UsdCents};
 
+es_entity::entity_id! { PriceObservationId }
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PriceOfOneBTC(UsdCents);
@@ -30,3 +33,30 @@ impl PriceOfOneBTC {
         self.0
     }
 }
+
+/// `source` of observations holding the aggregated price of all providers.
+pub const AGGREGATED_PRICE_SOURCE: &str = "aggregated";
+
+/// A persisted price tick, either quoted by a single provider or aggregated across them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceObservation {
+    pub id: PriceObservationId,
+    pub source: String,
+    pub price: PriceOfOneBTC,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl PriceObservation {
+    pub fn aggregated(
+        id: PriceObservationId,
+        price: PriceOfOneBTC,
+        observed_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            source: AGGREGATED_PRICE_SOURCE.to_string(),
+            price,
+            observed_at,
+        }
+    }
+}
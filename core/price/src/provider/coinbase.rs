@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client as ReqwestClient;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::{PriceProvider, PriceQuote, error::PriceProviderError};
+
+const BASE_URL: &str = "https://api.exchange.coinbase.com/";
+
+#[derive(Deserialize, Debug)]
+struct CoinbaseTicker {
+    price: Decimal,
+    time: DateTime<Utc>,
+}
+
+#[derive(Clone, Default)]
+pub struct CoinbaseClient {
+    client: ReqwestClient,
+}
+
+impl CoinbaseClient {
+    pub fn new() -> Self {
+        Self {
+            client: ReqwestClient::builder()
+                .use_rustls_tls()
+                .user_agent("lana")
+                .build()
+                .expect("should always build CoinbaseClient"),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for CoinbaseClient {
+    fn name(&self) -> &str {
+        "coinbase"
+    }
+
+    async fn fetch_btc_usd(&self) -> Result<PriceQuote, PriceProviderError> {
+        let url = format!("{BASE_URL}products/BTC-USD/ticker");
+        let response = self
+            .client
+            .get(&url)
+            .header("accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?;
+        let ticker = serde_json::from_str::<CoinbaseTicker>(&response.text().await?)?;
+
+        PriceQuote::from_usd(self.name(), ticker.price, ticker.time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn ticker_price_and_time() {
+        let response_text = r#"{"ask":"65000.01","bid":"64999.99","volume":"1000","trade_id":1,"price":"65000.00","size":"0.1","time":"2025-01-01T00:00:00.000000Z"}"#;
+        let ticker = serde_json::from_str::<CoinbaseTicker>(response_text).unwrap();
+        assert_eq!(ticker.price, dec!(65000));
+        assert_eq!(
+            ticker.time,
+            "2025-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PriceProviderError {
+    #[error("PriceProviderError - BfxClientError: {0}")]
+    BfxClientError(#[from] crate::bfx_client::error::BfxClientError),
+    #[error("PriceProviderError - Reqwest: {0}")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("PriceProviderError - SerdeJson: {0}")]
+    Deserialization(#[from] serde_json::Error),
+    #[error("PriceProviderError - UnexpectedResponse: {0}")]
+    UnexpectedResponse(String),
+    #[error("PriceProviderError - ConversionError: {0}")]
+    ConversionError(#[from] core_money::ConversionError),
+    #[error("PriceProviderError - NoQuote: {0}")]
+    NoQuote(String),
+}
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+
+use crate::primitives::PriceOfOneBTC;
+
+use super::{PriceProvider, PriceQuote, error::PriceProviderError};
+
+/// Always quotes the same price as of the time of the request.
+#[derive(Clone)]
+pub struct FixedPriceProvider {
+    name: String,
+    price: PriceOfOneBTC,
+}
+
+impl FixedPriceProvider {
+    pub fn new(name: impl Into<String>, price: PriceOfOneBTC) -> Self {
+        Self {
+            name: name.into(),
+            price,
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for FixedPriceProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn fetch_btc_usd(&self) -> Result<PriceQuote, PriceProviderError> {
+        Ok(PriceQuote {
+            source: self.name.clone(),
+            price: self.price,
+            observed_at: Utc::now(),
+        })
+    }
+}
+
+/// Replays a scripted sequence of quotes, `None` simulating an unavailable source.
+/// The last step is repeated once the script is exhausted.
+#[derive(Clone)]
+pub struct ScriptedPriceProvider {
+    name: String,
+    script: Arc<Mutex<Vec<Option<PriceQuote>>>>,
+}
+
+impl ScriptedPriceProvider {
+    pub fn new(name: impl Into<String>, script: Vec<Option<PriceQuote>>) -> Self {
+        let mut script = script;
+        script.reverse();
+        Self {
+            name: name.into(),
+            script: Arc::new(Mutex::new(script)),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for ScriptedPriceProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn fetch_btc_usd(&self) -> Result<PriceQuote, PriceProviderError> {
+        let mut script = self.script.lock().expect("poisoned price script");
+        let step = if script.len() > 1 {
+            script.pop().flatten()
+        } else {
+            script.last().cloned().flatten()
+        };
+        step.ok_or_else(|| PriceProviderError::NoQuote(self.name.clone()))
+    }
+}
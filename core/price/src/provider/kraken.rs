@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client as ReqwestClient;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{PriceProvider, PriceQuote, error::PriceProviderError};
+
+const BASE_URL: &str = "https://api.kraken.com/0/public/";
+
+#[derive(Deserialize, Debug)]
+struct KrakenResponse {
+    error: Vec<String>,
+    result: Option<HashMap<String, KrakenTicker>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KrakenTicker {
+    /// Last trade closed: `[price, lot volume]`.
+    c: (Decimal, Decimal),
+}
+
+#[derive(Clone, Default)]
+pub struct KrakenClient {
+    client: ReqwestClient,
+}
+
+impl KrakenClient {
+    pub fn new() -> Self {
+        Self {
+            client: ReqwestClient::builder()
+                .use_rustls_tls()
+                .build()
+                .expect("should always build KrakenClient"),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for KrakenClient {
+    fn name(&self) -> &str {
+        "kraken"
+    }
+
+    async fn fetch_btc_usd(&self) -> Result<PriceQuote, PriceProviderError> {
+        let url = format!("{BASE_URL}Ticker?pair=XBTUSD");
+        let response = self
+            .client
+            .get(&url)
+            .header("accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?;
+        let data = serde_json::from_str::<KrakenResponse>(&response.text().await?)?;
+        if !data.error.is_empty() {
+            return Err(PriceProviderError::UnexpectedResponse(
+                data.error.join(", "),
+            ));
+        }
+        let ticker = data
+            .result
+            .and_then(|result| result.into_values().next())
+            .ok_or_else(|| PriceProviderError::NoQuote(self.name().to_string()))?;
+
+        PriceQuote::from_usd(self.name(), ticker.c.0, Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn last_trade_price() {
+        let response_text = r#"{"error":[],"result":{"XXBTZUSD":{"a":["65000.10000","1","1.000"],"b":["64999.90000","2","2.000"],"c":["65000.00000","0.00100000"],"v":["100","200"],"p":["64000","64500"],"t":[10,20],"l":["63000","62000"],"h":["66000","67000"],"o":"64000.00000"}}}"#;
+        let data = serde_json::from_str::<KrakenResponse>(response_text).unwrap();
+        let ticker = data.result.unwrap().into_values().next().unwrap();
+        assert_eq!(ticker.c.0, dec!(65000));
+    }
+}
@@ -0,0 +1,58 @@
+mod coinbase;
+pub mod error;
+mod fixed;
+mod kraken;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use core_money::UsdCents;
+
+use crate::{bfx_client::BfxClient, primitives::PriceOfOneBTC};
+
+pub use coinbase::CoinbaseClient;
+use error::PriceProviderError;
+pub use fixed::{FixedPriceProvider, ScriptedPriceProvider};
+pub use kraken::KrakenClient;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceQuote {
+    pub source: String,
+    pub price: PriceOfOneBTC,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl PriceQuote {
+    pub(crate) fn from_usd(
+        source: &str,
+        usd: Decimal,
+        observed_at: DateTime<Utc>,
+    ) -> Result<Self, PriceProviderError> {
+        let usd = usd.round_dp_with_strategy(2, RoundingStrategy::ToZero);
+        Ok(Self {
+            source: source.to_string(),
+            price: PriceOfOneBTC::new(UsdCents::try_from_usd(usd)?),
+            observed_at,
+        })
+    }
+}
+
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn fetch_btc_usd(&self) -> Result<PriceQuote, PriceProviderError>;
+}
+
+#[async_trait]
+impl PriceProvider for BfxClient {
+    fn name(&self) -> &str {
+        "bitfinex"
+    }
+
+    async fn fetch_btc_usd(&self) -> Result<PriceQuote, PriceProviderError> {
+        let tick = self.btc_usd_tick().await?;
+        PriceQuote::from_usd(self.name(), tick.last_price, Utc::now())
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, types::uuid::Uuid};
+use tracing::instrument;
+
+use core_money::{ConversionError, UsdCents};
+
+use crate::{error::PriceError, primitives::*};
+
+/// Append-only store of price ticks.
+#[derive(Clone)]
+pub(crate) struct PriceObservationRepo {
+    pool: PgPool,
+}
+
+impl PriceObservationRepo {
+    pub(crate) fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    #[instrument(name = "price.observation.persist_all", skip_all, err)]
+    pub async fn persist_all(&self, observations: &[PriceObservation]) -> Result<(), PriceError> {
+        if observations.is_empty() {
+            return Ok(());
+        }
+
+        let mut ids = Vec::with_capacity(observations.len());
+        let mut sources = Vec::with_capacity(observations.len());
+        let mut prices = Vec::with_capacity(observations.len());
+        let mut observed_ats = Vec::with_capacity(observations.len());
+        for observation in observations {
+            ids.push(Uuid::from(observation.id));
+            sources.push(observation.source.clone());
+            prices.push(
+                i64::try_from(observation.price.into_inner().into_inner())
+                    .map_err(|_| ConversionError::Overflow)?,
+            );
+            observed_ats.push(observation.observed_at);
+        }
+
+        sqlx::query!(
+            r#"
+              INSERT INTO core_price_observations (id, source, usd_cents_per_btc, observed_at)
+              SELECT * FROM UNNEST($1::uuid[], $2::varchar[], $3::bigint[], $4::timestamptz[])
+            "#,
+            &ids,
+            &sources,
+            &prices,
+            &observed_ats,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(
+        &self,
+        id: PriceObservationId,
+    ) -> Result<Option<PriceObservation>, PriceError> {
+        let row = sqlx::query!(
+            r#"
+              SELECT id AS "id: PriceObservationId", source, usd_cents_per_btc, observed_at
+              FROM core_price_observations
+              WHERE id = $1
+            "#,
+            id as PriceObservationId,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            observation_from_columns(row.id, row.source, row.usd_cents_per_btc, row.observed_at)
+        })
+        .transpose()
+    }
+
+    pub async fn find_latest_at(
+        &self,
+        source: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<PriceObservation>, PriceError> {
+        let row = sqlx::query!(
+            r#"
+              SELECT id AS "id: PriceObservationId", source, usd_cents_per_btc, observed_at
+              FROM core_price_observations
+              WHERE source = $1 AND observed_at <= $2
+              ORDER BY observed_at DESC
+              LIMIT 1
+            "#,
+            source,
+            at,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            observation_from_columns(row.id, row.source, row.usd_cents_per_btc, row.observed_at)
+        })
+        .transpose()
+    }
+
+    pub async fn list_between(
+        &self,
+        source: &str,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<PriceObservation>, PriceError> {
+        let rows = sqlx::query!(
+            r#"
+              SELECT id AS "id: PriceObservationId", source, usd_cents_per_btc, observed_at
+              FROM core_price_observations
+              WHERE source = $1 AND observed_at >= $2 AND observed_at < $3
+              ORDER BY observed_at ASC
+            "#,
+            source,
+            from,
+            until,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                observation_from_columns(row.id, row.source, row.usd_cents_per_btc, row.observed_at)
+            })
+            .collect()
+    }
+}
+
+fn observation_from_columns(
+    id: PriceObservationId,
+    source: String,
+    usd_cents_per_btc: i64,
+    observed_at: DateTime<Utc>,
+) -> Result<PriceObservation, PriceError> {
+    let usd_cents_per_btc =
+        u64::try_from(usd_cents_per_btc).map_err(|_| ConversionError::Overflow)?;
+    Ok(PriceObservation {
+        id,
+        source,
+        price: PriceOfOneBTC::new(UsdCents::from(usd_cents_per_btc)),
+        observed_at,
+    })
+}
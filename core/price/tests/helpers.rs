@@ -0,0 +1,5 @@
+pub async fn init_pool() -> anyhow::Result<sqlx::PgPool> {
+    let pg_con = std::env::var("PG_CON").unwrap();
+    let pool = sqlx::PgPool::connect(&pg_con).await?;
+    Ok(pool)
+}
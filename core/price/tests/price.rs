@@ -1,10 +1,19 @@
+mod helpers;
+
+use std::sync::Arc;
+
 use core_money::{Satoshis, UsdCents};
-use core_price::{Price, PriceOfOneBTC};
+use core_price::{
+    Price, PriceOfOneBTC,
+    config::PriceConfig,
+    provider::{PriceProvider, PriceQuote, ScriptedPriceProvider},
+};
 use rust_decimal_macros::dec;
 
 #[tokio::test]
 async fn get_price() -> anyhow::Result<()> {
-    let price = Price::new();
+    let pool = helpers::init_pool().await?;
+    let price = Price::new(&pool);
     let res = price.usd_cents_per_btc().await;
     assert!(res.is_ok());
 
@@ -44,3 +53,118 @@ fn sats_to_cents_complex() {
     let sats = Satoshis::from(12_345);
     assert_eq!(UsdCents::from(617), price.sats_to_cents_round_down(sats));
 }
+
+fn quote(source: &str, usd: rust_decimal::Decimal, age_secs: i64) -> Option<PriceQuote> {
+    Some(PriceQuote {
+        source: source.to_string(),
+        price: PriceOfOneBTC::new(UsdCents::try_from_usd(usd).unwrap()),
+        observed_at: chrono::Utc::now() - chrono::Duration::seconds(age_secs),
+    })
+}
+
+fn scripted(pool: &sqlx::PgPool, providers: Vec<ScriptedPriceProvider>) -> Price {
+    Price::with_providers(
+        pool,
+        providers
+            .into_iter()
+            .map(|p| Arc::new(p) as Arc<dyn PriceProvider>)
+            .collect(),
+        PriceConfig {
+            cache_ttl: std::time::Duration::ZERO,
+            ..Default::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn median_price_across_providers() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let price = scripted(
+        &pool,
+        vec![
+            ScriptedPriceProvider::new("a", vec![quote("a", dec!(100_000), 0)]),
+            ScriptedPriceProvider::new("b", vec![quote("b", dec!(100_500), 0)]),
+            ScriptedPriceProvider::new("c", vec![quote("c", dec!(50_000), 0)]),
+        ],
+    );
+    let res = price.usd_cents_per_btc().await?;
+    assert_eq!(
+        res,
+        PriceOfOneBTC::new(UsdCents::try_from_usd(dec!(100_250))?)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn keeps_last_quote_of_unavailable_provider_until_stale() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let price = scripted(
+        &pool,
+        vec![
+            ScriptedPriceProvider::new("a", vec![quote("a", dec!(100_000), 0)]),
+            ScriptedPriceProvider::new("b", vec![quote("b", dec!(100_000), 0), None]),
+            ScriptedPriceProvider::new("c", vec![quote("c", dec!(100_000), 600)]),
+        ],
+    );
+    assert!(price.usd_cents_per_btc().await.is_ok());
+    assert!(price.usd_cents_per_btc().await.is_ok());
+
+    let price = scripted(
+        &pool,
+        vec![
+            ScriptedPriceProvider::new("a", vec![quote("a", dec!(100_000), 0)]),
+            ScriptedPriceProvider::new("b", vec![None]),
+            ScriptedPriceProvider::new("c", vec![quote("c", dec!(100_000), 600)]),
+        ],
+    );
+    let err = price.usd_cents_per_btc().await.unwrap_err();
+    assert!(err.is_unreliable_price());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn observed_prices_are_persisted() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+    let source = format!("source-{}", rand_suffix());
+    let before = chrono::Utc::now();
+    let price = scripted(
+        &pool,
+        vec![
+            ScriptedPriceProvider::new(&source, vec![quote(&source, dec!(90_000), 0)]),
+            ScriptedPriceProvider::new("other", vec![quote("other", dec!(90_000), 0)]),
+        ],
+    );
+    let observation = price.current_observation().await?;
+    let after = chrono::Utc::now();
+
+    let found = price
+        .find_observation_by_id(observation.id)
+        .await?
+        .expect("observation should be persisted");
+    assert_eq!(found.price, observation.price);
+    assert_eq!(found.source, observation.source);
+
+    let at = price
+        .price_at(after)
+        .await?
+        .expect("price should be recorded");
+    assert!(at.observed_at <= after);
+
+    let quotes = price.quotes_between(&source, before, after).await?;
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(
+        quotes[0].price,
+        PriceOfOneBTC::new(UsdCents::try_from_usd(dec!(90_000))?)
+    );
+
+    Ok(())
+}
+
+fn rand_suffix() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
@@ -6,13 +6,16 @@ use colored::*;
 use core_access::event_schema::{PermissionSetEvent, RoleEvent, UserEvent};
 use core_accounting::event_schema::{ChartEvent, ChartNodeEvent, ManualTransactionEvent};
 use core_credit::event_schema::{
-    CollateralEvent, CreditFacilityEvent, CreditFacilityProposalEvent, DisbursalEvent,
-    InterestAccrualCycleEvent, LiquidationProcessEvent, ObligationEvent, PaymentAllocationEvent,
-    PaymentEvent, PendingCreditFacilityEvent, TermsTemplateEvent,
+    CollateralEvent, CollateralReturnEvent, CreditFacilityEvent, CreditFacilityProposalEvent,
+    DisbursalEvent, InterestAccrualCycleEvent, LiquidationProcessEvent, MarginCallEvent,
+    ObligationEvent, PaymentAllocationEvent, PaymentEvent, PendingCreditFacilityEvent,
+    TermsTemplateEvent,
 };
 use core_custody::event_schema::CustodianEvent;
 use core_customer::event_schema::CustomerEvent;
-use core_deposit::event_schema::{DepositAccountEvent, DepositEvent, WithdrawalEvent};
+use core_deposit::event_schema::{
+    DepositAccountEvent, DepositEvent, TransferEvent, WithdrawalEvent,
+};
 use document_storage::event_schema::DocumentEvent;
 use governance::event_schema::{ApprovalProcessEvent, CommitteeEvent, PolicyEvent};
 use schemars::schema_for;
@@ -225,6 +228,19 @@ pub fn update_schemas(
             generate_schema: || serde_json::to_value(schema_for!(WithdrawalEvent)).unwrap(),
             ..Default::default()
         },
+        SchemaInfo {
+            name: "TransferEvent",
+            filename: "transfer_event_schema.json",
+            collections: vec![CollectionRollup {
+                column_name: "ledger_tx_ids",
+                values: "ledger_tx_id",
+                add_events: vec!["Executed".to_string()],
+                remove_events: vec![],
+            }],
+            toggle_events: vec!["ApprovalProcessConcluded", "Executed"],
+            generate_schema: || serde_json::to_value(schema_for!(TransferEvent)).unwrap(),
+            ..Default::default()
+        },
         SchemaInfo {
             name: "CustodianEvent",
             filename: "custodian_event_schema.json",
@@ -427,6 +443,25 @@ pub fn update_schemas(
             generate_schema: || serde_json::to_value(schema_for!(LiquidationProcessEvent)).unwrap(),
             ..Default::default()
         },
+        SchemaInfo {
+            name: "MarginCallEvent",
+            filename: "margin_call_event_schema.json",
+            toggle_events: vec!["Cured", "Escalated"],
+            generate_schema: || serde_json::to_value(schema_for!(MarginCallEvent)).unwrap(),
+            ..Default::default()
+        },
+        SchemaInfo {
+            name: "CollateralReturnEvent",
+            filename: "collateral_return_event_schema.json",
+            toggle_events: vec![
+                "ApprovalProcessConcluded",
+                "TransferRequested",
+                "TransferConfirmed",
+                "TransferFailed",
+            ],
+            generate_schema: || serde_json::to_value(schema_for!(CollateralReturnEvent)).unwrap(),
+            ..Default::default()
+        },
         SchemaInfo {
             name: "DocumentEvent",
             filename: "document_event_schema.json",
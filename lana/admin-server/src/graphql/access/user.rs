@@ -56,6 +56,10 @@ impl User {
         &self.entity.email
     }
 
+    async fn is_active(&self) -> bool {
+        self.entity.is_active()
+    }
+
     async fn user_can_update_role_of_user(&self, ctx: &Context<'_>) -> async_graphql::Result<bool> {
         let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
         Ok(app
@@ -65,6 +69,16 @@ impl User {
             .await
             .is_ok())
     }
+
+    async fn user_can_deactivate_user(&self, ctx: &Context<'_>) -> async_graphql::Result<bool> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        Ok(app
+            .access()
+            .users()
+            .subject_can_deactivate_user(sub, None, false)
+            .await
+            .is_ok())
+    }
 }
 
 #[derive(InputObject)]
@@ -81,3 +95,15 @@ pub struct UserUpdateRoleInput {
     pub role_id: UUID,
 }
 mutation_payload! { UserUpdateRolePayload, user: User }
+
+#[derive(InputObject)]
+pub struct UserDeactivateInput {
+    pub id: UUID,
+}
+mutation_payload! { UserDeactivatePayload, user: User }
+
+#[derive(InputObject)]
+pub struct UserReactivateInput {
+    pub id: UUID,
+}
+mutation_payload! { UserReactivatePayload, user: User }
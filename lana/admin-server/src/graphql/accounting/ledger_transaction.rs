@@ -5,12 +5,15 @@ pub use lana_app::{
         LedgerTransaction as DomainLedgerTransaction, LedgerTransactionCursor,
     },
     credit::DISBURSAL_TRANSACTION_ENTITY_TYPE,
-    deposit::{DEPOSIT_TRANSACTION_ENTITY_TYPE, WITHDRAWAL_TRANSACTION_ENTITY_TYPE},
+    deposit::{
+        DEPOSIT_TRANSACTION_ENTITY_TYPE, TRANSFER_TRANSACTION_ENTITY_TYPE,
+        WITHDRAWAL_TRANSACTION_ENTITY_TYPE,
+    },
 };
 
 use crate::{
     graphql::{
-        credit_facility::CreditFacilityDisbursal, deposit::Deposit, loader::*,
+        credit_facility::CreditFacilityDisbursal, deposit::Deposit, loader::*, transfer::Transfer,
         withdrawal::Withdrawal,
     },
     primitives::*,
@@ -32,6 +35,7 @@ pub struct LedgerTransaction {
 pub enum LedgerTransactionEntity {
     Deposit(Deposit),
     Withdrawal(Withdrawal),
+    Transfer(Transfer),
     Disbursal(CreditFacilityDisbursal),
 }
 
@@ -64,6 +68,13 @@ impl LedgerTransaction {
                     .expect("Could not find withdrawal entity");
                 Some(LedgerTransactionEntity::Withdrawal(withdrawal))
             }
+            entity_type if entity_type == &TRANSFER_TRANSACTION_ENTITY_TYPE => {
+                let transfer = loader
+                    .load_one(TransferId::from(entity_ref.entity_id))
+                    .await?
+                    .expect("Could not find transfer entity");
+                Some(LedgerTransactionEntity::Transfer(transfer))
+            }
             entity_type if entity_type == &DISBURSAL_TRANSACTION_ENTITY_TYPE => {
                 let disbursal = loader
                     .load_one(DisbursalId::from(entity_ref.entity_id))
@@ -4,12 +4,12 @@ use crate::primitives::*;
 
 use super::{
     access::User, approval_rules::*, credit_facility::*, loader::LanaDataLoader, policy::*,
-    withdrawal::*,
+    transfer::*, withdrawal::*,
 };
 
 pub use lana_app::governance::{
     ApprovalProcess as DomainApprovalProcess, ApprovalProcessStatus,
-    ApprovalProcessType as DomainApprovalProcessType,
+    ApprovalProcessType as DomainApprovalProcessType, CommitteeId,
     approval_process_cursor::ApprovalProcessesByCreatedAtCursor,
 };
 
@@ -42,13 +42,32 @@ impl From<DomainApprovalProcess> for ApprovalProcess {
 #[ComplexObject]
 impl ApprovalProcess {
     async fn rules(&self) -> ApprovalRules {
-        ApprovalRules::from(self.entity.rules)
+        ApprovalRules::from(self.entity.rules.clone())
+    }
+
+    async fn current_stage(&self) -> usize {
+        self.entity.current_stage
+    }
+
+    async fn stages(&self) -> Vec<ApprovalProcessStage> {
+        let n_stages = self.entity.n_stages().max(self.entity.current_stage + 1);
+        (0..n_stages)
+            .map(|stage| ApprovalProcessStage {
+                stage,
+                is_current: stage == self.entity.current_stage,
+                entity: self.entity.clone(),
+            })
+            .collect()
     }
 
     async fn denied_reason(&self) -> Option<&str> {
         self.entity.denied_reason()
     }
 
+    async fn expires_at(&self) -> Option<Timestamp> {
+        self.entity.expires_at.map(Timestamp::from)
+    }
+
     async fn policy(&self, ctx: &Context<'_>) -> async_graphql::Result<Policy> {
         let loader = ctx.data_unchecked::<LanaDataLoader>();
         let policy = loader
@@ -79,51 +98,13 @@ impl ApprovalProcess {
     }
 
     async fn voters(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ApprovalProcessVoter>> {
-        if let Some(committee_id) = self.entity.committee_id() {
-            let loader = ctx.data_unchecked::<LanaDataLoader>();
-            let committee = loader
-                .load_one(committee_id)
-                .await?
-                .expect("committee not found");
-            let mut approvers = self.entity.approvers();
-            let mut deniers = self.entity.deniers();
-            let mut voters: Vec<_> = committee
-                .entity
-                .members()
-                .into_iter()
-                .map(|member_id| ApprovalProcessVoter {
-                    still_eligible: true,
-                    did_vote: approvers.contains(&member_id) || deniers.contains(&member_id),
-                    did_approve: approvers.remove(&member_id),
-                    did_deny: deniers.remove(&member_id),
-                    user_id: UserId::from(member_id),
-                    voted_at: self.entity.member_voted_at(member_id).map(Into::into),
-                })
-                .collect();
-            voters.extend(
-                approvers
-                    .into_iter()
-                    .map(|member_id| ApprovalProcessVoter {
-                        user_id: UserId::from(member_id),
-                        still_eligible: false,
-                        did_vote: true,
-                        did_approve: true,
-                        did_deny: false,
-                        voted_at: self.entity.member_voted_at(member_id).map(Into::into),
-                    })
-                    .chain(deniers.into_iter().map(|member_id| ApprovalProcessVoter {
-                        user_id: UserId::from(member_id),
-                        still_eligible: false,
-                        did_vote: true,
-                        did_approve: false,
-                        did_deny: true,
-                        voted_at: self.entity.member_voted_at(member_id).map(Into::into),
-                    })),
-            );
-            Ok(voters)
-        } else {
-            Ok(vec![])
-        }
+        load_voters(
+            ctx,
+            &self.entity,
+            self.entity.committee_id(),
+            self.entity.current_stage,
+        )
+        .await
     }
 
     async fn target(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcessTarget> {
@@ -141,6 +122,18 @@ impl ApprovalProcess {
                     .expect("withdrawal not found");
                 Ok(ApprovalProcessTarget::Withdrawal(withdrawal))
             }
+            ApprovalProcessType::TransferApproval => {
+                let transfer = loader
+                    .load_one(
+                        self.entity
+                            .target_ref()
+                            .parse::<TransferId>()
+                            .expect("invalid target ref"),
+                    )
+                    .await?
+                    .expect("transfer not found");
+                Ok(ApprovalProcessTarget::Transfer(transfer))
+            }
             ApprovalProcessType::CreditFacilityProposalApproval => {
                 let credit_facility_proposal = loader
                     .load_one(
@@ -167,6 +160,19 @@ impl ApprovalProcess {
                     .expect("disbursal not found");
                 Ok(ApprovalProcessTarget::CreditFacilityDisbursal(disbursal))
             }
+            ApprovalProcessType::CreditFacilityTermsAmendmentApproval
+            | ApprovalProcessType::CollateralReturnApproval => {
+                let credit_facility = loader
+                    .load_one(
+                        self.entity
+                            .target_ref()
+                            .parse::<CreditFacilityId>()
+                            .expect("invalid target ref"),
+                    )
+                    .await?
+                    .expect("credit facility not found");
+                Ok(ApprovalProcessTarget::CreditFacility(credit_facility))
+            }
         }
     }
 }
@@ -175,24 +181,117 @@ impl ApprovalProcess {
 #[allow(clippy::enum_variant_names)]
 pub enum ApprovalProcessType {
     WithdrawalApproval,
+    TransferApproval,
     DisbursalApproval,
     CreditFacilityProposalApproval,
+    CreditFacilityTermsAmendmentApproval,
+    CollateralReturnApproval,
 }
 
 impl From<&DomainApprovalProcessType> for ApprovalProcessType {
     fn from(process_type: &DomainApprovalProcessType) -> Self {
         if process_type == &lana_app::governance::APPROVE_WITHDRAWAL_PROCESS {
             Self::WithdrawalApproval
+        } else if process_type == &lana_app::governance::APPROVE_TRANSFER_PROCESS {
+            Self::TransferApproval
         } else if process_type == &lana_app::governance::APPROVE_DISBURSAL_PROCESS {
             Self::DisbursalApproval
         } else if process_type == &lana_app::governance::APPROVE_CREDIT_FACILITY_PROPOSAL_PROCESS {
             Self::CreditFacilityProposalApproval
+        } else if process_type
+            == &lana_app::governance::APPROVE_CREDIT_FACILITY_TERMS_AMENDMENT_PROCESS
+        {
+            Self::CreditFacilityTermsAmendmentApproval
+        } else if process_type == &lana_app::governance::APPROVE_COLLATERAL_RETURN_PROCESS {
+            Self::CollateralReturnApproval
         } else {
             panic!("Unknown approval process type: {process_type:?}");
         }
     }
 }
 
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct ApprovalProcessStage {
+    stage: usize,
+    is_current: bool,
+
+    #[graphql(skip)]
+    entity: Arc<DomainApprovalProcess>,
+}
+
+#[ComplexObject]
+impl ApprovalProcessStage {
+    async fn rules(&self) -> ApprovalRules {
+        ApprovalRules::from(self.entity.rules.stage(self.stage))
+    }
+
+    async fn voters(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ApprovalProcessVoter>> {
+        load_voters(
+            ctx,
+            &self.entity,
+            self.entity.rules.stage(self.stage).committee_id(),
+            self.stage,
+        )
+        .await
+    }
+}
+
+async fn load_voters(
+    ctx: &Context<'_>,
+    process: &DomainApprovalProcess,
+    committee_id: Option<CommitteeId>,
+    stage: usize,
+) -> async_graphql::Result<Vec<ApprovalProcessVoter>> {
+    let Some(committee_id) = committee_id else {
+        return Ok(vec![]);
+    };
+    let loader = ctx.data_unchecked::<LanaDataLoader>();
+    let committee = loader
+        .load_one(committee_id)
+        .await?
+        .expect("committee not found");
+    let mut approvers = process.stage_approvers(stage);
+    let mut deniers = process.stage_deniers(stage);
+    let mut voters: Vec<_> = committee
+        .entity
+        .members()
+        .into_iter()
+        .map(|member_id| ApprovalProcessVoter {
+            still_eligible: true,
+            did_vote: approvers.contains(&member_id) || deniers.contains(&member_id),
+            did_approve: approvers.remove(&member_id),
+            did_deny: deniers.remove(&member_id),
+            user_id: UserId::from(member_id),
+            voted_at: process.member_voted_at(member_id).map(Into::into),
+            delegate_id: process.member_vote_delegate(member_id).map(UserId::from),
+        })
+        .collect();
+    voters.extend(
+        approvers
+            .into_iter()
+            .map(|member_id| ApprovalProcessVoter {
+                user_id: UserId::from(member_id),
+                still_eligible: false,
+                did_vote: true,
+                did_approve: true,
+                did_deny: false,
+                voted_at: process.member_voted_at(member_id).map(Into::into),
+                delegate_id: process.member_vote_delegate(member_id).map(UserId::from),
+            })
+            .chain(deniers.into_iter().map(|member_id| ApprovalProcessVoter {
+                user_id: UserId::from(member_id),
+                still_eligible: false,
+                did_vote: true,
+                did_approve: false,
+                did_deny: true,
+                voted_at: process.member_voted_at(member_id).map(Into::into),
+                delegate_id: process.member_vote_delegate(member_id).map(UserId::from),
+            })),
+    );
+    Ok(voters)
+}
+
 #[derive(SimpleObject, Clone)]
 #[graphql(complex)]
 pub struct ApprovalProcessVoter {
@@ -203,6 +302,8 @@ pub struct ApprovalProcessVoter {
     did_approve: bool,
     did_deny: bool,
     voted_at: Option<Timestamp>,
+    #[graphql(skip)]
+    delegate_id: Option<UserId>,
 }
 
 #[ComplexObject]
@@ -216,13 +317,23 @@ impl ApprovalProcessVoter {
 
         Ok(users)
     }
+
+    async fn voted_on_behalf_by(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<User>> {
+        let Some(delegate_id) = self.delegate_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(delegate_id).await?)
+    }
 }
 
 #[derive(async_graphql::Union)]
 pub(super) enum ApprovalProcessTarget {
     Withdrawal(Withdrawal),
+    Transfer(Transfer),
     CreditFacilityProposal(CreditFacilityProposal),
     CreditFacilityDisbursal(CreditFacilityDisbursal),
+    CreditFacility(CreditFacility),
 }
 
 #[derive(InputObject)]
@@ -1,13 +1,20 @@
 use async_graphql::*;
 
-use super::{committee::Committee, loader::LanaDataLoader};
+use crate::primitives::UsdCents;
 
-use lana_app::governance::{ApprovalRules as DomainApprovalRules, CommitteeId};
+use super::{committee::Committee, customer::CustomerType, loader::LanaDataLoader};
+
+use lana_app::governance::{
+    ApprovalBand as DomainApprovalBand, ApprovalExpiry as DomainApprovalExpiry,
+    ApprovalExpiryAction as DomainApprovalExpiryAction, ApprovalRules as DomainApprovalRules,
+    CommitteeId,
+};
 
 #[derive(async_graphql::Union)]
 pub(super) enum ApprovalRules {
     System(SystemApproval),
     CommitteeThreshold(CommitteeThreshold),
+    MultiStage(MultiStageApproval),
 }
 
 impl From<DomainApprovalRules> for ApprovalRules {
@@ -23,15 +30,93 @@ impl From<DomainApprovalRules> for ApprovalRules {
             DomainApprovalRules::SystemAutoApprove => {
                 ApprovalRules::System(SystemApproval { auto_approve: true })
             }
+            DomainApprovalRules::MultiStage { stages } => {
+                ApprovalRules::MultiStage(MultiStageApproval {
+                    stages: stages
+                        .into_iter()
+                        .map(|stage| CommitteeThreshold {
+                            threshold: stage.threshold,
+                            committee_id: stage.committee_id,
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub(super) struct ApprovalBand {
+    amount_below: Option<UsdCents>,
+    customer_type: Option<CustomerType>,
+    rules: ApprovalRules,
+}
+
+impl From<&DomainApprovalBand> for ApprovalBand {
+    fn from(band: &DomainApprovalBand) -> Self {
+        Self {
+            amount_below: band.amount_below,
+            customer_type: band
+                .customer_type
+                .as_deref()
+                .and_then(customer_type_from_str),
+            rules: ApprovalRules::from(band.rules.clone()),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub(super) struct ApprovalExpiry {
+    ttl_hours: u32,
+    remind_after_hours: Option<u32>,
+    escalation: Option<CommitteeThreshold>,
+}
+
+impl From<DomainApprovalExpiry> for ApprovalExpiry {
+    fn from(expiry: DomainApprovalExpiry) -> Self {
+        Self {
+            ttl_hours: expiry.ttl_hours,
+            remind_after_hours: expiry.remind_after_hours,
+            escalation: match expiry.action {
+                DomainApprovalExpiryAction::Deny => None,
+                DomainApprovalExpiryAction::Escalate {
+                    committee_id,
+                    threshold,
+                } => Some(CommitteeThreshold {
+                    threshold,
+                    committee_id,
+                }),
+            },
         }
     }
 }
 
+const CUSTOMER_TYPES: [CustomerType; 7] = [
+    CustomerType::Individual,
+    CustomerType::GovernmentEntity,
+    CustomerType::PrivateCompany,
+    CustomerType::Bank,
+    CustomerType::FinancialInstitution,
+    CustomerType::ForeignAgencyOrSubsidiary,
+    CustomerType::NonDomiciledCompany,
+];
+
+fn customer_type_from_str(customer_type: &str) -> Option<CustomerType> {
+    CUSTOMER_TYPES
+        .into_iter()
+        .find(|t| String::from(*t) == customer_type)
+}
+
 #[derive(SimpleObject)]
 pub(super) struct SystemApproval {
     auto_approve: bool,
 }
 
+#[derive(SimpleObject)]
+pub(super) struct MultiStageApproval {
+    stages: Vec<CommitteeThreshold>,
+}
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub(super) struct CommitteeThreshold {
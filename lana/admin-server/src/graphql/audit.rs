@@ -1,8 +1,13 @@
-use async_graphql::{ComplexObject, Context, ID, SimpleObject, Union, connection::CursorType};
+use async_graphql::{
+    ComplexObject, Context, ID, InputObject, SimpleObject, Union, connection::CursorType,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::primitives::*;
-use lana_app::primitives::Subject as DomainSubject;
+use lana_app::{
+    document::{DocumentStatus, GeneratedDocumentDownloadLink},
+    primitives::Subject as DomainSubject,
+};
 
 use super::{access::User, loader::*};
 
@@ -103,3 +108,88 @@ impl CursorType for AuditCursor {
         serde_json::from_str(&json).map_err(|e| e.to_string())
     }
 }
+
+#[derive(InputObject, Default)]
+pub struct AuditFilterInput {
+    pub subject_user_id: Option<UUID>,
+    pub object_prefix: Option<String>,
+    pub action: Option<String>,
+    pub authorized: Option<bool>,
+    pub recorded_after: Option<Timestamp>,
+    pub recorded_before: Option<Timestamp>,
+}
+
+impl From<AuditFilterInput> for lana_app::audit::AuditFilter {
+    fn from(input: AuditFilterInput) -> Self {
+        Self {
+            subject: input
+                .subject_user_id
+                .map(|id| DomainSubject::User(UserId::from(id)).to_string()),
+            object_prefix: input.object_prefix,
+            action: input.action,
+            authorized: input.authorized,
+            recorded_after: input.recorded_after.map(Timestamp::into_inner),
+            recorded_before: input.recorded_before.map(Timestamp::into_inner),
+        }
+    }
+}
+
+#[derive(async_graphql::Enum, Clone, Copy, PartialEq, Eq)]
+pub enum AuditExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl From<AuditExportFormat> for lana_app::audit::AuditExportFormat {
+    fn from(format: AuditExportFormat) -> Self {
+        match format {
+            AuditExportFormat::Csv => Self::Csv,
+            AuditExportFormat::Jsonl => Self::Jsonl,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct AuditExportInput {
+    pub filter: Option<AuditFilterInput>,
+    pub format: AuditExportFormat,
+}
+
+#[derive(SimpleObject)]
+pub struct AuditExportPayload {
+    pub document_id: UUID,
+    pub checksum_document_id: UUID,
+    pub filename: String,
+    pub status: DocumentStatus,
+}
+
+impl From<lana_app::audit::AuditExport> for AuditExportPayload {
+    fn from(export: lana_app::audit::AuditExport) -> Self {
+        Self {
+            document_id: UUID::from(export.document.id),
+            checksum_document_id: UUID::from(export.checksum_document.id),
+            filename: export.document.filename,
+            status: export.document.status,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct AuditExportDownloadLinkGenerateInput {
+    pub document_id: UUID,
+}
+
+#[derive(SimpleObject)]
+pub struct AuditExportDownloadLinkGeneratePayload {
+    pub document_id: UUID,
+    pub link: String,
+}
+
+impl From<GeneratedDocumentDownloadLink> for AuditExportDownloadLinkGeneratePayload {
+    fn from(generated: GeneratedDocumentDownloadLink) -> Self {
+        Self {
+            document_id: UUID::from(generated.document_id),
+            link: generated.link,
+        }
+    }
+}
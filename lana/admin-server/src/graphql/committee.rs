@@ -5,7 +5,8 @@ use crate::primitives::*;
 use super::{access::User, loader::LanaDataLoader};
 
 pub use lana_app::governance::{
-    Committee as DomainCommittee, committee_cursor::CommitteesByCreatedAtCursor,
+    Committee as DomainCommittee, VoteDelegation as DomainVoteDelegation,
+    committee_cursor::CommitteesByCreatedAtCursor,
 };
 
 #[derive(SimpleObject, Clone)]
@@ -45,6 +46,60 @@ impl Committee {
 
         Ok(users)
     }
+
+    async fn vote_delegations(&self) -> Vec<VoteDelegation> {
+        self.entity
+            .delegations()
+            .into_iter()
+            .map(VoteDelegation::from)
+            .collect()
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct VoteDelegation {
+    starts_at: Timestamp,
+    ends_at: Timestamp,
+    is_active: bool,
+
+    #[graphql(skip)]
+    delegator_id: UserId,
+    #[graphql(skip)]
+    delegate_id: UserId,
+}
+
+impl From<DomainVoteDelegation> for VoteDelegation {
+    fn from(delegation: DomainVoteDelegation) -> Self {
+        Self {
+            starts_at: delegation.starts_at.into(),
+            ends_at: delegation.ends_at.into(),
+            is_active: delegation.is_active_at(chrono::Utc::now()),
+            delegator_id: UserId::from(delegation.delegator_id),
+            delegate_id: UserId::from(delegation.delegate_id),
+        }
+    }
+}
+
+#[ComplexObject]
+impl VoteDelegation {
+    async fn delegator(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let user = loader
+            .load_one(self.delegator_id)
+            .await?
+            .expect("user not found");
+        Ok(user)
+    }
+
+    async fn delegate(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let user = loader
+            .load_one(self.delegate_id)
+            .await?
+            .expect("user not found");
+        Ok(user)
+    }
 }
 
 #[derive(InputObject)]
@@ -66,3 +121,20 @@ pub struct CommitteeRemoveUserInput {
     pub user_id: UUID,
 }
 crate::mutation_payload! { CommitteeRemoveUserPayload, committee: Committee }
+
+#[derive(InputObject)]
+pub struct CommitteeDelegateVotesInput {
+    pub committee_id: UUID,
+    pub delegator_id: UUID,
+    pub delegate_id: UUID,
+    pub starts_at: Timestamp,
+    pub ends_at: Timestamp,
+}
+crate::mutation_payload! { CommitteeDelegateVotesPayload, committee: Committee }
+
+#[derive(InputObject)]
+pub struct CommitteeRevokeVoteDelegationInput {
+    pub committee_id: UUID,
+    pub delegator_id: UUID,
+}
+crate::mutation_payload! { CommitteeRevokeVoteDelegationPayload, committee: Committee }
@@ -7,6 +7,7 @@ pub(super) struct CreditFacilityBalance {
     facility_remaining: FacilityRemaining,
     disbursed: Disbursed,
     interest: Interest,
+    penalty_outstanding: Outstanding,
     outstanding: Outstanding,
     outstanding_payable: Outstanding,
     due_outstanding: Outstanding,
@@ -41,6 +42,9 @@ impl From<lana_app::credit::CreditFacilityBalanceSummary> for CreditFacilityBala
                     usd_balance: balance.interest_outstanding_payable(),
                 },
             },
+            penalty_outstanding: Outstanding {
+                usd_balance: balance.penalty_outstanding(),
+            },
             outstanding: Outstanding {
                 usd_balance: balance.total_outstanding(),
             },
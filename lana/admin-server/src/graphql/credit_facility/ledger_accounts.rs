@@ -20,6 +20,9 @@ pub(super) struct CreditFacilityLedgerAccounts {
     pub interest_defaulted_account_id: UUID,
     pub interest_income_account_id: UUID,
     pub fee_income_account_id: UUID,
+    pub unapplied_payments_account_id: UUID,
+    pub penalty_receivable_account_id: UUID,
+    pub penalty_income_account_id: UUID,
 }
 
 #[ComplexObject]
@@ -152,4 +155,28 @@ impl CreditFacilityLedgerAccounts {
             .expect("Ledger account not found");
         Ok(fee_income_account)
     }
+    async fn unapplied_payments_account(&self, ctx: &Context<'_>) -> Result<LedgerAccount> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let unapplied_payments_account = loader
+            .load_one(LedgerAccountId::from(self.unapplied_payments_account_id))
+            .await?
+            .expect("Ledger account not found");
+        Ok(unapplied_payments_account)
+    }
+    async fn penalty_receivable_account(&self, ctx: &Context<'_>) -> Result<LedgerAccount> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let penalty_receivable_account = loader
+            .load_one(LedgerAccountId::from(self.penalty_receivable_account_id))
+            .await?
+            .expect("Ledger account not found");
+        Ok(penalty_receivable_account)
+    }
+    async fn penalty_income_account(&self, ctx: &Context<'_>) -> Result<LedgerAccount> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let penalty_income_account = loader
+            .load_one(LedgerAccountId::from(self.penalty_income_account_id))
+            .await?
+            .expect("Ledger account not found");
+        Ok(penalty_income_account)
+    }
 }
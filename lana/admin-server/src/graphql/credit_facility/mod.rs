@@ -5,6 +5,7 @@ mod error;
 mod history;
 mod ledger_accounts;
 pub(super) mod payment_allocation;
+mod payoff_quote;
 mod pending_facility;
 mod proposal;
 mod repayment;
@@ -35,6 +36,7 @@ pub use disbursal::*;
 pub use error::*;
 pub use history::*;
 use ledger_accounts::*;
+use payoff_quote::*;
 pub use pending_facility::*;
 pub use proposal::*;
 pub use repayment::*;
@@ -196,6 +198,30 @@ impl CreditFacility {
         Ok(CreditFacilityBalance::from(balance))
     }
 
+    async fn unapplied_payments(&self, ctx: &Context<'_>) -> async_graphql::Result<UsdCents> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let unapplied = app
+            .credit()
+            .facilities()
+            .unapplied_payments_balance(sub, self.entity.id)
+            .await?;
+        Ok(unapplied)
+    }
+
+    async fn payoff_quote(
+        &self,
+        ctx: &Context<'_>,
+        as_of: Date,
+    ) -> async_graphql::Result<CreditFacilityPayoffQuote> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let quote = app
+            .credit()
+            .facilities()
+            .payoff_quote(sub, self.entity.id, as_of.into_inner())
+            .await?;
+        Ok(CreditFacilityPayoffQuote::from(quote))
+    }
+
     async fn wallet(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Wallet>> {
         let loader = ctx.data_unchecked::<LanaDataLoader>();
         let collateral = loader
@@ -257,6 +283,17 @@ impl CreditFacility {
                 .into(),
             interest_income_account_id: self.entity.account_ids.interest_income_account_id.into(),
             fee_income_account_id: self.entity.account_ids.fee_income_account_id.into(),
+            unapplied_payments_account_id: self
+                .entity
+                .account_ids
+                .unapplied_payments_account_id
+                .into(),
+            penalty_receivable_account_id: self
+                .entity
+                .account_ids
+                .penalty_receivable_account_id
+                .into(),
+            penalty_income_account_id: self.entity.account_ids.penalty_income_account_id.into(),
         }
     }
 }
@@ -269,6 +306,33 @@ pub struct CreditFacilityCollateralUpdateInput {
 }
 crate::mutation_payload! { CreditFacilityCollateralUpdatePayload, credit_facility: CreditFacility }
 
+#[derive(InputObject)]
+pub struct CreditFacilityCollateralReturnInitiateInput {
+    pub credit_facility_id: UUID,
+    pub destination_address: String,
+}
+crate::mutation_payload! { CreditFacilityCollateralReturnInitiatePayload, credit_facility: CreditFacility }
+
+#[derive(InputObject)]
+pub struct CreditFacilityTermsAmendInput {
+    pub credit_facility_id: UUID,
+    pub terms: TermsInput,
+}
+crate::mutation_payload! { CreditFacilityTermsAmendPayload, credit_facility: CreditFacility }
+
+#[derive(InputObject)]
+pub struct CreditFacilityPayOffInput {
+    pub credit_facility_id: UUID,
+    pub amount: UsdCents,
+}
+crate::mutation_payload! { CreditFacilityPayOffPayload, credit_facility: CreditFacility }
+
+#[derive(InputObject)]
+pub struct CreditFacilityUnappliedPaymentsRefundInput {
+    pub credit_facility_id: UUID,
+}
+crate::mutation_payload! { CreditFacilityUnappliedPaymentsRefundPayload, credit_facility: CreditFacility }
+
 #[derive(InputObject)]
 pub struct CreditFacilityPartialPaymentRecordInput {
     pub credit_facility_id: UUID,
@@ -283,6 +347,15 @@ pub struct CreditFacilityPartialPaymentWithDateRecordInput {
 }
 crate::mutation_payload! { CreditFacilityPartialPaymentRecordPayload, credit_facility: CreditFacility }
 
+#[derive(InputObject)]
+pub struct CreditFacilityLiquidationExecutionRecordInput {
+    pub liquidation_process_id: UUID,
+    pub collateral_sold: Satoshis,
+    pub proceeds: UsdCents,
+    pub effective: Date,
+}
+crate::mutation_payload! { CreditFacilityLiquidationExecutionRecordPayload, credit_facility: CreditFacility }
+
 #[derive(InputObject)]
 pub struct CreditFacilityCompleteInput {
     pub credit_facility_id: UUID,
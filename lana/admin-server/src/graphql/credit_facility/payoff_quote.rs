@@ -0,0 +1,24 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+#[derive(SimpleObject)]
+pub(super) struct CreditFacilityPayoffQuote {
+    as_of: Timestamp,
+    outstanding: UsdCents,
+    accrued_interest: UsdCents,
+    prepayment_fee: UsdCents,
+    total: UsdCents,
+}
+
+impl From<lana_app::credit::PayoffQuote> for CreditFacilityPayoffQuote {
+    fn from(quote: lana_app::credit::PayoffQuote) -> Self {
+        Self {
+            as_of: quote.as_of.into(),
+            outstanding: quote.outstanding,
+            accrued_interest: quote.accrued_interest,
+            prepayment_fee: quote.prepayment_fee,
+            total: quote.total(),
+        }
+    }
+}
@@ -12,9 +12,14 @@ use crate::{
 
 use super::{ApprovalProcess, CreditFacilityRepaymentPlanEntry};
 
-pub use lana_app::credit::{
-    CreditFacilityProposal as DomainCreditFacilityProposal,
-    CreditFacilityProposalsByCreatedAtCursor,
+pub use lana_app::{
+    credit::{
+        CreditFacilityProposal as DomainCreditFacilityProposal,
+        CreditFacilityProposalsByCreatedAtCursor,
+    },
+    terms::{
+        DisbursalPolicy as DomainDisbursalPolicy, ScheduledDisbursal as DomainScheduledDisbursal,
+    },
 };
 
 #[derive(SimpleObject, Clone)]
@@ -27,6 +32,7 @@ pub struct CreditFacilityProposal {
     created_at: Timestamp,
     facility_amount: UsdCents,
     credit_facility_terms: TermValues,
+    disbursal_policy: DisbursalPolicy,
 
     #[graphql(skip)]
     pub(crate) entity: Arc<DomainCreditFacilityProposal>,
@@ -86,6 +92,7 @@ impl From<DomainCreditFacilityProposal> for CreditFacilityProposal {
             created_at: created_at.into(),
             facility_amount: proposal.amount,
             credit_facility_terms: proposal.terms.into(),
+            disbursal_policy: proposal.disbursal_policy.clone().into(),
 
             entity: Arc::new(proposal),
         }
@@ -98,6 +105,83 @@ pub struct CreditFacilityProposalCreateInput {
     pub disbursal_credit_account_id: UUID,
     pub facility: UsdCents,
     pub terms: TermsInput,
+    pub disbursal_policy: Option<DisbursalPolicyInput>,
     pub custodian_id: Option<UUID>,
 }
 crate::mutation_payload! { CreditFacilityProposalCreatePayload, credit_facility_proposal: CreditFacilityProposal }
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum DisbursalPolicyType {
+    OnDemand,
+    FullAtActivation,
+    Scheduled,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct ScheduledDisbursal {
+    date: Date,
+    amount: UsdCents,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct DisbursalPolicy {
+    policy_type: DisbursalPolicyType,
+    schedule: Vec<ScheduledDisbursal>,
+}
+
+impl From<DomainDisbursalPolicy> for DisbursalPolicy {
+    fn from(policy: DomainDisbursalPolicy) -> Self {
+        let (policy_type, schedule) = match policy {
+            DomainDisbursalPolicy::OnDemand => (DisbursalPolicyType::OnDemand, vec![]),
+            DomainDisbursalPolicy::FullAtActivation => {
+                (DisbursalPolicyType::FullAtActivation, vec![])
+            }
+            DomainDisbursalPolicy::Scheduled(schedule) => {
+                (DisbursalPolicyType::Scheduled, schedule)
+            }
+        };
+
+        Self {
+            policy_type,
+            schedule: schedule
+                .into_iter()
+                .map(|disbursal| ScheduledDisbursal {
+                    date: disbursal.date.into(),
+                    amount: disbursal.amount,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct ScheduledDisbursalInput {
+    pub date: Date,
+    pub amount: UsdCents,
+}
+
+#[derive(InputObject)]
+pub struct DisbursalPolicyInput {
+    pub policy_type: DisbursalPolicyType,
+    pub schedule: Option<Vec<ScheduledDisbursalInput>>,
+}
+
+impl From<DisbursalPolicyInput> for DomainDisbursalPolicy {
+    fn from(input: DisbursalPolicyInput) -> Self {
+        match input.policy_type {
+            DisbursalPolicyType::OnDemand => Self::OnDemand,
+            DisbursalPolicyType::FullAtActivation => Self::FullAtActivation,
+            DisbursalPolicyType::Scheduled => Self::Scheduled(
+                input
+                    .schedule
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|disbursal| DomainScheduledDisbursal {
+                        date: disbursal.date.into_inner(),
+                        amount: disbursal.amount,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -2,7 +2,9 @@ use async_graphql::*;
 
 use crate::{graphql::loader::LanaDataLoader, primitives::*};
 
-pub use lana_app::custody::{Wallet as DomainWallet, WalletNetwork};
+pub use lana_app::custody::{
+    Wallet as DomainWallet, WalletNetwork, WalletReconciliation as DomainWalletReconciliation,
+};
 
 use super::Custodian;
 
@@ -43,4 +45,35 @@ impl Wallet {
             .await?
             .expect("wallet must have a custodian"))
     }
+
+    async fn reconciliations(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<WalletReconciliation>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        Ok(app
+            .custody()
+            .list_wallet_reconciliations(sub, self.entity.id)
+            .await?
+            .into_iter()
+            .map(WalletReconciliation::from)
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct WalletReconciliation {
+    recorded_balance: Satoshis,
+    custodian_balance: Satoshis,
+    reconciled_at: Timestamp,
+}
+
+impl From<DomainWalletReconciliation> for WalletReconciliation {
+    fn from(reconciliation: DomainWalletReconciliation) -> Self {
+        Self {
+            recorded_balance: reconciliation.recorded_balance,
+            custodian_balance: reconciliation.custodian_balance,
+            reconciled_at: reconciliation.reconciled_at.into(),
+        }
+    }
 }
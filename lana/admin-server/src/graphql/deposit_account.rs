@@ -9,7 +9,7 @@ pub use lana_app::deposit::{
 
 use super::{
     accounting::LedgerAccount, customer::Customer, deposit::*, deposit_account_history::*,
-    loader::LanaDataLoader, withdrawal::*,
+    loader::LanaDataLoader, transfer::*, withdrawal::*,
 };
 
 #[derive(SimpleObject, Clone)]
@@ -106,6 +106,15 @@ impl DepositAccount {
         Ok(withdrawals.into_iter().map(Withdrawal::from).collect())
     }
 
+    async fn transfers(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Transfer>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let transfers = app
+            .deposits()
+            .list_transfers_for_account(sub, self.entity.id)
+            .await?;
+        Ok(transfers.into_iter().map(Transfer::from).collect())
+    }
+
     async fn history(
         &self,
         ctx: &Context<'_>,
@@ -7,6 +7,7 @@ use super::{
         disbursal::CreditFacilityDisbursal, payment_allocation::CreditFacilityPaymentAllocation,
     },
     deposit::Deposit,
+    transfer::Transfer,
     withdrawal::Withdrawal,
 };
 
@@ -15,8 +16,11 @@ pub enum DepositAccountHistoryEntry {
     Deposit(DepositEntry),
     Withdrawal(WithdrawalEntry),
     CancelledWithdrawal(CancelledWithdrawalEntry),
+    Transfer(TransferEntry),
     Disbursal(DisbursalEntry),
     Payment(PaymentEntry),
+    InterestAccrual(InterestAccrualEntry),
+    InterestPayment(InterestPaymentEntry),
     Unknown(UnknownEntry),
 }
 
@@ -43,6 +47,14 @@ pub struct CancelledWithdrawalEntry {
     pub recorded_at: Timestamp,
 }
 
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct TransferEntry {
+    #[graphql(skip)]
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+}
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub struct DisbursalEntry {
@@ -59,6 +71,22 @@ pub struct PaymentEntry {
     pub recorded_at: Timestamp,
 }
 
+#[derive(SimpleObject)]
+pub struct InterestAccrualEntry {
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+    pub amount: UsdCents,
+    pub accrued_on: Date,
+}
+
+#[derive(SimpleObject)]
+pub struct InterestPaymentEntry {
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+    pub amount: UsdCents,
+    pub period_end: Date,
+}
+
 #[derive(SimpleObject)]
 pub struct UnknownEntry {
     pub tx_id: UUID,
@@ -108,6 +136,21 @@ impl CancelledWithdrawalEntry {
         Ok(Withdrawal::from(withdrawal))
     }
 }
+#[ComplexObject]
+impl TransferEntry {
+    async fn transfer(&self, ctx: &Context<'_>) -> async_graphql::Result<Transfer> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+
+        let transfer = app
+            .deposits()
+            .find_transfer_by_id(sub, self.tx_id)
+            .await?
+            .expect("transfer should exist");
+
+        Ok(Transfer::from(transfer))
+    }
+}
+
 #[ComplexObject]
 impl DisbursalEntry {
     async fn disbursal(&self, ctx: &Context<'_>) -> async_graphql::Result<CreditFacilityDisbursal> {
@@ -162,6 +205,12 @@ impl From<lana_app::deposit::DepositAccountHistoryEntry> for DepositAccountHisto
                     recorded_at: entry.recorded_at.into(),
                 })
             }
+            lana_app::deposit::DepositAccountHistoryEntry::Transfer(entry) => {
+                Self::Transfer(TransferEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                })
+            }
             lana_app::deposit::DepositAccountHistoryEntry::Disbursal(entry) => {
                 Self::Disbursal(DisbursalEntry {
                     tx_id: UUID::from(entry.tx_id),
@@ -174,6 +223,22 @@ impl From<lana_app::deposit::DepositAccountHistoryEntry> for DepositAccountHisto
                     recorded_at: entry.recorded_at.into(),
                 })
             }
+            lana_app::deposit::DepositAccountHistoryEntry::InterestAccrual(entry) => {
+                Self::InterestAccrual(InterestAccrualEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                    amount: entry.amount,
+                    accrued_on: entry.accrued_on.into(),
+                })
+            }
+            lana_app::deposit::DepositAccountHistoryEntry::InterestPayment(entry) => {
+                Self::InterestPayment(InterestPaymentEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                    amount: entry.amount,
+                    period_end: entry.period_end.into(),
+                })
+            }
             lana_app::deposit::DepositAccountHistoryEntry::Unknown(entry) => {
                 Self::Unknown(UnknownEntry {
                     tx_id: UUID::from(entry.tx_id),
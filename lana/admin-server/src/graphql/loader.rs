@@ -24,7 +24,7 @@ use crate::primitives::*;
 use super::{
     access::*, accounting::*, approval_process::*, committee::*, credit_facility::*, custody::*,
     customer::*, deposit::*, deposit_account::*, document::*, policy::*, reports::*,
-    terms_template::*, withdrawal::*,
+    terms_template::*, transfer::*, withdrawal::*,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -229,6 +229,22 @@ impl Loader<WithdrawalId> for LanaLoader {
     }
 }
 
+impl Loader<TransferId> for LanaLoader {
+    type Value = Transfer;
+    type Error = Arc<CoreDepositError>;
+
+    async fn load(
+        &self,
+        keys: &[TransferId],
+    ) -> Result<HashMap<TransferId, Transfer>, Self::Error> {
+        self.app
+            .deposits()
+            .find_all_transfers(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
 impl Loader<DepositId> for LanaLoader {
     type Value = Deposit;
     type Error = Arc<CoreDepositError>;
@@ -25,6 +25,7 @@ mod reports;
 mod sumsub;
 mod terms;
 mod terms_template;
+mod transfer;
 mod withdrawal;
 #[macro_use]
 pub(crate) mod macros;
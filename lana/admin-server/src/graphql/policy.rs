@@ -2,9 +2,14 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
-use super::{approval_process::*, approval_rules::*};
+use super::{approval_process::*, approval_rules::*, customer::CustomerType};
 
-pub use lana_app::governance::{Policy as DomainPolicy, policy_cursor::PoliciesByCreatedAtCursor};
+pub use lana_app::governance::{
+    ApprovalBand as DomainApprovalBand, ApprovalExpiry as DomainApprovalExpiry,
+    ApprovalExpiryAction as DomainApprovalExpiryAction, ApprovalRules as DomainApprovalRules,
+    ApprovalStage as DomainApprovalStage, Policy as DomainPolicy,
+    policy_cursor::PoliciesByCreatedAtCursor,
+};
 
 #[derive(SimpleObject, Clone)]
 #[graphql(complex)]
@@ -31,7 +36,15 @@ impl From<DomainPolicy> for Policy {
 #[ComplexObject]
 impl Policy {
     async fn rules(&self) -> ApprovalRules {
-        ApprovalRules::from(self.entity.rules)
+        ApprovalRules::from(self.entity.rules.clone())
+    }
+
+    async fn bands(&self) -> Vec<ApprovalBand> {
+        self.entity.bands.iter().map(ApprovalBand::from).collect()
+    }
+
+    async fn expiry(&self) -> Option<ApprovalExpiry> {
+        self.entity.expiry.map(ApprovalExpiry::from)
     }
 }
 
@@ -43,3 +56,102 @@ pub struct PolicyAssignCommitteeInput {
 }
 
 mutation_payload! { PolicyAssignCommitteePayload, policy: Policy }
+
+#[derive(InputObject)]
+pub struct ApprovalStageInput {
+    pub committee_id: UUID,
+    pub threshold: usize,
+}
+
+impl From<ApprovalStageInput> for DomainApprovalStage {
+    fn from(input: ApprovalStageInput) -> Self {
+        Self {
+            committee_id: input.committee_id.into(),
+            threshold: input.threshold,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct PolicyAssignStagesInput {
+    pub policy_id: UUID,
+    pub stages: Vec<ApprovalStageInput>,
+}
+
+mutation_payload! { PolicyAssignStagesPayload, policy: Policy }
+
+#[derive(InputObject)]
+pub struct ApprovalBandInput {
+    pub amount_below: Option<UsdCents>,
+    pub customer_type: Option<CustomerType>,
+    pub committee_id: Option<UUID>,
+    pub threshold: Option<usize>,
+}
+
+impl TryFrom<ApprovalBandInput> for DomainApprovalBand {
+    type Error = async_graphql::Error;
+
+    fn try_from(input: ApprovalBandInput) -> Result<Self, Self::Error> {
+        let rules = match (input.committee_id, input.threshold) {
+            (Some(committee_id), Some(threshold)) => DomainApprovalRules::CommitteeThreshold {
+                committee_id: committee_id.into(),
+                threshold,
+            },
+            (None, None) => DomainApprovalRules::SystemAutoApprove,
+            _ => {
+                return Err(async_graphql::Error::new(
+                    "committeeId and threshold must be provided together",
+                ));
+            }
+        };
+        Ok(Self {
+            amount_below: input.amount_below,
+            customer_type: input.customer_type.map(String::from),
+            rules,
+        })
+    }
+}
+
+#[derive(InputObject)]
+pub struct PolicyUpdateApprovalBandsInput {
+    pub policy_id: UUID,
+    pub bands: Vec<ApprovalBandInput>,
+}
+
+mutation_payload! { PolicyUpdateApprovalBandsPayload, policy: Policy }
+
+#[derive(InputObject)]
+pub struct PolicyUpdateExpiryInput {
+    pub policy_id: UUID,
+    pub ttl_hours: Option<u32>,
+    pub remind_after_hours: Option<u32>,
+    pub escalation_committee_id: Option<UUID>,
+    pub escalation_threshold: Option<usize>,
+}
+
+impl PolicyUpdateExpiryInput {
+    pub fn expiry(&self) -> async_graphql::Result<Option<DomainApprovalExpiry>> {
+        let Some(ttl_hours) = self.ttl_hours else {
+            return Ok(None);
+        };
+        let action = match (self.escalation_committee_id, self.escalation_threshold) {
+            (Some(committee_id), Some(threshold)) => DomainApprovalExpiryAction::Escalate {
+                committee_id: committee_id.into(),
+                threshold,
+            },
+            (None, None) => DomainApprovalExpiryAction::Deny,
+            _ => {
+                return Err(async_graphql::Error::new(
+                    "escalationCommitteeId and escalationThreshold must be provided together",
+                ));
+            }
+        };
+        Ok(Some(DomainApprovalExpiry {
+            ttl_hours,
+            remind_after_hours: self.remind_after_hours,
+            action,
+        }))
+    }
+}
+
+mutation_payload! { PolicyUpdateExpiryPayload, policy: Policy }
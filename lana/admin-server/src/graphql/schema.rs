@@ -16,7 +16,7 @@ use super::{
     contract_creation::*, credit_config::*, credit_facility::*, custody::*, customer::*,
     dashboard::*, deposit::*, deposit_config::*, document::*, loader::*, me::*, policy::*,
     price::*, profit_and_loss_config::*, public_id::*, reports::*, sumsub::*, terms_template::*,
-    withdrawal::*,
+    transfer::*, withdrawal::*,
 };
 
 pub struct Query;
@@ -206,6 +206,15 @@ impl Query {
         )
     }
 
+    async fn transfer(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<Transfer>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(Transfer, ctx, app.deposits().find_transfer_by_id(sub, id))
+    }
+
     async fn deposit(&self, ctx: &Context<'_>, id: UUID) -> async_graphql::Result<Option<Deposit>> {
         let (app, sub) = app_and_sub_from_ctx!(ctx);
         maybe_fetch_one!(Deposit, ctx, app.deposits().find_deposit_by_id(sub, id))
@@ -803,8 +812,10 @@ impl Query {
         ctx: &Context<'_>,
         first: i32,
         after: Option<String>,
+        filter: Option<AuditFilterInput>,
     ) -> async_graphql::Result<Connection<AuditCursor, AuditEntry>> {
         let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let filter = lana_app::audit::AuditFilter::from(filter.unwrap_or_default());
         query(
             after,
             None,
@@ -819,6 +830,7 @@ impl Query {
                             first,
                             after: after.map(lana_app::audit::AuditCursor::from),
                         },
+                        filter,
                     )
                     .await?;
 
@@ -1079,6 +1091,34 @@ impl Mutation {
         )
     }
 
+    async fn user_deactivate(
+        &self,
+        ctx: &Context<'_>,
+        input: UserDeactivateInput,
+    ) -> async_graphql::Result<UserDeactivatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            UserDeactivatePayload,
+            User,
+            ctx,
+            app.deactivate_user(sub, input.id)
+        )
+    }
+
+    async fn user_reactivate(
+        &self,
+        ctx: &Context<'_>,
+        input: UserReactivateInput,
+    ) -> async_graphql::Result<UserReactivatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            UserReactivatePayload,
+            User,
+            ctx,
+            app.reactivate_user(sub, input.id)
+        )
+    }
+
     async fn role_create(
         &self,
         ctx: &Context<'_>,
@@ -1192,6 +1232,7 @@ impl Mutation {
 
         let DepositModuleConfigureInput {
             chart_of_accounts_omnibus_parent_code,
+            chart_of_accounts_interest_expense_parent_code,
             chart_of_accounts_individual_deposit_accounts_parent_code,
             chart_of_accounts_government_entity_deposit_accounts_parent_code,
             chart_of_account_private_company_deposit_accounts_parent_code,
@@ -1235,6 +1276,9 @@ impl Mutation {
                 chart_of_account_frozen_non_domiciled_individual_deposit_accounts_parent_code
                     .parse()?,
             chart_of_accounts_omnibus_parent_code: chart_of_accounts_omnibus_parent_code.parse()?,
+            chart_of_accounts_interest_expense_parent_code: Some(
+                chart_of_accounts_interest_expense_parent_code.parse()?,
+            ),
         };
 
         let config = app
@@ -1355,6 +1399,40 @@ impl Mutation {
         )
     }
 
+    pub async fn transfer_initiate(
+        &self,
+        ctx: &Context<'_>,
+        input: TransferInitiateInput,
+    ) -> async_graphql::Result<TransferInitiatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            TransferInitiatePayload,
+            Transfer,
+            ctx,
+            app.deposits().initiate_transfer(
+                sub,
+                input.from_account_id,
+                input.to_account_id,
+                input.amount,
+                input.reference
+            )
+        )
+    }
+
+    pub async fn transfer_execute(
+        &self,
+        ctx: &Context<'_>,
+        input: TransferExecuteInput,
+    ) -> async_graphql::Result<TransferExecutePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            TransferExecutePayload,
+            Transfer,
+            ctx,
+            app.deposits().execute_transfer(sub, input.transfer_id)
+        )
+    }
+
     pub async fn deposit_revert(
         &self,
         ctx: &Context<'_>,
@@ -1383,6 +1461,35 @@ impl Mutation {
         )
     }
 
+    pub async fn deposit_account_unfreeze(
+        &self,
+        ctx: &Context<'_>,
+        input: DepositAccountUnfreezeInput,
+    ) -> async_graphql::Result<DepositAccountUnfreezePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            DepositAccountUnfreezePayload,
+            DepositAccount,
+            ctx,
+            app.deposits()
+                .unfreeze_account(sub, input.deposit_account_id)
+        )
+    }
+
+    pub async fn deposit_account_close(
+        &self,
+        ctx: &Context<'_>,
+        input: DepositAccountCloseInput,
+    ) -> async_graphql::Result<DepositAccountClosePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            DepositAccountClosePayload,
+            DepositAccount,
+            ctx,
+            app.close_deposit_account(sub, input.deposit_account_id)
+        )
+    }
+
     async fn terms_template_create(
         &self,
         ctx: &Context<'_>,
@@ -1403,6 +1510,17 @@ impl Mutation {
             .liquidation_cvl(input.liquidation_cvl)
             .margin_call_cvl(input.margin_call_cvl)
             .initial_cvl(input.initial_cvl)
+            .payment_allocation_strategy(input.payment_allocation_strategy.unwrap_or_default())
+            .prepayment_fee_rate(input.prepayment_fee_rate)
+            .penalty_annual_rate(input.penalty_annual_rate)
+            .amortization(input.amortization)
+            .day_count_convention(input.day_count_convention.unwrap_or_default())
+            .margin_call_cure_duration(
+                input
+                    .margin_call_cure_duration
+                    .map(Into::into)
+                    .unwrap_or(lana_app::terms::TermValues::DEFAULT_MARGIN_CALL_CURE_DURATION),
+            )
             .build()?;
 
         exec_mutation!(
@@ -1436,6 +1554,17 @@ impl Mutation {
             .liquidation_cvl(input.liquidation_cvl)
             .margin_call_cvl(input.margin_call_cvl)
             .initial_cvl(input.initial_cvl)
+            .payment_allocation_strategy(input.payment_allocation_strategy.unwrap_or_default())
+            .prepayment_fee_rate(input.prepayment_fee_rate)
+            .penalty_annual_rate(input.penalty_annual_rate)
+            .amortization(input.amortization)
+            .day_count_convention(input.day_count_convention.unwrap_or_default())
+            .margin_call_cure_duration(
+                input
+                    .margin_call_cure_duration
+                    .map(Into::into)
+                    .unwrap_or(lana_app::terms::TermValues::DEFAULT_MARGIN_CALL_CURE_DURATION),
+            )
             .build()?;
         exec_mutation!(
             TermsTemplateUpdatePayload,
@@ -1471,6 +1600,8 @@ impl Mutation {
             chart_of_account_in_liquidation_parent_code,
             chart_of_account_interest_income_parent_code,
             chart_of_account_fee_income_parent_code,
+            chart_of_account_penalty_income_parent_code,
+            chart_of_account_penalty_receivable_parent_code,
 
             chart_of_account_short_term_individual_disbursed_receivable_parent_code,
             chart_of_account_short_term_government_entity_disbursed_receivable_parent_code,
@@ -1530,6 +1661,10 @@ impl Mutation {
                 chart_of_account_interest_income_parent_code.parse()?,
             chart_of_account_fee_income_parent_code: chart_of_account_fee_income_parent_code
                 .parse()?,
+            chart_of_account_penalty_income_parent_code:
+                chart_of_account_penalty_income_parent_code.parse()?,
+            chart_of_account_penalty_receivable_parent_code:
+                chart_of_account_penalty_receivable_parent_code.parse()?,
             chart_of_account_short_term_individual_disbursed_receivable_parent_code:
                 chart_of_account_short_term_individual_disbursed_receivable_parent_code.parse()?,
             chart_of_account_short_term_government_entity_disbursed_receivable_parent_code:
@@ -1656,6 +1791,7 @@ impl Mutation {
             customer_id,
             disbursal_credit_account_id,
             terms,
+            disbursal_policy,
             custodian_id,
         } = input;
 
@@ -1673,18 +1809,30 @@ impl Mutation {
             .liquidation_cvl(terms.liquidation_cvl)
             .margin_call_cvl(terms.margin_call_cvl)
             .initial_cvl(terms.initial_cvl)
+            .payment_allocation_strategy(terms.payment_allocation_strategy.unwrap_or_default())
+            .prepayment_fee_rate(terms.prepayment_fee_rate)
+            .penalty_annual_rate(terms.penalty_annual_rate)
+            .amortization(terms.amortization)
+            .day_count_convention(terms.day_count_convention.unwrap_or_default())
+            .margin_call_cure_duration(
+                terms
+                    .margin_call_cure_duration
+                    .map(Into::into)
+                    .unwrap_or(lana_app::terms::TermValues::DEFAULT_MARGIN_CALL_CURE_DURATION),
+            )
             .build()?;
 
         exec_mutation!(
             CreditFacilityProposalCreatePayload,
             CreditFacilityProposal,
             ctx,
-            app.credit().create_facility_proposal(
+            app.credit().create_facility_proposal_with_disbursal_policy(
                 sub,
                 customer_id,
                 disbursal_credit_account_id,
                 facility,
                 credit_facility_term_values,
+                disbursal_policy.map(Into::into).unwrap_or_default(),
                 custodian_id
             )
         )
@@ -1768,6 +1916,26 @@ impl Mutation {
         )
     }
 
+    pub async fn credit_facility_liquidation_execution_record(
+        &self,
+        ctx: &Context<'_>,
+        input: CreditFacilityLiquidationExecutionRecordInput,
+    ) -> async_graphql::Result<CreditFacilityLiquidationExecutionRecordPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            CreditFacilityLiquidationExecutionRecordPayload,
+            CreditFacility,
+            ctx,
+            app.record_liquidation_execution(
+                sub,
+                input.liquidation_process_id,
+                input.collateral_sold,
+                input.proceeds,
+                input.effective
+            )
+        )
+    }
+
     pub async fn credit_facility_disbursal_initiate(
         &self,
         ctx: &Context<'_>,
@@ -1798,6 +1966,104 @@ impl Mutation {
         )
     }
 
+    async fn credit_facility_terms_amend(
+        &self,
+        ctx: &Context<'_>,
+        input: CreditFacilityTermsAmendInput,
+    ) -> async_graphql::Result<CreditFacilityTermsAmendPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let CreditFacilityTermsAmendInput {
+            credit_facility_id,
+            terms,
+        } = input;
+
+        let term_values = lana_app::terms::TermValues::builder()
+            .annual_rate(terms.annual_rate)
+            .accrual_interval(terms.accrual_interval)
+            .accrual_cycle_interval(terms.accrual_cycle_interval)
+            .one_time_fee_rate(terms.one_time_fee_rate)
+            .duration(terms.duration)
+            .interest_due_duration_from_accrual(terms.interest_due_duration_from_accrual)
+            .obligation_overdue_duration_from_due(terms.obligation_overdue_duration_from_due)
+            .obligation_liquidation_duration_from_due(
+                terms.obligation_liquidation_duration_from_due,
+            )
+            .liquidation_cvl(terms.liquidation_cvl)
+            .margin_call_cvl(terms.margin_call_cvl)
+            .initial_cvl(terms.initial_cvl)
+            .payment_allocation_strategy(terms.payment_allocation_strategy.unwrap_or_default())
+            .prepayment_fee_rate(terms.prepayment_fee_rate)
+            .penalty_annual_rate(terms.penalty_annual_rate)
+            .amortization(terms.amortization)
+            .day_count_convention(terms.day_count_convention.unwrap_or_default())
+            .margin_call_cure_duration(
+                terms
+                    .margin_call_cure_duration
+                    .map(Into::into)
+                    .unwrap_or(lana_app::terms::TermValues::DEFAULT_MARGIN_CALL_CURE_DURATION),
+            )
+            .build()?;
+
+        exec_mutation!(
+            CreditFacilityTermsAmendPayload,
+            CreditFacility,
+            ctx,
+            app.credit().facilities().initiate_terms_amendment(
+                sub,
+                credit_facility_id,
+                term_values
+            )
+        )
+    }
+
+    async fn credit_facility_pay_off(
+        &self,
+        ctx: &Context<'_>,
+        input: CreditFacilityPayOffInput,
+    ) -> async_graphql::Result<CreditFacilityPayOffPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            CreditFacilityPayOffPayload,
+            CreditFacility,
+            ctx,
+            app.credit()
+                .pay_off_facility(sub, input.credit_facility_id, input.amount)
+        )
+    }
+
+    async fn credit_facility_collateral_return_initiate(
+        &self,
+        ctx: &Context<'_>,
+        input: CreditFacilityCollateralReturnInitiateInput,
+    ) -> async_graphql::Result<CreditFacilityCollateralReturnInitiatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            CreditFacilityCollateralReturnInitiatePayload,
+            CreditFacility,
+            ctx,
+            app.credit().initiate_collateral_return(
+                sub,
+                input.credit_facility_id,
+                input.destination_address
+            )
+        )
+    }
+
+    async fn credit_facility_unapplied_payments_refund(
+        &self,
+        ctx: &Context<'_>,
+        input: CreditFacilityUnappliedPaymentsRefundInput,
+    ) -> async_graphql::Result<CreditFacilityUnappliedPaymentsRefundPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            CreditFacilityUnappliedPaymentsRefundPayload,
+            CreditFacility,
+            ctx,
+            app.credit()
+                .refund_unapplied_payments(sub, input.credit_facility_id)
+        )
+    }
+
     async fn custodian_create(
         &self,
         ctx: &Context<'_>,
@@ -1872,6 +2138,45 @@ impl Mutation {
         )
     }
 
+    async fn committee_delegate_votes(
+        &self,
+        ctx: &Context<'_>,
+        input: CommitteeDelegateVotesInput,
+    ) -> async_graphql::Result<CommitteeDelegateVotesPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            CommitteeDelegateVotesPayload,
+            Committee,
+            ctx,
+            app.governance().delegate_committee_votes(
+                sub,
+                input.committee_id,
+                input.delegator_id,
+                input.delegate_id,
+                input.starts_at.into_inner(),
+                input.ends_at.into_inner()
+            )
+        )
+    }
+
+    async fn committee_revoke_vote_delegation(
+        &self,
+        ctx: &Context<'_>,
+        input: CommitteeRevokeVoteDelegationInput,
+    ) -> async_graphql::Result<CommitteeRevokeVoteDelegationPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            CommitteeRevokeVoteDelegationPayload,
+            Committee,
+            ctx,
+            app.governance().revoke_committee_vote_delegation(
+                sub,
+                input.committee_id,
+                input.delegator_id
+            )
+        )
+    }
+
     async fn policy_assign_committee(
         &self,
         ctx: &Context<'_>,
@@ -1891,6 +2196,62 @@ impl Mutation {
         )
     }
 
+    async fn policy_assign_stages(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyAssignStagesInput,
+    ) -> async_graphql::Result<PolicyAssignStagesPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let stages = input
+            .stages
+            .into_iter()
+            .map(DomainApprovalStage::from)
+            .collect();
+        exec_mutation!(
+            PolicyAssignStagesPayload,
+            Policy,
+            ctx,
+            app.governance()
+                .assign_stages_to_policy(sub, input.policy_id, stages)
+        )
+    }
+
+    async fn policy_update_approval_bands(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyUpdateApprovalBandsInput,
+    ) -> async_graphql::Result<PolicyUpdateApprovalBandsPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let bands = input
+            .bands
+            .into_iter()
+            .map(DomainApprovalBand::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        exec_mutation!(
+            PolicyUpdateApprovalBandsPayload,
+            Policy,
+            ctx,
+            app.governance()
+                .update_policy_approval_bands(sub, input.policy_id, bands)
+        )
+    }
+
+    async fn policy_update_expiry(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyUpdateExpiryInput,
+    ) -> async_graphql::Result<PolicyUpdateExpiryPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let expiry = input.expiry()?;
+        exec_mutation!(
+            PolicyUpdateExpiryPayload,
+            Policy,
+            ctx,
+            app.governance()
+                .update_policy_expiry(sub, input.policy_id, expiry)
+        )
+    }
+
     async fn approval_process_approve(
         &self,
         ctx: &Context<'_>,
@@ -2198,6 +2559,31 @@ impl Mutation {
         Ok(LoanAgreementDownloadLinksGeneratePayload::from(doc))
     }
 
+    async fn audit_export(
+        &self,
+        ctx: &Context<'_>,
+        input: AuditExportInput,
+    ) -> async_graphql::Result<AuditExportPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let AuditExportInput { filter, format } = input;
+        let export = app
+            .export_audit(sub, filter.unwrap_or_default().into(), format.into())
+            .await?;
+        Ok(AuditExportPayload::from(export))
+    }
+
+    async fn audit_export_download_link_generate(
+        &self,
+        ctx: &Context<'_>,
+        input: AuditExportDownloadLinkGenerateInput,
+    ) -> async_graphql::Result<AuditExportDownloadLinkGeneratePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let link = app
+            .generate_audit_export_download_link(sub, input.document_id.into())
+            .await?;
+        Ok(AuditExportDownloadLinkGeneratePayload::from(link))
+    }
+
     async fn trigger_report_run(
         &self,
         ctx: &Context<'_>,
@@ -2,8 +2,9 @@ use async_graphql::*;
 use serde::{Deserialize, Serialize};
 
 pub use lana_app::terms::{
-    AnnualRatePct, CVLPct as DomainCVLPct, FacilityDuration as DomainDuration, InterestInterval,
-    ObligationDuration as DomainObligationDuration, OneTimeFeeRatePct,
+    AmortizationSchedule, AnnualRatePct, CVLPct as DomainCVLPct, DayCountConvention,
+    FacilityDuration as DomainDuration, InterestInterval,
+    ObligationDuration as DomainObligationDuration, OneTimeFeeRatePct, PaymentAllocationStrategy,
     TermValues as DomainTermValues,
 };
 
@@ -17,6 +18,12 @@ pub struct TermValues {
     liquidation_cvl: CVLPct,
     margin_call_cvl: CVLPct,
     initial_cvl: CVLPct,
+    payment_allocation_strategy: PaymentAllocationStrategy,
+    prepayment_fee_rate: Option<OneTimeFeeRatePct>,
+    penalty_annual_rate: Option<AnnualRatePct>,
+    amortization: Option<AmortizationSchedule>,
+    day_count_convention: DayCountConvention,
+    margin_call_cure_duration: Duration,
 }
 
 impl From<DomainTermValues> for TermValues {
@@ -30,6 +37,12 @@ impl From<DomainTermValues> for TermValues {
             liquidation_cvl: values.liquidation_cvl.into(),
             margin_call_cvl: values.margin_call_cvl.into(),
             initial_cvl: values.initial_cvl.into(),
+            payment_allocation_strategy: values.payment_allocation_strategy,
+            prepayment_fee_rate: values.prepayment_fee_rate,
+            penalty_annual_rate: values.penalty_annual_rate,
+            amortization: values.amortization,
+            day_count_convention: values.day_count_convention,
+            margin_call_cure_duration: values.margin_call_cure_duration.into(),
         }
     }
 }
@@ -46,6 +59,12 @@ pub struct TermsInput {
     pub margin_call_cvl: CVLPctValue,
     pub initial_cvl: CVLPctValue,
     pub liquidation_cvl: CVLPctValue,
+    pub payment_allocation_strategy: Option<PaymentAllocationStrategy>,
+    pub prepayment_fee_rate: Option<OneTimeFeeRatePct>,
+    pub penalty_annual_rate: Option<AnnualRatePct>,
+    pub amortization: Option<AmortizationSchedule>,
+    pub day_count_convention: Option<DayCountConvention>,
+    pub margin_call_cure_duration: Option<DurationInput>,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
@@ -64,6 +64,12 @@ pub(super) struct TermsTemplateCreateInput {
     pub liquidation_cvl: CVLPctValue,
     pub margin_call_cvl: CVLPctValue,
     pub initial_cvl: CVLPctValue,
+    pub payment_allocation_strategy: Option<PaymentAllocationStrategy>,
+    pub prepayment_fee_rate: Option<OneTimeFeeRatePct>,
+    pub penalty_annual_rate: Option<AnnualRatePct>,
+    pub amortization: Option<AmortizationSchedule>,
+    pub day_count_convention: Option<DayCountConvention>,
+    pub margin_call_cure_duration: Option<DurationInput>,
 }
 crate::mutation_payload! { TermsTemplateCreatePayload, terms_template: TermsTemplate }
 
@@ -81,5 +87,11 @@ pub(super) struct TermsTemplateUpdateInput {
     pub obligation_liquidation_duration_from_due: DurationInput,
     pub margin_call_cvl: CVLPctValue,
     pub initial_cvl: CVLPctValue,
+    pub payment_allocation_strategy: Option<PaymentAllocationStrategy>,
+    pub prepayment_fee_rate: Option<OneTimeFeeRatePct>,
+    pub penalty_annual_rate: Option<AnnualRatePct>,
+    pub amortization: Option<AmortizationSchedule>,
+    pub day_count_convention: Option<DayCountConvention>,
+    pub margin_call_cure_duration: Option<DurationInput>,
 }
 crate::mutation_payload! { TermsTemplateUpdatePayload, terms_template: TermsTemplate }
@@ -69,6 +69,7 @@ pub struct AdminJwtClaims {
 pub async fn graphql_handler(
     headers: HeaderMap,
     schema: Extension<Schema<graphql::Query, graphql::Mutation, EmptySubscription>>,
+    Extension(app): Extension<LanaApp>,
     Claims(jwt_claims): Claims<AdminJwtClaims>,
     req: GraphQLRequest,
 ) -> GraphQLResponse {
@@ -77,6 +78,21 @@ pub async fn graphql_handler(
 
     match uuid::Uuid::parse_str(&jwt_claims.subject) {
         Ok(id) => {
+            match app.access().users().is_active_without_audit(id).await {
+                Ok(true) => (),
+                Ok(false) => {
+                    return async_graphql::Response::from_errors(vec![
+                        async_graphql::ServerError::new("User is deactivated", None),
+                    ])
+                    .into();
+                }
+                Err(e) => {
+                    return async_graphql::Response::from_errors(vec![
+                        async_graphql::ServerError::new(e.to_string(), None),
+                    ])
+                    .into();
+                }
+            }
             let auth_context = AdminAuthContext::new(id);
             req = req.data(auth_context);
             schema.execute(req).await.into()
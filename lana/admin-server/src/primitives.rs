@@ -11,8 +11,8 @@ pub use lana_app::{
         CustomerId, DepositAccountId, DepositId, DisbursalId, DisbursalStatus, DocumentId, EntryId,
         LedgerTransactionId, ManualTransactionId, PaymentAllocationId, PaymentId,
         PendingCreditFacilityId, PendingCreditFacilityStatus, PermissionSetId, PolicyId, ReportId,
-        RoleId, Satoshis, SignedSatoshis, SignedUsdCents, Subject, TermsTemplateId, UsdCents,
-        UserId, WalletId, WithdrawalId,
+        RoleId, Satoshis, SignedSatoshis, SignedUsdCents, Subject, TermsTemplateId, TransferId,
+        UsdCents, UserId, WalletId, WithdrawalId,
     },
     public_id::PublicId,
     report::ReportRunId,
@@ -120,6 +120,7 @@ impl_to_global_id! {
     PolicyId,
     CommitteeId,
     WithdrawalId,
+    TransferId,
     DepositId,
     ReportId,
     ReportRunId,
@@ -181,6 +181,25 @@ CREATE TABLE core_withdrawal_events (
   UNIQUE(id, sequence)
 );
 
+CREATE TABLE core_transfers (
+  id UUID PRIMARY KEY,
+  from_account_id UUID NOT NULL REFERENCES core_deposit_accounts(id),
+  to_account_id UUID NOT NULL REFERENCES core_deposit_accounts(id),
+  approval_process_id UUID REFERENCES core_approval_processes(id),
+  reference VARCHAR NOT NULL UNIQUE,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_transfer_events (
+  id UUID NOT NULL REFERENCES core_transfers(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  context JSONB DEFAULT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
 CREATE TABLE core_terms_templates (
   id UUID PRIMARY KEY,
   name VARCHAR NOT NULL UNIQUE,
@@ -281,6 +300,7 @@ CREATE TABLE core_credit_facility_proposals (
   id UUID PRIMARY KEY,
   customer_id UUID NOT NULL REFERENCES core_customers(id),
   approval_process_id UUID NOT NULL REFERENCES core_approval_processes(id),
+  status VARCHAR NOT NULL,
   created_at TIMESTAMPTZ NOT NULL
 );
 
@@ -302,6 +322,7 @@ CREATE TABLE core_pending_credit_facilities (
   collateral_id UUID NOT NULL REFERENCES core_collaterals(id),
   collateralization_ratio NUMERIC,
   collateralization_state VARCHAR NOT NULL,
+  status VARCHAR NOT NULL,
   created_at TIMESTAMPTZ NOT NULL
 );
 
@@ -323,6 +344,7 @@ CREATE TABLE core_credit_facilities (
   collateralization_ratio NUMERIC,
   collateralization_state VARCHAR NOT NULL,
   status VARCHAR NOT NULL,
+  margin_call_cvl NUMERIC,
   public_id VARCHAR NOT NULL REFERENCES core_public_ids(id),
   created_at TIMESTAMPTZ NOT NULL
 );
@@ -362,6 +384,15 @@ CREATE TABLE core_custodian_webhook_notifications (
   recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
+CREATE TABLE core_wallet_reconciliations (
+  wallet_id UUID NOT NULL REFERENCES core_wallets(id),
+  custodian_id UUID NOT NULL REFERENCES core_custodians(id),
+  recorded_balance BIGINT NOT NULL,
+  custodian_balance BIGINT NOT NULL,
+  reconciled_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_core_wallet_reconciliations_wallet_id ON core_wallet_reconciliations(wallet_id);
+
 CREATE TABLE core_obligations (
   id UUID PRIMARY KEY,
   credit_facility_id UUID NOT NULL REFERENCES core_credit_facilities(id),
@@ -396,6 +427,42 @@ CREATE TABLE core_liquidation_process_events (
   UNIQUE(id, sequence)
 );
 
+CREATE TABLE core_margin_calls (
+  id UUID PRIMARY KEY,
+  credit_facility_id UUID NOT NULL REFERENCES core_credit_facilities(id),
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_margin_call_events (
+  id UUID NOT NULL REFERENCES core_margin_calls(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  context JSONB DEFAULT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_collateral_returns (
+  id UUID PRIMARY KEY,
+  credit_facility_id UUID NOT NULL REFERENCES core_credit_facilities(id),
+  approval_process_id UUID NOT NULL REFERENCES core_approval_processes(id),
+  external_transfer_id VARCHAR UNIQUE,
+  allows_new_return BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMPTZ NOT NULL
+);
+CREATE UNIQUE INDEX idx_core_collateral_returns_in_progress ON core_collateral_returns (credit_facility_id) WHERE NOT allows_new_return;
+
+CREATE TABLE core_collateral_return_events (
+  id UUID NOT NULL REFERENCES core_collateral_returns(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  context JSONB DEFAULT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
 CREATE TABLE core_disbursals (
   id UUID PRIMARY KEY,
   credit_facility_id UUID NOT NULL REFERENCES core_credit_facilities(id),
@@ -572,6 +639,22 @@ CREATE TABLE audit_entries (
   object VARCHAR NOT NULL,
   action VARCHAR NOT NULL,
   authorized BOOLEAN NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  chain_position BIGINT UNIQUE,
+  prev_hash BYTEA,
+  hash BYTEA
+);
+CREATE INDEX idx_audit_entries_subject ON audit_entries(subject, id);
+CREATE INDEX idx_audit_entries_object ON audit_entries(object text_pattern_ops);
+CREATE INDEX idx_audit_entries_action ON audit_entries(action, id);
+CREATE INDEX idx_audit_entries_recorded_at ON audit_entries(recorded_at);
+CREATE INDEX idx_audit_entries_unauthorized ON audit_entries(id) WHERE NOT authorized;
+CREATE INDEX idx_audit_entries_unchained ON audit_entries(id) WHERE chain_position IS NULL;
+
+CREATE TABLE audit_chain_heads (
+  chain_position BIGINT PRIMARY KEY,
+  entry_id BIGINT NOT NULL,
+  hash BYTEA NOT NULL,
   recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
@@ -589,6 +672,15 @@ CREATE TABLE core_credit_facility_repayment_plans (
   modified_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
+CREATE TABLE core_price_observations (
+  id UUID PRIMARY KEY,
+  source VARCHAR NOT NULL,
+  usd_cents_per_btc BIGINT NOT NULL,
+  observed_at TIMESTAMPTZ NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX idx_core_price_observations_source_observed_at ON core_price_observations(source, observed_at);
+
 CREATE TABLE dashboards (
   id UUID PRIMARY KEY,
   dashboard_json JSONB NOT NULL,
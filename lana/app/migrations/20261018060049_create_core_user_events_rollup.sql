@@ -29,7 +29,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'role_updated') THEN
+  IF event_type NOT IN ('initialized', 'role_updated', 'deactivated', 'reactivated') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -56,6 +56,8 @@ BEGIN
       new_row.role_id := (NEW.event ->> 'role_id')::UUID;
     WHEN 'role_updated' THEN
       new_row.role_id := (NEW.event ->> 'role_id')::UUID;
+    WHEN 'deactivated' THEN
+    WHEN 'reactivated' THEN
   END CASE;
 
   INSERT INTO core_user_events_rollup (
@@ -5,6 +5,8 @@ CREATE TABLE core_committee_events_rollup (
   created_at TIMESTAMPTZ NOT NULL,
   modified_at TIMESTAMPTZ NOT NULL,
   -- Flattened fields from the event JSON
+  delegation JSONB,
+  delegator_id UUID,
   name VARCHAR,
 
   -- Collection rollups
@@ -31,7 +33,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'member_added', 'member_removed') THEN
+  IF event_type NOT IN ('initialized', 'member_added', 'member_removed', 'votes_delegated', 'vote_delegation_revoked') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -43,6 +45,8 @@ BEGIN
 
   -- Initialize fields with default values if this is a new record
   IF current_row.id IS NULL THEN
+    new_row.delegation := (NEW.event -> 'delegation');
+    new_row.delegator_id := (NEW.event ->> 'delegator_id')::UUID;
     new_row.member_ids := CASE
        WHEN NEW.event ? 'member_ids' THEN
          ARRAY(SELECT value::text::UUID FROM jsonb_array_elements_text(NEW.event -> 'member_ids'))
@@ -52,6 +56,8 @@ BEGIN
     new_row.name := (NEW.event ->> 'name');
   ELSE
     -- Default all fields to current values
+    new_row.delegation := current_row.delegation;
+    new_row.delegator_id := current_row.delegator_id;
     new_row.member_ids := current_row.member_ids;
     new_row.name := current_row.name;
   END IF;
@@ -64,6 +70,10 @@ BEGIN
       new_row.member_ids := array_append(COALESCE(current_row.member_ids, ARRAY[]::UUID[]), (NEW.event ->> 'member_id')::UUID);
     WHEN 'member_removed' THEN
       new_row.member_ids := array_remove(COALESCE(current_row.member_ids, ARRAY[]::UUID[]), (NEW.event ->> 'member_id')::UUID);
+    WHEN 'votes_delegated' THEN
+      new_row.delegation := (NEW.event -> 'delegation');
+    WHEN 'vote_delegation_revoked' THEN
+      new_row.delegator_id := (NEW.event ->> 'delegator_id')::UUID;
   END CASE;
 
   INSERT INTO core_committee_events_rollup (
@@ -71,6 +81,8 @@ BEGIN
     version,
     created_at,
     modified_at,
+    delegation,
+    delegator_id,
     member_ids,
     name
   )
@@ -79,6 +91,8 @@ BEGIN
     new_row.version,
     new_row.created_at,
     new_row.modified_at,
+    new_row.delegation,
+    new_row.delegator_id,
     new_row.member_ids,
     new_row.name
   );
@@ -5,6 +5,8 @@ CREATE TABLE core_policy_events_rollup (
   created_at TIMESTAMPTZ NOT NULL,
   modified_at TIMESTAMPTZ NOT NULL,
   -- Flattened fields from the event JSON
+  bands JSONB,
+  expiry JSONB,
   process_type VARCHAR,
   rules JSONB
 ,
@@ -29,7 +31,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'approval_rules_updated') THEN
+  IF event_type NOT IN ('initialized', 'approval_rules_updated', 'approval_bands_updated', 'expiry_updated') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -41,10 +43,14 @@ BEGIN
 
   -- Initialize fields with default values if this is a new record
   IF current_row.id IS NULL THEN
+    new_row.bands := (NEW.event -> 'bands');
+    new_row.expiry := (NEW.event -> 'expiry');
     new_row.process_type := (NEW.event ->> 'process_type');
     new_row.rules := (NEW.event -> 'rules');
   ELSE
     -- Default all fields to current values
+    new_row.bands := current_row.bands;
+    new_row.expiry := current_row.expiry;
     new_row.process_type := current_row.process_type;
     new_row.rules := current_row.rules;
   END IF;
@@ -56,6 +62,10 @@ BEGIN
       new_row.rules := (NEW.event -> 'rules');
     WHEN 'approval_rules_updated' THEN
       new_row.rules := (NEW.event -> 'rules');
+    WHEN 'approval_bands_updated' THEN
+      new_row.bands := (NEW.event -> 'bands');
+    WHEN 'expiry_updated' THEN
+      new_row.expiry := (NEW.event -> 'expiry');
   END CASE;
 
   INSERT INTO core_policy_events_rollup (
@@ -63,6 +73,8 @@ BEGIN
     version,
     created_at,
     modified_at,
+    bands,
+    expiry,
     process_type,
     rules
   )
@@ -71,6 +83,8 @@ BEGIN
     new_row.version,
     new_row.created_at,
     new_row.modified_at,
+    new_row.bands,
+    new_row.expiry,
     new_row.process_type,
     new_row.rules
   );
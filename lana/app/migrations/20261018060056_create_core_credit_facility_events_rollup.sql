@@ -8,6 +8,7 @@ CREATE TABLE core_credit_facility_events_rollup (
   account_ids JSONB,
   activated_at TIMESTAMPTZ,
   amount BIGINT,
+  approval_process_id UUID,
   collateral BIGINT,
   collateral_id UUID,
   collateralization_ratio JSONB,
@@ -15,12 +16,20 @@ CREATE TABLE core_credit_facility_events_rollup (
   customer_id UUID,
   customer_type VARCHAR,
   disbursal_credit_account_id UUID,
+  disbursal_id UUID,
+  disbursal_policy JSONB,
+  drawdown_idx INTEGER,
   interest_accrual_cycle_idx INTEGER,
   interest_period JSONB,
   maturity_date VARCHAR,
+  new_maturity_date VARCHAR,
+  new_terms JSONB,
+  old_maturity_date VARCHAR,
+  old_terms JSONB,
   outstanding JSONB,
   pending_credit_facility_id UUID,
   price JSONB,
+  price_observation_id UUID,
   public_id VARCHAR,
   terms JSONB,
 
@@ -54,7 +63,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'interest_accrual_cycle_started', 'interest_accrual_cycle_concluded', 'collateralization_state_changed', 'collateralization_ratio_changed', 'matured', 'completed', 'activated') THEN
+  IF event_type NOT IN ('initialized', 'interest_accrual_cycle_started', 'interest_accrual_cycle_concluded', 'collateralization_state_changed', 'collateralization_ratio_changed', 'terms_amendment_initiated', 'terms_amendment_denied', 'terms_amended', 'scheduled_disbursal_executed', 'ledger_accounts_backfilled', 'matured', 'completed', 'activated') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -69,6 +78,7 @@ BEGIN
     new_row.account_ids := (NEW.event -> 'account_ids');
     new_row.activated_at := (NEW.event ->> 'activated_at')::TIMESTAMPTZ;
     new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+    new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
     new_row.collateral := (NEW.event ->> 'collateral')::BIGINT;
     new_row.collateral_id := (NEW.event ->> 'collateral_id')::UUID;
     new_row.collateralization_ratio := (NEW.event -> 'collateralization_ratio');
@@ -76,6 +86,9 @@ BEGIN
     new_row.customer_id := (NEW.event ->> 'customer_id')::UUID;
     new_row.customer_type := (NEW.event ->> 'customer_type');
     new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
+    new_row.disbursal_id := (NEW.event ->> 'disbursal_id')::UUID;
+    new_row.disbursal_policy := (NEW.event -> 'disbursal_policy');
+    new_row.drawdown_idx := (NEW.event ->> 'drawdown_idx')::INTEGER;
     new_row.interest_accrual_cycle_idx := (NEW.event ->> 'interest_accrual_cycle_idx')::INTEGER;
     new_row.interest_accrual_ids := CASE
        WHEN NEW.event ? 'interest_accrual_ids' THEN
@@ -93,15 +106,20 @@ BEGIN
      END
 ;
     new_row.maturity_date := (NEW.event ->> 'maturity_date');
+    new_row.new_maturity_date := (NEW.event ->> 'new_maturity_date');
+    new_row.new_terms := (NEW.event -> 'new_terms');
     new_row.obligation_ids := CASE
        WHEN NEW.event ? 'obligation_ids' THEN
          ARRAY(SELECT value::text::UUID FROM jsonb_array_elements_text(NEW.event -> 'obligation_ids'))
        ELSE ARRAY[]::UUID[]
      END
 ;
+    new_row.old_maturity_date := (NEW.event ->> 'old_maturity_date');
+    new_row.old_terms := (NEW.event -> 'old_terms');
     new_row.outstanding := (NEW.event -> 'outstanding');
     new_row.pending_credit_facility_id := (NEW.event ->> 'pending_credit_facility_id')::UUID;
     new_row.price := (NEW.event -> 'price');
+    new_row.price_observation_id := (NEW.event ->> 'price_observation_id')::UUID;
     new_row.public_id := (NEW.event ->> 'public_id');
     new_row.terms := (NEW.event -> 'terms');
   ELSE
@@ -109,6 +127,7 @@ BEGIN
     new_row.account_ids := current_row.account_ids;
     new_row.activated_at := current_row.activated_at;
     new_row.amount := current_row.amount;
+    new_row.approval_process_id := current_row.approval_process_id;
     new_row.collateral := current_row.collateral;
     new_row.collateral_id := current_row.collateral_id;
     new_row.collateralization_ratio := current_row.collateralization_ratio;
@@ -116,6 +135,9 @@ BEGIN
     new_row.customer_id := current_row.customer_id;
     new_row.customer_type := current_row.customer_type;
     new_row.disbursal_credit_account_id := current_row.disbursal_credit_account_id;
+    new_row.disbursal_id := current_row.disbursal_id;
+    new_row.disbursal_policy := current_row.disbursal_policy;
+    new_row.drawdown_idx := current_row.drawdown_idx;
     new_row.interest_accrual_cycle_idx := current_row.interest_accrual_cycle_idx;
     new_row.interest_accrual_ids := current_row.interest_accrual_ids;
     new_row.interest_period := current_row.interest_period;
@@ -123,10 +145,15 @@ BEGIN
     new_row.is_matured := current_row.is_matured;
     new_row.ledger_tx_ids := current_row.ledger_tx_ids;
     new_row.maturity_date := current_row.maturity_date;
+    new_row.new_maturity_date := current_row.new_maturity_date;
+    new_row.new_terms := current_row.new_terms;
     new_row.obligation_ids := current_row.obligation_ids;
+    new_row.old_maturity_date := current_row.old_maturity_date;
+    new_row.old_terms := current_row.old_terms;
     new_row.outstanding := current_row.outstanding;
     new_row.pending_credit_facility_id := current_row.pending_credit_facility_id;
     new_row.price := current_row.price;
+    new_row.price_observation_id := current_row.price_observation_id;
     new_row.public_id := current_row.public_id;
     new_row.terms := current_row.terms;
   END IF;
@@ -141,6 +168,7 @@ BEGIN
       new_row.customer_id := (NEW.event ->> 'customer_id')::UUID;
       new_row.customer_type := (NEW.event ->> 'customer_type');
       new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
+      new_row.disbursal_policy := (NEW.event -> 'disbursal_policy');
       new_row.ledger_tx_ids := array_append(COALESCE(current_row.ledger_tx_ids, ARRAY[]::UUID[]), (NEW.event ->> 'ledger_tx_id')::UUID);
       new_row.maturity_date := (NEW.event ->> 'maturity_date');
       new_row.pending_credit_facility_id := (NEW.event ->> 'pending_credit_facility_id')::UUID;
@@ -159,8 +187,27 @@ BEGIN
       new_row.collateralization_state := (NEW.event ->> 'collateralization_state');
       new_row.outstanding := (NEW.event -> 'outstanding');
       new_row.price := (NEW.event -> 'price');
+      new_row.price_observation_id := (NEW.event ->> 'price_observation_id')::UUID;
     WHEN 'collateralization_ratio_changed' THEN
       new_row.collateralization_ratio := (NEW.event -> 'collateralization_ratio');
+      new_row.price_observation_id := (NEW.event ->> 'price_observation_id')::UUID;
+    WHEN 'terms_amendment_initiated' THEN
+      new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+      new_row.terms := (NEW.event -> 'terms');
+    WHEN 'terms_amendment_denied' THEN
+      new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+    WHEN 'terms_amended' THEN
+      new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+      new_row.new_maturity_date := (NEW.event ->> 'new_maturity_date');
+      new_row.new_terms := (NEW.event -> 'new_terms');
+      new_row.old_maturity_date := (NEW.event ->> 'old_maturity_date');
+      new_row.old_terms := (NEW.event -> 'old_terms');
+    WHEN 'scheduled_disbursal_executed' THEN
+      new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+      new_row.disbursal_id := (NEW.event ->> 'disbursal_id')::UUID;
+      new_row.drawdown_idx := (NEW.event ->> 'drawdown_idx')::INTEGER;
+    WHEN 'ledger_accounts_backfilled' THEN
+      new_row.account_ids := (NEW.event -> 'account_ids');
     WHEN 'matured' THEN
       new_row.is_matured := true;
     WHEN 'completed' THEN
@@ -177,6 +224,7 @@ BEGIN
     account_ids,
     activated_at,
     amount,
+    approval_process_id,
     collateral,
     collateral_id,
     collateralization_ratio,
@@ -184,6 +232,9 @@ BEGIN
     customer_id,
     customer_type,
     disbursal_credit_account_id,
+    disbursal_id,
+    disbursal_policy,
+    drawdown_idx,
     interest_accrual_cycle_idx,
     interest_accrual_ids,
     interest_period,
@@ -191,10 +242,15 @@ BEGIN
     is_matured,
     ledger_tx_ids,
     maturity_date,
+    new_maturity_date,
+    new_terms,
     obligation_ids,
+    old_maturity_date,
+    old_terms,
     outstanding,
     pending_credit_facility_id,
     price,
+    price_observation_id,
     public_id,
     terms
   )
@@ -206,6 +262,7 @@ BEGIN
     new_row.account_ids,
     new_row.activated_at,
     new_row.amount,
+    new_row.approval_process_id,
     new_row.collateral,
     new_row.collateral_id,
     new_row.collateralization_ratio,
@@ -213,6 +270,9 @@ BEGIN
     new_row.customer_id,
     new_row.customer_type,
     new_row.disbursal_credit_account_id,
+    new_row.disbursal_id,
+    new_row.disbursal_policy,
+    new_row.drawdown_idx,
     new_row.interest_accrual_cycle_idx,
     new_row.interest_accrual_ids,
     new_row.interest_period,
@@ -220,10 +280,15 @@ BEGIN
     new_row.is_matured,
     new_row.ledger_tx_ids,
     new_row.maturity_date,
+    new_row.new_maturity_date,
+    new_row.new_terms,
     new_row.obligation_ids,
+    new_row.old_maturity_date,
+    new_row.old_terms,
     new_row.outstanding,
     new_row.pending_credit_facility_id,
     new_row.price,
+    new_row.price_observation_id,
     new_row.public_id,
     new_row.terms
   );
@@ -11,6 +11,7 @@ CREATE TABLE core_credit_facility_proposal_events_rollup (
   customer_id UUID,
   customer_type VARCHAR,
   disbursal_credit_account_id UUID,
+  disbursal_policy JSONB,
   status VARCHAR,
   terms JSONB,
 
@@ -56,6 +57,7 @@ BEGIN
     new_row.customer_id := (NEW.event ->> 'customer_id')::UUID;
     new_row.customer_type := (NEW.event ->> 'customer_type');
     new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
+    new_row.disbursal_policy := (NEW.event -> 'disbursal_policy');
     new_row.is_approval_process_concluded := false;
     new_row.status := (NEW.event ->> 'status');
     new_row.terms := (NEW.event -> 'terms');
@@ -67,6 +69,7 @@ BEGIN
     new_row.customer_id := current_row.customer_id;
     new_row.customer_type := current_row.customer_type;
     new_row.disbursal_credit_account_id := current_row.disbursal_credit_account_id;
+    new_row.disbursal_policy := current_row.disbursal_policy;
     new_row.is_approval_process_concluded := current_row.is_approval_process_concluded;
     new_row.status := current_row.status;
     new_row.terms := current_row.terms;
@@ -81,6 +84,7 @@ BEGIN
       new_row.customer_id := (NEW.event ->> 'customer_id')::UUID;
       new_row.customer_type := (NEW.event ->> 'customer_type');
       new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
+      new_row.disbursal_policy := (NEW.event -> 'disbursal_policy');
       new_row.status := (NEW.event ->> 'status');
       new_row.terms := (NEW.event -> 'terms');
     WHEN 'approval_process_concluded' THEN
@@ -100,6 +104,7 @@ BEGIN
     customer_id,
     customer_type,
     disbursal_credit_account_id,
+    disbursal_policy,
     is_approval_process_concluded,
     status,
     terms
@@ -115,6 +120,7 @@ BEGIN
     new_row.customer_id,
     new_row.customer_type,
     new_row.disbursal_credit_account_id,
+    new_row.disbursal_policy,
     new_row.is_approval_process_concluded,
     new_row.status,
     new_row.terms
@@ -16,7 +16,9 @@ CREATE TABLE core_pending_credit_facility_events_rollup (
   customer_id UUID,
   customer_type VARCHAR,
   disbursal_credit_account_id UUID,
+  disbursal_policy JSONB,
   price JSONB,
+  price_observation_id UUID,
   terms JSONB,
 
   -- Collection rollups
@@ -71,6 +73,7 @@ BEGIN
     new_row.customer_id := (NEW.event ->> 'customer_id')::UUID;
     new_row.customer_type := (NEW.event ->> 'customer_type');
     new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
+    new_row.disbursal_policy := (NEW.event -> 'disbursal_policy');
     new_row.is_collateralization_ratio_changed := false;
     new_row.is_collateralization_state_changed := false;
     new_row.is_completed := false;
@@ -81,6 +84,7 @@ BEGIN
      END
 ;
     new_row.price := (NEW.event -> 'price');
+    new_row.price_observation_id := (NEW.event ->> 'price_observation_id')::UUID;
     new_row.terms := (NEW.event -> 'terms');
   ELSE
     -- Default all fields to current values
@@ -95,11 +99,13 @@ BEGIN
     new_row.customer_id := current_row.customer_id;
     new_row.customer_type := current_row.customer_type;
     new_row.disbursal_credit_account_id := current_row.disbursal_credit_account_id;
+    new_row.disbursal_policy := current_row.disbursal_policy;
     new_row.is_collateralization_ratio_changed := current_row.is_collateralization_ratio_changed;
     new_row.is_collateralization_state_changed := current_row.is_collateralization_state_changed;
     new_row.is_completed := current_row.is_completed;
     new_row.ledger_tx_ids := current_row.ledger_tx_ids;
     new_row.price := current_row.price;
+    new_row.price_observation_id := current_row.price_observation_id;
     new_row.terms := current_row.terms;
   END IF;
 
@@ -114,6 +120,7 @@ BEGIN
       new_row.customer_id := (NEW.event ->> 'customer_id')::UUID;
       new_row.customer_type := (NEW.event ->> 'customer_type');
       new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
+      new_row.disbursal_policy := (NEW.event -> 'disbursal_policy');
       new_row.ledger_tx_ids := array_append(COALESCE(current_row.ledger_tx_ids, ARRAY[]::UUID[]), (NEW.event ->> 'ledger_tx_id')::UUID);
       new_row.terms := (NEW.event -> 'terms');
     WHEN 'collateralization_state_changed' THEN
@@ -121,9 +128,11 @@ BEGIN
       new_row.collateralization_state := (NEW.event ->> 'collateralization_state');
       new_row.is_collateralization_state_changed := true;
       new_row.price := (NEW.event -> 'price');
+      new_row.price_observation_id := (NEW.event ->> 'price_observation_id')::UUID;
     WHEN 'collateralization_ratio_changed' THEN
       new_row.collateralization_ratio := (NEW.event -> 'collateralization_ratio');
       new_row.is_collateralization_ratio_changed := true;
+      new_row.price_observation_id := (NEW.event ->> 'price_observation_id')::UUID;
     WHEN 'completed' THEN
       new_row.is_completed := true;
   END CASE;
@@ -144,11 +153,13 @@ BEGIN
     customer_id,
     customer_type,
     disbursal_credit_account_id,
+    disbursal_policy,
     is_collateralization_ratio_changed,
     is_collateralization_state_changed,
     is_completed,
     ledger_tx_ids,
     price,
+    price_observation_id,
     terms
   )
   VALUES (
@@ -167,11 +178,13 @@ BEGIN
     new_row.customer_id,
     new_row.customer_type,
     new_row.disbursal_credit_account_id,
+    new_row.disbursal_policy,
     new_row.is_collateralization_ratio_changed,
     new_row.is_collateralization_state_changed,
     new_row.is_completed,
     new_row.ledger_tx_ids,
     new_row.price,
+    new_row.price_observation_id,
     new_row.terms
   );
 
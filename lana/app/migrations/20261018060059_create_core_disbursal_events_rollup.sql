@@ -9,6 +9,7 @@ CREATE TABLE core_disbursal_events_rollup (
   amount BIGINT,
   approval_process_id UUID,
   approved BOOLEAN,
+  day_count_convention VARCHAR,
   disbursal_credit_account_id UUID,
   due_date VARCHAR,
   effective VARCHAR,
@@ -16,6 +17,7 @@ CREATE TABLE core_disbursal_events_rollup (
   liquidation_date JSONB,
   obligation_id UUID,
   overdue_date JSONB,
+  penalty_annual_rate VARCHAR,
   public_id VARCHAR,
 
   -- Collection rollups
@@ -63,6 +65,7 @@ BEGIN
     new_row.amount := (NEW.event ->> 'amount')::BIGINT;
     new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
     new_row.approved := (NEW.event ->> 'approved')::BOOLEAN;
+    new_row.day_count_convention := (NEW.event ->> 'day_count_convention');
     new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
     new_row.due_date := (NEW.event ->> 'due_date');
     new_row.effective := (NEW.event ->> 'effective');
@@ -79,6 +82,7 @@ BEGIN
     new_row.liquidation_date := (NEW.event -> 'liquidation_date');
     new_row.obligation_id := (NEW.event ->> 'obligation_id')::UUID;
     new_row.overdue_date := (NEW.event -> 'overdue_date');
+    new_row.penalty_annual_rate := (NEW.event ->> 'penalty_annual_rate');
     new_row.public_id := (NEW.event ->> 'public_id');
   ELSE
     -- Default all fields to current values
@@ -86,6 +90,7 @@ BEGIN
     new_row.amount := current_row.amount;
     new_row.approval_process_id := current_row.approval_process_id;
     new_row.approved := current_row.approved;
+    new_row.day_count_convention := current_row.day_count_convention;
     new_row.disbursal_credit_account_id := current_row.disbursal_credit_account_id;
     new_row.due_date := current_row.due_date;
     new_row.effective := current_row.effective;
@@ -97,6 +102,7 @@ BEGIN
     new_row.liquidation_date := current_row.liquidation_date;
     new_row.obligation_id := current_row.obligation_id;
     new_row.overdue_date := current_row.overdue_date;
+    new_row.penalty_annual_rate := current_row.penalty_annual_rate;
     new_row.public_id := current_row.public_id;
   END IF;
 
@@ -106,12 +112,14 @@ BEGIN
       new_row.account_ids := (NEW.event -> 'account_ids');
       new_row.amount := (NEW.event ->> 'amount')::BIGINT;
       new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+      new_row.day_count_convention := (NEW.event ->> 'day_count_convention');
       new_row.disbursal_credit_account_id := (NEW.event ->> 'disbursal_credit_account_id')::UUID;
       new_row.due_date := (NEW.event ->> 'due_date');
       new_row.facility_id := (NEW.event ->> 'facility_id')::UUID;
       new_row.ledger_tx_ids := array_append(COALESCE(current_row.ledger_tx_ids, ARRAY[]::UUID[]), (NEW.event ->> 'ledger_tx_id')::UUID);
       new_row.liquidation_date := (NEW.event -> 'liquidation_date');
       new_row.overdue_date := (NEW.event -> 'overdue_date');
+      new_row.penalty_annual_rate := (NEW.event ->> 'penalty_annual_rate');
       new_row.public_id := (NEW.event ->> 'public_id');
     WHEN 'approval_process_concluded' THEN
       new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
@@ -137,6 +145,7 @@ BEGIN
     amount,
     approval_process_id,
     approved,
+    day_count_convention,
     disbursal_credit_account_id,
     due_date,
     effective,
@@ -148,6 +157,7 @@ BEGIN
     liquidation_date,
     obligation_id,
     overdue_date,
+    penalty_annual_rate,
     public_id
   )
   VALUES (
@@ -159,6 +169,7 @@ BEGIN
     new_row.amount,
     new_row.approval_process_id,
     new_row.approved,
+    new_row.day_count_convention,
     new_row.disbursal_credit_account_id,
     new_row.due_date,
     new_row.effective,
@@ -170,6 +181,7 @@ BEGIN
     new_row.liquidation_date,
     new_row.obligation_id,
     new_row.overdue_date,
+    new_row.penalty_annual_rate,
     new_row.public_id
   );
 
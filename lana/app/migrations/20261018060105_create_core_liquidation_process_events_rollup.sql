@@ -5,12 +5,19 @@ CREATE TABLE core_liquidation_process_events_rollup (
   created_at TIMESTAMPTZ NOT NULL,
   modified_at TIMESTAMPTZ NOT NULL,
   -- Flattened fields from the event JSON
+  amount_applied BIGINT,
+  collateral_sold BIGINT,
   credit_facility_id UUID,
   effective VARCHAR,
+  execution_price JSONB,
   in_liquidation_account_id UUID,
   initial_amount BIGINT,
   ledger_tx_id UUID,
   obligation_id UUID,
+  payment_id UUID,
+  proceeds BIGINT,
+  shortfall BIGINT,
+  surplus BIGINT,
 
   -- Toggle fields
   is_completed BOOLEAN DEFAULT false
@@ -36,7 +43,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'completed') THEN
+  IF event_type NOT IN ('initialized', 'collateral_sold', 'completed') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -48,22 +55,36 @@ BEGIN
 
   -- Initialize fields with default values if this is a new record
   IF current_row.id IS NULL THEN
+    new_row.amount_applied := (NEW.event ->> 'amount_applied')::BIGINT;
+    new_row.collateral_sold := (NEW.event ->> 'collateral_sold')::BIGINT;
     new_row.credit_facility_id := (NEW.event ->> 'credit_facility_id')::UUID;
     new_row.effective := (NEW.event ->> 'effective');
+    new_row.execution_price := (NEW.event -> 'execution_price');
     new_row.in_liquidation_account_id := (NEW.event ->> 'in_liquidation_account_id')::UUID;
     new_row.initial_amount := (NEW.event ->> 'initial_amount')::BIGINT;
     new_row.is_completed := false;
     new_row.ledger_tx_id := (NEW.event ->> 'ledger_tx_id')::UUID;
     new_row.obligation_id := (NEW.event ->> 'obligation_id')::UUID;
+    new_row.payment_id := (NEW.event ->> 'payment_id')::UUID;
+    new_row.proceeds := (NEW.event ->> 'proceeds')::BIGINT;
+    new_row.shortfall := (NEW.event ->> 'shortfall')::BIGINT;
+    new_row.surplus := (NEW.event ->> 'surplus')::BIGINT;
   ELSE
     -- Default all fields to current values
+    new_row.amount_applied := current_row.amount_applied;
+    new_row.collateral_sold := current_row.collateral_sold;
     new_row.credit_facility_id := current_row.credit_facility_id;
     new_row.effective := current_row.effective;
+    new_row.execution_price := current_row.execution_price;
     new_row.in_liquidation_account_id := current_row.in_liquidation_account_id;
     new_row.initial_amount := current_row.initial_amount;
     new_row.is_completed := current_row.is_completed;
     new_row.ledger_tx_id := current_row.ledger_tx_id;
     new_row.obligation_id := current_row.obligation_id;
+    new_row.payment_id := current_row.payment_id;
+    new_row.proceeds := current_row.proceeds;
+    new_row.shortfall := current_row.shortfall;
+    new_row.surplus := current_row.surplus;
   END IF;
 
   -- Update only the fields that are modified by the specific event
@@ -75,8 +96,19 @@ BEGIN
       new_row.initial_amount := (NEW.event ->> 'initial_amount')::BIGINT;
       new_row.ledger_tx_id := (NEW.event ->> 'ledger_tx_id')::UUID;
       new_row.obligation_id := (NEW.event ->> 'obligation_id')::UUID;
+    WHEN 'collateral_sold' THEN
+      new_row.collateral_sold := (NEW.event ->> 'collateral_sold')::BIGINT;
+      new_row.effective := (NEW.event ->> 'effective');
+      new_row.execution_price := (NEW.event -> 'execution_price');
+      new_row.ledger_tx_id := (NEW.event ->> 'ledger_tx_id')::UUID;
+      new_row.proceeds := (NEW.event ->> 'proceeds')::BIGINT;
     WHEN 'completed' THEN
+      new_row.amount_applied := (NEW.event ->> 'amount_applied')::BIGINT;
+      new_row.effective := (NEW.event ->> 'effective');
       new_row.is_completed := true;
+      new_row.payment_id := (NEW.event ->> 'payment_id')::UUID;
+      new_row.shortfall := (NEW.event ->> 'shortfall')::BIGINT;
+      new_row.surplus := (NEW.event ->> 'surplus')::BIGINT;
   END CASE;
 
   INSERT INTO core_liquidation_process_events_rollup (
@@ -84,26 +116,40 @@ BEGIN
     version,
     created_at,
     modified_at,
+    amount_applied,
+    collateral_sold,
     credit_facility_id,
     effective,
+    execution_price,
     in_liquidation_account_id,
     initial_amount,
     is_completed,
     ledger_tx_id,
-    obligation_id
+    obligation_id,
+    payment_id,
+    proceeds,
+    shortfall,
+    surplus
   )
   VALUES (
     new_row.id,
     new_row.version,
     new_row.created_at,
     new_row.modified_at,
+    new_row.amount_applied,
+    new_row.collateral_sold,
     new_row.credit_facility_id,
     new_row.effective,
+    new_row.execution_price,
     new_row.in_liquidation_account_id,
     new_row.initial_amount,
     new_row.is_completed,
     new_row.ledger_tx_id,
-    new_row.obligation_id
+    new_row.obligation_id,
+    new_row.payment_id,
+    new_row.proceeds,
+    new_row.shortfall,
+    new_row.surplus
   );
 
   RETURN NEW;
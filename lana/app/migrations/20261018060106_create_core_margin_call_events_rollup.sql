@@ -0,0 +1,144 @@
+-- Auto-generated rollup table for MarginCallEvent
+CREATE TABLE core_margin_call_events_rollup (
+  id UUID NOT NULL,
+  version INT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  modified_at TIMESTAMPTZ NOT NULL,
+  -- Flattened fields from the event JSON
+  collateral_required BIGINT,
+  collateralization_state VARCHAR,
+  credit_facility_id UUID,
+  cure_deadline TIMESTAMPTZ,
+  effective VARCHAR,
+  obligation_ids JSONB,
+  price JSONB,
+  repayment_required BIGINT,
+  target_cvl JSONB,
+
+  -- Toggle fields
+  is_cured BOOLEAN DEFAULT false,
+  is_escalated BOOLEAN DEFAULT false
+,
+  PRIMARY KEY (id, version)
+);
+
+-- Auto-generated trigger function for MarginCallEvent
+CREATE OR REPLACE FUNCTION core_margin_call_events_rollup_trigger()
+RETURNS TRIGGER AS $$
+DECLARE
+  event_type TEXT;
+  current_row core_margin_call_events_rollup%ROWTYPE;
+  new_row core_margin_call_events_rollup%ROWTYPE;
+BEGIN
+  event_type := NEW.event_type;
+
+  -- Load the previous version if this isn't the first event
+  IF NEW.sequence > 1 THEN
+    SELECT * INTO current_row
+    FROM core_margin_call_events_rollup
+    WHERE id = NEW.id AND version = NEW.sequence - 1;
+  END IF;
+
+  -- Validate event type is known
+  IF event_type NOT IN ('initialized', 'cured', 'escalated') THEN
+    RAISE EXCEPTION 'Unknown event type: %', event_type;
+  END IF;
+
+  -- Construct the new row based on event type
+  new_row.id := NEW.id;
+  new_row.version := NEW.sequence;
+  new_row.created_at := COALESCE(current_row.created_at, NEW.recorded_at);
+  new_row.modified_at := NEW.recorded_at;
+
+  -- Initialize fields with default values if this is a new record
+  IF current_row.id IS NULL THEN
+    new_row.collateral_required := (NEW.event ->> 'collateral_required')::BIGINT;
+    new_row.collateralization_state := (NEW.event ->> 'collateralization_state');
+    new_row.credit_facility_id := (NEW.event ->> 'credit_facility_id')::UUID;
+    new_row.cure_deadline := (NEW.event ->> 'cure_deadline')::TIMESTAMPTZ;
+    new_row.effective := (NEW.event ->> 'effective');
+    new_row.is_cured := false;
+    new_row.is_escalated := false;
+    new_row.obligation_ids := (NEW.event -> 'obligation_ids');
+    new_row.price := (NEW.event -> 'price');
+    new_row.repayment_required := (NEW.event ->> 'repayment_required')::BIGINT;
+    new_row.target_cvl := (NEW.event -> 'target_cvl');
+  ELSE
+    -- Default all fields to current values
+    new_row.collateral_required := current_row.collateral_required;
+    new_row.collateralization_state := current_row.collateralization_state;
+    new_row.credit_facility_id := current_row.credit_facility_id;
+    new_row.cure_deadline := current_row.cure_deadline;
+    new_row.effective := current_row.effective;
+    new_row.is_cured := current_row.is_cured;
+    new_row.is_escalated := current_row.is_escalated;
+    new_row.obligation_ids := current_row.obligation_ids;
+    new_row.price := current_row.price;
+    new_row.repayment_required := current_row.repayment_required;
+    new_row.target_cvl := current_row.target_cvl;
+  END IF;
+
+  -- Update only the fields that are modified by the specific event
+  CASE event_type
+    WHEN 'initialized' THEN
+      new_row.collateral_required := (NEW.event ->> 'collateral_required')::BIGINT;
+      new_row.collateralization_state := (NEW.event ->> 'collateralization_state');
+      new_row.credit_facility_id := (NEW.event ->> 'credit_facility_id')::UUID;
+      new_row.cure_deadline := (NEW.event ->> 'cure_deadline')::TIMESTAMPTZ;
+      new_row.effective := (NEW.event ->> 'effective');
+      new_row.price := (NEW.event -> 'price');
+      new_row.repayment_required := (NEW.event ->> 'repayment_required')::BIGINT;
+      new_row.target_cvl := (NEW.event -> 'target_cvl');
+    WHEN 'cured' THEN
+      new_row.effective := (NEW.event ->> 'effective');
+      new_row.is_cured := true;
+    WHEN 'escalated' THEN
+      new_row.effective := (NEW.event ->> 'effective');
+      new_row.is_escalated := true;
+      new_row.obligation_ids := (NEW.event -> 'obligation_ids');
+  END CASE;
+
+  INSERT INTO core_margin_call_events_rollup (
+    id,
+    version,
+    created_at,
+    modified_at,
+    collateral_required,
+    collateralization_state,
+    credit_facility_id,
+    cure_deadline,
+    effective,
+    is_cured,
+    is_escalated,
+    obligation_ids,
+    price,
+    repayment_required,
+    target_cvl
+  )
+  VALUES (
+    new_row.id,
+    new_row.version,
+    new_row.created_at,
+    new_row.modified_at,
+    new_row.collateral_required,
+    new_row.collateralization_state,
+    new_row.credit_facility_id,
+    new_row.cure_deadline,
+    new_row.effective,
+    new_row.is_cured,
+    new_row.is_escalated,
+    new_row.obligation_ids,
+    new_row.price,
+    new_row.repayment_required,
+    new_row.target_cvl
+  );
+
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Auto-generated trigger for MarginCallEvent
+CREATE TRIGGER core_margin_call_events_rollup_trigger
+  AFTER INSERT ON core_margin_call_events
+  FOR EACH ROW
+  EXECUTE FUNCTION core_margin_call_events_rollup_trigger();
@@ -0,0 +1,163 @@
+-- Auto-generated rollup table for CollateralReturnEvent
+CREATE TABLE core_collateral_return_events_rollup (
+  id UUID NOT NULL,
+  version INT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  modified_at TIMESTAMPTZ NOT NULL,
+  -- Flattened fields from the event JSON
+  amount BIGINT,
+  approval_process_id UUID,
+  approved BOOLEAN,
+  collateral_id UUID,
+  confirmed_at TIMESTAMPTZ,
+  credit_facility_id UUID,
+  custody_wallet_id UUID,
+  destination_address VARCHAR,
+  external_transfer_id VARCHAR,
+  failed_at TIMESTAMPTZ,
+
+  -- Toggle fields
+  is_approval_process_concluded BOOLEAN DEFAULT false,
+  is_transfer_confirmed BOOLEAN DEFAULT false,
+  is_transfer_failed BOOLEAN DEFAULT false,
+  is_transfer_requested BOOLEAN DEFAULT false
+,
+  PRIMARY KEY (id, version)
+);
+
+-- Auto-generated trigger function for CollateralReturnEvent
+CREATE OR REPLACE FUNCTION core_collateral_return_events_rollup_trigger()
+RETURNS TRIGGER AS $$
+DECLARE
+  event_type TEXT;
+  current_row core_collateral_return_events_rollup%ROWTYPE;
+  new_row core_collateral_return_events_rollup%ROWTYPE;
+BEGIN
+  event_type := NEW.event_type;
+
+  -- Load the previous version if this isn't the first event
+  IF NEW.sequence > 1 THEN
+    SELECT * INTO current_row
+    FROM core_collateral_return_events_rollup
+    WHERE id = NEW.id AND version = NEW.sequence - 1;
+  END IF;
+
+  -- Validate event type is known
+  IF event_type NOT IN ('initialized', 'approval_process_concluded', 'transfer_requested', 'transfer_confirmed', 'transfer_failed') THEN
+    RAISE EXCEPTION 'Unknown event type: %', event_type;
+  END IF;
+
+  -- Construct the new row based on event type
+  new_row.id := NEW.id;
+  new_row.version := NEW.sequence;
+  new_row.created_at := COALESCE(current_row.created_at, NEW.recorded_at);
+  new_row.modified_at := NEW.recorded_at;
+
+  -- Initialize fields with default values if this is a new record
+  IF current_row.id IS NULL THEN
+    new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+    new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+    new_row.approved := (NEW.event ->> 'approved')::BOOLEAN;
+    new_row.collateral_id := (NEW.event ->> 'collateral_id')::UUID;
+    new_row.confirmed_at := (NEW.event ->> 'confirmed_at')::TIMESTAMPTZ;
+    new_row.credit_facility_id := (NEW.event ->> 'credit_facility_id')::UUID;
+    new_row.custody_wallet_id := (NEW.event ->> 'custody_wallet_id')::UUID;
+    new_row.destination_address := (NEW.event ->> 'destination_address');
+    new_row.external_transfer_id := (NEW.event ->> 'external_transfer_id');
+    new_row.failed_at := (NEW.event ->> 'failed_at')::TIMESTAMPTZ;
+    new_row.is_approval_process_concluded := false;
+    new_row.is_transfer_confirmed := false;
+    new_row.is_transfer_failed := false;
+    new_row.is_transfer_requested := false;
+  ELSE
+    -- Default all fields to current values
+    new_row.amount := current_row.amount;
+    new_row.approval_process_id := current_row.approval_process_id;
+    new_row.approved := current_row.approved;
+    new_row.collateral_id := current_row.collateral_id;
+    new_row.confirmed_at := current_row.confirmed_at;
+    new_row.credit_facility_id := current_row.credit_facility_id;
+    new_row.custody_wallet_id := current_row.custody_wallet_id;
+    new_row.destination_address := current_row.destination_address;
+    new_row.external_transfer_id := current_row.external_transfer_id;
+    new_row.failed_at := current_row.failed_at;
+    new_row.is_approval_process_concluded := current_row.is_approval_process_concluded;
+    new_row.is_transfer_confirmed := current_row.is_transfer_confirmed;
+    new_row.is_transfer_failed := current_row.is_transfer_failed;
+    new_row.is_transfer_requested := current_row.is_transfer_requested;
+  END IF;
+
+  -- Update only the fields that are modified by the specific event
+  CASE event_type
+    WHEN 'initialized' THEN
+      new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+      new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+      new_row.collateral_id := (NEW.event ->> 'collateral_id')::UUID;
+      new_row.credit_facility_id := (NEW.event ->> 'credit_facility_id')::UUID;
+      new_row.custody_wallet_id := (NEW.event ->> 'custody_wallet_id')::UUID;
+      new_row.destination_address := (NEW.event ->> 'destination_address');
+    WHEN 'approval_process_concluded' THEN
+      new_row.approval_process_id := (NEW.event ->> 'approval_process_id')::UUID;
+      new_row.approved := (NEW.event ->> 'approved')::BOOLEAN;
+      new_row.is_approval_process_concluded := true;
+    WHEN 'transfer_requested' THEN
+      new_row.external_transfer_id := (NEW.event ->> 'external_transfer_id');
+      new_row.is_transfer_requested := true;
+    WHEN 'transfer_confirmed' THEN
+      new_row.confirmed_at := (NEW.event ->> 'confirmed_at')::TIMESTAMPTZ;
+      new_row.is_transfer_confirmed := true;
+    WHEN 'transfer_failed' THEN
+      new_row.failed_at := (NEW.event ->> 'failed_at')::TIMESTAMPTZ;
+      new_row.is_transfer_failed := true;
+  END CASE;
+
+  INSERT INTO core_collateral_return_events_rollup (
+    id,
+    version,
+    created_at,
+    modified_at,
+    amount,
+    approval_process_id,
+    approved,
+    collateral_id,
+    confirmed_at,
+    credit_facility_id,
+    custody_wallet_id,
+    destination_address,
+    external_transfer_id,
+    failed_at,
+    is_approval_process_concluded,
+    is_transfer_confirmed,
+    is_transfer_failed,
+    is_transfer_requested
+  )
+  VALUES (
+    new_row.id,
+    new_row.version,
+    new_row.created_at,
+    new_row.modified_at,
+    new_row.amount,
+    new_row.approval_process_id,
+    new_row.approved,
+    new_row.collateral_id,
+    new_row.confirmed_at,
+    new_row.credit_facility_id,
+    new_row.custody_wallet_id,
+    new_row.destination_address,
+    new_row.external_transfer_id,
+    new_row.failed_at,
+    new_row.is_approval_process_concluded,
+    new_row.is_transfer_confirmed,
+    new_row.is_transfer_failed,
+    new_row.is_transfer_requested
+  );
+
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Auto-generated trigger for CollateralReturnEvent
+CREATE TRIGGER core_collateral_return_events_rollup_trigger
+  AFTER INSERT ON core_collateral_return_events
+  FOR EACH ROW
+  EXECUTE FUNCTION core_collateral_return_events_rollup_trigger();
@@ -16,15 +16,18 @@ CREATE TABLE core_obligation_events_rollup (
   effective VARCHAR,
   in_liquidation_account_id UUID,
   initial_amount BIGINT,
+  installment_obligation_id UUID,
   liquidation_date JSONB,
   liquidation_process_id UUID,
   not_yet_due_accounts JSONB,
-  payment_allocation_amount BIGINT,
   obligation_type VARCHAR,
   overdue_accounts JSONB,
   overdue_amount BIGINT,
   overdue_date JSONB,
+  payment_allocation_amount BIGINT,
   payment_id UUID,
+  penalty JSONB,
+  penalty_obligation_id UUID,
   reference VARCHAR,
 
   -- Collection rollups
@@ -58,7 +61,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'due_recorded', 'overdue_recorded', 'defaulted_recorded', 'payment_allocated', 'liquidation_process_started', 'liquidation_process_concluded', 'completed') THEN
+  IF event_type NOT IN ('initialized', 'due_recorded', 'overdue_recorded', 'defaulted_recorded', 'payment_allocated', 'liquidation_process_started', 'liquidation_process_concluded', 'penalty_accrued', 'principal_installment_scheduled', 'rescheduled', 'split_into_installments', 'completed') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -81,6 +84,7 @@ BEGIN
     new_row.effective := (NEW.event ->> 'effective');
     new_row.in_liquidation_account_id := (NEW.event ->> 'in_liquidation_account_id')::UUID;
     new_row.initial_amount := (NEW.event ->> 'initial_amount')::BIGINT;
+    new_row.installment_obligation_id := (NEW.event ->> 'installment_obligation_id')::UUID;
     new_row.is_completed := false;
     new_row.is_defaulted_recorded := false;
     new_row.is_due_recorded := false;
@@ -94,6 +98,10 @@ BEGIN
     new_row.liquidation_date := (NEW.event -> 'liquidation_date');
     new_row.liquidation_process_id := (NEW.event ->> 'liquidation_process_id')::UUID;
     new_row.not_yet_due_accounts := (NEW.event -> 'not_yet_due_accounts');
+    new_row.obligation_type := (NEW.event ->> 'obligation_type');
+    new_row.overdue_accounts := (NEW.event -> 'overdue_accounts');
+    new_row.overdue_amount := (NEW.event ->> 'overdue_amount')::BIGINT;
+    new_row.overdue_date := (NEW.event -> 'overdue_date');
     new_row.payment_allocation_amount := (NEW.event ->> 'payment_allocation_amount')::BIGINT;
     new_row.payment_allocation_ids := CASE
        WHEN NEW.event ? 'payment_allocation_ids' THEN
@@ -101,11 +109,9 @@ BEGIN
        ELSE ARRAY[]::UUID[]
      END
 ;
-    new_row.obligation_type := (NEW.event ->> 'obligation_type');
-    new_row.overdue_accounts := (NEW.event -> 'overdue_accounts');
-    new_row.overdue_amount := (NEW.event ->> 'overdue_amount')::BIGINT;
-    new_row.overdue_date := (NEW.event -> 'overdue_date');
     new_row.payment_id := (NEW.event ->> 'payment_id')::UUID;
+    new_row.penalty := (NEW.event -> 'penalty');
+    new_row.penalty_obligation_id := (NEW.event ->> 'penalty_obligation_id')::UUID;
     new_row.reference := (NEW.event ->> 'reference');
   ELSE
     -- Default all fields to current values
@@ -120,6 +126,7 @@ BEGIN
     new_row.effective := current_row.effective;
     new_row.in_liquidation_account_id := current_row.in_liquidation_account_id;
     new_row.initial_amount := current_row.initial_amount;
+    new_row.installment_obligation_id := current_row.installment_obligation_id;
     new_row.is_completed := current_row.is_completed;
     new_row.is_defaulted_recorded := current_row.is_defaulted_recorded;
     new_row.is_due_recorded := current_row.is_due_recorded;
@@ -128,13 +135,15 @@ BEGIN
     new_row.liquidation_date := current_row.liquidation_date;
     new_row.liquidation_process_id := current_row.liquidation_process_id;
     new_row.not_yet_due_accounts := current_row.not_yet_due_accounts;
-    new_row.payment_allocation_amount := current_row.payment_allocation_amount;
-    new_row.payment_allocation_ids := current_row.payment_allocation_ids;
     new_row.obligation_type := current_row.obligation_type;
     new_row.overdue_accounts := current_row.overdue_accounts;
     new_row.overdue_amount := current_row.overdue_amount;
     new_row.overdue_date := current_row.overdue_date;
+    new_row.payment_allocation_amount := current_row.payment_allocation_amount;
+    new_row.payment_allocation_ids := current_row.payment_allocation_ids;
     new_row.payment_id := current_row.payment_id;
+    new_row.penalty := current_row.penalty;
+    new_row.penalty_obligation_id := current_row.penalty_obligation_id;
     new_row.reference := current_row.reference;
   END IF;
 
@@ -155,6 +164,7 @@ BEGIN
       new_row.obligation_type := (NEW.event ->> 'obligation_type');
       new_row.overdue_accounts := (NEW.event -> 'overdue_accounts');
       new_row.overdue_date := (NEW.event -> 'overdue_date');
+      new_row.penalty := (NEW.event -> 'penalty');
       new_row.reference := (NEW.event ->> 'reference');
     WHEN 'due_recorded' THEN
       new_row.due_amount := (NEW.event ->> 'due_amount')::BIGINT;
@@ -179,6 +189,22 @@ BEGIN
       new_row.liquidation_process_id := (NEW.event ->> 'liquidation_process_id')::UUID;
     WHEN 'liquidation_process_concluded' THEN
       new_row.liquidation_process_id := (NEW.event ->> 'liquidation_process_id')::UUID;
+    WHEN 'penalty_accrued' THEN
+      new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+      new_row.effective := (NEW.event ->> 'effective');
+      new_row.penalty_obligation_id := (NEW.event ->> 'penalty_obligation_id')::UUID;
+    WHEN 'principal_installment_scheduled' THEN
+      new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+      new_row.effective := (NEW.event ->> 'effective');
+      new_row.installment_obligation_id := (NEW.event ->> 'installment_obligation_id')::UUID;
+      new_row.reference := (NEW.event ->> 'reference');
+    WHEN 'rescheduled' THEN
+      new_row.defaulted_date := (NEW.event -> 'defaulted_date');
+      new_row.due_date := (NEW.event ->> 'due_date');
+      new_row.liquidation_date := (NEW.event -> 'liquidation_date');
+      new_row.overdue_date := (NEW.event -> 'overdue_date');
+    WHEN 'split_into_installments' THEN
+      new_row.effective := (NEW.event ->> 'effective');
     WHEN 'completed' THEN
       new_row.effective := (NEW.event ->> 'effective');
       new_row.is_completed := true;
@@ -200,6 +226,7 @@ BEGIN
     effective,
     in_liquidation_account_id,
     initial_amount,
+    installment_obligation_id,
     is_completed,
     is_defaulted_recorded,
     is_due_recorded,
@@ -208,13 +235,15 @@ BEGIN
     liquidation_date,
     liquidation_process_id,
     not_yet_due_accounts,
-    payment_allocation_amount,
-    payment_allocation_ids,
     obligation_type,
     overdue_accounts,
     overdue_amount,
     overdue_date,
+    payment_allocation_amount,
+    payment_allocation_ids,
     payment_id,
+    penalty,
+    penalty_obligation_id,
     reference
   )
   VALUES (
@@ -233,6 +262,7 @@ BEGIN
     new_row.effective,
     new_row.in_liquidation_account_id,
     new_row.initial_amount,
+    new_row.installment_obligation_id,
     new_row.is_completed,
     new_row.is_defaulted_recorded,
     new_row.is_due_recorded,
@@ -241,13 +271,15 @@ BEGIN
     new_row.liquidation_date,
     new_row.liquidation_process_id,
     new_row.not_yet_due_accounts,
-    new_row.payment_allocation_amount,
-    new_row.payment_allocation_ids,
     new_row.obligation_type,
     new_row.overdue_accounts,
     new_row.overdue_amount,
     new_row.overdue_date,
+    new_row.payment_allocation_amount,
+    new_row.payment_allocation_ids,
     new_row.payment_id,
+    new_row.penalty,
+    new_row.penalty_obligation_id,
     new_row.reference
   );
 
@@ -6,9 +6,15 @@ CREATE TABLE core_approval_process_events_rollup (
   modified_at TIMESTAMPTZ NOT NULL,
   -- Flattened fields from the event JSON
   approved BOOLEAN,
+  delegate_id UUID,
+  expires_at TIMESTAMPTZ,
+  expiry JSONB,
+  pending_voters JSONB,
   policy_id UUID,
   process_type VARCHAR,
+  remind_at TIMESTAMPTZ,
   rules JSONB,
+  stage INTEGER,
   target_ref VARCHAR,
 
   -- Collection rollups
@@ -40,7 +46,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'approved', 'denied', 'concluded') THEN
+  IF event_type NOT IN ('initialized', 'approved', 'denied', 'stage_approved', 'reminder_sent', 'escalated', 'expired', 'concluded') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -59,6 +65,7 @@ BEGIN
        ELSE ARRAY[]::UUID[]
      END
 ;
+    new_row.delegate_id := (NEW.event ->> 'delegate_id')::UUID;
     new_row.denier_ids := CASE
        WHEN NEW.event ? 'denier_ids' THEN
          ARRAY(SELECT value::text::UUID FROM jsonb_array_elements_text(NEW.event -> 'denier_ids'))
@@ -71,36 +78,64 @@ BEGIN
        ELSE ARRAY[]::VARCHAR[]
      END
 ;
+    new_row.expires_at := (NEW.event ->> 'expires_at')::TIMESTAMPTZ;
+    new_row.expiry := (NEW.event -> 'expiry');
     new_row.is_concluded := false;
+    new_row.pending_voters := (NEW.event -> 'pending_voters');
     new_row.policy_id := (NEW.event ->> 'policy_id')::UUID;
     new_row.process_type := (NEW.event ->> 'process_type');
+    new_row.remind_at := (NEW.event ->> 'remind_at')::TIMESTAMPTZ;
     new_row.rules := (NEW.event -> 'rules');
+    new_row.stage := (NEW.event ->> 'stage')::INTEGER;
     new_row.target_ref := (NEW.event ->> 'target_ref');
   ELSE
     -- Default all fields to current values
     new_row.approved := current_row.approved;
     new_row.approver_ids := current_row.approver_ids;
+    new_row.delegate_id := current_row.delegate_id;
     new_row.denier_ids := current_row.denier_ids;
     new_row.deny_reasons := current_row.deny_reasons;
+    new_row.expires_at := current_row.expires_at;
+    new_row.expiry := current_row.expiry;
     new_row.is_concluded := current_row.is_concluded;
+    new_row.pending_voters := current_row.pending_voters;
     new_row.policy_id := current_row.policy_id;
     new_row.process_type := current_row.process_type;
+    new_row.remind_at := current_row.remind_at;
     new_row.rules := current_row.rules;
+    new_row.stage := current_row.stage;
     new_row.target_ref := current_row.target_ref;
   END IF;
 
   -- Update only the fields that are modified by the specific event
   CASE event_type
     WHEN 'initialized' THEN
+      new_row.expires_at := (NEW.event ->> 'expires_at')::TIMESTAMPTZ;
+      new_row.expiry := (NEW.event -> 'expiry');
       new_row.policy_id := (NEW.event ->> 'policy_id')::UUID;
       new_row.process_type := (NEW.event ->> 'process_type');
+      new_row.remind_at := (NEW.event ->> 'remind_at')::TIMESTAMPTZ;
       new_row.rules := (NEW.event -> 'rules');
       new_row.target_ref := (NEW.event ->> 'target_ref');
     WHEN 'approved' THEN
       new_row.approver_ids := array_append(COALESCE(current_row.approver_ids, ARRAY[]::UUID[]), (NEW.event ->> 'approver_id')::UUID);
+      new_row.delegate_id := (NEW.event ->> 'delegate_id')::UUID;
+      new_row.stage := (NEW.event ->> 'stage')::INTEGER;
     WHEN 'denied' THEN
+      new_row.delegate_id := (NEW.event ->> 'delegate_id')::UUID;
       new_row.denier_ids := array_append(COALESCE(current_row.denier_ids, ARRAY[]::UUID[]), (NEW.event ->> 'denier_id')::UUID);
       new_row.deny_reasons := array_append(COALESCE(current_row.deny_reasons, ARRAY[]::VARCHAR[]), (NEW.event ->> 'reason'));
+      new_row.stage := (NEW.event ->> 'stage')::INTEGER;
+    WHEN 'stage_approved' THEN
+      new_row.stage := (NEW.event ->> 'stage')::INTEGER;
+    WHEN 'reminder_sent' THEN
+      new_row.pending_voters := (NEW.event -> 'pending_voters');
+    WHEN 'escalated' THEN
+      new_row.expires_at := (NEW.event ->> 'expires_at')::TIMESTAMPTZ;
+      new_row.remind_at := (NEW.event ->> 'remind_at')::TIMESTAMPTZ;
+      new_row.rules := (NEW.event -> 'rules');
+      new_row.stage := (NEW.event ->> 'stage')::INTEGER;
+    WHEN 'expired' THEN
     WHEN 'concluded' THEN
       new_row.approved := (NEW.event ->> 'approved')::BOOLEAN;
       new_row.is_concluded := true;
@@ -113,12 +148,18 @@ BEGIN
     modified_at,
     approved,
     approver_ids,
+    delegate_id,
     denier_ids,
     deny_reasons,
+    expires_at,
+    expiry,
     is_concluded,
+    pending_voters,
     policy_id,
     process_type,
+    remind_at,
     rules,
+    stage,
     target_ref
   )
   VALUES (
@@ -128,12 +169,18 @@ BEGIN
     new_row.modified_at,
     new_row.approved,
     new_row.approver_ids,
+    new_row.delegate_id,
     new_row.denier_ids,
     new_row.deny_reasons,
+    new_row.expires_at,
+    new_row.expiry,
     new_row.is_concluded,
+    new_row.pending_voters,
     new_row.policy_id,
     new_row.process_type,
+    new_row.remind_at,
     new_row.rules,
+    new_row.stage,
     new_row.target_ref
   );
 
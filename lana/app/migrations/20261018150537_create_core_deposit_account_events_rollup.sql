@@ -7,6 +7,11 @@ CREATE TABLE core_deposit_account_events_rollup (
   -- Flattened fields from the event JSON
   account_holder_id UUID,
   account_ids JSONB,
+  accrued_cents VARCHAR,
+  accrued_on VARCHAR,
+  amount BIGINT,
+  ledger_tx_id UUID,
+  period_end VARCHAR,
   public_id VARCHAR,
   status VARCHAR
 ,
@@ -31,7 +36,7 @@ BEGIN
   END IF;
 
   -- Validate event type is known
-  IF event_type NOT IN ('initialized', 'account_status_updated') THEN
+  IF event_type NOT IN ('initialized', 'account_status_updated', 'unfrozen', 'closed', 'interest_accrued', 'interest_capitalized') THEN
     RAISE EXCEPTION 'Unknown event type: %', event_type;
   END IF;
 
@@ -45,12 +50,22 @@ BEGIN
   IF current_row.id IS NULL THEN
     new_row.account_holder_id := (NEW.event ->> 'account_holder_id')::UUID;
     new_row.account_ids := (NEW.event -> 'account_ids');
+    new_row.accrued_cents := (NEW.event ->> 'accrued_cents');
+    new_row.accrued_on := (NEW.event ->> 'accrued_on');
+    new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+    new_row.ledger_tx_id := (NEW.event ->> 'ledger_tx_id')::UUID;
+    new_row.period_end := (NEW.event ->> 'period_end');
     new_row.public_id := (NEW.event ->> 'public_id');
     new_row.status := (NEW.event ->> 'status');
   ELSE
     -- Default all fields to current values
     new_row.account_holder_id := current_row.account_holder_id;
     new_row.account_ids := current_row.account_ids;
+    new_row.accrued_cents := current_row.accrued_cents;
+    new_row.accrued_on := current_row.accrued_on;
+    new_row.amount := current_row.amount;
+    new_row.ledger_tx_id := current_row.ledger_tx_id;
+    new_row.period_end := current_row.period_end;
     new_row.public_id := current_row.public_id;
     new_row.status := current_row.status;
   END IF;
@@ -64,6 +79,17 @@ BEGIN
       new_row.status := (NEW.event ->> 'status');
     WHEN 'account_status_updated' THEN
       new_row.status := (NEW.event ->> 'status');
+    WHEN 'unfrozen' THEN
+    WHEN 'closed' THEN
+    WHEN 'interest_accrued' THEN
+      new_row.accrued_cents := (NEW.event ->> 'accrued_cents');
+      new_row.accrued_on := (NEW.event ->> 'accrued_on');
+      new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+      new_row.ledger_tx_id := (NEW.event ->> 'ledger_tx_id')::UUID;
+    WHEN 'interest_capitalized' THEN
+      new_row.amount := (NEW.event ->> 'amount')::BIGINT;
+      new_row.ledger_tx_id := (NEW.event ->> 'ledger_tx_id')::UUID;
+      new_row.period_end := (NEW.event ->> 'period_end');
   END CASE;
 
   INSERT INTO core_deposit_account_events_rollup (
@@ -73,6 +99,11 @@ BEGIN
     modified_at,
     account_holder_id,
     account_ids,
+    accrued_cents,
+    accrued_on,
+    amount,
+    ledger_tx_id,
+    period_end,
     public_id,
     status
   )
@@ -83,6 +114,11 @@ BEGIN
     new_row.modified_at,
     new_row.account_holder_id,
     new_row.account_ids,
+    new_row.accrued_cents,
+    new_row.accrued_on,
+    new_row.amount,
+    new_row.ledger_tx_id,
+    new_row.period_end,
     new_row.public_id,
     new_row.status
   );
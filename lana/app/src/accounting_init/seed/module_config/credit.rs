@@ -20,6 +20,8 @@ struct CreditConfigData {
     in_liquidation_parent_code: String,
     interest_income_parent_code: String,
     fee_income_parent_code: String,
+    penalty_income_parent_code: String,
+    penalty_receivable_parent_code: String,
     short_term_individual_interest_receivable_parent_code: String,
     short_term_government_entity_interest_receivable_parent_code: String,
     short_term_private_company_interest_receivable_parent_code: String,
@@ -72,6 +74,8 @@ pub(in crate::accounting_init::seed) async fn credit_module_configure(
         in_liquidation_parent_code,
         interest_income_parent_code,
         fee_income_parent_code,
+        penalty_income_parent_code,
+        penalty_receivable_parent_code,
         short_term_individual_interest_receivable_parent_code,
         short_term_government_entity_interest_receivable_parent_code,
         short_term_private_company_interest_receivable_parent_code,
@@ -120,6 +124,8 @@ pub(in crate::accounting_init::seed) async fn credit_module_configure(
         chart_of_account_in_liquidation_parent_code: in_liquidation_parent_code.parse()?,
         chart_of_account_interest_income_parent_code: interest_income_parent_code.parse()?,
         chart_of_account_fee_income_parent_code: fee_income_parent_code.parse()?,
+        chart_of_account_penalty_income_parent_code: penalty_income_parent_code.parse()?,
+        chart_of_account_penalty_receivable_parent_code: penalty_receivable_parent_code.parse()?,
         chart_of_account_short_term_individual_interest_receivable_parent_code:
             short_term_individual_interest_receivable_parent_code.parse()?,
         chart_of_account_short_term_government_entity_interest_receivable_parent_code:
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use job::*;
+
+use crate::audit::{AUDIT_CHAIN_INTERVAL, Audit, AuditSvc};
+
+#[derive(serde::Serialize)]
+pub struct AuditChainJobConfig;
+impl JobConfig for AuditChainJobConfig {
+    type Initializer = AuditChainInit;
+}
+
+pub struct AuditChainInit {
+    audit: Audit,
+}
+
+impl AuditChainInit {
+    pub fn new(audit: &Audit) -> Self {
+        Self {
+            audit: audit.clone(),
+        }
+    }
+}
+
+const AUDIT_CHAIN_JOB: JobType = JobType::new("cron.audit-chain");
+impl JobInitializer for AuditChainInit {
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        AUDIT_CHAIN_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(AuditChainJobRunner {
+            audit: self.audit.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+pub struct AuditChainJobRunner {
+    audit: Audit,
+}
+
+#[async_trait]
+impl JobRunner for AuditChainJobRunner {
+    #[instrument(name = "lana.audit_chain.run", skip_all, fields(n_chained), err)]
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let n_chained = self.audit.chain_pending_entries().await?;
+        tracing::Span::current().record("n_chained", n_chained);
+
+        Ok(JobCompletion::RescheduleIn(AUDIT_CHAIN_INTERVAL))
+    }
+}
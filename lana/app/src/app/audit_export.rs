@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use job::*;
+
+use crate::{
+    audit::{Audit, AuditExportFormat, AuditExportSink, AuditFilter, AuditSvc},
+    document::{ChunkedUpload, DocumentId, DocumentStorage},
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct AuditExportJobConfig {
+    pub document_id: DocumentId,
+    pub checksum_document_id: DocumentId,
+    pub filename: String,
+    pub filter: AuditFilter,
+    pub format: AuditExportFormat,
+}
+impl JobConfig for AuditExportJobConfig {
+    type Initializer = AuditExportInit;
+}
+
+pub struct AuditExportInit {
+    audit: Audit,
+    documents: DocumentStorage,
+}
+
+impl AuditExportInit {
+    pub fn new(audit: &Audit, documents: &DocumentStorage) -> Self {
+        Self {
+            audit: audit.clone(),
+            documents: documents.clone(),
+        }
+    }
+}
+
+const AUDIT_EXPORT_JOB: JobType = JobType::new("task.audit-export");
+impl JobInitializer for AuditExportInit {
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        AUDIT_EXPORT_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(AuditExportJobRunner {
+            config: job.config()?,
+            audit: self.audit.clone(),
+            documents: self.documents.clone(),
+        }))
+    }
+}
+
+pub struct AuditExportJobRunner {
+    config: AuditExportJobConfig,
+    audit: Audit,
+    documents: DocumentStorage,
+}
+
+#[async_trait]
+impl JobRunner for AuditExportJobRunner {
+    #[instrument(
+        name = "lana.audit_export.run",
+        skip_all,
+        fields(document_id = %self.config.document_id, n_entries),
+        err
+    )]
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut document = self.documents.find_by_id(self.config.document_id).await?;
+        let mut sink = DocumentUploadSink(self.documents.start_chunked_upload(&document).await?);
+        let extract = self
+            .audit
+            .export(&self.config.filter, self.config.format, &mut sink)
+            .await?;
+        self.documents
+            .finish_chunked_upload(sink.0, &mut document)
+            .await?;
+        tracing::Span::current().record("n_entries", extract.n_entries);
+
+        let mut checksum_document = self
+            .documents
+            .find_by_id(self.config.checksum_document_id)
+            .await?;
+        let checksum_content = format!("{}  {}\n", extract.checksum, self.config.filename);
+        self.documents
+            .upload(checksum_content.into_bytes(), &mut checksum_document)
+            .await?;
+
+        Ok(JobCompletion::Complete)
+    }
+}
+
+struct DocumentUploadSink(ChunkedUpload);
+
+#[async_trait]
+impl AuditExportSink for DocumentUploadSink {
+    async fn write_chunk(
+        &mut self,
+        chunk: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.0.upload_chunk(chunk).await?;
+        Ok(())
+    }
+}
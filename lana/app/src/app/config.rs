@@ -4,9 +4,9 @@ use std::path::PathBuf;
 
 use crate::{
     access::config::AccessConfig, applicant::SumsubConfig, credit::CreditConfig,
-    custody::CustodyConfig, customer_sync::CustomerSyncConfig, job::JobPollerConfig,
-    notification::NotificationConfig, report::ReportConfig, storage::config::StorageConfig,
-    user_onboarding::UserOnboardingConfig,
+    custody::CustodyConfig, customer_sync::CustomerSyncConfig, deposit::DepositConfig,
+    job::JobPollerConfig, notification::NotificationConfig, price::config::PriceConfig,
+    report::ReportConfig, storage::config::StorageConfig, user_onboarding::UserOnboardingConfig,
 };
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -22,6 +22,8 @@ pub struct AppConfig {
     #[serde(default)]
     pub credit: CreditConfig,
     #[serde(default)]
+    pub deposit: DepositConfig,
+    #[serde(default)]
     pub storage: StorageConfig,
     #[serde(default)]
     pub user_onboarding: UserOnboardingConfig,
@@ -32,6 +34,8 @@ pub struct AppConfig {
     #[serde(default)]
     pub custody: CustodyConfig,
     #[serde(default)]
+    pub price: PriceConfig,
+    #[serde(default)]
     pub notification: NotificationConfig,
     #[serde(default)]
     pub report: ReportConfig,
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use crate::{
+    credit::Credit,
+    deposit::{DepositAccountClosureCheck, DepositAccountHolderId},
+};
+
+pub(super) struct CreditFacilitiesClosureCheck<'a> {
+    credit: &'a Credit,
+}
+
+impl<'a> CreditFacilitiesClosureCheck<'a> {
+    pub(super) fn new(credit: &'a Credit) -> Self {
+        Self { credit }
+    }
+}
+
+#[async_trait]
+impl DepositAccountClosureCheck for CreditFacilitiesClosureCheck<'_> {
+    async fn holder_has_open_credit_facilities(
+        &self,
+        holder_id: DepositAccountHolderId,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self
+            .credit
+            .customer_has_open_credit_facilities(holder_id)
+            .await?)
+    }
+}
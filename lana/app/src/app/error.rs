@@ -17,7 +17,7 @@ pub enum ApplicationError {
     #[error("ApplicationError - NotificationError: {0}")]
     NotificationError(#[from] notification::error::NotificationError),
     #[error("ApplicationError - CreditFacilityError: {0}")]
-    CreditFacilityError(#[from] crate::credit::error::CoreCreditError),
+    CreditFacilityError(Box<crate::credit::error::CoreCreditError>),
     #[error("ApplicationError - TrialBalanceError: {0}")]
     TrialBalanceError(#[from] crate::trial_balance::error::TrialBalanceError),
     #[error("ApplicationError - ProfitAndLossStatementError: {0}")]
@@ -56,4 +56,14 @@ pub enum ApplicationError {
     ContractCreationError(#[from] crate::contract_creation::ContractCreationError),
     #[error("ApplicationError - ReportError: {0}")]
     ReportError(#[from] crate::report::error::ReportError),
+    #[error("ApplicationError - DocumentStorageError: {0}")]
+    DocumentStorageError(#[from] crate::document::error::DocumentStorageError),
+    #[error("ApplicationError - NotAnAuditExport: {0}")]
+    NotAnAuditExport(crate::document::DocumentId),
+}
+
+impl From<crate::credit::error::CoreCreditError> for ApplicationError {
+    fn from(e: crate::credit::error::CoreCreditError) -> Self {
+        Self::CreditFacilityError(Box::new(e))
+    }
 }
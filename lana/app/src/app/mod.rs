@@ -1,4 +1,7 @@
+mod audit_chain;
+mod audit_export;
 mod config;
+mod deposit_closure;
 mod error;
 
 use sqlx::PgPool;
@@ -9,36 +12,49 @@ use authz::PermissionCheck;
 use rbac_types::{AuditAction, AuditEntityAction, AuditObject};
 
 use crate::{
-    access::Access,
+    access::{Access, User, UserId},
     accounting::Accounting,
     accounting_init::{ChartsInit, JournalInit, StatementsInit},
     applicant::Applicants,
-    audit::{Audit, AuditCursor, AuditEntry},
+    audit::{
+        Audit, AuditCursor, AuditEntry, AuditExport, AuditExportFormat, AuditFilter, AuditSvc,
+    },
     authorization::{Authorization, seed},
     contract_creation::ContractCreation,
-    credit::Credit,
+    credit::{Credit, CreditFacility},
     custody::Custody,
     customer::Customers,
     customer_sync::CustomerSync,
     dashboard::Dashboard,
-    deposit::Deposits,
+    deposit::{DepositAccount, Deposits},
     deposit_sync::DepositSync,
-    document::DocumentStorage,
+    document::{
+        DocumentId, DocumentStorage, DocumentType, GeneratedDocumentDownloadLink, ReferenceId,
+    },
     governance::Governance,
     job::Jobs,
     notification::Notification,
     outbox::Outbox,
     price::Price,
-    primitives::Subject,
+    primitives::{
+        CommitteeMemberId, DepositAccountId, LiquidationProcessId, Satoshis, Subject, UsdCents,
+    },
     public_id::PublicIds,
     report::Reports,
     storage::Storage,
     user_onboarding::UserOnboarding,
 };
 
+use audit_chain::{AuditChainInit, AuditChainJobConfig};
+use audit_export::{AuditExportInit, AuditExportJobConfig};
 pub use config::*;
+use deposit_closure::CreditFacilitiesClosureCheck;
 use error::ApplicationError;
 
+const AUDIT_EXPORT_DOCUMENT_TYPE: DocumentType = DocumentType::new("audit_export");
+const AUDIT_EXPORT_CHECKSUM_DOCUMENT_TYPE: DocumentType =
+    DocumentType::new("audit_export_checksum");
+
 #[derive(Clone)]
 pub struct LanaApp {
     _pool: PgPool,
@@ -55,6 +71,7 @@ pub struct LanaApp {
     price: Price,
     outbox: Outbox,
     governance: Governance,
+    documents: DocumentStorage,
     dashboard: Dashboard,
     public_ids: PublicIds,
     contract_creation: ContractCreation,
@@ -90,13 +107,16 @@ impl LanaApp {
                 .expect("Couldn't build JobSvcConfig"),
         )
         .await?;
+        jobs.add_initializer_and_spawn_unique(AuditChainInit::new(&audit), AuditChainJobConfig)
+            .await?;
 
         let dashboard = Dashboard::init(&pool, &authz, &jobs, &outbox).await?;
-        let governance = Governance::new(&pool, &authz, &outbox);
+        let governance = Governance::new(&pool, &authz, &jobs, &outbox);
         let storage = Storage::new(&config.storage);
         let reports = Reports::init(&pool, &authz, config.report, &outbox, &jobs, &storage).await?;
-        let price = Price::new();
+        let price = Price::init(&pool, config.price, &jobs, &outbox).await?;
         let documents = DocumentStorage::new(&pool, &storage);
+        jobs.add_initializer(AuditExportInit::new(&audit, &documents));
         let public_ids = PublicIds::new(&pool);
 
         let user_onboarding = UserOnboarding::init(&jobs, &outbox, config.user_onboarding).await?;
@@ -135,6 +155,7 @@ impl LanaApp {
             &cala,
             journal_init.journal_id,
             &public_ids,
+            config.deposit,
         )
         .await?;
         let customer_sync =
@@ -151,7 +172,7 @@ impl LanaApp {
         )
         .await?;
 
-        let custody = Custody::init(&pool, &authz, config.custody, &outbox).await?;
+        let custody = Custody::init(&pool, &authz, config.custody, &jobs, &outbox).await?;
 
         let credit = Credit::init(
             &pool,
@@ -201,6 +222,7 @@ impl LanaApp {
             custody,
             outbox,
             governance,
+            documents,
             dashboard,
             public_ids,
             contract_creation,
@@ -244,9 +266,8 @@ impl LanaApp {
         &self,
         sub: &Subject,
         query: es_entity::PaginatedQueryArgs<AuditCursor>,
+        filter: AuditFilter,
     ) -> Result<es_entity::PaginatedQueryRet<AuditEntry, AuditCursor>, ApplicationError> {
-        use crate::audit::AuditSvc;
-
         self.authz
             .enforce_permission(
                 sub,
@@ -255,7 +276,94 @@ impl LanaApp {
             )
             .await?;
 
-        self.audit.list(query).await.map_err(ApplicationError::from)
+        self.audit
+            .search(query, &filter)
+            .await
+            .map_err(ApplicationError::from)
+    }
+
+    #[instrument(name = "lana.audit.export_audit", skip(self), err)]
+    pub async fn export_audit(
+        &self,
+        sub: &Subject,
+        filter: AuditFilter,
+        format: AuditExportFormat,
+    ) -> Result<AuditExport, ApplicationError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                AuditObject::all_audits(),
+                AuditAction::from(AuditEntityAction::Export),
+            )
+            .await?;
+
+        let reference_id = ReferenceId::new();
+        let filename = format!("audit-export-{}.{}", reference_id, format.file_extension());
+
+        let mut db = self.documents.begin_op().await?;
+        let document = self
+            .documents
+            .create_in_op(
+                filename.clone(),
+                format.content_type(),
+                reference_id,
+                AUDIT_EXPORT_DOCUMENT_TYPE,
+                &mut db,
+            )
+            .await?;
+        let checksum_document = self
+            .documents
+            .create_in_op(
+                format!("{filename}.sha256"),
+                "text/plain",
+                reference_id,
+                AUDIT_EXPORT_CHECKSUM_DOCUMENT_TYPE,
+                &mut db,
+            )
+            .await?;
+        self.jobs
+            .create_and_spawn_in_op(
+                &mut db,
+                job::JobId::from(uuid::Uuid::from(document.id)),
+                AuditExportJobConfig {
+                    document_id: document.id,
+                    checksum_document_id: checksum_document.id,
+                    filename,
+                    filter,
+                    format,
+                },
+            )
+            .await?;
+        db.commit().await?;
+
+        Ok(AuditExport {
+            document,
+            checksum_document,
+        })
+    }
+
+    #[instrument(name = "lana.audit.generate_export_download_link", skip(self), err)]
+    pub async fn generate_audit_export_download_link(
+        &self,
+        sub: &Subject,
+        document_id: DocumentId,
+    ) -> Result<GeneratedDocumentDownloadLink, ApplicationError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                AuditObject::all_audits(),
+                AuditAction::from(AuditEntityAction::Export),
+            )
+            .await?;
+
+        let document = self.documents.find_by_id(document_id).await?;
+        if document.document_type != AUDIT_EXPORT_DOCUMENT_TYPE
+            && document.document_type != AUDIT_EXPORT_CHECKSUM_DOCUMENT_TYPE
+        {
+            return Err(ApplicationError::NotAnAuditExport(document_id));
+        }
+
+        Ok(self.documents.generate_download_link(document_id).await?)
     }
 
     pub fn accounting(&self) -> &Accounting {
@@ -266,6 +374,22 @@ impl LanaApp {
         &self.deposits
     }
 
+    #[instrument(name = "lana.deposit.close_account", skip(self), err)]
+    pub async fn close_deposit_account(
+        &self,
+        sub: &Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<DepositAccount, ApplicationError> {
+        Ok(self
+            .deposits
+            .close_account(
+                sub,
+                account_id,
+                &CreditFacilitiesClosureCheck::new(&self.credit),
+            )
+            .await?)
+    }
+
     pub fn applicants(&self) -> &Applicants {
         &self.applicants
     }
@@ -278,10 +402,55 @@ impl LanaApp {
         &self.credit
     }
 
+    #[instrument(name = "lana.credit.record_liquidation_execution", skip(self), err)]
+    pub async fn record_liquidation_execution(
+        &self,
+        sub: &Subject,
+        liquidation_process_id: impl Into<LiquidationProcessId> + std::fmt::Debug + Copy,
+        collateral_sold: Satoshis,
+        proceeds: UsdCents,
+        effective: impl Into<chrono::NaiveDate> + std::fmt::Debug + Copy,
+    ) -> Result<CreditFacility, ApplicationError> {
+        Ok(self
+            .credit
+            .record_liquidation_execution(
+                sub,
+                liquidation_process_id,
+                collateral_sold,
+                proceeds,
+                effective,
+            )
+            .await?)
+    }
+
     pub fn access(&self) -> &Access {
         &self.access
     }
 
+    #[instrument(name = "lana.access.deactivate_user", skip(self), err)]
+    pub async fn deactivate_user(
+        &self,
+        sub: &Subject,
+        user_id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<User, ApplicationError> {
+        let user = self.access.deactivate_user(sub, user_id).await?;
+        self.governance
+            .remove_member_from_all_committees(CommitteeMemberId::from(user.id))
+            .await?;
+
+        Ok(user)
+    }
+
+    /// Committee memberships removed on deactivation are not restored.
+    #[instrument(name = "lana.access.reactivate_user", skip(self), err)]
+    pub async fn reactivate_user(
+        &self,
+        sub: &Subject,
+        user_id: impl Into<UserId> + std::fmt::Debug,
+    ) -> Result<User, ApplicationError> {
+        Ok(self.access.reactivate_user(sub, user_id).await?)
+    }
+
     pub fn public_ids(&self) -> &PublicIds {
         &self.public_ids
     }
@@ -19,7 +19,7 @@ pub mod storage {
 
 pub mod document {
     pub use document_storage::{
-        Document, DocumentId, DocumentRepo, DocumentStatus, DocumentType,
+        ChunkedUpload, Document, DocumentId, DocumentRepo, DocumentStatus, DocumentType,
         DocumentsByCreatedAtCursor, GeneratedDocumentDownloadLink, NewDocument, ReferenceId, error,
     };
     pub type DocumentStorage = document_storage::DocumentStorage;
@@ -49,7 +49,7 @@ pub mod rbac {
     pub use rbac_types::PermissionSetName;
 }
 pub mod access {
-    pub use core_access::{Role, RoleId, UserId, config, error, permission_set, role, user};
+    pub use core_access::{Role, RoleId, User, UserId, config, error, permission_set, role, user};
     pub type Access = core_access::CoreAccess<crate::audit::Audit, lana_events::LanaEvent>;
 }
 
@@ -85,12 +85,17 @@ pub mod governance {
     use crate::authorization::Authorization;
     use lana_events::LanaEvent;
     pub type Governance = governance::Governance<Authorization, LanaEvent>;
+    pub use crate::credit::APPROVE_COLLATERAL_RETURN_PROCESS;
     pub use crate::credit::APPROVE_CREDIT_FACILITY_PROPOSAL_PROCESS;
+    pub use crate::credit::APPROVE_CREDIT_FACILITY_TERMS_AMENDMENT_PROCESS;
     pub use crate::credit::APPROVE_DISBURSAL_PROCESS;
+    pub use core_deposit::APPROVE_TRANSFER_PROCESS;
     pub use core_deposit::APPROVE_WITHDRAWAL_PROCESS;
     pub use governance::{
-        ApprovalProcess, ApprovalProcessStatus, ApprovalProcessType, ApprovalRules, Committee,
-        CommitteeId, Policy, approval_process_cursor, committee_cursor, error, policy_cursor,
+        ApprovalBand, ApprovalExpiry, ApprovalExpiryAction, ApprovalProcess,
+        ApprovalProcessContext, ApprovalProcessStatus, ApprovalProcessType, ApprovalRules,
+        ApprovalStage, Committee, CommitteeId, Policy, VoteDelegation, approval_process_cursor,
+        committee_cursor, error, policy_cursor,
     };
 }
 
@@ -100,18 +105,32 @@ pub mod audit {
         primitives::Subject,
     };
 
-    pub use audit::{AuditCursor, AuditEntryId, AuditInfo, AuditSvc, error};
+    pub use audit::{
+        AUDIT_CHAIN_INTERVAL, AuditChainBreakReason, AuditChainVerification, AuditCursor,
+        AuditEntryId, AuditExportFormat, AuditExportSink, AuditFilter, AuditInfo, AuditSvc, error,
+        verify_chain,
+    };
     pub type Audit = audit::Audit<Subject, LanaObject, LanaAction>;
     pub type AuditEntry = audit::AuditEntry<Subject, LanaObject, LanaAction>;
+
+    /// An audit extract being generated in the background. The `checksum_document` receives
+    /// the SHA-256 checksum of the extract in `sha256sum` format once it has been uploaded.
+    pub struct AuditExport {
+        pub document: crate::document::Document,
+        pub checksum_document: crate::document::Document,
+    }
 }
 
 pub mod deposit {
     pub use core_deposit::{
         ChartOfAccountsIntegrationConfig, CoreDepositEvent, DEPOSIT_ACCOUNT_ENTITY_TYPE,
         DEPOSIT_TRANSACTION_ENTITY_TYPE, Deposit, DepositAccount, DepositAccountBalance,
-        DepositAccountHistoryCursor, DepositAccountHistoryEntry, DepositAccountStatus, DepositId,
-        DepositStatus, DepositsByCreatedAtCursor, WITHDRAWAL_TRANSACTION_ENTITY_TYPE, Withdrawal,
-        WithdrawalId, WithdrawalStatus, WithdrawalsByCreatedAtCursor, error,
+        DepositAccountClosureCheck, DepositAccountHistoryCursor, DepositAccountHistoryEntry,
+        DepositAccountHolderId, DepositAccountStatus, DepositConfig, DepositId,
+        DepositInterestRatePct, DepositStatus, DepositsByCreatedAtCursor,
+        TRANSFER_TRANSACTION_ENTITY_TYPE, Transfer, TransferId, TransferStatus,
+        TransfersByCreatedAtCursor, WITHDRAWAL_TRANSACTION_ENTITY_TYPE, Withdrawal, WithdrawalId,
+        WithdrawalStatus, WithdrawalsByCreatedAtCursor, error,
     };
 
     pub type Deposits =
@@ -149,7 +168,8 @@ pub mod trial_balance {
 
 pub mod custody {
     pub use core_custody::{
-        CustodyConfig, CustodyPublisher, Wallet, WalletId, WalletNetwork, custodian, error,
+        CustodyConfig, CustodyPublisher, Wallet, WalletId, WalletNetwork, WalletReconciliation,
+        custodian, error,
     };
     pub type Custody =
         core_custody::CoreCustody<crate::authorization::Authorization, lana_events::LanaEvent>;
@@ -157,7 +177,8 @@ pub mod custody {
 
 pub mod credit {
     pub use core_credit::{
-        APPROVE_CREDIT_FACILITY_PROPOSAL_PROCESS, APPROVE_DISBURSAL_PROCESS,
+        APPROVE_COLLATERAL_RETURN_PROCESS, APPROVE_CREDIT_FACILITY_PROPOSAL_PROCESS,
+        APPROVE_CREDIT_FACILITY_TERMS_AMENDMENT_PROCESS, APPROVE_DISBURSAL_PROCESS,
         COLLATERAL_ENTITY_TYPE, CREDIT_FACILITY_ENTITY_TYPE, ChartOfAccountsIntegrationConfig,
         Collateral, CollateralUpdated, CollateralizationUpdated, CoreCreditEvent, CreditConfig,
         CreditFacilitiesCursor, CreditFacilitiesFilter, CreditFacilitiesSortBy, CreditFacility,
@@ -166,9 +187,10 @@ pub mod credit {
         CreditFacilityRepaymentPlanEntry, CreditFacilityStatus, DISBURSAL_TRANSACTION_ENTITY_TYPE,
         Disbursal, DisbursalExecuted, DisbursalStatus, DisbursalsCursor, DisbursalsFilter,
         DisbursalsSortBy, IncrementalPayment, InterestAccrualsPosted, ListDirection,
-        ObligationMovedToLiquidation, Payment, PaymentAllocation,
+        ObligationMovedToLiquidation, Payment, PaymentAllocation, PayoffQuote,
         PendingCreditFacilitiesByCreatedAtCursor, PendingCreditFacility, PendingCreditFacilityId,
-        RepaymentStatus, RepaymentType, Sort, TermsTemplate, error, terms_template_error,
+        RepaymentInstallment, RepaymentStatus, RepaymentType, Sort, TermsTemplate, error,
+        terms_template_error,
     };
 
     pub type Credit =
@@ -177,8 +199,9 @@ pub mod credit {
 
 pub mod terms {
     pub use core_credit::{
-        AnnualRatePct, CVLPct, CollateralizationState, FacilityDuration, InterestInterval,
-        ObligationDuration, OneTimeFeeRatePct, PendingCreditFacilityCollateralizationState,
+        AmortizationSchedule, AnnualRatePct, CVLPct, CollateralizationState, DayCountConvention,
+        DisbursalPolicy, FacilityDuration, InterestInterval, ObligationDuration, OneTimeFeeRatePct,
+        PaymentAllocationStrategy, PendingCreditFacilityCollateralizationState, ScheduledDisbursal,
         TermValues,
     };
 }
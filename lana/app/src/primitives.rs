@@ -5,12 +5,15 @@ pub use core_accounting::{
 pub use core_credit::{
     CollateralAction, CollateralId, CreditFacilityId, CreditFacilityProposalId,
     CreditFacilityProposalStatus, CreditFacilityStatus, DisbursalId, DisbursalStatus,
-    PaymentAllocationId, PaymentId, PendingCreditFacilityCollateralizationState,
-    PendingCreditFacilityId, PendingCreditFacilityStatus, TermsTemplateId,
+    LiquidationProcessId, PaymentAllocationId, PaymentId,
+    PendingCreditFacilityCollateralizationState, PendingCreditFacilityId,
+    PendingCreditFacilityStatus, TermsTemplateId,
 };
 pub use core_custody::{CustodianId, WalletId};
 pub use core_customer::{CustomerDocumentId, CustomerId};
-pub use core_deposit::{DepositAccountHolderId, DepositAccountId, DepositId, WithdrawalId};
+pub use core_deposit::{
+    DepositAccountHolderId, DepositAccountId, DepositId, TransferId, WithdrawalId,
+};
 pub use core_money::*;
 pub use core_price::PriceOfOneBTC;
 pub use core_report::ReportId;
@@ -56,6 +56,8 @@ enum Commands {
     Genencryptionkey,
     /// Generate default configuration file (lana.yml) with all default values
     DumpDefaultConfig,
+    /// Verify the hash chain over the audit log and report the first broken link
+    VerifyAuditChain,
     /// Run the main server (default when no subcommand is specified)
     Run,
 }
@@ -80,6 +82,39 @@ pub async fn run() -> anyhow::Result<()> {
             println!("{yaml_output}");
             return Ok(());
         }
+        Commands::VerifyAuditChain => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(1)
+                .connect(&cli.pg_con)
+                .await?;
+            let verification = lana_app::audit::verify_chain(&pool).await?;
+            println!("Verified audit entries: {}", verification.n_entries);
+            if let Some((id, hash)) = &verification.head {
+                println!("Chain head: {id} {hash}");
+            }
+            if let Some(chain_break) = verification.first_break {
+                let reason = match chain_break.reason {
+                    lana_app::audit::AuditChainBreakReason::PrevHashMismatch => {
+                        "does not link to its predecessor"
+                    }
+                    lana_app::audit::AuditChainBreakReason::HashMismatch => {
+                        "content does not match its hash"
+                    }
+                    lana_app::audit::AuditChainBreakReason::PositionGap => {
+                        "chain position does not follow its predecessor"
+                    }
+                    lana_app::audit::AuditChainBreakReason::Unchained => {
+                        "was never linked to the chain"
+                    }
+                    lana_app::audit::AuditChainBreakReason::AnchorMismatch => {
+                        "chain does not match its anchored head"
+                    }
+                };
+                anyhow::bail!("Audit chain broken at entry {}: {reason}", chain_break.id);
+            }
+            println!("Audit chain intact");
+            return Ok(());
+        }
         Commands::Run => {
             let config = Config::init(
                 cli.config,
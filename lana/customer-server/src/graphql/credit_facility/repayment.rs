@@ -6,6 +6,7 @@ use crate::primitives::*;
 pub enum CreditFacilityRepaymentType {
     Disbursal,
     Interest,
+    Penalty,
 }
 
 impl From<lana_app::credit::RepaymentType> for CreditFacilityRepaymentType {
@@ -13,6 +14,7 @@ impl From<lana_app::credit::RepaymentType> for CreditFacilityRepaymentType {
         match repayment_type {
             lana_app::credit::RepaymentType::Disbursal => Self::Disbursal,
             lana_app::credit::RepaymentType::Interest => Self::Interest,
+            lana_app::credit::RepaymentType::Penalty => Self::Penalty,
         }
     }
 }
@@ -42,6 +44,23 @@ impl From<lana_app::credit::RepaymentStatus> for CreditFacilityRepaymentStatus {
     }
 }
 
+#[derive(SimpleObject)]
+pub struct CreditFacilityRepaymentInstallment {
+    pub principal: UsdCents,
+    pub interest: UsdCents,
+    pub total: UsdCents,
+}
+
+impl From<lana_app::credit::RepaymentInstallment> for CreditFacilityRepaymentInstallment {
+    fn from(installment: lana_app::credit::RepaymentInstallment) -> Self {
+        Self {
+            principal: installment.principal,
+            interest: installment.interest,
+            total: installment.total(),
+        }
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct CreditFacilityRepaymentPlanEntry {
     pub repayment_type: CreditFacilityRepaymentType,
@@ -50,6 +69,7 @@ pub struct CreditFacilityRepaymentPlanEntry {
     pub outstanding: UsdCents,
     pub accrual_at: Timestamp,
     pub due_at: Timestamp,
+    pub installment: Option<CreditFacilityRepaymentInstallment>,
 }
 
 impl From<lana_app::credit::CreditFacilityRepaymentPlanEntry> for CreditFacilityRepaymentPlanEntry {
@@ -61,6 +81,7 @@ impl From<lana_app::credit::CreditFacilityRepaymentPlanEntry> for CreditFacility
             outstanding: repayment.outstanding,
             accrual_at: repayment.recorded_at.into(),
             due_at: repayment.due_at.start_of_day().into(),
+            installment: repayment.installment.map(Into::into),
         }
     }
 }
@@ -13,8 +13,11 @@ pub enum DepositAccountHistoryEntry {
     Deposit(DepositEntry),
     Withdrawal(WithdrawalEntry),
     CancelledWithdrawal(CancelledWithdrawalEntry),
+    Transfer(TransferEntry),
     Disbursal(DisbursalEntry),
     Payment(PaymentEntry),
+    InterestAccrual(InterestAccrualEntry),
+    InterestPayment(InterestPaymentEntry),
     Unknown(UnknownEntry),
 }
 
@@ -41,6 +44,12 @@ pub struct CancelledWithdrawalEntry {
     pub recorded_at: Timestamp,
 }
 
+#[derive(SimpleObject)]
+pub struct TransferEntry {
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+}
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub struct DisbursalEntry {
@@ -57,6 +66,18 @@ pub struct PaymentEntry {
     pub recorded_at: Timestamp,
 }
 
+#[derive(SimpleObject)]
+pub struct InterestAccrualEntry {
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+}
+
+#[derive(SimpleObject)]
+pub struct InterestPaymentEntry {
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+}
+
 #[derive(SimpleObject)]
 pub struct UnknownEntry {
     pub tx_id: UUID,
@@ -162,6 +183,12 @@ impl From<lana_app::deposit::DepositAccountHistoryEntry> for DepositAccountHisto
                     recorded_at: entry.recorded_at.into(),
                 })
             }
+            lana_app::deposit::DepositAccountHistoryEntry::Transfer(entry) => {
+                Self::Transfer(TransferEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                })
+            }
             lana_app::deposit::DepositAccountHistoryEntry::Disbursal(entry) => {
                 Self::Disbursal(DisbursalEntry {
                     tx_id: UUID::from(entry.tx_id),
@@ -174,6 +201,18 @@ impl From<lana_app::deposit::DepositAccountHistoryEntry> for DepositAccountHisto
                     recorded_at: entry.recorded_at.into(),
                 })
             }
+            lana_app::deposit::DepositAccountHistoryEntry::InterestAccrual(entry) => {
+                Self::InterestAccrual(InterestAccrualEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                })
+            }
+            lana_app::deposit::DepositAccountHistoryEntry::InterestPayment(entry) => {
+                Self::InterestPayment(InterestPaymentEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                })
+            }
             lana_app::deposit::DepositAccountHistoryEntry::Unknown(entry) => {
                 Self::Unknown(UnknownEntry {
                     tx_id: UUID::from(entry.tx_id),
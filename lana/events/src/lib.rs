@@ -8,6 +8,7 @@ pub use core_credit::{CollateralAction, CoreCreditEvent, ObligationStatus, Oblig
 pub use core_custody::CoreCustodyEvent;
 pub use core_customer::CoreCustomerEvent;
 pub use core_deposit::CoreDepositEvent;
+pub use core_price::CorePriceEvent;
 pub use core_report::CoreReportEvent;
 pub use governance::GovernanceEvent;
 pub use outbox::OutboxEventMarker;
@@ -22,6 +23,7 @@ pub enum LanaEvent {
     Deposit(CoreDepositEvent),
     Custody(CoreCustodyEvent),
     Report(CoreReportEvent),
+    Price(CorePriceEvent),
 }
 
 macro_rules! impl_event_marker {
@@ -49,3 +51,4 @@ impl_event_marker!(CoreDepositEvent, Deposit);
 impl_event_marker!(CoreCustomerEvent, Customer);
 impl_event_marker!(CoreCustodyEvent, Custody);
 impl_event_marker!(CoreReportEvent, Report);
+impl_event_marker!(CorePriceEvent, Price);
@@ -14,11 +14,19 @@ pub enum EmailError {
     #[error("EmailError - User: {0}")]
     User(#[from] core_access::user::error::UserError),
     #[error("EmailError - CoreCredit: {0}")]
-    CoreCredit(#[from] core_credit::error::CoreCreditError),
+    CoreCredit(Box<core_credit::error::CoreCreditError>),
     #[error("EmailError - Customer: {0}")]
     Customer(#[from] core_customer::error::CustomerError),
     #[error("EmailError - Obligation: {0}")]
     Obligation(#[from] core_credit::ObligationError),
     #[error("EmailError - CreditFacility: {0}")]
     CreditFacility(#[from] core_credit::CreditFacilityError),
+    #[error("EmailError - MarginCall: {0}")]
+    MarginCall(#[from] core_credit::MarginCallError),
+}
+
+impl From<core_credit::error::CoreCreditError> for EmailError {
+    fn from(e: core_credit::error::CoreCreditError) -> Self {
+        Self::CoreCredit(Box::new(e))
+    }
 }
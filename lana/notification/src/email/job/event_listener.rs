@@ -3,10 +3,11 @@ use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use tracing::{Span, instrument};
 
+use core_credit::MarginCallStatus;
 use job::{
     CurrentJob, Job, JobCompletion, JobConfig, JobInitializer, JobRunner, JobType, RetrySettings,
 };
-use lana_events::{CoreAccessEvent, CoreCreditEvent, CoreDepositEvent, LanaEvent};
+use lana_events::{CoreAccessEvent, CoreCreditEvent, CoreDepositEvent, GovernanceEvent, LanaEvent};
 use outbox::{Outbox, PersistentOutboxEvent};
 
 use crate::email::EmailNotification;
@@ -166,6 +167,24 @@ where
                     .send_obligation_overdue_notification(op, id, credit_facility_id, amount)
                     .await?;
             }
+            Some(LanaEvent::Credit(
+                credit_event @ (CoreCreditEvent::MarginCallOpened { id, .. }
+                | CoreCreditEvent::MarginCallCured { id, .. }
+                | CoreCreditEvent::MarginCallEscalated { id, .. }),
+            )) => {
+                message.inject_trace_parent();
+                Span::current().record("handled", true);
+                Span::current().record("event_type", credit_event.as_ref());
+
+                let status = match credit_event {
+                    CoreCreditEvent::MarginCallOpened { .. } => MarginCallStatus::Open,
+                    CoreCreditEvent::MarginCallCured { .. } => MarginCallStatus::Cured,
+                    _ => MarginCallStatus::Escalated,
+                };
+                self.email_notification
+                    .send_margin_call_notification(op, id, status)
+                    .await?;
+            }
             Some(LanaEvent::Deposit(
                 deposit_event @ CoreDepositEvent::DepositAccountCreated {
                     id,
@@ -189,6 +208,27 @@ where
                     .send_role_created_notification(op, id, name)
                     .await?;
             }
+            Some(LanaEvent::Governance(
+                governance_event @ GovernanceEvent::ApprovalProcessReminderSent {
+                    process_type,
+                    pending_voters,
+                    target_ref,
+                    ..
+                },
+            )) => {
+                message.inject_trace_parent();
+                Span::current().record("handled", true);
+                Span::current().record("event_type", governance_event.as_ref());
+
+                self.email_notification
+                    .send_approval_reminder_notification(
+                        op,
+                        process_type,
+                        target_ref,
+                        pending_voters,
+                    )
+                    .await?;
+            }
             _ => {}
         }
         Ok(())
@@ -4,16 +4,22 @@ pub mod job;
 pub mod templates;
 
 use ::job::{JobId, Jobs};
-use core_access::user::Users;
-use core_credit::{CoreCredit, CreditFacilityId, ObligationId, ObligationType};
+use core_access::{
+    UserId,
+    user::{User, Users},
+};
+use core_credit::{
+    CoreCredit, CreditFacilityId, MarginCallId, MarginCallStatus, ObligationId, ObligationType,
+};
 use core_customer::Customers;
+use governance::{ApprovalProcessType, CommitteeMemberId};
 use job::{EmailSenderConfig, EmailSenderInit};
 use lana_events::LanaEvent;
 use smtp_client::SmtpClient;
 
 use templates::{
-    DepositAccountCreatedEmailData, EmailTemplate, EmailType, OverduePaymentEmailData,
-    RoleCreatedEmailData,
+    ApprovalReminderEmailData, DepositAccountCreatedEmailData, EmailTemplate, EmailType,
+    MarginCallEmailData, OverduePaymentEmailData, RoleCreatedEmailData,
 };
 
 pub use config::EmailConfig;
@@ -97,6 +103,7 @@ where
             payment_type: match obligation.obligation_type {
                 ObligationType::Disbursal => "Principal Repayment".to_string(),
                 ObligationType::Interest => "Interest Payment".to_string(),
+                ObligationType::Penalty => "Penalty Payment".to_string(),
             },
             original_amount: obligation.initial_amount,
             outstanding_amount: *amount,
@@ -135,6 +142,50 @@ where
         Ok(())
     }
 
+    pub async fn send_margin_call_notification(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        margin_call_id: &MarginCallId,
+        status: MarginCallStatus,
+    ) -> Result<(), EmailError> {
+        let margin_call = self
+            .credit
+            .margin_calls()
+            .find_by_id_without_audit(*margin_call_id)
+            .await?;
+
+        let credit_facility = self
+            .credit
+            .facilities()
+            .find_by_id_without_audit(margin_call.credit_facility_id)
+            .await?;
+
+        let customer = self
+            .customers
+            .find_by_id_without_audit(credit_facility.customer_id)
+            .await?;
+
+        let email_data = MarginCallEmailData {
+            facility_id: credit_facility.id.to_string(),
+            status,
+            collateral_required: margin_call.collateral_required,
+            repayment_required: margin_call.repayment_required,
+            cure_deadline: margin_call.cure_deadline,
+            customer_email: customer.email.clone(),
+        };
+
+        let email_config = EmailSenderConfig {
+            recipient: customer.email,
+            email_type: EmailType::MarginCall(email_data),
+        };
+
+        self.jobs
+            .create_and_spawn_in_op(op, JobId::new(), email_config)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn send_deposit_account_created_notification(
         &self,
         op: &mut impl es_entity::AtomicOperation,
@@ -201,4 +252,31 @@ where
         }
         Ok(())
     }
+
+    pub async fn send_approval_reminder_notification(
+        &self,
+        op: &mut impl es_entity::AtomicOperation,
+        process_type: &ApprovalProcessType,
+        target_ref: &str,
+        pending_voters: &[CommitteeMemberId],
+    ) -> Result<(), EmailError> {
+        let user_ids: Vec<UserId> = pending_voters.iter().map(|id| (*id).into()).collect();
+        let users = self.users.find_all::<User>(&user_ids).await?;
+
+        let email_data = ApprovalReminderEmailData {
+            process_type: process_type.to_string(),
+            target_ref: target_ref.to_string(),
+        };
+
+        for user in users.into_values() {
+            let email_config = EmailSenderConfig {
+                recipient: user.email,
+                email_type: EmailType::ApprovalReminder(email_data.clone()),
+            };
+            self.jobs
+                .create_and_spawn_in_op(op, JobId::new(), email_config)
+                .await?;
+        }
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
-use core_money::UsdCents;
+use core_credit::MarginCallStatus;
+use core_money::{Satoshis, UsdCents};
 use handlebars::Handlebars;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -9,8 +10,10 @@ use crate::email::error::EmailError;
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EmailType {
     OverduePayment(OverduePaymentEmailData),
+    MarginCall(MarginCallEmailData),
     DepositAccountCreated(DepositAccountCreatedEmailData),
     RoleCreated(RoleCreatedEmailData),
+    ApprovalReminder(ApprovalReminderEmailData),
     General { subject: String, body: String },
 }
 
@@ -28,12 +31,18 @@ impl EmailTemplate {
         handlebars.register_template_string("styles", include_str!("partials/styles.hbs"))?;
         handlebars.register_template_string("general", include_str!("views/general.hbs"))?;
         handlebars.register_template_string("overdue", include_str!("views/overdue.hbs"))?;
+        handlebars
+            .register_template_string("margin_call", include_str!("views/margin_call.hbs"))?;
         handlebars.register_template_string(
             "account_created",
             include_str!("views/account_created.hbs"),
         )?;
         handlebars
             .register_template_string("role_created", include_str!("views/role_created.hbs"))?;
+        handlebars.register_template_string(
+            "approval_reminder",
+            include_str!("views/approval_reminder.hbs"),
+        )?;
         Ok(Self {
             handlebars,
             admin_panel_url,
@@ -44,10 +53,12 @@ impl EmailTemplate {
     pub fn render_email(&self, email_type: &EmailType) -> Result<(String, String), EmailError> {
         match email_type {
             EmailType::OverduePayment(data) => self.render_overdue_payment_email(data),
+            EmailType::MarginCall(data) => self.render_margin_call_email(data),
             EmailType::DepositAccountCreated(data) => {
                 self.render_deposit_account_created_email(data)
             }
             EmailType::RoleCreated(data) => self.render_role_created_email(data),
+            EmailType::ApprovalReminder(data) => self.render_approval_reminder_email(data),
             EmailType::General { subject, body } => self.generic_email_template(subject, body),
         }
     }
@@ -93,6 +104,31 @@ impl EmailTemplate {
         Ok((subject, html_body))
     }
 
+    #[allow(clippy::result_large_err)]
+    fn render_margin_call_email(
+        &self,
+        data: &MarginCallEmailData,
+    ) -> Result<(String, String), EmailError> {
+        let subject = match data.status {
+            MarginCallStatus::Open => "Lana Bank: Margin Call on Your Credit Facility",
+            MarginCallStatus::Cured => "Lana Bank: Margin Call Resolved",
+            MarginCallStatus::Escalated => "Lana Bank: Margin Call Escalated to Liquidation",
+        }
+        .to_string();
+        let data = json!({
+            "subject": &subject,
+            "facility_id": &data.facility_id,
+            "is_open": data.status == MarginCallStatus::Open,
+            "is_cured": data.status == MarginCallStatus::Cured,
+            "is_escalated": data.status == MarginCallStatus::Escalated,
+            "collateral_required": data.collateral_required.formatted_btc(),
+            "repayment_required": data.repayment_required.formatted_usd(),
+            "cure_deadline": data.cure_deadline,
+        });
+        let html_body = self.handlebars.render("margin_call", &data)?;
+        Ok((subject, html_body))
+    }
+
     #[allow(clippy::result_large_err)]
     fn render_deposit_account_created_email(
         &self,
@@ -122,6 +158,22 @@ impl EmailTemplate {
         let html_body = self.handlebars.render("role_created", &data)?;
         Ok((subject, html_body))
     }
+
+    #[allow(clippy::result_large_err)]
+    fn render_approval_reminder_email(
+        &self,
+        data: &ApprovalReminderEmailData,
+    ) -> Result<(String, String), EmailError> {
+        let subject = format!("Lana Bank: Approval Pending - {}", data.process_type);
+        let data = json!({
+            "subject": &subject,
+            "process_type": &data.process_type,
+            "target_ref": &data.target_ref,
+            "admin_panel_url": &self.admin_panel_url,
+        });
+        let html_body = self.handlebars.render("approval_reminder", &data)?;
+        Ok((subject, html_body))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +186,16 @@ pub struct OverduePaymentEmailData {
     pub customer_email: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginCallEmailData {
+    pub facility_id: String,
+    pub status: MarginCallStatus,
+    pub collateral_required: Satoshis,
+    pub repayment_required: UsdCents,
+    pub cure_deadline: DateTime<Utc>,
+    pub customer_email: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DepositAccountCreatedEmailData {
     pub account_id: String,
@@ -145,3 +207,9 @@ pub struct RoleCreatedEmailData {
     pub role_id: String,
     pub role_name: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalReminderEmailData {
+    pub process_type: String,
+    pub target_ref: String,
+}
@@ -6,7 +6,13 @@ use ::job::error::JobError;
 #[derive(Error, Debug)]
 pub enum NotificationError {
     #[error("NotificationError - Email: {0}")]
-    Email(#[from] EmailError),
+    Email(Box<EmailError>),
     #[error("NotificationError - Job: {0}")]
     Job(#[from] JobError),
 }
+
+impl From<EmailError> for NotificationError {
+    fn from(e: EmailError) -> Self {
+        Self::Email(Box::new(e))
+    }
+}
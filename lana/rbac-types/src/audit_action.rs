@@ -46,12 +46,13 @@ impl FromStr for AuditAction {
 #[strum(serialize_all = "kebab-case")]
 pub enum AuditEntityAction {
     List,
+    Export,
 }
 
 impl ActionPermission for AuditEntityAction {
     fn permission_set(&self) -> &'static str {
         match self {
-            Self::List => PERMISSION_SET_AUDIT_VIEWER,
+            Self::List | Self::Export => PERMISSION_SET_AUDIT_VIEWER,
         }
     }
 }
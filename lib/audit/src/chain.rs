@@ -0,0 +1,395 @@
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::{error::AuditError, primitives::AuditEntryId};
+
+/// `prev_hash` of the very first entry in the chain.
+pub(crate) const GENESIS_HASH: [u8; 32] = [0; 32];
+
+/// Key of the transaction scoped advisory lock serializing extensions of the chain.
+pub(crate) const AUDIT_CHAIN_LOCK_KEY: i64 = 0x6175_6469_745f_6368;
+
+/// How often pending entries are expected to be linked to the chain. Entries that are
+/// still unchained well after that were most likely written around the sequencer.
+pub const AUDIT_CHAIN_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Age after which an unchained entry is reported as a break. Leaves room for
+/// transactions that were still open when the sequencer last ran.
+const UNCHAINED_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(60);
+
+pub(crate) fn entry_hash(
+    id: AuditEntryId,
+    subject: &str,
+    object: &str,
+    action: &str,
+    authorized: bool,
+    recorded_at: DateTime<Utc>,
+    prev_hash: &[u8],
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(i64::from(id).to_be_bytes());
+    for field in [subject, object, action] {
+        hasher.update((field.len() as u64).to_be_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.update([authorized as u8]);
+    hasher.update(recorded_at.timestamp_micros().to_be_bytes());
+    hasher.update(prev_hash);
+    hasher.finalize().to_vec()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditChainBreakReason {
+    /// The entry does not point at the hash of its predecessor,
+    /// e.g. because an entry was deleted or inserted out of band.
+    PrevHashMismatch,
+    /// The stored hash does not match the entry's content.
+    HashMismatch,
+    /// The entry's chain position does not follow the one of its predecessor.
+    PositionGap,
+    /// The entry was recorded well before the last sequencer run but is not chained.
+    Unchained,
+    /// The chain does not reach or does not match the last head anchored outside of
+    /// `audit_entries`, e.g. because its tail was truncated or rewritten.
+    AnchorMismatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditChainBreak {
+    pub id: AuditEntryId,
+    pub reason: AuditChainBreakReason,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditChainVerification {
+    pub n_entries: usize,
+    /// Last verified entry and its hex encoded hash. Anchoring it externally
+    /// allows detecting truncation of the tail of the chain.
+    pub head: Option<(AuditEntryId, String)>,
+    pub first_break: Option<AuditChainBreak>,
+}
+
+impl AuditChainVerification {
+    pub fn is_intact(&self) -> bool {
+        self.first_break.is_none()
+    }
+}
+
+pub(crate) struct ChainedAuditEntry {
+    pub id: AuditEntryId,
+    pub chain_position: i64,
+    pub subject: String,
+    pub object: String,
+    pub action: String,
+    pub authorized: bool,
+    pub recorded_at: DateTime<Utc>,
+    pub prev_hash: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+/// Chain head recorded in `audit_chain_heads` each time the sequencer extends the chain.
+pub(crate) struct ChainAnchor {
+    pub chain_position: i64,
+    pub entry_id: AuditEntryId,
+    pub hash: Vec<u8>,
+}
+
+struct ChainVerifier {
+    n_entries: usize,
+    head: Option<(AuditEntryId, Vec<u8>)>,
+    anchor: Option<ChainAnchor>,
+}
+
+impl ChainVerifier {
+    fn new(anchor: Option<ChainAnchor>) -> Self {
+        Self {
+            n_entries: 0,
+            head: None,
+            anchor,
+        }
+    }
+
+    fn check(&mut self, entry: ChainedAuditEntry) -> Option<AuditChainBreak> {
+        if entry.chain_position != self.n_entries as i64 + 1 {
+            return Some(AuditChainBreak {
+                id: entry.id,
+                reason: AuditChainBreakReason::PositionGap,
+            });
+        }
+
+        let expected_prev = self
+            .head
+            .as_ref()
+            .map(|(_, hash)| hash.as_slice())
+            .unwrap_or(&GENESIS_HASH);
+        if entry.prev_hash != expected_prev {
+            return Some(AuditChainBreak {
+                id: entry.id,
+                reason: AuditChainBreakReason::PrevHashMismatch,
+            });
+        }
+
+        let hash = entry_hash(
+            entry.id,
+            &entry.subject,
+            &entry.object,
+            &entry.action,
+            entry.authorized,
+            entry.recorded_at,
+            &entry.prev_hash,
+        );
+        if hash != entry.hash {
+            return Some(AuditChainBreak {
+                id: entry.id,
+                reason: AuditChainBreakReason::HashMismatch,
+            });
+        }
+
+        if let Some(anchor) = &self.anchor
+            && anchor.chain_position == entry.chain_position
+            && (anchor.entry_id != entry.id || anchor.hash != hash)
+        {
+            return Some(AuditChainBreak {
+                id: entry.id,
+                reason: AuditChainBreakReason::AnchorMismatch,
+            });
+        }
+
+        self.n_entries += 1;
+        self.head = Some((entry.id, hash));
+        None
+    }
+
+    /// Reports a break if the chain ended before reaching the anchored head.
+    fn check_anchor_reached(&self) -> Option<AuditChainBreak> {
+        self.anchor
+            .as_ref()
+            .filter(|anchor| anchor.chain_position > self.n_entries as i64)
+            .map(|anchor| AuditChainBreak {
+                id: anchor.entry_id,
+                reason: AuditChainBreakReason::AnchorMismatch,
+            })
+    }
+
+    fn finish(self, first_break: Option<AuditChainBreak>) -> AuditChainVerification {
+        AuditChainVerification {
+            n_entries: self.n_entries,
+            head: self.head.map(|(id, hash)| (id, hex::encode(hash))),
+            first_break,
+        }
+    }
+}
+
+/// Walks the chained part of `audit_entries` in chain order and reports the first entry whose
+/// position, link to its predecessor or own hash does not verify, checking the chain against
+/// the last head anchored in `audit_chain_heads`. An intact chain is then checked for entries
+/// that should have been chained by now.
+/// Only needs a connection to the database so it can be run independently of the application.
+pub async fn verify_chain(pool: &sqlx::PgPool) -> Result<AuditChainVerification, AuditError> {
+    use futures::TryStreamExt;
+
+    let anchor = sqlx::query_as!(
+        ChainAnchor,
+        r#"
+        SELECT chain_position, entry_id AS "entry_id: AuditEntryId", hash
+        FROM audit_chain_heads
+        ORDER BY chain_position DESC
+        LIMIT 1
+        "#
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let mut verifier = ChainVerifier::new(anchor);
+    let mut rows = sqlx::query_as!(
+        ChainedAuditEntry,
+        r#"
+        SELECT id AS "id: AuditEntryId", chain_position AS "chain_position!", subject, object,
+          action, authorized, recorded_at, prev_hash AS "prev_hash!", hash AS "hash!"
+        FROM audit_entries
+        WHERE chain_position IS NOT NULL
+        ORDER BY chain_position ASC
+        "#
+    )
+    .fetch(pool);
+    while let Some(entry) = rows.try_next().await? {
+        if let Some(chain_break) = verifier.check(entry) {
+            return Ok(verifier.finish(Some(chain_break)));
+        }
+    }
+    drop(rows);
+    if let Some(chain_break) = verifier.check_anchor_reached() {
+        return Ok(verifier.finish(Some(chain_break)));
+    }
+
+    let unchained = sqlx::query_scalar!(
+        r#"
+        SELECT id AS "id: AuditEntryId"
+        FROM audit_entries
+        WHERE chain_position IS NULL AND recorded_at < NOW() - make_interval(secs => $1)
+        ORDER BY id ASC
+        LIMIT 1
+        "#,
+        UNCHAINED_GRACE_PERIOD.as_secs_f64(),
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(verifier.finish(unchained.map(|id| AuditChainBreak {
+        id,
+        reason: AuditChainBreakReason::Unchained,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(n: i64) -> Vec<ChainedAuditEntry> {
+        let recorded_at: DateTime<Utc> = "2025-01-01T00:00:00Z".parse().unwrap();
+        let mut prev_hash = GENESIS_HASH.to_vec();
+        (1..=n)
+            .map(|i| {
+                let id = AuditEntryId::from(i);
+                let subject = format!("user:{i}");
+                let hash = entry_hash(
+                    id,
+                    &subject,
+                    "audit:audit",
+                    "audit:audit:list",
+                    true,
+                    recorded_at,
+                    &prev_hash,
+                );
+                ChainedAuditEntry {
+                    id,
+                    chain_position: i,
+                    subject,
+                    object: "audit:audit".to_string(),
+                    action: "audit:audit:list".to_string(),
+                    authorized: true,
+                    recorded_at,
+                    prev_hash: std::mem::replace(&mut prev_hash, hash.clone()),
+                    hash,
+                }
+            })
+            .collect()
+    }
+
+    fn verify_anchored(
+        entries: Vec<ChainedAuditEntry>,
+        anchor: Option<ChainAnchor>,
+    ) -> AuditChainVerification {
+        let mut verifier = ChainVerifier::new(anchor);
+        for entry in entries {
+            if let Some(chain_break) = verifier.check(entry) {
+                return verifier.finish(Some(chain_break));
+            }
+        }
+        let chain_break = verifier.check_anchor_reached();
+        verifier.finish(chain_break)
+    }
+
+    fn verify(entries: Vec<ChainedAuditEntry>) -> AuditChainVerification {
+        verify_anchored(entries, None)
+    }
+
+    fn anchor_at(entry: &ChainedAuditEntry) -> Option<ChainAnchor> {
+        Some(ChainAnchor {
+            chain_position: entry.chain_position,
+            entry_id: entry.id,
+            hash: entry.hash.clone(),
+        })
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let res = verify(chain(3));
+        assert!(res.is_intact());
+        assert_eq!(res.n_entries, 3);
+        assert_eq!(res.head.unwrap().0, AuditEntryId::from(3));
+    }
+
+    #[test]
+    fn tampered_entry_is_detected() {
+        let mut entries = chain(3);
+        entries[1].authorized = false;
+        let res = verify(entries);
+        assert_eq!(
+            res.first_break,
+            Some(AuditChainBreak {
+                id: AuditEntryId::from(2),
+                reason: AuditChainBreakReason::HashMismatch,
+            })
+        );
+        assert_eq!(res.n_entries, 1);
+    }
+
+    #[test]
+    fn deleted_entry_is_detected() {
+        let mut entries = chain(3);
+        entries.remove(1);
+        let res = verify(entries);
+        assert_eq!(
+            res.first_break,
+            Some(AuditChainBreak {
+                id: AuditEntryId::from(3),
+                reason: AuditChainBreakReason::PositionGap,
+            })
+        );
+    }
+
+    #[test]
+    fn relinked_entry_after_deletion_is_detected() {
+        let mut entries = chain(3);
+        entries.remove(1);
+        entries[1].chain_position = 2;
+        let res = verify(entries);
+        assert_eq!(
+            res.first_break,
+            Some(AuditChainBreak {
+                id: AuditEntryId::from(3),
+                reason: AuditChainBreakReason::PrevHashMismatch,
+            })
+        );
+    }
+
+    #[test]
+    fn chain_reaching_anchor_verifies() {
+        let entries = chain(3);
+        let anchor = anchor_at(&entries[1]);
+        assert!(verify_anchored(entries, anchor).is_intact());
+    }
+
+    #[test]
+    fn truncated_tail_is_detected() {
+        let mut entries = chain(3);
+        let anchor = anchor_at(&entries[2]);
+        entries.pop();
+        let res = verify_anchored(entries, anchor);
+        assert_eq!(
+            res.first_break,
+            Some(AuditChainBreak {
+                id: AuditEntryId::from(3),
+                reason: AuditChainBreakReason::AnchorMismatch,
+            })
+        );
+    }
+
+    #[test]
+    fn rewritten_chain_is_detected() {
+        let entries = chain(3);
+        let mut anchor = anchor_at(&entries[2]);
+        if let Some(anchor) = anchor.as_mut() {
+            anchor.hash = GENESIS_HASH.to_vec();
+        }
+        let res = verify_anchored(entries, anchor);
+        assert_eq!(
+            res.first_break,
+            Some(AuditChainBreak {
+                id: AuditEntryId::from(3),
+                reason: AuditChainBreakReason::AnchorMismatch,
+            })
+        );
+    }
+}
@@ -10,4 +10,10 @@ pub enum AuditError {
     ObjectParseError(String),
     #[error("AuditError - ActionParseError: Could not parse '{0}'")]
     ActionParseError(String),
+    #[error("AuditError - Csv: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("AuditError - SerdeJson: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("AuditError - ExportSink: {0}")]
+    ExportSink(Box<dyn std::error::Error + Send + Sync>),
 }
@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{error::AuditError, primitives::AuditEntryId};
+
+/// Amount of serialized content buffered before it is handed to the sink.
+const EXPORT_CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::Display, strum::EnumString,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum AuditExportFormat {
+    Csv,
+    Jsonl,
+}
+
+impl AuditExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Jsonl => "application/x-ndjson",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// Receives the content of an extract chunk by chunk while it is being written.
+#[async_trait]
+pub trait AuditExportSink: Send {
+    async fn write_chunk(
+        &mut self,
+        chunk: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Summary of an extract handed to a sink together with the SHA-256 checksum of its content.
+#[derive(Debug)]
+pub struct AuditExtract {
+    pub format: AuditExportFormat,
+    pub checksum: String,
+    pub n_entries: usize,
+}
+
+#[derive(sqlx::FromRow, Serialize)]
+pub(crate) struct RawAuditEntry {
+    pub id: AuditEntryId,
+    pub subject: String,
+    pub object: String,
+    pub action: String,
+    pub authorized: bool,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Serializes entries into chunks of roughly `EXPORT_CHUNK_SIZE` and hashes every chunk
+/// as it is taken so the checksum is known without holding the whole extract.
+pub(crate) struct ExtractWriter {
+    format: AuditExportFormat,
+    buffer: Vec<u8>,
+    hasher: Sha256,
+    n_entries: usize,
+}
+
+impl ExtractWriter {
+    pub fn new(format: AuditExportFormat) -> Self {
+        Self {
+            format,
+            buffer: Vec::new(),
+            hasher: Sha256::new(),
+            n_entries: 0,
+        }
+    }
+
+    pub fn write(&mut self, entry: &RawAuditEntry) -> Result<(), AuditError> {
+        match self.format {
+            AuditExportFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(self.n_entries == 0)
+                    .from_writer(&mut self.buffer);
+                writer.serialize(entry)?;
+                writer.flush().map_err(csv::Error::from)?;
+            }
+            AuditExportFormat::Jsonl => {
+                serde_json::to_writer(&mut self.buffer, entry)?;
+                self.buffer.push(b'\n');
+            }
+        }
+        self.n_entries += 1;
+        Ok(())
+    }
+
+    /// Returns the buffered content once enough of it has accumulated.
+    pub fn take_full_chunk(&mut self) -> Option<Vec<u8>> {
+        if self.buffer.len() >= EXPORT_CHUNK_SIZE {
+            Some(self.take_chunk())
+        } else {
+            None
+        }
+    }
+
+    /// Returns whatever content is left together with the summary of the whole extract.
+    pub fn finish(mut self) -> (Vec<u8>, AuditExtract) {
+        let last_chunk = self.take_chunk();
+        let checksum = hex::encode(self.hasher.finalize());
+
+        (
+            last_chunk,
+            AuditExtract {
+                format: self.format,
+                checksum,
+                n_entries: self.n_entries,
+            },
+        )
+    }
+
+    fn take_chunk(&mut self) -> Vec<u8> {
+        let chunk = std::mem::take(&mut self.buffer);
+        self.hasher.update(&chunk);
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: i64) -> RawAuditEntry {
+        RawAuditEntry {
+            id: AuditEntryId::from(id),
+            subject: "user:1".to_string(),
+            object: "customer:customer/1".to_string(),
+            action: "customer:customer:read".to_string(),
+            authorized: id % 2 == 0,
+            recorded_at: "2025-01-01T00:00:00Z".parse().unwrap(),
+        }
+    }
+
+    fn write_all(
+        format: AuditExportFormat,
+        ids: impl Iterator<Item = i64>,
+    ) -> (Vec<u8>, AuditExtract) {
+        let mut writer = ExtractWriter::new(format);
+        let mut content = Vec::new();
+        for id in ids {
+            writer.write(&entry(id)).unwrap();
+            if let Some(chunk) = writer.take_full_chunk() {
+                content.extend(chunk);
+            }
+        }
+        let (last_chunk, extract) = writer.finish();
+        content.extend(last_chunk);
+        (content, extract)
+    }
+
+    #[test]
+    fn csv_extract_has_header_and_checksum() {
+        let (content, extract) = write_all(AuditExportFormat::Csv, 1..=2);
+
+        let text = String::from_utf8(content.clone()).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("id,subject,object,action,authorized,recorded_at")
+        );
+        assert_eq!(lines.count(), 2);
+        assert_eq!(extract.n_entries, 2);
+        assert_eq!(extract.checksum, hex::encode(Sha256::digest(&content)));
+    }
+
+    #[test]
+    fn jsonl_extract_has_one_line_per_entry() {
+        let (content, _) = write_all(AuditExportFormat::Jsonl, 1..=2);
+
+        let content = String::from_utf8(content).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["id"], 1);
+        assert_eq!(first["authorized"], false);
+    }
+
+    #[test]
+    fn chunked_extract_checksum_covers_all_chunks() {
+        let mut writer = ExtractWriter::new(AuditExportFormat::Csv);
+        let mut n_chunks = 0;
+        let mut content = Vec::new();
+        for id in 1..=20_000 {
+            writer.write(&entry(id)).unwrap();
+            if let Some(chunk) = writer.take_full_chunk() {
+                n_chunks += 1;
+                content.extend(chunk);
+            }
+        }
+        let (last_chunk, extract) = writer.finish();
+        content.extend(last_chunk);
+
+        assert!(n_chunks > 0);
+        let text = String::from_utf8(content.clone()).unwrap();
+        assert_eq!(text.lines().count(), 20_001);
+        assert_eq!(extract.checksum, hex::encode(Sha256::digest(&content)));
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+
+/// Criteria for narrowing down audit entries. Unset fields do not restrict the result.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditFilter {
+    pub subject: Option<String>,
+    pub object_prefix: Option<String>,
+    pub action: Option<String>,
+    pub authorized: Option<bool>,
+    pub recorded_after: Option<DateTime<Utc>>,
+    pub recorded_before: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn subject(mut self, subject: impl std::fmt::Display) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn object_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.object_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn action(mut self, action: impl std::fmt::Display) -> Self {
+        self.action = Some(action.to_string());
+        self
+    }
+
+    pub fn authorized(mut self, authorized: bool) -> Self {
+        self.authorized = Some(authorized);
+        self
+    }
+
+    /// Restricts entries to `from <= recorded_at < until`.
+    pub fn recorded_between(
+        mut self,
+        from: impl Into<Option<DateTime<Utc>>>,
+        until: impl Into<Option<DateTime<Utc>>>,
+    ) -> Self {
+        self.recorded_after = from.into();
+        self.recorded_before = until.into();
+        self
+    }
+
+    pub(crate) fn push_conditions(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push(" WHERE true");
+        if let Some(subject) = &self.subject {
+            builder.push(" AND subject = ").push_bind(subject.clone());
+        }
+        if let Some(prefix) = &self.object_prefix {
+            builder
+                .push(" AND object LIKE ")
+                .push_bind(format!("{}%", escape_like(prefix)));
+        }
+        if let Some(action) = &self.action {
+            builder.push(" AND action = ").push_bind(action.clone());
+        }
+        if let Some(authorized) = self.authorized {
+            builder.push(" AND authorized = ").push_bind(authorized);
+        }
+        if let Some(after) = self.recorded_after {
+            builder.push(" AND recorded_at >= ").push_bind(after);
+        }
+        if let Some(before) = self.recorded_before {
+            builder.push(" AND recorded_at < ").push_bind(before);
+        }
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -3,10 +3,19 @@
 
 use std::{fmt, marker::PhantomData, str::FromStr};
 
+mod chain;
 pub mod error;
+mod export;
+mod filter;
 mod primitives;
 mod svc_trait;
 
+pub use chain::{
+    AUDIT_CHAIN_INTERVAL, AuditChainBreak, AuditChainBreakReason, AuditChainVerification,
+    verify_chain,
+};
+pub use export::{AuditExportFormat, AuditExportSink, AuditExtract};
+pub use filter::AuditFilter;
 pub use primitives::*;
 pub use svc_trait::*;
 
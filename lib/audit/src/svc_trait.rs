@@ -2,7 +2,14 @@ use async_trait::async_trait;
 
 use std::{collections::HashMap, fmt, str::FromStr};
 
-use crate::{AuditEntry, error::AuditError, primitives::*};
+use crate::{
+    AuditEntry,
+    chain::{AUDIT_CHAIN_LOCK_KEY, AuditChainVerification, GENESIS_HASH, entry_hash},
+    error::AuditError,
+    export::{AuditExportFormat, AuditExportSink, AuditExtract, ExtractWriter, RawAuditEntry},
+    filter::AuditFilter,
+    primitives::*,
+};
 
 pub trait SystemSubject {
     fn system() -> Self;
@@ -184,6 +191,206 @@ pub trait AuditSvc: Clone + Sync + Send + 'static {
         })
     }
 
+    async fn search(
+        &self,
+        query: es_entity::PaginatedQueryArgs<AuditCursor>,
+        filter: &AuditFilter,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<
+            AuditEntry<Self::Subject, Self::Object, Self::Action>,
+            AuditCursor,
+        >,
+        AuditError,
+    > {
+        let limit = query.first;
+
+        let mut builder = sqlx::QueryBuilder::new(
+            "SELECT id, subject, object, action, authorized, recorded_at FROM audit_entries",
+        );
+        filter.push_conditions(&mut builder);
+        if let Some(after) = query.after {
+            builder.push(" AND id < ").push_bind(after.id);
+        }
+        builder
+            .push(" ORDER BY id DESC LIMIT ")
+            .push_bind((limit + 1) as i64);
+
+        let rows = builder
+            .build_query_as::<RawAuditEntry>()
+            .fetch_all(self.pool())
+            .await?;
+
+        let has_next_page = rows.len() > limit;
+
+        let entries: Vec<AuditEntry<_, _, _>> = rows
+            .into_iter()
+            .take(limit)
+            .map(|raw_entry| -> Result<AuditEntry<_, _, _>, AuditError> {
+                Ok(AuditEntry {
+                    id: raw_entry.id,
+                    subject: raw_entry
+                        .subject
+                        .parse()
+                        .map_err(|_| AuditError::SubjectParseError(raw_entry.subject))?,
+                    object: raw_entry
+                        .object
+                        .parse()
+                        .map_err(|_| AuditError::ObjectParseError(raw_entry.object))?,
+                    action: raw_entry
+                        .action
+                        .parse()
+                        .map_err(|_| AuditError::ActionParseError(raw_entry.action))?,
+                    authorized: raw_entry.authorized,
+                    recorded_at: raw_entry.recorded_at,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let end_cursor = if has_next_page {
+            entries.last().map(|entry| AuditCursor { id: entry.id })
+        } else {
+            None
+        };
+
+        Ok(es_entity::PaginatedQueryRet {
+            entities: entries,
+            has_next_page,
+            end_cursor,
+        })
+    }
+
+    /// Streams all entries matching `filter` in recording order into `sink`, handing over the
+    /// serialized content in chunks while the checksum is computed along the way.
+    /// Entries are written exactly as stored so the extract can be verified against the table.
+    async fn export<S: AuditExportSink>(
+        &self,
+        filter: &AuditFilter,
+        format: AuditExportFormat,
+        sink: &mut S,
+    ) -> Result<AuditExtract, AuditError> {
+        use futures::TryStreamExt;
+
+        let mut builder = sqlx::QueryBuilder::new(
+            "SELECT id, subject, object, action, authorized, recorded_at FROM audit_entries",
+        );
+        filter.push_conditions(&mut builder);
+        builder.push(" ORDER BY id ASC");
+
+        let mut writer = ExtractWriter::new(format);
+        let mut rows = builder.build_query_as::<RawAuditEntry>().fetch(self.pool());
+        while let Some(row) = rows.try_next().await? {
+            writer.write(&row)?;
+            if let Some(chunk) = writer.take_full_chunk() {
+                sink.write_chunk(chunk)
+                    .await
+                    .map_err(AuditError::ExportSink)?;
+            }
+        }
+
+        let (last_chunk, extract) = writer.finish();
+        if !last_chunk.is_empty() {
+            sink.write_chunk(last_chunk)
+                .await
+                .map_err(AuditError::ExportSink)?;
+        }
+        Ok(extract)
+    }
+
+    /// Links entries committed since the last call to the hash chain in id order and
+    /// anchors the new head in `audit_chain_heads`.
+    /// Runs in short transactions of its own so that recording entries never waits on the chain.
+    async fn chain_pending_entries(&self) -> Result<usize, AuditError> {
+        const BATCH_SIZE: i64 = 1000;
+
+        let mut n_chained = 0;
+        loop {
+            let mut tx = self.pool().begin().await?;
+            sqlx::query!("SELECT pg_advisory_xact_lock($1)", AUDIT_CHAIN_LOCK_KEY)
+                .execute(&mut *tx)
+                .await?;
+            let head = sqlx::query!(
+                r#"
+                SELECT chain_position AS "chain_position!", hash AS "hash!"
+                FROM audit_entries
+                WHERE chain_position IS NOT NULL
+                ORDER BY chain_position DESC
+                LIMIT 1
+                "#
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            let (mut position, mut prev_hash) = head
+                .map(|head| (head.chain_position, head.hash))
+                .unwrap_or_else(|| (0, GENESIS_HASH.to_vec()));
+
+            let pending = sqlx::query_as!(
+                RawAuditEntry,
+                r#"
+                SELECT id AS "id: AuditEntryId", subject, object, action, authorized, recorded_at
+                FROM audit_entries
+                WHERE chain_position IS NULL
+                ORDER BY id ASC
+                LIMIT $1
+                "#,
+                BATCH_SIZE,
+            )
+            .fetch_all(&mut *tx)
+            .await?;
+            let n_pending = pending.len();
+            let last_id = pending.last().map(|entry| entry.id);
+
+            for entry in pending {
+                position += 1;
+                let hash = entry_hash(
+                    entry.id,
+                    &entry.subject,
+                    &entry.object,
+                    &entry.action,
+                    entry.authorized,
+                    entry.recorded_at,
+                    &prev_hash,
+                );
+                sqlx::query!(
+                    r#"
+                    UPDATE audit_entries
+                    SET chain_position = $2, prev_hash = $3, hash = $4
+                    WHERE id = $1
+                    "#,
+                    entry.id as AuditEntryId,
+                    position,
+                    &prev_hash,
+                    &hash,
+                )
+                .execute(&mut *tx)
+                .await?;
+                prev_hash = hash;
+            }
+            if let Some(last) = last_id {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO audit_chain_heads (chain_position, entry_id, hash)
+                    VALUES ($1, $2, $3)
+                    "#,
+                    position,
+                    last as AuditEntryId,
+                    &prev_hash,
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+
+            n_chained += n_pending;
+            if n_pending < BATCH_SIZE as usize {
+                return Ok(n_chained);
+            }
+        }
+    }
+
+    async fn verify_chain(&self) -> Result<AuditChainVerification, AuditError> {
+        crate::chain::verify_chain(self.pool()).await
+    }
+
     async fn find_all<T: From<AuditEntry<Self::Subject, Self::Object, Self::Action>>>(
         &self,
         ids: &[AuditEntryId],
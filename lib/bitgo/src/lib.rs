@@ -196,6 +196,33 @@ impl BitgoClient {
         Ok(serde_json::from_value(response)?)
     }
 
+    #[tracing::instrument(name = "bitgo.send_coins", skip(self), fields(response, url), err)]
+    pub async fn send_coins(
+        &self,
+        wallet_id: &str,
+        address: &str,
+        amount: u64,
+        sequence_id: &str,
+    ) -> Result<(SendCoinsResponse, Value), BitgoError> {
+        // https://developers.bitgo.com/api/express.wallet.sendcoins
+
+        let response_json: Value = self
+            .post(
+                self.url_with_coin(&format!("wallet/{wallet_id}/sendcoins")),
+                &json!({
+                    "address": address,
+                    "amount": amount.to_string(),
+                    "sequenceId": sequence_id,
+                    "walletPassphrase": self.passphrase
+                }),
+            )
+            .await?;
+
+        let response = serde_json::from_value(response_json.clone())?;
+
+        Ok((response, response_json))
+    }
+
     #[tracing::instrument(name = "bitgo.get_transfer", skip(self), fields(response, url), err)]
     pub async fn get_transfer(&self, id: &str, wallet_id: &str) -> Result<Transfer, BitgoError> {
         // https://developers.bitgo.com/api/v2.wallet.gettransfer
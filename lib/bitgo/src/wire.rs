@@ -73,6 +73,7 @@ pub struct Address {
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transfer {
+    pub id: String,
     pub wallet: String,
     pub txid: String,
     pub confirmations: u32,
@@ -85,6 +86,19 @@ pub struct Transfer {
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct SendCoinsResponse {
+    pub transfer: SentTransfer,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentTransfer {
+    pub id: String,
+    pub state: TransferState,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub enum TransferType {
     Receive,
     Send,
@@ -1,17 +1,25 @@
 use async_trait::async_trait;
 use google_cloud_storage::{
     client::{Client, ClientConfig},
-    http::objects::{
-        delete::DeleteObjectRequest,
-        upload::{Media, UploadObjectRequest, UploadType},
+    http::{
+        objects::{
+            delete::DeleteObjectRequest,
+            upload::{Media, UploadObjectRequest, UploadType},
+        },
+        resumable_upload_client::{ChunkSize, ResumableUploadClient},
     },
     sign::SignedURLOptions,
 };
 use serde::{Deserialize, Serialize};
 
-use super::{StorageClientError, r#trait::StorageClient};
+use super::{
+    StorageClientError,
+    r#trait::{ChunkedUploadClient, StorageClient},
+};
 
 const LINK_DURATION_IN_SECS: u64 = 60 * 5;
+// Resumable uploads only accept chunks that are a multiple of 256 KiB, except for the last one.
+const RESUMABLE_CHUNK_SIZE: usize = 32 * 256 * 1024;
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -70,6 +78,30 @@ impl StorageClient for GcpClient {
         Ok(())
     }
 
+    async fn start_chunked_upload(
+        &self,
+        path_in_bucket: &str,
+        mime_type: &str,
+    ) -> Result<Box<dyn ChunkedUploadClient>, StorageClientError> {
+        let mut media = Media::new(path_in_bucket.to_string());
+        media.content_type = mime_type.to_owned().into();
+        let upload_type = UploadType::Simple(media);
+
+        let req = UploadObjectRequest {
+            bucket: self.bucket_name().to_owned(),
+            ..Default::default()
+        };
+        let session = self
+            .client
+            .prepare_resumable_upload(&req, &upload_type)
+            .await?;
+        Ok(Box::new(GcpChunkedUpload {
+            session,
+            buffer: Vec::with_capacity(RESUMABLE_CHUNK_SIZE),
+            n_uploaded: 0,
+        }))
+    }
+
     async fn remove<'a>(
         &self,
         location_in_storage: super::r#trait::LocationInStorage<'a>,
@@ -106,3 +138,41 @@ impl StorageClient for GcpClient {
         Ok(signed_url)
     }
 }
+
+struct GcpChunkedUpload {
+    session: ResumableUploadClient,
+    buffer: Vec<u8>,
+    n_uploaded: u64,
+}
+
+#[async_trait]
+impl ChunkedUploadClient for GcpChunkedUpload {
+    async fn upload_chunk(&mut self, chunk: Vec<u8>) -> Result<(), StorageClientError> {
+        self.buffer.extend_from_slice(&chunk);
+        while self.buffer.len() >= RESUMABLE_CHUNK_SIZE {
+            let rest = self.buffer.split_off(RESUMABLE_CHUNK_SIZE);
+            let full = std::mem::replace(&mut self.buffer, rest);
+            let size = ChunkSize::new(
+                self.n_uploaded,
+                self.n_uploaded + full.len() as u64 - 1,
+                None,
+            );
+            self.session.upload_multiple_chunk(full, &size).await?;
+            self.n_uploaded += RESUMABLE_CHUNK_SIZE as u64;
+        }
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<(), StorageClientError> {
+        let total = self.n_uploaded + self.buffer.len() as u64;
+        let size = if self.buffer.is_empty() {
+            ChunkSize::new(total, total, Some(total))
+        } else {
+            ChunkSize::new(self.n_uploaded, total - 1, Some(total))
+        };
+        self.session
+            .upload_multiple_chunk(self.buffer, &size)
+            .await?;
+        Ok(())
+    }
+}
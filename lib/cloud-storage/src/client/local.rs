@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
 use std::path::{Path, PathBuf};
 
-use super::{StorageClient, error::StorageClientError};
+use super::{ChunkedUploadClient, StorageClient, error::StorageClientError};
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -44,6 +46,21 @@ impl StorageClient for LocalClient {
         Ok(())
     }
 
+    async fn start_chunked_upload(
+        &self,
+        path_in_bucket: &str,
+        _mime_type: &str,
+    ) -> Result<Box<dyn ChunkedUploadClient>, StorageClientError> {
+        let full_path = self.resolve(path_in_bucket);
+
+        if let Some(parent) = full_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let file = tokio::fs::File::create(full_path).await?;
+        Ok(Box::new(LocalChunkedUpload { file }))
+    }
+
     async fn remove<'a>(
         &self,
         location_in_storage: super::LocationInStorage<'a>,
@@ -61,3 +78,20 @@ impl StorageClient for LocalClient {
         Ok(format!("file://{}", full_path.to_string_lossy()))
     }
 }
+
+struct LocalChunkedUpload {
+    file: tokio::fs::File,
+}
+
+#[async_trait::async_trait]
+impl ChunkedUploadClient for LocalChunkedUpload {
+    async fn upload_chunk(&mut self, chunk: Vec<u8>) -> Result<(), StorageClientError> {
+        self.file.write_all(&chunk).await?;
+        Ok(())
+    }
+
+    async fn finish(mut self: Box<Self>) -> Result<(), StorageClientError> {
+        self.file.flush().await?;
+        Ok(())
+    }
+}
@@ -16,6 +16,12 @@ pub trait StorageClient: Send + Sync {
         mime_type: &str,
     ) -> Result<(), StorageClientError>;
 
+    async fn start_chunked_upload(
+        &self,
+        path: &str,
+        mime_type: &str,
+    ) -> Result<Box<dyn ChunkedUploadClient>, StorageClientError>;
+
     async fn remove<'a>(
         &self,
         location_in_storage: LocationInStorage<'a>,
@@ -26,3 +32,11 @@ pub trait StorageClient: Send + Sync {
         location_in_storage: LocationInStorage<'a>,
     ) -> Result<String, StorageClientError>;
 }
+
+/// An upload whose content is handed over in pieces so it never has to be held in memory at once.
+#[async_trait]
+pub trait ChunkedUploadClient: Send {
+    async fn upload_chunk(&mut self, chunk: Vec<u8>) -> Result<(), StorageClientError>;
+
+    async fn finish(self: Box<Self>) -> Result<(), StorageClientError>;
+}
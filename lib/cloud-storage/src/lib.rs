@@ -9,7 +9,7 @@ use std::sync::Arc;
 use tokio::sync::OnceCell;
 
 pub use client::LocationInStorage;
-use client::{GcpClient, LocalClient, StorageClient};
+use client::{ChunkedUploadClient, GcpClient, LocalClient, StorageClient};
 use config::StorageConfig;
 use error::*;
 #[derive(Clone)]
@@ -62,6 +62,16 @@ impl Storage {
         Ok(())
     }
 
+    pub async fn start_chunked_upload(
+        &self,
+        path: &str,
+        mime_type: &str,
+    ) -> Result<ChunkedUpload, StorageError> {
+        let client = self.get_client().await?;
+        let inner = client.start_chunked_upload(path, mime_type).await?;
+        Ok(ChunkedUpload { inner })
+    }
+
     pub async fn remove(
         &self,
         location_in_storage: LocationInStorage<'_>,
@@ -84,3 +94,20 @@ impl Storage {
         self.config.identifier()
     }
 }
+
+/// An upload in progress. The stored object is only complete once the upload has been finished.
+pub struct ChunkedUpload {
+    inner: Box<dyn ChunkedUploadClient>,
+}
+
+impl ChunkedUpload {
+    pub async fn upload_chunk(&mut self, chunk: Vec<u8>) -> Result<(), StorageError> {
+        self.inner.upload_chunk(chunk).await?;
+        Ok(())
+    }
+
+    pub async fn finish(self) -> Result<(), StorageError> {
+        self.inner.finish().await?;
+        Ok(())
+    }
+}
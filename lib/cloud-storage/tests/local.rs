@@ -32,3 +32,26 @@ async fn upload_and_download_local() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn chunked_upload_local() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+    let root = dir.path().to_str().unwrap().to_string();
+    let config = StorageConfig::new_local(root);
+    let storage = Storage::new(&config);
+
+    let filename = "sub/chunked.txt";
+    let mut upload = storage.start_chunked_upload(filename, "text/plain").await?;
+    upload.upload_chunk(b"local".to_vec()).await?;
+    upload.upload_chunk(b"test".to_vec()).await?;
+    upload.finish().await?;
+
+    let link = storage
+        .generate_download_link(cloud_storage::LocationInStorage { path: filename })
+        .await?;
+    let path = link.trim_start_matches("file://");
+    let downloaded = tokio::fs::read_to_string(path).await?;
+    assert_eq!(downloaded, "localtest");
+
+    Ok(())
+}
@@ -20,14 +20,19 @@ use reqwest::{
     Client, Method, RequestBuilder, Url,
     header::{CONTENT_TYPE, HeaderValue},
 };
+use rust_decimal::Decimal;
 use serde::{Serialize, de::DeserializeOwned};
+use serde_json::json;
 use sha2::{Digest as _, Sha256};
 use tokio::sync::RwLock;
 
 pub use config::{KomainuConfig, KomainuDirectoryConfig, KomainuSecretKey};
 pub use error::KomainuError;
-pub use wire::{EntityType, EventType, Notification, Request, Transaction, Wallet};
-use wire::{Fallible, GetToken, GetTokenResponse, Many};
+use wire::{CreateTransactionRequest, Fallible, GetToken, GetTokenResponse, Many, RequestType};
+pub use wire::{
+    EntityType, EventType, Notification, Request, RequestEntity, RequestStatus, Transaction,
+    TransactionDirection, TransactionStatus, Wallet,
+};
 
 #[derive(Clone)]
 pub struct KomainuClient {
@@ -109,6 +114,31 @@ impl KomainuClient {
         self.get_one(&format!("v1/custody/requests/{id}")).await
     }
 
+    #[tracing::instrument(name = "komainu.create_transaction_request", skip(self))]
+    pub async fn create_transaction_request(
+        &self,
+        wallet_id: &str,
+        receiver_address: &str,
+        amount: Decimal,
+        note: &str,
+    ) -> Result<Request, KomainuError> {
+        // https://docs.komainu.io/apispec/#tag/Requests
+
+        self.post_one(
+            "v1/custody/requests",
+            &json!({
+                "request_type": RequestType::CreateTransaction,
+                "request_data": CreateTransactionRequest {
+                    wallet_id: wallet_id.to_owned(),
+                    amount,
+                    receiver_address: receiver_address.to_owned(),
+                    note: note.to_owned(),
+                },
+            }),
+        )
+        .await
+    }
+
     #[tracing::instrument(name = "komainu.list_requests", skip(self))]
     pub async fn list_requests(&self) -> Result<Vec<Request>, KomainuError> {
         self.get_many("v1/custody/requests").await
@@ -170,6 +200,26 @@ impl KomainuClient {
             .json()
             .await?;
 
+        Self::into_result(response)
+    }
+
+    async fn post_one<P: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        payload: &P,
+    ) -> Result<T, KomainuError> {
+        let response = self
+            .request(Method::POST, endpoint, None, Some(payload))
+            .await?
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        Self::into_result(response)
+    }
+
+    fn into_result<T>(response: Fallible<T>) -> Result<T, KomainuError> {
         match response {
             Fallible::Error {
                 error_code,
@@ -13,12 +13,16 @@ pub struct Notification {
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EventType {
     BalanceUpdated,
+    RequestUpdated,
+    TransactionUpdated,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum EntityType {
     Wallet,
+    Request,
+    Transaction,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -103,14 +107,22 @@ pub enum WalletStatus {
     ViewOnly,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateTransactionRequest {
+    pub wallet_id: String,
+    pub amount: Decimal,
+    pub receiver_address: String,
+    pub note: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RequestType {
     CreateTransaction,
     CollateralOperation,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RequestStatus {
     Created,
@@ -122,14 +134,14 @@ pub enum RequestStatus {
     Blocked,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RequestEntity {
     Transaction,
     Collateral,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionDirection {
     In,
@@ -137,7 +149,7 @@ pub enum TransactionDirection {
     Flat,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TransactionStatus {
     Pending,